@@ -120,6 +120,12 @@ pub enum HcAppBundle {
         /// that have their location bundled
         #[structopt(short, long)]
         recursive: bool,
+
+        /// Sign the bundle as a publisher with the Ed25519 secret key stored
+        /// in this file, written as 64 hex digits.
+        /// Resources which are not bundled must have their hash pinned.
+        #[structopt(long)]
+        signing_key: Option<PathBuf>,
     },
 
     /// Unpack parts of the `.happ` bundle file into a specific directory.
@@ -189,6 +195,12 @@ pub enum HcWebAppBundle {
         /// that have their location bundled
         #[structopt(short, long)]
         recursive: bool,
+
+        /// Sign the bundle as a publisher with the Ed25519 secret key stored
+        /// in this file, written as 64 hex digits.
+        /// Resources which are not bundled must have their hash pinned.
+        #[structopt(long)]
+        signing_key: Option<PathBuf>,
     },
 
     /// Unpack parts of the `.webhapp` bundle file into a specific directory.
@@ -278,16 +290,29 @@ impl HcAppBundle {
                 path,
                 output,
                 recursive,
+                signing_key,
             } => {
                 let name = get_app_name(&path).await?;
+                let signing_key = match signing_key {
+                    Some(key_path) => Some(crate::packing::read_signing_key(&key_path).await?),
+                    None => None,
+                };
 
                 if recursive {
                     app_pack_recursive(&path).await?;
                 }
 
-                let (bundle_path, _) =
-                    crate::packing::pack::<AppManifest>(&path, output, name).await?;
+                let (bundle_path, _) = crate::packing::pack_signed::<AppManifest>(
+                    &path,
+                    output,
+                    name,
+                    signing_key.as_ref(),
+                )
+                .await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if let Some(signing_key) = signing_key {
+                    println!("Signed bundle as publisher {}", signing_key.publisher_key());
+                }
             }
             Self::Unpack {
                 path,
@@ -326,16 +351,29 @@ impl HcWebAppBundle {
                 path,
                 output,
                 recursive,
+                signing_key: signing_key_path,
             } => {
                 let name = get_web_app_name(&path).await?;
+                let signing_key = match &signing_key_path {
+                    Some(key_path) => Some(crate::packing::read_signing_key(key_path).await?),
+                    None => None,
+                };
 
                 if recursive {
-                    web_app_pack_recursive(&path).await?;
+                    web_app_pack_recursive(&path, signing_key_path).await?;
                 }
 
-                let (bundle_path, _) =
-                    crate::packing::pack::<WebAppManifest>(&path, output, name).await?;
+                let (bundle_path, _) = crate::packing::pack_signed::<WebAppManifest>(
+                    &path,
+                    output,
+                    name,
+                    signing_key.as_ref(),
+                )
+                .await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if let Some(signing_key) = signing_key {
+                    println!("Signed bundle as publisher {}", signing_key.publisher_key());
+                }
             }
             Self::Unpack {
                 path,
@@ -392,8 +430,12 @@ async fn get_web_app_name(manifest_path: &Path) -> HcBundleResult<String> {
     Ok(manifest.app_name().to_string())
 }
 
-// Pack the app's manifest and all its DNAs if their location is bundled
-async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::Result<()> {
+// Pack the app's manifest and all its DNAs if their location is bundled,
+// signing the app bundle with the same key as the web-app bundle
+async fn web_app_pack_recursive(
+    web_app_workdir_path: &PathBuf,
+    signing_key: Option<PathBuf>,
+) -> anyhow::Result<()> {
     let canonical_web_app_workdir_path = ffs::canonicalize(web_app_workdir_path).await?;
 
     let web_app_manifest_path = canonical_web_app_workdir_path.join(WebAppManifest::path());
@@ -417,6 +459,7 @@ async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::Resul
             path: ffs::canonicalize(app_workdir_location).await?,
             output: None,
             recursive: true,
+            signing_key,
         }
         .run()
         .await?;
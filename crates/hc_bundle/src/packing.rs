@@ -4,8 +4,9 @@
 
 use crate::error::{HcBundleError, HcBundleResult};
 use holochain_util::ffs;
+use mr_bundle::error::MrBundleError;
 use mr_bundle::RawBundle;
-use mr_bundle::{Bundle, Manifest};
+use mr_bundle::{Bundle, Manifest, SigningKey};
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -76,10 +77,25 @@ pub async fn pack<M: Manifest>(
     dir_path: &std::path::Path,
     target_path: Option<PathBuf>,
     name: String,
+) -> HcBundleResult<(PathBuf, Bundle<M>)> {
+    pack_signed(dir_path, target_path, name, None).await
+}
+
+/// Pack a directory like [`pack`], signing the bundle as a publisher if a
+/// signing key is given
+pub async fn pack_signed<M: Manifest>(
+    dir_path: &std::path::Path,
+    target_path: Option<PathBuf>,
+    name: String,
+    signing_key: Option<&SigningKey>,
 ) -> HcBundleResult<(PathBuf, Bundle<M>)> {
     let dir_path = ffs::canonicalize(dir_path).await?;
     let manifest_path = dir_path.join(&M::path());
     let bundle: Bundle<M> = Bundle::pack_yaml(&manifest_path).await?;
+    let bundle = match signing_key {
+        Some(signing_key) => bundle.sign(signing_key)?,
+        None => bundle,
+    };
     let target_path = match target_path {
         Some(target_path) => {
             if target_path.is_dir() {
@@ -94,6 +110,15 @@ pub async fn pack<M: Manifest>(
     Ok((target_path, bundle))
 }
 
+/// Read a publisher signing key from a file containing the Ed25519 secret key
+/// as 64 hex digits
+pub async fn read_signing_key(path: &Path) -> HcBundleResult<SigningKey> {
+    Ok(ffs::read_to_string(path)
+        .await?
+        .parse()
+        .map_err(MrBundleError::from)?)
+}
+
 fn dir_to_bundle_path(dir_path: &Path, name: String, extension: &str) -> HcBundleResult<PathBuf> {
     Ok(dir_path.join(format!("{}.{}", name, extension)))
 }
@@ -101,7 +126,7 @@ fn dir_to_bundle_path(dir_path: &Path, name: String, extension: &str) -> HcBundl
 #[cfg(test)]
 mod tests {
     use holochain_types::prelude::ValidatedDnaManifest;
-    use mr_bundle::error::{MrBundleError, UnpackingError};
+    use mr_bundle::error::{BundleError, UnpackingError};
 
     use super::*;
 
@@ -203,4 +228,59 @@ integrity:
         let (_, bundle2) = pack(&dir, None, "test_dna".to_string()).await.unwrap();
         assert_eq!(bundle, bundle2);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_signed_pack() {
+        let tmpdir = tempfile::Builder::new()
+            .prefix("hc-bundle-test")
+            .tempdir()
+            .unwrap();
+        let dir = tmpdir.path().join("test-dna");
+        std::fs::create_dir(&dir).unwrap();
+
+        let manifest_yaml = r#"
+---
+manifest_version: "1"
+name: test_dna
+integrity:
+    network_seed: blablabla
+    origin_time: 2022-02-11T23:29:00.789576Z
+    zomes:
+      - name: zome1
+        bundled: zome-1.wasm
+        "#;
+        std::fs::write(dir.join("zome-1.wasm"), &[1, 2, 3]).unwrap();
+        std::fs::write(dir.join("dna.yaml"), manifest_yaml.as_bytes()).unwrap();
+
+        let key_path = tmpdir.path().join("publisher.key");
+        std::fs::write(&key_path, format!("{}\n", "01".repeat(32))).unwrap();
+        let signing_key = read_signing_key(&key_path).await.unwrap();
+        let publisher_key = signing_key.publisher_key();
+
+        let (bundle_path, _) = pack_signed::<ValidatedDnaManifest>(
+            &dir,
+            None,
+            "test_dna".to_string(),
+            Some(&signing_key),
+        )
+        .await
+        .unwrap();
+
+        // The signature is written into the bundle file
+        let bundle: Bundle<ValidatedDnaManifest> =
+            Bundle::read_from_file(&bundle_path).await.unwrap();
+        assert_eq!(
+            bundle.verify_signatures(&[publisher_key]).unwrap(),
+            publisher_key
+        );
+
+        // Only secret keys written as hex are accepted
+        std::fs::write(&key_path, "not a key").unwrap();
+        matches::assert_matches!(
+            read_signing_key(&key_path).await,
+            Err(HcBundleError::MrBundleError(MrBundleError::BundleError(
+                BundleError::InvalidSigningKey
+            )))
+        );
+    }
 }
@@ -55,6 +55,7 @@ async fn main() -> anyhow::Result<()> {
             source: AppBundleSource::Bundle(bundle),
             membrane_proofs: Default::default(),
             network_seed: None,
+            trusted_publishers: None,
         };
 
         let r = AdminRequest::InstallApp(Box::new(payload));
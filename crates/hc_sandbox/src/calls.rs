@@ -1,34 +1,68 @@
-//! Helpers for making [`AdminRequest`]s to the admin api.
+//! Helpers for making [`AdminRequest`]s to the admin api
+//! and [`AppRequest`]s to an app api.
 //!
 //! This module is designed for use in a CLI so it is more simplified
 //! then calling the [`CmdRunner`] directly.
 //! For simple calls like [`AdminRequest::ListDnas`] this is probably easier
 //! but if you want more control use [`CmdRunner::command`].
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::ensure;
+use holo_hash::AnyLinkableHash;
+use holo_hash::HOLO_HASH_FULL_LEN;
+use holochain_conductor_api::backup::BackupManifest;
+use holochain_conductor_api::config::DEFAULT_BIND_ADDRESS;
+use holochain_conductor_api::source_chain_archive::SourceChainArchive;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AllowedOrigins;
+use holochain_conductor_api::AppRequest;
+use holochain_conductor_api::AppResponse;
 use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::ExternalApiWireError;
 use holochain_conductor_api::InterfaceDriver;
+use holochain_conductor_api::NetworkInfo;
+use holochain_conductor_api::ZomeCall;
 use holochain_conductor_api::{AdminInterfaceConfig, AppInfo};
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
+use holochain_types::prelude::CapAccess;
+use holochain_types::prelude::CapSecret;
+use holochain_types::prelude::CloneCellId;
+use holochain_types::prelude::CloneId;
+use holochain_types::prelude::CreateCloneCellPayload;
+use holochain_types::prelude::DisableCloneCellPayload;
 use holochain_types::prelude::DnaHash;
 use holochain_types::prelude::DnaModifiersOpt;
+use holochain_types::prelude::EnableCloneCellPayload;
+use holochain_types::prelude::ExternIO;
+use holochain_types::prelude::GrantZomeCallCapabilityPayload;
+use holochain_types::prelude::GrantedFunction;
+use holochain_types::prelude::GrantedFunctions;
+use holochain_types::prelude::InstalledCell;
+use holochain_types::prelude::NetworkInfoRequestPayload;
+use holochain_types::prelude::Nonce256Bits;
 use holochain_types::prelude::RegisterDnaPayload;
+use holochain_types::prelude::Signature;
 use holochain_types::prelude::Timestamp;
 use holochain_types::prelude::YamlProperties;
+use holochain_types::prelude::ZomeCallCapGrant;
+use holochain_types::prelude::ZomeCallUnsigned;
 use holochain_types::prelude::{AgentPubKey, AppBundleSource};
 use holochain_types::prelude::{CellId, InstallAppPayload};
 use holochain_types::prelude::{DnaSource, NetworkSeed};
+use holochain_websocket::WebsocketSender;
 use std::convert::TryFrom;
+use std::net::IpAddr;
+use std::time::Duration;
 
 use crate::cmds::Existing;
 use crate::expect_match;
 use crate::ports::get_admin_ports;
+use crate::ports::get_app_api;
 use crate::run::run_async;
 use crate::CmdRunner;
 use structopt::StructOpt;
@@ -41,6 +75,10 @@ pub struct Call {
     /// If this is empty existing sandboxes will be used.
     /// Cannot be combined with existing sandboxes.
     pub running: Vec<u16>,
+    #[structopt(long, conflicts_with_all = &["running", "existing_paths", "indices"])]
+    /// Path to the unix domain socket of a running conductor's admin interface.
+    /// Cannot be combined with ports or existing sandboxes.
+    pub unix_socket: Option<PathBuf>,
     #[structopt(flatten)]
     pub existing: Existing,
     #[structopt(subcommand)]
@@ -72,10 +110,18 @@ pub enum AdminRequestCli {
     EnableApp(EnableApp),
     DisableApp(DisableApp),
     DumpState(DumpState),
+    CreateBackup(CreateBackup),
+    ExportSourceChain(ExportSourceChain),
+    ImportSourceChain(ImportSourceChain),
     /// Calls AdminRequest::AddAgentInfo.
     /// _Unimplemented_.
     AddAgents,
     ListAgents(ListAgents),
+    CallZome(CallZome),
+    CreateCloneCell(CreateCloneCell),
+    DisableCloneCell(DisableCloneCell),
+    EnableCloneCell(EnableCloneCell),
+    NetworkInfo(NetworkInfoArgs),
 }
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::AddAdminInterfaces
@@ -84,6 +130,19 @@ pub struct AddAdminWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    #[structopt(long, conflicts_with = "port")]
+    /// Listen on a unix domain socket at this path instead of a port.
+    /// Only the user running the conductor can connect to it.
+    pub unix_socket: Option<PathBuf>,
+    #[structopt(long, conflicts_with = "unix_socket")]
+    /// Address to bind the interface to.
+    /// Defaults to 127.0.0.1.
+    pub bind_address: Option<IpAddr>,
+    #[structopt(long = "allowed-origin", conflicts_with = "unix_socket")]
+    /// An HTTP `Origin` browsers may connect from, e.g. `http://localhost:8888`.
+    /// Can be given multiple times, or as `*` to allow any origin.
+    /// Defaults to origins on this machine.
+    pub allowed_origins: Vec<String>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -93,6 +152,23 @@ pub struct AddAppWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    #[structopt(long, conflicts_with = "port")]
+    /// Listen on a unix domain socket at this path instead of a port.
+    /// Only the user running the conductor can connect to it.
+    pub unix_socket: Option<PathBuf>,
+    #[structopt(long, conflicts_with = "unix_socket")]
+    /// Address to bind the interface to.
+    /// Defaults to 127.0.0.1.
+    pub bind_address: Option<IpAddr>,
+    #[structopt(long = "allowed-origin", conflicts_with = "unix_socket")]
+    /// An HTTP `Origin` browsers may connect from, e.g. `http://localhost:8888`.
+    /// Can be given multiple times, or as `*` to allow any origin.
+    /// Defaults to origins on this machine.
+    pub allowed_origins: Vec<String>,
+    #[structopt(long)]
+    /// Require connections to authenticate with a token issued
+    /// for the apps they will access.
+    pub require_authentication: bool,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -180,6 +256,47 @@ pub struct DumpState {
     pub agent_key: AgentPubKey,
 }
 
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::CreateBackup
+/// and writes a backup of all of the conductor's databases
+/// to an archive. The keystore is in use, so it is not included.
+/// Restore it with `hc sandbox restore`.
+pub struct CreateBackup {
+    /// Where the conductor writes the archive.
+    /// Must not exist yet.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::ExportSourceChain
+/// and writes the cell's source chain to an archive file.
+pub struct ExportSourceChain {
+    #[structopt(parse(try_from_str = parse_dna_hash))]
+    /// The dna hash half of the cell id to export.
+    pub dna: DnaHash,
+    #[structopt(parse(try_from_str = parse_agent_key))]
+    /// The agent half of the cell id to export.
+    pub agent_key: AgentPubKey,
+    /// The file to write the archive to.
+    pub path: PathBuf,
+    #[structopt(long)]
+    /// Encrypt private entries, so only a keystore holding
+    /// the agent's key can import them.
+    pub encrypt_private_entries: bool,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::ImportSourceChain
+/// and imports a source chain from an archive file
+/// written by `export-source-chain`.
+pub struct ImportSourceChain {
+    /// The archive file to import.
+    pub path: PathBuf,
+    #[structopt(long)]
+    /// Also validate the records with the DNA's validation rules.
+    pub validate: bool,
+}
+
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::RequestAgentInfo
 /// and pretty prints the agent info on
@@ -203,14 +320,120 @@ pub struct ListApps {
     pub status: Option<AppStatusFilter>,
 }
 
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AppRequest::CallZome
+/// and prints the decoded result as JSON.
+///
+/// A signing key is generated and granted access to the
+/// function the first time it is called. In a sandbox, the
+/// key is kept in the sandbox directory for later calls.
+pub struct CallZome {
+    #[structopt(long)]
+    /// The app interface port to make the call on.
+    /// Defaults to the first attached app interface,
+    /// attaching a new one if there are none.
+    pub app_port: Option<u16>,
+    #[structopt(parse(try_from_str = parse_dna_hash))]
+    /// The dna hash half of the cell id to call.
+    pub dna: DnaHash,
+    #[structopt(parse(try_from_str = parse_agent_key))]
+    /// The agent half of the cell id to call.
+    pub agent_key: AgentPubKey,
+    /// The zome containing the function to call.
+    pub zome_name: String,
+    /// The name of the function to call.
+    pub fn_name: String,
+    #[structopt(default_value = "null")]
+    /// The payload to call the function with, as JSON.
+    pub payload: String,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AppRequest::CreateCloneCell
+/// and creates a clone of a cell in an installed app.
+///
+/// At least one of the modifiers must be set.
+pub struct CreateCloneCell {
+    #[structopt(long)]
+    /// The app interface port to make the call on.
+    /// Defaults to the first attached app interface,
+    /// attaching a new one if there are none.
+    pub app_port: Option<u16>,
+    /// The InstalledAppId the cell belongs to.
+    pub app_id: String,
+    /// The role name of the cell to clone.
+    pub role_name: String,
+    #[structopt(short, long)]
+    /// Network seed to set for the clone.
+    pub network_seed: Option<String>,
+    #[structopt(long)]
+    /// Path to a YAML file of properties to set for the clone.
+    pub properties: Option<PathBuf>,
+    #[structopt(long)]
+    /// Origin time to set for the clone.
+    pub origin_time: Option<Timestamp>,
+    #[structopt(long)]
+    /// Optional name for the clone.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AppRequest::DisableCloneCell
+/// and disables a clone cell.
+pub struct DisableCloneCell {
+    #[structopt(long)]
+    /// The app interface port to make the call on.
+    /// Defaults to the first attached app interface,
+    /// attaching a new one if there are none.
+    pub app_port: Option<u16>,
+    /// The InstalledAppId the clone cell belongs to.
+    pub app_id: String,
+    #[structopt(parse(try_from_str = parse_clone_id))]
+    /// The clone id of the clone cell, e.g. `my-role.0`.
+    pub clone_id: CloneId,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AppRequest::EnableCloneCell
+/// and enables a previously disabled clone cell.
+pub struct EnableCloneCell {
+    #[structopt(long)]
+    /// The app interface port to make the call on.
+    /// Defaults to the first attached app interface,
+    /// attaching a new one if there are none.
+    pub app_port: Option<u16>,
+    /// The InstalledAppId the clone cell belongs to.
+    pub app_id: String,
+    #[structopt(parse(try_from_str = parse_clone_id))]
+    /// The clone id of the clone cell, e.g. `my-role.0`.
+    pub clone_id: CloneId,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AppRequest::NetworkInfo
+/// and prints the networking info for the given dnas.
+pub struct NetworkInfoArgs {
+    #[structopt(long)]
+    /// The app interface port to make the call on.
+    /// Defaults to the first attached app interface,
+    /// attaching a new one if there are none.
+    pub app_port: Option<u16>,
+    #[structopt(parse(try_from_str = parse_dna_hash), required = true)]
+    /// The dnas to get networking info for.
+    pub dnas: Vec<DnaHash>,
+}
+
 #[doc(hidden)]
 pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     let Call {
         existing,
         running,
+        unix_socket,
         call,
     } = req;
-    let cmds = if running.is_empty() {
+    let cmds = if let Some(path) = unix_socket {
+        vec![(connect_unix_socket(&path).await?, None, None, None)]
+    } else if running.is_empty() {
         let paths = if existing.is_empty() {
             crate::save::load(std::env::current_dir()?)?
         } else {
@@ -220,15 +443,20 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
         let mut cmds = Vec::with_capacity(ports.len());
         for (port, path) in ports.into_iter().zip(paths.into_iter()) {
             match CmdRunner::try_new(port).await {
-                Ok(cmd) => cmds.push((cmd, None, None)),
+                Ok(cmd) => cmds.push((cmd, None, None, Some(path))),
                 Err(e) => {
                     if let holochain_websocket::WebsocketError::Io(e) = &e {
                         if let std::io::ErrorKind::ConnectionRefused
                         | std::io::ErrorKind::AddrNotAvailable = e.kind()
                         {
                             let (port, holochain, lair) =
-                                run_async(holochain_path, path, None).await?;
-                            cmds.push((CmdRunner::new(port).await, Some(holochain), Some(lair)));
+                                run_async(holochain_path, path.clone(), None).await?;
+                            cmds.push((
+                                CmdRunner::new(port).await,
+                                Some(holochain),
+                                Some(lair),
+                                Some(path),
+                            ));
                             continue;
                         }
                     }
@@ -243,22 +471,51 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     } else {
         let mut cmds = Vec::with_capacity(running.len());
         for port in running {
-            cmds.push((CmdRunner::new(port).await, None, None));
+            cmds.push((CmdRunner::new(port).await, None, None, None));
         }
         cmds
     };
     for mut cmd in cmds {
-        call_inner(&mut cmd.0, call.clone()).await?;
+        call_inner(&mut cmd.0, call.clone(), cmd.3.as_deref()).await?;
     }
     Ok(())
 }
 
-async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Result<()> {
+#[cfg(unix)]
+async fn connect_unix_socket(path: &Path) -> anyhow::Result<CmdRunner> {
+    Ok(CmdRunner::try_new_unix(path).await?)
+}
+
+#[cfg(not(unix))]
+async fn connect_unix_socket(_path: &Path) -> anyhow::Result<CmdRunner> {
+    bail!("Unix domain sockets are not supported on this platform")
+}
+
+async fn call_inner(
+    cmd: &mut CmdRunner,
+    call: AdminRequestCli,
+    sandbox: Option<&Path>,
+) -> anyhow::Result<()> {
     match call {
+        AdminRequestCli::AddAdminWs(AddAdminWs {
+            unix_socket: Some(path),
+            ..
+        }) => {
+            add_admin_unix_socket_interface(cmd, path.clone()).await?;
+            msg!("Added Admin unix socket {}", path.display());
+        }
         AdminRequestCli::AddAdminWs(args) => {
             let port = add_admin_interface(cmd, args).await?;
             msg!("Added Admin port {}", port);
         }
+        AdminRequestCli::AddAppWs(AddAppWs {
+            unix_socket: Some(path),
+            require_authentication,
+            ..
+        }) => {
+            attach_app_unix_socket_interface(cmd, path.clone(), require_authentication).await?;
+            msg!("Added App unix socket {}", path.display());
+        }
         AdminRequestCli::AddAppWs(args) => {
             let port = attach_app_interface(cmd, args).await?;
             msg!("Added App port {}", port);
@@ -310,6 +567,23 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let state = dump_state(cmd, args).await?;
             msg!("DUMP STATE \n{}", state);
         }
+        AdminRequestCli::ExportSourceChain(args) => {
+            let path = args.path.clone();
+            let records = export_source_chain(cmd, args).await?;
+            msg!("Exported {} records to {}", records, path.display());
+        }
+        AdminRequestCli::ImportSourceChain(args) => {
+            let cell_id = import_source_chain(cmd, args).await?;
+            msg!("Imported source chain of cell {:?}", cell_id);
+        }
+        AdminRequestCli::CreateBackup(args) => {
+            let manifest = create_backup(cmd, args).await?;
+            msg!(
+                "Backed up {} databases:\n{:#?}",
+                manifest.databases.len(),
+                manifest.databases
+            );
+        }
         AdminRequestCli::AddAgents => todo!("Adding agent info via cli is coming soon"),
         AdminRequestCli::ListAgents(args) => {
             use std::fmt::Write;
@@ -360,6 +634,30 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
                 msg!("{}\n", out);
             }
         }
+        AdminRequestCli::CallZome(args) => {
+            let result = call_zome(cmd, args, sandbox).await?;
+            msg!(
+                "Zome call result:\n{}",
+                serde_json::to_string_pretty(&msgpack_to_json(result))?
+            );
+        }
+        AdminRequestCli::CreateCloneCell(args) => {
+            let cell = create_clone_cell(cmd, args).await?;
+            msg!("Created clone cell: {:?}", cell);
+        }
+        AdminRequestCli::DisableCloneCell(args) => {
+            let clone_id = args.clone_id.clone();
+            disable_clone_cell(cmd, args).await?;
+            msg!("Disabled clone cell: {}", clone_id);
+        }
+        AdminRequestCli::EnableCloneCell(args) => {
+            let cell = enable_clone_cell(cmd, args).await?;
+            msg!("Enabled clone cell: {:?}", cell);
+        }
+        AdminRequestCli::NetworkInfo(args) => {
+            let info = network_info(cmd, args).await?;
+            msg!("Network info: {:?}", info);
+        }
     }
     Ok(())
 }
@@ -370,7 +668,12 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
     let resp = cmd
         .command(AdminRequest::AddAdminInterfaces(vec![
             AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
+                driver: InterfaceDriver::Websocket {
+                    port,
+                    bind_address: args.bind_address.unwrap_or(DEFAULT_BIND_ADDRESS),
+                    allowed_origins: allowed_origins(args.allowed_origins),
+                },
+                require_authentication: false,
             },
         ]))
         .await?;
@@ -383,6 +686,28 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
     Ok(port)
 }
 
+/// Calls [`AdminRequest::AddAdminInterfaces`] and adds another admin interface
+/// listening on a unix domain socket.
+pub async fn add_admin_unix_socket_interface(
+    cmd: &mut CmdRunner,
+    path: PathBuf,
+) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::AddAdminInterfaces(vec![
+            AdminInterfaceConfig {
+                driver: InterfaceDriver::UnixSocket { path },
+                require_authentication: false,
+            },
+        ]))
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::AdminInterfacesAdded),
+        "Failed to add admin interface, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 /// Calls [`AdminRequest::RegisterDna`] and registers dna.
 pub async fn register_dna(cmd: &mut CmdRunner, args: RegisterDna) -> anyhow::Result<DnaHash> {
     let RegisterDna {
@@ -440,6 +765,7 @@ pub async fn install_app_bundle(cmd: &mut CmdRunner, args: InstallApp) -> anyhow
         source: AppBundleSource::Path(path),
         membrane_proofs: Default::default(),
         network_seed,
+        trusted_publishers: None,
     };
 
     let r = AdminRequest::InstallApp(Box::new(payload));
@@ -536,7 +862,12 @@ pub async fn disable_app(cmd: &mut CmdRunner, args: DisableApp) -> anyhow::Resul
 /// Calls [`AdminRequest::AttachAppInterface`] and adds another app interface.
 pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow::Result<u16> {
     let resp = cmd
-        .command(AdminRequest::AttachAppInterface { port: args.port })
+        .command(AdminRequest::AttachAppInterface {
+            port: args.port,
+            bind_address: args.bind_address,
+            allowed_origins: allowed_origins(args.allowed_origins.clone()),
+            require_authentication: args.require_authentication,
+        })
         .await?;
     tracing::debug!(?resp);
     match resp {
@@ -549,6 +880,27 @@ pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow
     }
 }
 
+/// Calls [`AdminRequest::AttachAppUnixSocketInterface`] and adds another app
+/// interface listening on a unix domain socket.
+pub async fn attach_app_unix_socket_interface(
+    cmd: &mut CmdRunner,
+    path: PathBuf,
+    require_authentication: bool,
+) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::AttachAppUnixSocketInterface {
+            path,
+            require_authentication,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::AppUnixSocketInterfaceAttached),
+        "Failed to attach app interface, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 /// Calls [`AdminRequest::DumpState`] and dumps the current cell's state.
 // TODO: Add pretty print.
 // TODO: Default to dumping all cell state.
@@ -561,6 +913,57 @@ pub async fn dump_state(cmd: &mut CmdRunner, args: DumpState) -> anyhow::Result<
     Ok(expect_match!(resp => AdminResponse::StateDumped, "Failed to dump state"))
 }
 
+/// Calls [`AdminRequest::CreateBackup`] and writes a backup archive of the conductor's databases.
+/// A relative path is resolved against the current directory, not the conductor's.
+pub async fn create_backup(
+    cmd: &mut CmdRunner,
+    args: CreateBackup,
+) -> anyhow::Result<BackupManifest> {
+    let path = std::env::current_dir()?.join(args.path);
+    let resp = cmd.command(AdminRequest::CreateBackup { path }).await?;
+    Ok(expect_match!(resp => AdminResponse::BackupCreated, "Failed to create backup"))
+}
+
+/// Calls [`AdminRequest::ExportSourceChain`] and writes the archive to a file.
+/// Returns the number of exported records.
+pub async fn export_source_chain(
+    cmd: &mut CmdRunner,
+    args: ExportSourceChain,
+) -> anyhow::Result<usize> {
+    let resp = cmd
+        .command(AdminRequest::ExportSourceChain {
+            cell_id: CellId::new(args.dna, args.agent_key),
+            encrypt_private_entries: args.encrypt_private_entries,
+        })
+        .await?;
+    let archive =
+        expect_match!(resp => AdminResponse::SourceChainExported, "Failed to export source chain");
+    std::fs::write(&args.path, archive.encode()?)?;
+    Ok(archive.records.len())
+}
+
+/// Reads an archive file and calls [`AdminRequest::ImportSourceChain`] with it.
+/// Returns the cell whose source chain was imported.
+pub async fn import_source_chain(
+    cmd: &mut CmdRunner,
+    args: ImportSourceChain,
+) -> anyhow::Result<CellId> {
+    let archive = SourceChainArchive::decode(&std::fs::read(&args.path)?)?;
+    let cell_id = archive.cell_id.clone();
+    let resp = cmd
+        .command(AdminRequest::ImportSourceChain {
+            archive,
+            validate: args.validate,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::SourceChainImported),
+        "Failed to import source chain, got: {:?}",
+        resp
+    );
+    Ok(cell_id)
+}
+
 /// Calls [`AdminRequest::AddAgentInfo`] with and adds the list of agent info.
 pub async fn add_agent_info(cmd: &mut CmdRunner, args: Vec<AgentInfoSigned>) -> anyhow::Result<()> {
     let resp = cmd
@@ -587,6 +990,438 @@ pub async fn request_agent_info(
     Ok(expect_match!(resp => AdminResponse::AgentInfo, "Failed to request agent info"))
 }
 
+/// Calls [`AppRequest::CallZome`] and decodes the msgpack result.
+///
+/// The call is signed by a key which is granted access to just the called
+/// function with [`AdminRequest::GrantZomeCallCapability`]. In a sandbox,
+/// the key and cap secret are kept in [`ZOME_CALL_CREDENTIALS_FILE`] in the
+/// sandbox directory, so later calls to the same function reuse the grant
+/// rather than committing a new one. If the conductor no longer knows the
+/// grant, for example because the sandbox was reset, a new one is made.
+pub async fn call_zome(
+    cmd: &mut CmdRunner,
+    args: CallZome,
+    sandbox: Option<&Path>,
+) -> anyhow::Result<rmpv::Value> {
+    let CallZome {
+        app_port,
+        dna,
+        agent_key,
+        zome_name,
+        fn_name,
+        payload,
+    } = args;
+    let cell_id = CellId::new(dna, agent_key);
+    let function: GrantedFunction = (zome_name.into(), fn_name.into());
+    let payload = ExternIO::encode(serde_json::from_str::<serde_json::Value>(&payload)?)?;
+
+    let path = sandbox.map(|sandbox| sandbox.join(ZOME_CALL_CREDENTIALS_FILE));
+    let mut all = match &path {
+        Some(path) => load_credentials(path)?,
+        None => CredentialsFile::new(),
+    };
+    let key = credentials_key(&cell_id);
+    let stored = all
+        .get(&key)
+        .filter(|credentials| credentials.functions.contains(&function))
+        .cloned();
+    let mut app = connect_app(cmd, app_port).await?;
+    let resp = match stored {
+        Some(credentials) => {
+            let resp =
+                send_zome_call(&mut app, &credentials, &cell_id, &function, &payload).await?;
+            if let AppResponse::Error(ExternalApiWireError::ZomeCallUnauthorized(_)) = resp {
+                None
+            } else {
+                Some(resp)
+            }
+        }
+        None => None,
+    };
+    let resp = match resp {
+        Some(resp) => resp,
+        None => {
+            // Grant the functions which were granted before as well, so
+            // that the stored credentials keep working for them.
+            let mut functions = all
+                .remove(&key)
+                .map(|credentials| credentials.functions)
+                .unwrap_or_default();
+            functions.insert(function.clone());
+            let credentials = grant_zome_call_credentials(cmd, &cell_id, functions).await?;
+            all.insert(key, credentials.clone());
+            if let Some(path) = &path {
+                save_credentials(path, &all)?;
+            }
+            send_zome_call(&mut app, &credentials, &cell_id, &function, &payload).await?
+        }
+    };
+    let result = expect_match!(resp => AppResponse::ZomeCalled, "Failed to call zome");
+    Ok(result.decode()?)
+}
+
+async fn send_zome_call(
+    app: &mut WebsocketSender,
+    credentials: &ZomeCallCredentials,
+    cell_id: &CellId,
+    (zome_name, fn_name): &GrantedFunction,
+    payload: &ExternIO,
+) -> anyhow::Result<AppResponse> {
+    let unsigned = ZomeCallUnsigned {
+        cell_id: cell_id.clone(),
+        zome_name: zome_name.clone(),
+        fn_name: fn_name.clone(),
+        payload: payload.clone(),
+        cap_secret: Some(credentials.cap_secret()?),
+        provenance: credentials.provenance()?,
+        nonce: Nonce256Bits::from(random_bytes::<32>().await?),
+        expires_at: (Timestamp::now() + ZOME_CALL_EXPIRES_AFTER)?,
+    };
+    let signature = sign(
+        credentials.signing_key()?,
+        unsigned.data_to_sign()?.to_vec(),
+    )
+    .await?;
+    let call = ZomeCall {
+        cell_id: unsigned.cell_id,
+        zome_name: unsigned.zome_name,
+        fn_name: unsigned.fn_name,
+        payload: unsigned.payload,
+        cap_secret: unsigned.cap_secret,
+        provenance: unsigned.provenance,
+        signature,
+        nonce: unsigned.nonce,
+        expires_at: unsigned.expires_at,
+    };
+    // An unauthorized call is not an error here, so that it can be retried
+    // with a new grant.
+    Ok(app.request(AppRequest::CallZome(Box::new(call))).await?)
+}
+
+/// The file in the sandbox directory which holds the keys used to sign
+/// zome calls for each cell of the sandbox's conductor.
+pub const ZOME_CALL_CREDENTIALS_FILE: &str = ".hc_zome_call_credentials";
+
+/// The signing key and cap secret used to make zome calls to one cell,
+/// and the functions which they are granted access to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ZomeCallCredentials {
+    provenance: String,
+    signing_key: Vec<u8>,
+    cap_secret: Vec<u8>,
+    functions: BTreeSet<GrantedFunction>,
+}
+
+impl ZomeCallCredentials {
+    fn provenance(&self) -> anyhow::Result<AgentPubKey> {
+        parse_agent_key(&self.provenance)
+    }
+
+    fn signing_key(
+        &self,
+    ) -> anyhow::Result<sodoken::BufReadSized<{ sodoken::sign::SECRETKEYBYTES }>> {
+        ensure!(
+            self.signing_key.len() == sodoken::sign::SECRETKEYBYTES,
+            "Invalid zome call signing key"
+        );
+        let key = sodoken::BufWriteSized::new_no_lock();
+        key.write_lock().copy_from_slice(&self.signing_key);
+        Ok(key.to_read_sized())
+    }
+
+    fn cap_secret(&self) -> anyhow::Result<CapSecret> {
+        let bytes: [u8; 64] = self
+            .cap_secret
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("Invalid zome call cap secret"))?;
+        Ok(CapSecret::from(bytes))
+    }
+}
+
+/// Zome call credentials keyed by cell id.
+type CredentialsFile = std::collections::BTreeMap<String, ZomeCallCredentials>;
+
+fn credentials_key(cell_id: &CellId) -> String {
+    format!("{}:{}", cell_id.dna_hash(), cell_id.agent_pubkey())
+}
+
+fn load_credentials(path: &Path) -> anyhow::Result<CredentialsFile> {
+    match std::fs::read(path) {
+        // The file is not overwritten when it can't be read, as that would
+        // lose the credentials of every other cell in it.
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+            anyhow!(
+                "Failed to read the zome call credentials in {}, remove the file to grant new ones: {}",
+                path.display(),
+                e
+            )
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CredentialsFile::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save_credentials(path: &Path, credentials: &CredentialsFile) -> anyhow::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    // The mode only applies to a new file
+    #[cfg(unix)]
+    std::fs::set_permissions(path, std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    std::io::Write::write_all(&mut file, &serde_json::to_vec(credentials)?)?;
+    Ok(())
+}
+
+/// Generate a new signing key and grant it access to some functions of a cell.
+async fn grant_zome_call_credentials(
+    cmd: &mut CmdRunner,
+    cell_id: &CellId,
+    functions: BTreeSet<GrantedFunction>,
+) -> anyhow::Result<ZomeCallCredentials> {
+    let (signing_key, signing_secret) = generate_signing_keypair().await?;
+    let cap_secret = random_bytes::<64>().await?;
+    let cap_grant = ZomeCallCapGrant {
+        tag: "hc-sandbox".to_string(),
+        access: CapAccess::Assigned {
+            secret: CapSecret::from(cap_secret),
+            assignees: [signing_key.clone()].into_iter().collect(),
+        },
+        functions: GrantedFunctions::Listed(functions.clone()),
+    };
+    let resp = cmd
+        .command(AdminRequest::GrantZomeCallCapability(Box::new(
+            GrantZomeCallCapabilityPayload {
+                cell_id: cell_id.clone(),
+                cap_grant,
+            },
+        )))
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::ZomeCallCapabilityGranted),
+        "Failed to grant zome call capability, got: {:?}",
+        resp
+    );
+
+    Ok(ZomeCallCredentials {
+        provenance: signing_key.to_string(),
+        signing_key: signing_secret.read_lock().to_vec(),
+        cap_secret: cap_secret.to_vec(),
+        functions,
+    })
+}
+
+/// Render a decoded msgpack value as JSON.
+/// Binary data which is a holo hash is shown in its base64 form,
+/// and any other binary data as an array of bytes.
+pub fn msgpack_to_json(value: rmpv::Value) -> serde_json::Value {
+    use rmpv::Value;
+    use serde_json::Value as Json;
+    match value {
+        Value::Nil => Json::Null,
+        Value::Boolean(b) => Json::Bool(b),
+        Value::Integer(i) => match (i.as_u64(), i.as_i64()) {
+            (Some(u), _) => u.into(),
+            (_, Some(i)) => i.into(),
+            _ => Json::Null,
+        },
+        Value::F32(f) => f.into(),
+        Value::F64(f) => f.into(),
+        Value::String(s) => match s.into_str() {
+            Some(s) => Json::String(s),
+            None => Json::Null,
+        },
+        Value::Binary(bytes) => binary_to_json(bytes),
+        Value::Array(values) => Json::Array(values.into_iter().map(msgpack_to_json).collect()),
+        Value::Map(entries) => Json::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| {
+                    let key = match msgpack_to_json(k) {
+                        Json::String(s) => s,
+                        k => k.to_string(),
+                    };
+                    (key, msgpack_to_json(v))
+                })
+                .collect(),
+        ),
+        Value::Ext(_, bytes) => binary_to_json(bytes),
+    }
+}
+
+fn binary_to_json(bytes: Vec<u8>) -> serde_json::Value {
+    if bytes.len() == HOLO_HASH_FULL_LEN {
+        if let Ok(hash) = AgentPubKey::from_raw_39(bytes.clone()) {
+            return hash.to_string().into();
+        }
+        if let Ok(hash) = AnyLinkableHash::from_raw_39(bytes.clone()) {
+            return hash.to_string().into();
+        }
+        if let Ok(hash) = DnaHash::from_raw_39(bytes.clone()) {
+            return hash.to_string().into();
+        }
+    }
+    bytes.into()
+}
+
+/// Calls [`AppRequest::CreateCloneCell`] and creates a clone cell.
+pub async fn create_clone_cell(
+    cmd: &mut CmdRunner,
+    args: CreateCloneCell,
+) -> anyhow::Result<InstalledCell> {
+    let CreateCloneCell {
+        app_port,
+        app_id,
+        role_name,
+        network_seed,
+        properties,
+        origin_time,
+        name,
+    } = args;
+    let properties = match properties {
+        Some(path) => Some(YamlProperties::new(serde_yaml::from_str(
+            &std::fs::read_to_string(path)?,
+        )?)),
+        None => None,
+    };
+    let payload = CreateCloneCellPayload {
+        app_id,
+        role_name,
+        modifiers: DnaModifiersOpt {
+            properties,
+            network_seed,
+            origin_time,
+            quantum_time: None,
+        },
+        membrane_proof: None,
+        name,
+    };
+    let mut app = connect_app(cmd, app_port).await?;
+    let resp = app_request(&mut app, AppRequest::CreateCloneCell(Box::new(payload))).await?;
+    Ok(expect_match!(resp => AppResponse::CloneCellCreated, "Failed to create clone cell"))
+}
+
+/// Calls [`AppRequest::DisableCloneCell`] and disables a clone cell.
+pub async fn disable_clone_cell(cmd: &mut CmdRunner, args: DisableCloneCell) -> anyhow::Result<()> {
+    let payload = DisableCloneCellPayload {
+        app_id: args.app_id,
+        clone_cell_id: CloneCellId::CloneId(args.clone_id),
+    };
+    let mut app = connect_app(cmd, args.app_port).await?;
+    let resp = app_request(&mut app, AppRequest::DisableCloneCell(Box::new(payload))).await?;
+    ensure!(
+        matches!(resp, AppResponse::CloneCellDisabled),
+        "Failed to disable clone cell, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AppRequest::EnableCloneCell`] and enables a disabled clone cell.
+pub async fn enable_clone_cell(
+    cmd: &mut CmdRunner,
+    args: EnableCloneCell,
+) -> anyhow::Result<InstalledCell> {
+    let payload = EnableCloneCellPayload {
+        app_id: args.app_id,
+        clone_cell_id: CloneCellId::CloneId(args.clone_id),
+    };
+    let mut app = connect_app(cmd, args.app_port).await?;
+    let resp = app_request(&mut app, AppRequest::EnableCloneCell(Box::new(payload))).await?;
+    Ok(expect_match!(resp => AppResponse::CloneCellEnabled, "Failed to enable clone cell"))
+}
+
+/// Calls [`AppRequest::NetworkInfo`] for the given dnas.
+pub async fn network_info(
+    cmd: &mut CmdRunner,
+    args: NetworkInfoArgs,
+) -> anyhow::Result<Vec<NetworkInfo>> {
+    let payload = NetworkInfoRequestPayload { dnas: args.dnas };
+    let mut app = connect_app(cmd, args.app_port).await?;
+    let resp = app_request(&mut app, AppRequest::NetworkInfo(Box::new(payload))).await?;
+    Ok(expect_match!(resp => AppResponse::NetworkInfo, "Failed to get network info"))
+}
+
+/// How long a zome call made from the CLI remains valid for.
+const ZOME_CALL_EXPIRES_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// Connect to the given app port, or to the first app interface
+/// of this conductor, attaching one if there are none.
+async fn connect_app(
+    cmd: &mut CmdRunner,
+    app_port: Option<u16>,
+) -> anyhow::Result<WebsocketSender> {
+    let port = match app_port {
+        Some(port) => port,
+        None => match list_app_ws(cmd).await?.first() {
+            Some(port) => *port,
+            None => {
+                attach_app_interface(
+                    cmd,
+                    AddAppWs {
+                        port: None,
+                        require_authentication: false,
+                    },
+                )
+                .await?
+            }
+        },
+    };
+    Ok(get_app_api(port).await?)
+}
+
+async fn app_request(app: &mut WebsocketSender, req: AppRequest) -> anyhow::Result<AppResponse> {
+    let resp: AppResponse = app.request(req).await?;
+    match resp {
+        AppResponse::Error(e) => bail!("App request failed: {:?}", e),
+        resp => Ok(resp),
+    }
+}
+
+async fn generate_signing_keypair() -> anyhow::Result<(
+    AgentPubKey,
+    sodoken::BufReadSized<{ sodoken::sign::SECRETKEYBYTES }>,
+)> {
+    let pub_key = sodoken::BufWriteSized::new_no_lock();
+    let sec_key = sodoken::BufWriteSized::new_no_lock();
+    sodoken::sign::keypair(pub_key.clone(), sec_key.clone()).await?;
+    let pub_key = AgentPubKey::from_raw_32(pub_key.read_lock().to_vec());
+    Ok((pub_key, sec_key.to_read_sized()))
+}
+
+async fn sign(
+    sec_key: sodoken::BufReadSized<{ sodoken::sign::SECRETKEYBYTES }>,
+    data: Vec<u8>,
+) -> anyhow::Result<Signature> {
+    let sig = sodoken::BufWriteSized::<{ sodoken::sign::BYTES }>::new_no_lock();
+    sodoken::sign::detached(sig.clone(), data, sec_key).await?;
+    let mut bytes = [0; sodoken::sign::BYTES];
+    bytes.copy_from_slice(&sig.read_lock()[..]);
+    Ok(Signature(bytes))
+}
+
+async fn random_bytes<const N: usize>() -> anyhow::Result<[u8; N]> {
+    let buf = sodoken::BufWriteSized::<N>::new_no_lock();
+    sodoken::random::bytes_buf(buf.clone()).await?;
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(&buf.read_lock()[..]);
+    Ok(bytes)
+}
+
+/// No origins given on the command line means only origins on this
+/// machine are allowed, and `*` means any origin is.
+fn allowed_origins(origins: Vec<String>) -> AllowedOrigins {
+    if origins.is_empty() {
+        AllowedOrigins::Localhost
+    } else if origins.iter().any(|origin| origin == "*") {
+        AllowedOrigins::Any
+    } else {
+        AllowedOrigins::Origins(origins.into_iter().collect())
+    }
+}
+
 fn parse_agent_key(arg: &str) -> anyhow::Result<AgentPubKey> {
     AgentPubKey::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
@@ -595,6 +1430,10 @@ fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
     DnaHash::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
 
+fn parse_clone_id(arg: &str) -> anyhow::Result<CloneId> {
+    CloneId::try_from(arg.to_string()).map_err(|e| anyhow::anyhow!("{:?}", e))
+}
+
 fn parse_status_filter(arg: &str) -> anyhow::Result<AppStatusFilter> {
     match arg {
         "active" => Ok(AppStatusFilter::Enabled),
@@ -629,3 +1468,123 @@ impl From<ListAgents> for Option<CellId> {
             .map(|(d, a)| CellId::new(d, a))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::ActionHash;
+    use matches::assert_matches;
+    use serde_json::json;
+
+    fn parse(args: &[&str]) -> anyhow::Result<AdminRequestCli> {
+        Ok(AdminRequestCli::from_iter_safe(
+            std::iter::once("call").chain(args.iter().copied()),
+        )?)
+    }
+
+    #[test]
+    fn app_interface_subcommands_are_parsed() {
+        let dna = DnaHash::from_raw_36(vec![1; 36]);
+        let agent = AgentPubKey::from_raw_36(vec![2; 36]);
+
+        let call = parse(&[
+            "call-zome",
+            &dna.to_string(),
+            &agent.to_string(),
+            "zome",
+            "func",
+        ])
+        .unwrap();
+        assert_matches!(call, AdminRequestCli::CallZome(CallZome {
+            app_port: None,
+            dna: d,
+            agent_key: a,
+            payload,
+            ..
+        }) if d == dna && a == agent && payload == "null");
+
+        let call = parse(&[
+            "create-clone-cell",
+            "--app-port",
+            "8888",
+            "--network-seed",
+            "seed",
+            "app",
+            "role",
+        ])
+        .unwrap();
+        assert_matches!(call, AdminRequestCli::CreateCloneCell(CreateCloneCell {
+            app_port: Some(8888),
+            network_seed: Some(seed),
+            ..
+        }) if seed == "seed");
+
+        let call = parse(&["disable-clone-cell", "app", "role.1"]).unwrap();
+        assert_matches!(call, AdminRequestCli::DisableCloneCell(DisableCloneCell {
+            clone_id,
+            ..
+        }) if clone_id == CloneId::new(&"role".into(), 1));
+        assert!(parse(&["enable-clone-cell", "app", "not-a-clone-id"]).is_err());
+
+        assert!(parse(&["network-info"]).is_err());
+        let call = parse(&["network-info", &dna.to_string()]).unwrap();
+        assert_matches!(call, AdminRequestCli::NetworkInfo(NetworkInfoArgs { dnas, .. }) if dnas == vec![dna]);
+    }
+
+    #[test]
+    fn msgpack_results_are_rendered_as_json() {
+        let hash = ActionHash::from_raw_36(vec![3; 36]);
+        let result = ExternIO::encode((hash.clone(), -1, "text", Option::<()>::None)).unwrap();
+        let value: rmpv::Value = result.decode().unwrap();
+        assert_eq!(
+            msgpack_to_json(value),
+            json!([hash.to_string(), -1, "text", null])
+        );
+
+        let value = rmpv::Value::Map(vec![
+            (rmpv::Value::from(1), rmpv::Value::Binary(vec![1, 2, 3])),
+            (rmpv::Value::from("key"), rmpv::Value::from(1.5)),
+        ]);
+        assert_eq!(
+            msgpack_to_json(value),
+            json!({ "1": [1, 2, 3], "key": 1.5 })
+        );
+    }
+
+    #[test]
+    fn zome_call_credentials_are_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ZOME_CALL_CREDENTIALS_FILE);
+        assert!(load_credentials(&path).unwrap().is_empty());
+
+        let cell_id = CellId::new(
+            DnaHash::from_raw_36(vec![1; 36]),
+            AgentPubKey::from_raw_36(vec![2; 36]),
+        );
+        let credentials = ZomeCallCredentials {
+            provenance: AgentPubKey::from_raw_36(vec![4; 36]).to_string(),
+            signing_key: vec![5; sodoken::sign::SECRETKEYBYTES],
+            cap_secret: vec![6; 64],
+            functions: [("zome".into(), "fn".into())].into_iter().collect(),
+        };
+        let mut all = CredentialsFile::new();
+        all.insert(credentials_key(&cell_id), credentials.clone());
+        // A file which can't be read is an error rather than being replaced,
+        // and an existing file is made private too
+        std::fs::write(&path, "stale").unwrap();
+        assert!(load_credentials(&path).is_err());
+        save_credentials(&path, &all).unwrap();
+
+        let loaded = load_credentials(&path).unwrap();
+        assert_eq!(loaded.get(&credentials_key(&cell_id)), Some(&credentials));
+        assert!(credentials.signing_key().is_ok());
+        assert_eq!(credentials.cap_secret().unwrap(), CapSecret::from([6; 64]));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -84,6 +84,17 @@ pub enum HcSandboxSubcommand {
 
     /// Create a fresh sandbox with no apps installed.
     Create(Create),
+
+    /// Restore the databases of a sandbox from a backup archive
+    /// made with `hc sandbox call create-backup`.
+    ///
+    /// The sandbox must not contain any databases yet, e.g. a sandbox made with `hc sandbox create`.
+    /// Every database and source chain in the archive is verified before anything is restored.
+    ///
+    /// The keystore of a sandbox is run by a separate lair process, so it is not restored.
+    /// To keep using the same agent keys, replace the files in the `keystore` directory
+    /// of the restored sandbox with those in the `keystore` directory of the archive.
+    Restore(Restore),
 }
 
 /// Options for running a sandbox
@@ -102,6 +113,17 @@ pub struct Run {
     existing: Existing,
 }
 
+/// Options for restoring a backup into a sandbox
+#[derive(Debug, StructOpt)]
+pub struct Restore {
+    /// The backup archive to restore.
+    archive: PathBuf,
+
+    /// (flattened)
+    #[structopt(flatten)]
+    existing: Existing,
+}
+
 impl HcSandbox {
     /// Run this command
     pub async fn run(self) -> anyhow::Result<()> {
@@ -181,6 +203,16 @@ impl HcSandbox {
                 crate::save::save(std::env::current_dir()?, paths.clone())?;
                 msg!("Created {:?}", paths);
             }
+            HcSandboxSubcommand::Restore(Restore { archive, existing }) => {
+                let paths = existing.load()?;
+                let path = match paths.as_slice() {
+                    [path] => path.clone(),
+                    [] => anyhow::bail!("There is no sandbox to restore the backup into"),
+                    _ => anyhow::bail!("A backup can only be restored into one sandbox at a time"),
+                };
+                crate::run::restore(&self.holochain_path, path.clone(), archive).await?;
+                msg!("Restored backup into {}", path.display());
+            }
         }
 
         Ok(())
@@ -73,6 +73,13 @@
 //! ```shell
 //! hc sandbox call list-cells
 //! ```
+//! Some calls are made over an app interface instead, such as making
+//! zome calls and managing clone cells. These use the first attached app
+//! interface unless `--app-port` is given. Payloads and results are JSON.
+//!
+//! ```shell
+//! hc sandbox call call-zome <DNA> <AGENT_KEY> my_zome my_fn '{"some": "input"}'
+//! ```
 //! #### List and Clean
 //! These commands allow you to list the persisted sandboxes
 //! in the current directory (from the`.hcXXX`) file.
@@ -115,6 +122,8 @@ use holochain_conductor_api::{AdminRequest, AdminResponse};
 use holochain_websocket::WebsocketResult;
 use holochain_websocket::WebsocketSender;
 use ports::get_admin_api;
+#[cfg(unix)]
+use ports::get_admin_api_unix;
 
 pub use ports::force_admin_port;
 
@@ -163,6 +172,14 @@ impl CmdRunner {
         Ok(Self { client })
     }
 
+    /// Create a new connection for calling admin interface commands
+    /// over the unix domain socket at this path.
+    #[cfg(unix)]
+    pub async fn try_new_unix(path: &Path) -> WebsocketResult<Self> {
+        let client = get_admin_api_unix(path).await?;
+        Ok(Self { client })
+    }
+
     /// Create a command runner from a sandbox path.
     /// This expects holochain to be on the path.
     pub async fn from_sandbox(
@@ -1,4 +1,4 @@
-//! Helpers for working with websockets and ports.
+//! Helpers for working with websockets, ports and unix domain sockets.
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -32,7 +32,8 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
         if let Some(config) = read_config(p)? {
             if let Some(ai) = config.admin_interfaces {
                 if let Some(AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port },
+                    driver: InterfaceDriver::Websocket { port, .. },
+                    ..
                 }) = ai.get(0)
                 {
                     ports.push(*port)
@@ -48,6 +49,19 @@ pub(crate) async fn get_admin_api(port: u16) -> WebsocketResult<WebsocketSender>
     websocket_client_by_port(port).await.map(|p| p.0)
 }
 
+#[cfg(unix)]
+pub(crate) async fn get_admin_api_unix(path: &std::path::Path) -> WebsocketResult<WebsocketSender> {
+    tracing::debug!(?path);
+    ws::connect_unix(path, Arc::new(WebsocketConfig::default()))
+        .await
+        .map(|p| p.0)
+}
+
+pub(crate) async fn get_app_api(port: u16) -> WebsocketResult<WebsocketSender> {
+    tracing::debug!(port);
+    websocket_client_by_port(port).await.map(|p| p.0)
+}
+
 async fn websocket_client_by_port(
     port: u16,
 ) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
@@ -61,35 +75,48 @@ async fn websocket_client_by_port(
 pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
     match config.admin_interfaces.as_mut().and_then(|i| i.first_mut()) {
         Some(AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::Websocket { port, .. },
+            ..
         }) => {
             if *port != 0 {
                 *port = 0;
             }
         }
+        // A unix domain socket has no port to randomize.
+        Some(AdminInterfaceConfig {
+            driver: InterfaceDriver::UnixSocket { .. },
+            ..
+        }) => (),
         None => {
             let port = 0;
             config.admin_interfaces = Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
+                driver: InterfaceDriver::websocket(port),
+                require_authentication: false,
             }]);
         }
     }
 }
 
 pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
-    let p = port;
-    let port = AdminInterfaceConfig {
-        driver: InterfaceDriver::Websocket { port },
-    };
     match config
         .admin_interfaces
         .as_mut()
         .and_then(|ai| ai.get_mut(0))
     {
+        // Keep the configured bind address and allowed origins.
+        Some(AdminInterfaceConfig {
+            driver: InterfaceDriver::Websocket { port: p, .. },
+            ..
+        }) => *p = port,
         Some(admin_interface) => {
-            *admin_interface = port;
+            admin_interface.driver = InterfaceDriver::websocket(port);
+        }
+        None => {
+            config.admin_interfaces = Some(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::websocket(port),
+                require_authentication: false,
+            }])
         }
-        None => config.admin_interfaces = Some(vec![port]),
     }
-    msg!("Admin port set to: {}", p);
+    msg!("Admin port set to: {}", port);
 }
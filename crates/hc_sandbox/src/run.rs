@@ -45,6 +45,10 @@ pub async fn run(
             &mut cmd,
             AddAppWs {
                 port: Some(app_port),
+                unix_socket: None,
+                bind_address: None,
+                allowed_origins: Vec::new(),
+                require_authentication: false,
             },
         )
         .await?;
@@ -99,6 +103,34 @@ pub async fn run_async(
     Ok((port, child, lair))
 }
 
+/// Restore a backup archive into the databases of a sandbox and wait for it to finish.
+/// Requires the holochain binary is available
+/// on the `holochain_path`.
+/// The sandbox must not contain any databases yet.
+pub async fn restore(
+    holochain_path: &Path,
+    sandbox_path: PathBuf,
+    archive: PathBuf,
+) -> anyhow::Result<()> {
+    let mut config_path = sandbox_path;
+    config_path.push(CONDUCTOR_CONFIG);
+    let mut cmd = Command::new(holochain_path);
+    cmd.arg("--config-path")
+        .arg(config_path)
+        .arg("--restore-backup")
+        .arg(archive);
+
+    msg!("{:?}", cmd);
+
+    let status = cmd.status().await?;
+    anyhow::ensure!(
+        status.success(),
+        "Failed to restore backup, holochain exited with {}",
+        status
+    );
+    Ok(())
+}
+
 async fn start_holochain(
     holochain_path: &Path,
     config_path: PathBuf,
@@ -198,3 +198,43 @@ pub fn update_cap_grant(
         chain_top_ordering: ChainTopOrdering::default(),
     })
 }
+
+/// List the live capability grants on the local source chain.
+///
+/// Only grants that have not been updated or deleted are returned, each with the
+/// [`ActionHash`] of the action that created it, suitable for passing to
+/// [`update_cap_grant`] or [`delete_cap_grant`].
+///
+/// Filter the grants by tag, assigned grantee and granted function with [`CapGrantQuery`].
+/// The default query returns every live grant.
+///
+/// The implicit [`CapGrant::ChainAuthor`] grant is never included as it is not an entry on the chain.
+/// Grants are read from the chain as it was at the start of the current call, so grants created
+/// by the current call are not included.
+///
+/// ```ignore
+/// let grants = capability_grants(CapGrantQuery::default().tag("my-tag"))?;
+/// ```
+pub fn capability_grants(cap_grant_query: CapGrantQuery) -> ExternResult<Vec<CapGrantInfo>> {
+    HDK.with(|h| h.borrow().capability_grants(cap_grant_query))
+}
+
+/// List the live capability claims on the local source chain.
+///
+/// Only claims that have not been deleted are returned.
+/// Filter the claims by tag and grantor with [`CapClaimQuery`].
+/// The default query returns every live claim.
+///
+/// As with [`capability_grants`], claims created by the current call are not included.
+pub fn capability_claims(cap_claim_query: CapClaimQuery) -> ExternResult<Vec<CapClaim>> {
+    HDK.with(|h| h.borrow().capability_claims(cap_claim_query))
+}
+
+/// Get the capability grant which authorized the current zome call.
+///
+/// The [`CapabilityInfo`] includes the calling agent, the grant itself and the [`ActionHash`] of
+/// the action that created the grant. Calls made by the chain author are authorized by the
+/// implicit [`CapGrant::ChainAuthor`] grant, which has no action hash.
+pub fn capability_info() -> ExternResult<CapabilityInfo> {
+    HDK.with(|h| h.borrow().capability_info(()))
+}
@@ -31,3 +31,33 @@ pub fn get_agent_activity(
 pub fn query(filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
     HDK.with(|h| h.borrow().query(filter))
 }
+
+/// Get the hash of the DNA this agent's source chain was migrated from,
+/// if the chain was opened with an [`Action::OpenChain`].
+///
+/// The agent's data from before the migration is held by their cell in that DNA.
+pub fn get_prev_dna_hash() -> ExternResult<Option<DnaHash>> {
+    Ok(
+        query(ChainQueryFilter::new().action_type(ActionType::OpenChain))?
+            .iter()
+            .find_map(|record| match record.action() {
+                Action::OpenChain(open_chain) => Some(open_chain.prev_dna_hash.clone()),
+                _ => None,
+            }),
+    )
+}
+
+/// Get the hash of the DNA this agent's source chain was migrated to,
+/// if the chain has been closed with an [`Action::CloseChain`].
+///
+/// Nothing more can be written to a closed chain.
+pub fn get_new_dna_hash() -> ExternResult<Option<DnaHash>> {
+    Ok(
+        query(ChainQueryFilter::new().action_type(ActionType::CloseChain))?
+            .iter()
+            .find_map(|record| match record.action() {
+                Action::CloseChain(close_chain) => Some(close_chain.new_dna_hash.clone()),
+                _ => None,
+            }),
+    )
+}
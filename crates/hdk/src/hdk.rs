@@ -36,6 +36,11 @@ pub trait HdkT: HdiT {
     fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
     fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
     fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
+    // Capability
+    fn capability_claims(&self, cap_claim_query: CapClaimQuery) -> ExternResult<Vec<CapClaim>>;
+    fn capability_grants(&self, cap_grant_query: CapGrantQuery)
+        -> ExternResult<Vec<CapGrantInfo>>;
+    fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapabilityInfo>;
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -52,6 +57,7 @@ pub trait HdkT: HdiT {
         &self,
         get_links_input: Vec<GetLinksInput>,
     ) -> ExternResult<Vec<LinkDetails>>;
+    fn count_links(&self, get_links_input: GetLinksInput) -> ExternResult<usize>;
     // P2P
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
@@ -106,6 +112,11 @@ mockall::mock! {
         fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
         fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
         fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
+        // Capability
+        fn capability_claims(&self, cap_claim_query: CapClaimQuery) -> ExternResult<Vec<CapClaim>>;
+        fn capability_grants(&self, cap_grant_query: CapGrantQuery)
+            -> ExternResult<Vec<CapGrantInfo>>;
+        fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapabilityInfo>;
         // CounterSigning
         fn accept_countersigning_preflight_request(
             &self,
@@ -122,6 +133,7 @@ mockall::mock! {
             &self,
             get_links_input: Vec<GetLinksInput>,
         ) -> ExternResult<Vec<LinkDetails>>;
+        fn count_links(&self, get_links_input: GetLinksInput) -> ExternResult<usize>;
         // P2P
         fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
         fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
@@ -297,6 +309,16 @@ impl HdkT for ErrHdk {
     fn get_details(&self, _: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         Self::err()
     }
+    // Capability
+    fn capability_claims(&self, _: CapClaimQuery) -> ExternResult<Vec<CapClaim>> {
+        Self::err()
+    }
+    fn capability_grants(&self, _: CapGrantQuery) -> ExternResult<Vec<CapGrantInfo>> {
+        Self::err()
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapabilityInfo> {
+        Self::err()
+    }
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -323,6 +345,9 @@ impl HdkT for ErrHdk {
     fn get_link_details(&self, _: Vec<GetLinksInput>) -> ExternResult<Vec<LinkDetails>> {
         Self::err()
     }
+    fn count_links(&self, _: GetLinksInput) -> ExternResult<usize> {
+        Self::err()
+    }
     // P2P
     fn call(&self, _: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>> {
         Self::err()
@@ -488,6 +513,19 @@ impl HdkT for HostHdk {
     fn get_details(&self, get_inputs: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         host_call::<Vec<GetInput>, Vec<Option<Details>>>(__get_details, get_inputs)
     }
+    // Capability
+    fn capability_claims(&self, cap_claim_query: CapClaimQuery) -> ExternResult<Vec<CapClaim>> {
+        host_call::<CapClaimQuery, Vec<CapClaim>>(__capability_claims, cap_claim_query)
+    }
+    fn capability_grants(
+        &self,
+        cap_grant_query: CapGrantQuery,
+    ) -> ExternResult<Vec<CapGrantInfo>> {
+        host_call::<CapGrantQuery, Vec<CapGrantInfo>>(__capability_grants, cap_grant_query)
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapabilityInfo> {
+        host_call::<(), CapabilityInfo>(__capability_info, ())
+    }
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -519,6 +557,9 @@ impl HdkT for HostHdk {
     ) -> ExternResult<Vec<LinkDetails>> {
         host_call::<Vec<GetLinksInput>, Vec<LinkDetails>>(__get_link_details, get_links_input)
     }
+    fn count_links(&self, get_links_input: GetLinksInput) -> ExternResult<usize> {
+        host_call::<GetLinksInput, usize>(__count_links, get_links_input)
+    }
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>> {
         host_call::<Vec<Call>, Vec<ZomeCallResponse>>(__call, call)
     }
@@ -195,3 +195,96 @@ pub fn get_link_details(
         .next()
         .unwrap())
 }
+
+/// Like [ `get_links` ] but only returns the links matching a [ `GetLinksFilter` ].
+///
+/// Links can be narrowed down by author and by the time they were created,
+/// and paged through by setting a limit and passing a [ `LinkCursor` ] to the last
+/// link of the previous page.
+///
+/// e.g. to get all links created by `agent` in pages of ten:
+///
+/// ```ignore
+/// let mut filter = GetLinksFilter::default().author(agent).limit(10);
+/// loop {
+///     let links = get_links_with_filter(base.clone(), .., None, filter.clone())?;
+///     let Some(last) = links.last() else { break };
+///     filter = filter.cursor(LinkCursor::from(last));
+///     // ...
+/// }
+/// ```
+///
+/// Links are ordered by their timestamp and then by the hash of their create action.
+/// A page may hold fewer links than the limit while later links exist, so only an
+/// empty page means there are no more links.
+pub fn get_links_with_filter(
+    base: impl Into<AnyLinkableHash>,
+    link_type: impl LinkTypeFilterExt,
+    link_tag: Option<LinkTag>,
+    filter: GetLinksFilter,
+) -> ExternResult<Vec<Link>> {
+    let link_type = link_type.try_into_filter()?;
+    Ok(HDK
+        .with(|h| {
+            h.borrow().get_links(vec![
+                GetLinksInput::new(base.into(), link_type, link_tag).with_filter(filter)
+            ])
+        })?
+        .into_iter()
+        .next()
+        .unwrap())
+}
+
+/// Like [ `get_link_details` ] but only returns the links matching a [ `GetLinksFilter` ].
+///
+/// Unlike [ `get_links_with_filter` ], deleted links count towards the limit of the filter,
+/// as they are returned along with their deletes.
+/// Use [ `LinkCursor::from_create_link` ] on the last create of a page to get the next page.
+///
+/// See [ `get_links_with_filter` ].
+pub fn get_link_details_with_filter(
+    base: impl Into<AnyLinkableHash>,
+    link_type: impl LinkTypeFilterExt,
+    link_tag: Option<LinkTag>,
+    filter: GetLinksFilter,
+) -> ExternResult<LinkDetails> {
+    let link_type = link_type.try_into_filter()?;
+    Ok(HDK
+        .with(|h| {
+            h.borrow().get_link_details(vec![
+                GetLinksInput::new(base.into(), link_type, link_tag).with_filter(filter)
+            ])
+        })?
+        .into_iter()
+        .next()
+        .unwrap())
+}
+
+/// Count the links on a base which haven't been deleted, optionally filtered by
+/// link type and tag, without fetching the links themselves.
+///
+/// The author and time conditions of the filter narrow down the links which are counted,
+/// its limit and cursor are ignored.
+///
+/// e.g. to show how many likes a post has:
+///
+/// ```ignore
+/// let likes = count_links(post_hash, LinkTypes::Like, None, GetLinksFilter::default())?;
+/// ```
+///
+/// The links counted by the authorities for the base are merged with the links held
+/// locally, so a link is never counted twice.
+///
+/// See [ `get_links` ].
+pub fn count_links(
+    base: impl Into<AnyLinkableHash>,
+    link_type: impl LinkTypeFilterExt,
+    link_tag: Option<LinkTag>,
+    filter: GetLinksFilter,
+) -> ExternResult<usize> {
+    let link_type = link_type.try_into_filter()?;
+    HDK.with(|h| {
+        h.borrow()
+            .count_links(GetLinksInput::new(base.into(), link_type, link_tag).with_filter(filter))
+    })
+}
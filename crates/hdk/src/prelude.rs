@@ -1,9 +1,14 @@
+pub use crate::capability::capability_claims;
+pub use crate::capability::capability_grants;
+pub use crate::capability::capability_info;
 pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
 pub use crate::capability::generate_cap_secret;
 pub use crate::capability::update_cap_grant;
 pub use crate::chain::get_agent_activity;
+pub use crate::chain::get_new_dna_hash;
+pub use crate::chain::get_prev_dna_hash;
 pub use crate::chain::must_get_agent_activity;
 pub use crate::chain::query;
 pub use crate::countersigning::accept_countersigning_preflight_request;
@@ -40,10 +45,13 @@ pub use crate::info::agent_info;
 pub use crate::info::call_info;
 pub use crate::info::dna_info;
 pub use crate::info::zome_info;
+pub use crate::link::count_links;
 pub use crate::link::create_link;
 pub use crate::link::delete_link;
 pub use crate::link::get_link_details;
+pub use crate::link::get_link_details_with_filter;
 pub use crate::link::get_links;
+pub use crate::link::get_links_with_filter;
 pub use crate::link::LinkTypeFilterExt;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
@@ -129,6 +137,7 @@ macro_rules! holochain_externs {
             __get_details,
             __get_links,
             __get_link_details,
+            __count_links,
             __get_agent_activity,
             __must_get_entry,
             __must_get_valid_record,
@@ -136,7 +136,19 @@ pub fn schedule(scheduled_fn: &str) -> ExternResult<()> {
     HDK.with(|h| h.borrow().schedule(String::from(scheduled_fn)))
 }
 
-/// @todo Not implemented
+/// Suspend the current zome call for the given duration.
+///
+/// The host blocks the thread running the zome call while sleeping, which is
+/// not one of the threads running other zome calls or conductor tasks, so
+/// only this call waits. This is suitable for short backoff and polling loops
+/// without busy waiting.
+///
+/// - A single sleep is bounded by the host to the timeout which the conductor
+///   applies to zome calls made over app interfaces, currently 30 seconds.
+///   Longer durations return an error rather than sleeping, as the zome call
+///   would time out anyway.
+/// - Sleeping is non-deterministic so it is not available in validation or any
+///   other integrity context.
 pub fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
     HDK.with(|h| h.borrow().sleep(wake_after))
 }
@@ -0,0 +1,26 @@
+//! A stand-in Chain Head Coordinator server, for testing multi-device agents.
+//! All state is kept in memory and lost when the server stops.
+
+use std::net::SocketAddr;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "hc-chc-server", about = "A local, in-memory CHC server for testing.")]
+struct Opt {
+    /// The address to bind to.
+    #[structopt(short, long, default_value = "127.0.0.1:0")]
+    interface: SocketAddr,
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    let opt = Opt::from_args();
+
+    match holochain::conductor::chc::chc_server::run_chc_server(opt.interface).await {
+        Ok((addr, server)) => {
+            println!("http://{}", addr);
+            let _ = server.await;
+        }
+        Err(err) => eprintln!("{}", err),
+    }
+}
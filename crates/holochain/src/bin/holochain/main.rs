@@ -60,6 +60,28 @@ struct Opt {
         help = "Display version information such as git revision and HDK version"
     )]
     build_info: bool,
+
+    #[structopt(
+        long,
+        value_name = "ARCHIVE",
+        help = "Restore the databases from a backup archive into the configured environment
+    directory and exit, instead of running the conductor. The environment directory must
+    not contain any databases yet. Every database and source chain in the archive is
+    verified before anything is restored. The keystore is restored into the lair root of
+    an in-process keystore, which must be empty. For other keystores, restore the files
+    in the archive's keystore directory by hand."
+    )]
+    restore_backup: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        value_name = "ARCHIVE",
+        help = "Write a backup of the databases and the keystore of the configured conductor
+    to a new archive and exit, instead of running the conductor. The conductor must be
+    stopped, so that its keystore doesn't change while it is copied. A running conductor
+    can back up its databases with the CreateBackup admin request."
+    )]
+    create_backup: Option<PathBuf>,
 }
 
 fn main() {
@@ -83,6 +105,15 @@ async fn async_main() {
     observability::init_fmt(opt.structured.clone()).expect("Failed to start contextual logging");
     debug!("observability initialized");
 
+    if let Some(archive) = opt.create_backup.clone() {
+        create_backup(&opt, archive).await;
+        return;
+    }
+    if let Some(archive) = opt.restore_backup.clone() {
+        restore_backup(&opt, archive).await;
+        return;
+    }
+
     kitsune_p2p_types::metrics::init_sys_info_poll();
 
     let conductor = conductor_handle_from_config_path(&opt).await;
@@ -170,6 +201,75 @@ async fn conductor_handle_from_config_path(opt: &Opt) -> ConductorHandle {
         .expect("Could not initialize Conductor from configuration")
 }
 
+/// Back up the databases and keystore of the configured conductor while it is stopped
+async fn create_backup(opt: &Opt, archive: PathBuf) {
+    let config_path_default = opt.config_path.is_none();
+    let config_path: ConfigFilePath = opt.config_path.clone().map(Into::into).unwrap_or_default();
+    let config = load_config(&config_path, config_path_default);
+    let keystore_path = holochain::conductor::backup::keystore_directory(&config);
+    let env_path = PathBuf::from(config.environment_path);
+
+    match holochain::conductor::backup::create_backup(env_path, keystore_path, archive.clone())
+        .await
+    {
+        Ok(manifest) => println!(
+            "Backed up {} databases and {} keystore files to {}.",
+            manifest.databases.len(),
+            manifest.keystore.len(),
+            archive.display()
+        ),
+        Err(e) => {
+            println!("Couldn't create backup: {}", e);
+            std::process::exit(ERROR_CODE);
+        }
+    }
+}
+
+/// Restore a backup into the environment directory of the configured conductor
+async fn restore_backup(opt: &Opt, archive: PathBuf) {
+    let config_path_default = opt.config_path.is_none();
+    let config_path: ConfigFilePath = opt.config_path.clone().map(Into::into).unwrap_or_default();
+    let config = load_config(&config_path, config_path_default);
+    // An external lair may be running, so its files are never replaced.
+    let keystore_path = match config.keystore {
+        KeystoreConfig::LairServerInProc { .. } => {
+            holochain::conductor::backup::keystore_directory(&config)
+        }
+        _ => None,
+    };
+    let env_path = PathBuf::from(config.environment_path);
+
+    match holochain::conductor::backup::restore_backup(
+        archive,
+        env_path.clone(),
+        keystore_path.clone(),
+    )
+    .await
+    {
+        Ok(manifest) => {
+            println!(
+                "Restored {} databases into {}.",
+                manifest.databases.len(),
+                env_path.display()
+            );
+            match keystore_path {
+                Some(keystore_path) if !manifest.keystore.is_empty() => {
+                    println!("Restored the keystore into {}.", keystore_path.display())
+                }
+                None if !manifest.keystore.is_empty() => println!(
+                    "The keystore was not restored. Its files are in the archive's {} directory.",
+                    holochain::conductor::backup::KEYSTORE_DIR
+                ),
+                _ => (),
+            }
+        }
+        Err(e) => {
+            println!("Couldn't restore backup: {}", e);
+            std::process::exit(ERROR_CODE);
+        }
+    }
+}
+
 /// Load config, throw friendly error on failure
 fn load_config(config_path: &ConfigFilePath, config_path_default: bool) -> ConductorConfig {
     match ConductorConfig::load_yaml(config_path.as_ref()) {
@@ -14,6 +14,7 @@
 // TODO: clean up allow(missing_docs) once parent is fully documented
 
 pub mod api;
+pub mod backup;
 mod cell;
 #[cfg(feature = "chc")]
 pub mod chc;
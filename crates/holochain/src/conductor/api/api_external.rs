@@ -1,4 +1,5 @@
 use crate::conductor::interface::error::InterfaceResult;
+use holochain_conductor_api::authentication::AuthenticationToken;
 use holochain_serialized_bytes::prelude::*;
 
 mod admin_interface;
@@ -18,4 +19,7 @@ pub trait InterfaceApi: 'static + Send + Sync + Clone {
         &self,
         request: Result<Self::ApiRequest, SerializedBytesError>,
     ) -> InterfaceResult<Self::ApiResponse>;
+    /// Authenticate a connection with the token its client presented,
+    /// returning the API which serves that connection
+    async fn authenticate(&self, token: AuthenticationToken) -> InterfaceResult<Self>;
 }
@@ -6,9 +6,12 @@ use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::SerializationError;
 use crate::conductor::conductor::CellStatus;
 use crate::conductor::error::ConductorError;
+use crate::conductor::interface::authentication::InterfaceKind;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
 use crate::conductor::ConductorHandle;
+use holochain_conductor_api::authentication::AuthenticationToken;
+use holochain_conductor_api::config::DEFAULT_BIND_ADDRESS;
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::dna::DnaBundle;
 use holochain_types::prelude::*;
@@ -50,12 +53,17 @@ pub trait AdminInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAdminInterfaceApi {
     /// Mutable access to the Conductor
     conductor_handle: ConductorHandle,
+    /// The token the connection this api serves was authenticated with, if any
+    token: Option<AuthenticationToken>,
 }
 
 impl RealAdminInterfaceApi {
     /// Create an admin interface api.
     pub fn new(conductor_handle: ConductorHandle) -> Self {
-        RealAdminInterfaceApi { conductor_handle }
+        RealAdminInterfaceApi {
+            conductor_handle,
+            token: None,
+        }
     }
 }
 
@@ -123,6 +131,10 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .ok_or(ConductorApiError::DnaMissing(*dna_hash))?;
                 Ok(AdminResponse::DnaDefinitionReturned(dna_def))
             }
+            ListZomeFunctions(dna_hash) => {
+                let zome_functions = self.conductor_handle.list_zome_functions(&dna_hash)?;
+                Ok(AdminResponse::ZomeFunctionsListed(zome_functions))
+            }
             UpdateCoordinators(payload) => {
                 let UpdateCoordinatorsPayload { dna_hash, source } = *payload;
                 let (coordinator_zomes, wasms) = match source {
@@ -227,15 +239,35 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppStarted(app.status().is_running()))
             }
-            AttachAppInterface { port } => {
+            AttachAppInterface {
+                port,
+                bind_address,
+                allowed_origins,
+                require_authentication,
+            } => {
                 let port = port.unwrap_or(0);
                 let port = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(either::Either::Left(port))
+                    .add_app_interface(
+                        either::Either::Left(port),
+                        bind_address.unwrap_or(DEFAULT_BIND_ADDRESS),
+                        allowed_origins,
+                        require_authentication,
+                    )
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
             }
+            AttachAppUnixSocketInterface {
+                path,
+                require_authentication,
+            } => {
+                self.conductor_handle
+                    .clone()
+                    .add_app_unix_socket_interface(path, require_authentication)
+                    .await?;
+                Ok(AdminResponse::AppUnixSocketInterfaceAttached)
+            }
             ListAppInterfaces => {
                 let interfaces = self.conductor_handle.list_app_interfaces().await?;
                 Ok(AdminResponse::AppInterfacesListed(interfaces))
@@ -291,6 +323,48 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::CloneCellDeleted)
             }
+            MigrateChain(payload) => {
+                let app_info = self
+                    .conductor_handle
+                    .clone()
+                    .migrate_chain(*payload)
+                    .await?;
+                Ok(AdminResponse::ChainMigrated(app_info))
+            }
+            IssueAuthenticationToken(payload) => {
+                let issued = self
+                    .conductor_handle
+                    .issue_authentication_token(*payload)
+                    .await?;
+                Ok(AdminResponse::AuthenticationTokenIssued(issued))
+            }
+            RevokeAuthenticationToken(token) => {
+                self.conductor_handle
+                    .revoke_authentication_token(&token)
+                    .await?;
+                Ok(AdminResponse::AuthenticationTokenRevoked)
+            }
+            CreateBackup { path } => {
+                let manifest = self.conductor_handle.create_backup(path).await?;
+                Ok(AdminResponse::BackupCreated(manifest))
+            }
+            ExportSourceChain {
+                cell_id,
+                encrypt_private_entries,
+            } => {
+                let archive = self
+                    .conductor_handle
+                    .export_source_chain(cell_id, encrypt_private_entries)
+                    .await?;
+                Ok(AdminResponse::SourceChainExported(archive))
+            }
+            ImportSourceChain { archive, validate } => {
+                self.conductor_handle
+                    .clone()
+                    .import_source_chain(archive, validate)
+                    .await?;
+                Ok(AdminResponse::SourceChainImported)
+            }
         }
     }
 }
@@ -311,11 +385,30 @@ impl InterfaceApi for RealAdminInterfaceApi {
                 .map_err(Box::new)
                 .map_err(InterfaceError::RequestHandler)?;
         }
+        if let Some(token) = &self.token {
+            // The token may have expired or been revoked since the
+            // connection was authenticated.
+            if let Err(e) = self
+                .conductor_handle
+                .authenticate(token, InterfaceKind::Admin)
+            {
+                return Ok(AdminResponse::Error(e.into()));
+            }
+        }
         match request {
             Ok(request) => Ok(AdminInterfaceApi::handle_admin_request(self, request).await),
             Err(e) => Ok(AdminResponse::Error(SerializationError::from(e).into())),
         }
     }
+
+    async fn authenticate(&self, token: AuthenticationToken) -> InterfaceResult<Self> {
+        self.conductor_handle
+            .authenticate(&token, InterfaceKind::Admin)?;
+        Ok(Self {
+            conductor_handle: self.conductor_handle.clone(),
+            token: Some(token),
+        })
+    }
 }
 
 #[cfg(test)]
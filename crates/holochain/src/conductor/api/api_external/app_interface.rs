@@ -2,16 +2,28 @@ use super::InterfaceApi;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
+use crate::conductor::interface::authentication::InterfaceKind;
+use crate::conductor::interface::error::AuthenticationError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::interface::SignalSubscriptions;
 use crate::conductor::ConductorHandle;
 
+use holochain_conductor_api::authentication::AuthenticationScope;
+use holochain_conductor_api::authentication::AuthenticationToken;
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
+use holochain_types::signal::Signal;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 pub use holochain_conductor_api::*;
 
+/// How long a zome call made over an app interface may run before the
+/// conductor stops waiting for it and responds with an error.
+pub const ZOME_CALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// The interface that a Conductor exposes to the outside world.
 #[async_trait::async_trait]
 pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
@@ -41,12 +53,123 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 #[derive(Clone)]
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
+    signal_subscriptions: SignalSubscriptions,
+    authorization: Option<AppAuthorization>,
+}
+
+/// What a connection which was authenticated with an app scoped token
+/// may access.
+#[derive(Clone)]
+struct AppAuthorization {
+    /// The token the connection was authenticated with
+    token: AuthenticationToken,
+    /// The apps the token was issued for
+    installed_app_ids: Arc<HashSet<InstalledAppId>>,
+    /// The cells of those apps, whose signals are sent over the connection.
+    /// They are looked up again when a request names a cell or DNA which
+    /// isn't among them, such as a clone cell created since.
+    cells: Arc<parking_lot::RwLock<HashSet<CellId>>>,
 }
 
 impl RealAppInterfaceApi {
     /// Create a new instance from a shared Conductor reference
     pub fn new(conductor_handle: ConductorHandle) -> Self {
-        Self { conductor_handle }
+        Self {
+            conductor_handle,
+            signal_subscriptions: SignalSubscriptions::default(),
+            authorization: None,
+        }
+    }
+
+    /// Create a copy of this API with no signal subscriptions of its own,
+    /// to serve a single connection
+    pub fn for_connection(&self) -> Self {
+        Self::new(self.conductor_handle.clone())
+    }
+
+    /// The signal subscriptions made through this API
+    pub fn signal_subscriptions(&self) -> &SignalSubscriptions {
+        &self.signal_subscriptions
+    }
+
+    /// Whether a signal may be sent over the connection this API serves.
+    ///
+    /// A connection authenticated with an app scoped token only gets signals
+    /// from the cells of its apps, and no system signals.
+    pub fn allows_signal(&self, signal: &Signal) -> bool {
+        let authorized = match (&self.authorization, signal) {
+            (None, _) => true,
+            (Some(authorization), Signal::App { cell_id, .. }) => {
+                authorization.cells.read().contains(cell_id)
+            }
+            (Some(_), Signal::System(_)) => false,
+        };
+        authorized && self.signal_subscriptions.allows(signal)
+    }
+
+    /// Check that the token this connection was authenticated with is still
+    /// valid, and that the apps it was issued for cover the request.
+    async fn check_authorized(
+        &self,
+        authorization: &AppAuthorization,
+        request: &AppRequest,
+    ) -> Result<(), ExternalApiWireError> {
+        self.conductor_handle
+            .authenticate(&authorization.token, InterfaceKind::App)?;
+        let installed_app_id = match request {
+            AppRequest::AppInfo { installed_app_id } => Some(installed_app_id),
+            AppRequest::CreateCloneCell(payload) => Some(&payload.app_id),
+            AppRequest::DisableCloneCell(payload) => Some(&payload.app_id),
+            AppRequest::EnableCloneCell(payload) => Some(&payload.app_id),
+            AppRequest::SignalSubscription(subscription) => Some(subscription.installed_app_id()),
+            AppRequest::CallZome(_) | AppRequest::NetworkInfo(_) => None,
+        };
+        if let Some(installed_app_id) = installed_app_id {
+            return if authorization.installed_app_ids.contains(installed_app_id) {
+                Ok(())
+            } else {
+                Err(AuthenticationError::AppNotAuthorized(installed_app_id.clone()).into())
+            };
+        }
+
+        let unauthorized = |cells: &HashSet<CellId>| match request {
+            AppRequest::CallZome(call) if !cells.contains(&call.cell_id) => {
+                Some(AuthenticationError::CellNotAuthorized(call.cell_id.clone()))
+            }
+            AppRequest::NetworkInfo(payload) => payload
+                .dnas
+                .iter()
+                .find(|dna_hash| !cells.iter().any(|cell| cell.dna_hash() == *dna_hash))
+                .map(|dna_hash| AuthenticationError::DnaNotAuthorized(dna_hash.clone())),
+            _ => None,
+        };
+        if unauthorized(&authorization.cells.read()).is_none() {
+            return Ok(());
+        }
+        // The apps may have gained cells since they were last looked up.
+        let cells = self
+            .authorized_cells(&authorization.installed_app_ids)
+            .await
+            .map_err(ExternalApiWireError::internal)?;
+        let result = match unauthorized(&cells) {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        };
+        *authorization.cells.write() = cells;
+        result
+    }
+
+    /// All the cells of these apps.
+    async fn authorized_cells(
+        &self,
+        installed_app_ids: &HashSet<InstalledAppId>,
+    ) -> ConductorApiResult<HashSet<CellId>> {
+        let state = self.conductor_handle.get_state().await?;
+        Ok(installed_app_ids
+            .iter()
+            .filter_map(|installed_app_id| state.get_app(installed_app_id).ok())
+            .flat_map(|app| app.all_cells().cloned())
+            .collect())
     }
 }
 
@@ -64,7 +187,23 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     .await?,
             )),
             AppRequest::CallZome(call) => {
-                match self.conductor_handle.call_zome(*call.clone()).await? {
+                let response = match tokio::time::timeout(
+                    ZOME_CALL_TIMEOUT,
+                    self.conductor_handle.call_zome(*call.clone()),
+                )
+                .await
+                {
+                    Ok(response) => response?,
+                    Err(_) => {
+                        return Ok(AppResponse::Error(ExternalApiWireError::InternalError(
+                            format!(
+                                "The call to the function {} in zome {} timed out after {:?}",
+                                call.fn_name, call.zome_name, ZOME_CALL_TIMEOUT
+                            ),
+                        )))
+                    }
+                };
+                match response {
                     Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
                     Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Ok(AppResponse::Error(
                         ExternalApiWireError::ZomeCallUnauthorized(format!(
@@ -112,7 +251,18 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                 let info = self.conductor_handle.network_info(&payload.dnas).await?;
                 Ok(AppResponse::NetworkInfo(info))
             }
-            AppRequest::SignalSubscription(_) => Ok(AppResponse::Unimplemented(request)),
+            AppRequest::SignalSubscription(subscription) => {
+                let cells = self
+                    .conductor_handle
+                    .get_state()
+                    .await?
+                    .get_app(subscription.installed_app_id())?
+                    .all_cells()
+                    .cloned()
+                    .collect();
+                self.signal_subscriptions.subscribe(subscription, cells);
+                Ok(AppResponse::SignalSubscribed)
+            }
         }
     }
 }
@@ -132,8 +282,41 @@ impl InterfaceApi for RealAppInterfaceApi {
                 .map_err(InterfaceError::RequestHandler)?;
         }
         match request {
-            Ok(request) => Ok(AppInterfaceApi::handle_app_request(self, request).await),
+            Ok(request) => {
+                if let Some(authorization) = &self.authorization {
+                    if let Err(e) = self.check_authorized(authorization, &request).await {
+                        return Ok(AppResponse::Error(e));
+                    }
+                }
+                Ok(AppInterfaceApi::handle_app_request(self, request).await)
+            }
             Err(e) => Ok(AppResponse::Error(SerializationError::from(e).into())),
         }
     }
+
+    async fn authenticate(&self, token: AuthenticationToken) -> InterfaceResult<Self> {
+        let installed_app_ids: HashSet<_> = match self
+            .conductor_handle
+            .authenticate(&token, InterfaceKind::App)?
+        {
+            AuthenticationScope::App { installed_app_ids } => {
+                installed_app_ids.into_iter().collect()
+            }
+            AuthenticationScope::Admin => {
+                return Err(AuthenticationError::WrongScope(InterfaceKind::App).into())
+            }
+        };
+        let cells = self
+            .authorized_cells(&installed_app_ids)
+            .await
+            .map_err(|e| InterfaceError::Other(e.to_string()))?;
+        Ok(Self {
+            authorization: Some(AppAuthorization {
+                token,
+                installed_app_ids: Arc::new(installed_app_ids),
+                cells: Arc::new(parking_lot::RwLock::new(cells)),
+            }),
+            ..self.for_connection()
+        })
+    }
 }
@@ -1,5 +1,6 @@
 //! Errors occurring during a [`CellConductorApi`](super::CellConductorApi) or [`InterfaceApi`](super::InterfaceApi) call
 use crate::conductor::error::ConductorError;
+use crate::conductor::interface::error::AuthenticationError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::CellError;
 use crate::core::ribosome::error::RibosomeError;
@@ -157,6 +158,12 @@ impl From<SerializationError> for ExternalApiWireError {
     }
 }
 
+impl From<AuthenticationError> for ExternalApiWireError {
+    fn from(e: AuthenticationError) -> Self {
+        ExternalApiWireError::AuthenticationFailed(e.to_string())
+    }
+}
+
 impl From<RibosomeError> for ExternalApiWireError {
     fn from(e: RibosomeError) -> Self {
         ExternalApiWireError::RibosomeError(e.to_string())
@@ -0,0 +1,391 @@
+//! Backup and restore of every database in a conductor's environment directory.
+//!
+//! A backup is a tar archive holding a copy of each database file, at the same
+//! path relative to the environment directory, the files of the keystore below
+//! [`KEYSTORE_DIR`], and a [`BackupManifest`] at its root. The copies are made
+//! with SQLite's online backup API, so a backup can be taken while the
+//! conductor is running. A read transaction is opened on every database before
+//! any of them is copied, so each copy is consistent on its own and none of
+//! them holds writes made after the last transaction was opened. The
+//! transactions are opened one after another, so a write made in the meantime
+//! may show up in some copies but not in others.
+//!
+//! The keystore's files are copied as they are, which is only consistent
+//! while the keystore isn't running, so they are only included in a backup
+//! made while the conductor is stopped, e.g. with `holochain --create-backup`.
+//! A backup made by a running conductor holds its databases only.
+//!
+//! Restoring only ever happens into an environment directory without any
+//! databases and an empty keystore directory, and only after every database in
+//! the archive has passed SQLite's integrity check and every source chain in
+//! the authored databases has been verified.
+
+use crate::conductor::error::ConductorError;
+use crate::conductor::error::ConductorResult;
+use crate::core::validate_chain;
+use holochain_conductor_api::backup::BackupManifest;
+use holochain_conductor_api::backup::BACKUP_FORMAT_VERSION;
+use holochain_conductor_api::backup::BACKUP_MANIFEST_FILE_NAME;
+use holochain_conductor_api::config::conductor::ConductorConfig;
+use holochain_conductor_api::config::conductor::KeystoreConfig;
+use holochain_sqlite::backup::*;
+use holochain_sqlite::error::DatabaseError;
+use holochain_state::prelude::from_blob;
+use holochain_types::prelude::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// The directory, relative to the environment directory, which holds the
+/// authored databases.
+const AUTHORED_DIR: &str = "authored";
+
+/// The directory in a backup archive which holds the files of the keystore.
+pub const KEYSTORE_DIR: &str = "keystore";
+
+/// The name of the config file lair keeps in its root directory.
+const LAIR_CONFIG_FILE_NAME: &str = "lair-keystore-config.yaml";
+
+/// The directory holding the files of the lair keystore this conductor uses,
+/// if they are on this machine.
+///
+/// That is the lair root of an in-process keystore, or the directory of the
+/// unix socket an external lair listens on when its config is found there.
+pub fn keystore_directory(config: &ConductorConfig) -> Option<PathBuf> {
+    match &config.keystore {
+        KeystoreConfig::DangerTestKeystore => None,
+        KeystoreConfig::LairServerInProc { lair_root } => Some(
+            lair_root
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(config.environment_path.clone()).join("keystore")),
+        ),
+        KeystoreConfig::LairServer { connection_url } => {
+            if connection_url.scheme() != "unix" {
+                return None;
+            }
+            Path::new(connection_url.path())
+                .parent()
+                .filter(|dir| dir.join(LAIR_CONFIG_FILE_NAME).is_file())
+                .map(Path::to_owned)
+        }
+    }
+}
+
+/// Write a backup of every database below `environment_path`, and of the
+/// files in `keystore_path`, to a new tar archive at `archive`.
+///
+/// The keystore must be stopped while its files are copied, so
+/// `keystore_path` must only be given while the conductor using it isn't
+/// running. Fails if a lair is still listening on a socket in `keystore_path`.
+///
+/// Everything is copied into a temporary directory next to `archive` and then
+/// written to a temporary file there, so a failed backup never leaves a
+/// partial archive behind.
+pub async fn create_backup(
+    environment_path: PathBuf,
+    keystore_path: Option<PathBuf>,
+    archive: PathBuf,
+) -> ConductorResult<BackupManifest> {
+    tokio::task::spawn_blocking(move || {
+        create_backup_blocking(&environment_path, keystore_path.as_deref(), &archive)
+    })
+    .await
+    .map_err(|e| ConductorError::BackupError(e.to_string()))?
+}
+
+fn create_backup_blocking(
+    environment_path: &Path,
+    keystore_path: Option<&Path>,
+    archive: &Path,
+) -> ConductorResult<BackupManifest> {
+    if archive.exists() {
+        return Err(ConductorError::BackupError(format!(
+            "The backup archive {} already exists",
+            archive.display()
+        )));
+    }
+    let archive_dir = match archive.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let staging = tempfile::Builder::new()
+        .prefix(".backup")
+        .tempdir_in(archive_dir)?;
+
+    if let Some(keystore_path) = keystore_path {
+        check_keystore_stopped(keystore_path)?;
+    }
+
+    let created_at = Timestamp::now();
+    let databases = find_database_files(environment_path)?;
+    let snapshots = DatabaseSnapshots::take(environment_path, &databases)?;
+    let keystore = match keystore_path {
+        Some(keystore_path) => copy_keystore(keystore_path, &staging.path().join(KEYSTORE_DIR))?,
+        None => Vec::new(),
+    };
+    snapshots.backup_to(staging.path())?;
+    drop(snapshots);
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at,
+        databases,
+        keystore,
+    };
+    std::fs::write(
+        staging.path().join(BACKUP_MANIFEST_FILE_NAME),
+        serde_yaml::to_string(&manifest)?,
+    )?;
+
+    let mut builder = tar::Builder::new(tempfile::NamedTempFile::new_in(archive_dir)?);
+    builder.append_path_with_name(
+        staging.path().join(BACKUP_MANIFEST_FILE_NAME),
+        BACKUP_MANIFEST_FILE_NAME,
+    )?;
+    for database in manifest.databases.iter() {
+        builder.append_path_with_name(staging.path().join(database), database)?;
+    }
+    for file in manifest.keystore.iter() {
+        builder.append_path_with_name(
+            staging.path().join(KEYSTORE_DIR).join(file),
+            Path::new(KEYSTORE_DIR).join(file),
+        )?;
+    }
+    builder
+        .into_inner()?
+        .persist_noclobber(archive)
+        .map_err(|e| ConductorError::BackupError(e.to_string()))?;
+
+    tracing::info!(
+        "Backed up {} databases and {} keystore files to {}",
+        manifest.databases.len(),
+        manifest.keystore.len(),
+        archive.display()
+    );
+    Ok(manifest)
+}
+
+/// Fail if a lair accepts connections on a socket in the keystore directory,
+/// as its files could change while they are copied.
+fn check_keystore_stopped(keystore_path: &Path) -> ConductorResult<()> {
+    #[cfg(unix)]
+    for entry in std::fs::read_dir(keystore_path)? {
+        use std::os::unix::fs::FileTypeExt;
+        let entry = entry?;
+        if entry.file_type()?.is_socket()
+            && std::os::unix::net::UnixStream::connect(entry.path()).is_ok()
+        {
+            return Err(ConductorError::BackupError(format!(
+                "The keystore in {} is running, stop it before backing it up",
+                keystore_path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Copy the regular files in the keystore directory `source` to `destination`,
+/// returning their names, sorted.
+fn copy_keystore(source: &Path, destination: &Path) -> ConductorResult<Vec<PathBuf>> {
+    std::fs::create_dir_all(destination)?;
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        // Skips the socket an external lair listens on.
+        if entry.file_type()?.is_file() {
+            std::fs::copy(entry.path(), destination.join(entry.file_name()))?;
+            files.push(PathBuf::from(entry.file_name()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Restore the databases in the backup `archive` into `environment_path`,
+/// and its keystore into `keystore_path`.
+///
+/// Fails without touching either directory if `environment_path` already
+/// contains any databases, if `keystore_path` already contains any files,
+/// or if the archive doesn't pass verification. If the archive holds a
+/// keystore but no `keystore_path` is given, only the databases are restored.
+pub async fn restore_backup(
+    archive: PathBuf,
+    environment_path: PathBuf,
+    keystore_path: Option<PathBuf>,
+) -> ConductorResult<BackupManifest> {
+    if !find_database_files(&environment_path)?.is_empty() {
+        return Err(ConductorError::BackupError(format!(
+            "Refusing to restore into {}, which already contains databases",
+            environment_path.display()
+        )));
+    }
+    if let Some(keystore_path) = keystore_path.as_ref() {
+        if keystore_path.is_dir() && std::fs::read_dir(keystore_path)?.next().is_some() {
+            return Err(ConductorError::BackupError(format!(
+                "Refusing to restore the keystore into {}, which isn't empty",
+                keystore_path.display()
+            )));
+        }
+    }
+    std::fs::create_dir_all(&environment_path)?;
+
+    // Unpack next to the final location, so the databases can be moved into
+    // place with a rename once they are verified.
+    let staging = tempfile::Builder::new()
+        .prefix(".restore")
+        .tempdir_in(&environment_path)?;
+    let manifest = {
+        let archive = archive.clone();
+        let staging = staging.path().to_owned();
+        tokio::task::spawn_blocking(move || unpack_and_check(&archive, &staging))
+            .await
+            .map_err(|e| ConductorError::BackupError(e.to_string()))??
+    };
+
+    for database in manifest.databases.iter() {
+        if database.starts_with(AUTHORED_DIR) {
+            verify_authored_chains(&staging.path().join(database)).await?;
+        }
+    }
+
+    for database in manifest.databases.iter() {
+        let destination = environment_path.join(database);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(staging.path().join(database), destination)?;
+    }
+    match keystore_path {
+        Some(keystore_path) if !manifest.keystore.is_empty() => {
+            std::fs::create_dir_all(&keystore_path)?;
+            for file in manifest.keystore.iter() {
+                // The keystore may be on another file system, so copy it.
+                std::fs::copy(
+                    staging.path().join(KEYSTORE_DIR).join(file),
+                    keystore_path.join(file),
+                )?;
+            }
+        }
+        None if !manifest.keystore.is_empty() => tracing::warn!(
+            "The keystore in {} was not restored, it is in the archive's {} directory",
+            archive.display(),
+            KEYSTORE_DIR
+        ),
+        _ => (),
+    }
+
+    tracing::info!(
+        "Restored {} databases from {} into {}",
+        manifest.databases.len(),
+        archive.display(),
+        environment_path.display()
+    );
+    Ok(manifest)
+}
+
+/// Unpack the archive and check that its contents match its manifest
+/// and that every database is intact.
+fn unpack_and_check(archive: &Path, staging: &Path) -> ConductorResult<BackupManifest> {
+    tar::Archive::new(std::fs::File::open(archive)?).unpack(staging)?;
+
+    let manifest: BackupManifest = serde_yaml::from_str(&std::fs::read_to_string(
+        staging.join(BACKUP_MANIFEST_FILE_NAME),
+    )?)?;
+    if manifest.format_version != BACKUP_FORMAT_VERSION {
+        return Err(ConductorError::BackupError(format!(
+            "Unsupported backup format version {}, expected {}",
+            manifest.format_version, BACKUP_FORMAT_VERSION
+        )));
+    }
+    let keystore = staging.join(KEYSTORE_DIR);
+    if find_database_files(staging)?
+        .into_iter()
+        .filter(|database| !database.starts_with(KEYSTORE_DIR))
+        .ne(manifest.databases.iter().cloned())
+    {
+        return Err(ConductorError::BackupError(
+            "The databases in the archive don't match its manifest".to_string(),
+        ));
+    }
+    for file in manifest.keystore.iter() {
+        if file.components().count() != 1 || !keystore.join(file).is_file() {
+            return Err(ConductorError::BackupError(format!(
+                "The keystore file {} is missing from the archive",
+                file.display()
+            )));
+        }
+    }
+    for database in manifest.databases.iter() {
+        check_database_integrity(&staging.join(database))?;
+    }
+    Ok(manifest)
+}
+
+/// Check that every action in an authored database is correctly hashed and
+/// signed, and that each author's actions form a single valid chain.
+async fn verify_authored_chains(path: &Path) -> ConductorResult<()> {
+    let chains = {
+        let path = path.to_owned();
+        tokio::task::spawn_blocking(move || read_authored_chains(&path))
+            .await
+            .map_err(|e| ConductorError::BackupError(e.to_string()))??
+    };
+
+    for (author, actions) in chains {
+        for action in actions.iter() {
+            action.validate().await.map_err(|e| {
+                ConductorError::BackupError(format!(
+                    "Action {} in {} has an invalid signature: {}",
+                    action.as_hash(),
+                    path.display(),
+                    e
+                ))
+            })?;
+        }
+        validate_chain(actions.iter(), &None).map_err(|e| {
+            ConductorError::BackupError(format!(
+                "The source chain of {} in {} is invalid: {}",
+                author,
+                path.display(),
+                e
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+/// Read the actions of an authored database, grouped by author
+/// and in sequence order.
+fn read_authored_chains(
+    path: &Path,
+) -> ConductorResult<BTreeMap<AgentPubKey, Vec<SignedActionHashed>>> {
+    let conn = open_database_file(path)?;
+    let mut stmt = conn
+        .prepare("SELECT hash, blob FROM Action ORDER BY author, seq")
+        .map_err(DatabaseError::from)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, ActionHash>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })
+        .map_err(DatabaseError::from)?;
+
+    let mut chains: BTreeMap<AgentPubKey, Vec<SignedActionHashed>> = BTreeMap::new();
+    for row in rows {
+        let (hash, blob) = row.map_err(DatabaseError::from)?;
+        let signed_action = from_blob::<SignedAction>(blob)
+            .map_err(|e| ConductorError::BackupError(e.to_string()))?;
+        let action = SignedActionHashed::from_content_sync(signed_action);
+        if *action.as_hash() != hash {
+            return Err(ConductorError::BackupError(format!(
+                "Action {} in {} doesn't match its hash",
+                hash,
+                path.display()
+            )));
+        }
+        chains
+            .entry(action.action().author().clone())
+            .or_default()
+            .push(action);
+    }
+    Ok(chains)
+}
@@ -161,7 +161,10 @@ impl Cell {
 
     /// Performs the Genesis workflow for the Cell, ensuring that its initial
     /// records are committed. This is a prerequisite for any other interaction
-    /// with the SourceChain
+    /// with the SourceChain.
+    ///
+    /// If `prev_dna_hash` is given, the chain is opened as the continuation
+    /// of a chain migrated from that DNA.
     #[allow(clippy::too_many_arguments)]
     pub async fn genesis<Ribosome>(
         id: CellId,
@@ -172,6 +175,7 @@ impl Cell {
         ribosome: Ribosome,
         membrane_proof: Option<MembraneProof>,
         chc: Option<ChcImpl>,
+        prev_dna_hash: Option<DnaHash>,
     ) -> CellResult<()>
     where
         Ribosome: RibosomeT + 'static,
@@ -200,6 +204,7 @@ impl Cell {
             ribosome,
             dht_db_cache,
             chc,
+            prev_dna_hash,
         );
 
         genesis_workflow(workspace, conductor_api, args)
@@ -384,6 +389,7 @@ impl Cell {
             | QueryAgentInfoSignedNearBasis { .. }
             | QueryPeerDensity { .. }
             | Publish { .. }
+            | PublishWarrants { .. }
             | FetchOpData { .. } => {
                 // These events are aggregated over a set of cells, so need to be handled at the conductor level.
                 unreachable!()
@@ -470,6 +476,23 @@ impl Cell {
                 .await;
             }
 
+            CountLinks {
+                span_context: _,
+                respond,
+                query,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_count_links(query)
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_count_links"))
+                .await;
+            }
+
             GetAgentActivity {
                 span_context: _,
                 respond,
@@ -650,14 +673,18 @@ impl Cell {
             .map_err(Into::into)
     }
 
-    #[instrument(skip(self, _dht_hash, _options))]
+    #[instrument(skip(self, options))]
     /// a remote node is asking us for metadata
     async fn handle_get_meta(
         &self,
-        _dht_hash: holo_hash::AnyDhtHash,
-        _options: holochain_p2p::event::GetMetaOptions,
+        dht_hash: holo_hash::AnyDhtHash,
+        options: holochain_p2p::event::GetMetaOptions,
     ) -> CellResult<MetadataSet> {
-        unimplemented!()
+        debug!(id = ?self.id());
+        let db = self.space.dht_db.clone();
+        authority::handle_get_meta(db.into(), dht_hash, options)
+            .await
+            .map_err(Into::into)
     }
 
     #[instrument(skip(self, options))]
@@ -677,6 +704,16 @@ impl Cell {
             .map_err(Into::into)
     }
 
+    #[instrument(skip(self))]
+    /// a remote node is asking us to count links
+    async fn handle_count_links(&self, query: WireLinkQuery) -> CellResult<CountLinksResponse> {
+        debug!(id = ?self.id());
+        let db = self.space.dht_db.clone();
+        authority::handle_count_links(db.into(), query)
+            .await
+            .map_err(Into::into)
+    }
+
     #[instrument(skip(self, options))]
     async fn handle_get_agent_activity(
         &self,
@@ -888,7 +925,7 @@ impl Cell {
 
     /// Check if each Zome's init callback has been run, and if not, run it.
     #[tracing::instrument(skip(self))]
-    async fn check_or_run_zome_init(&self) -> CellResult<()> {
+    pub(crate) async fn check_or_run_zome_init(&self) -> CellResult<()> {
         // Ensure that only one init check is run at a time
         let _guard = tokio::time::timeout(
             std::time::Duration::from_secs(INIT_MUTEX_TIMEOUT_SECS),
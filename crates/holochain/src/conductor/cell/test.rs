@@ -46,6 +46,7 @@ async fn test_cell_handle_publish() {
         ribosome,
         None,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -2,15 +2,38 @@
 //!
 //! **NOTE** this API is not set in stone. Do not design a CHC against this API yet,
 //! as it will change!
+//!
+//! Each cell talks to the CHC at `{namespace}/{dna_hash}/{agent_pubkey}`, where
+//! `namespace` is the base URL configured as `chc_namespace` in the conductor config.
+//! All request and response bodies are msgpack-encoded. Errors are signalled via
+//! HTTP status codes:
+//!
+//! - `409 Conflict`: the actions would produce an invalid chain. The body is the
+//!   encoded `(Option<u32>, String)` pair of [`ChcError::InvalidChain`].
+//! - `404 Not Found`: some requested entries are missing. The body is the encoded
+//!   set of missing [`EntryHash`]es.
+//! - Any other non-success status: the body is a plain UTF-8 error message.
 
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use ::bytes::Bytes;
 use holo_hash::{ActionHash, EntryHash};
 use holochain_serialized_bytes::{decode, encode};
 use holochain_types::chc::{ChainHeadCoordinator, ChcError, ChcResult};
 use holochain_zome_types::prelude::*;
-use reqwest::Url;
+use reqwest::{StatusCode, Url};
+
+/// The number of times a request will be retried if the CHC service is
+/// unreachable or responds with a server error. Adding actions is never
+/// retried, since the service may have added them before failing.
+pub const CHC_REMOTE_MAX_RETRIES: u32 = 3;
+
+/// The delay before the first retry. Each subsequent retry doubles the delay.
+pub const CHC_REMOTE_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The timeout for any single request to the CHC service.
+pub const CHC_REMOTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// An HTTP client which can talk to a remote CHC implementation
 pub struct ChcRemote {
@@ -29,7 +52,7 @@ impl ChainHeadCoordinator for ChcRemote {
 
     async fn add_actions(&self, actions: Vec<Self::Item>) -> ChcResult<()> {
         let body = encode(&actions)?;
-        let _response = self.actions.post("/add_actions", body).await?;
+        let _response = self.actions.post_once("/add_actions", body).await?;
         Ok(())
     }
 
@@ -47,52 +70,314 @@ impl ChainHeadCoordinator for ChcRemote {
 
     async fn get_entries(
         &self,
-        _hashes: HashSet<&EntryHash>,
+        hashes: HashSet<&EntryHash>,
     ) -> ChcResult<HashMap<EntryHash, Entry>> {
-        todo!()
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let body = encode(&hashes)?;
+        let response = self.entries.post("/get_entries", body).await?;
+        let entries: HashMap<EntryHash, Entry> = decode(&response)?;
+        let missing: HashSet<EntryHash> = hashes
+            .into_iter()
+            .filter(|h| !entries.contains_key(*h))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            Ok(entries)
+        } else {
+            Err(ChcError::MissingEntries(missing))
+        }
     }
 }
 
 impl ChcRemote {
-    /// Constructor
-    pub fn new(_namespace: &str, _cell_id: &CellId) -> Self {
-        todo!("Implement remote CHC client")
+    /// Constructor.
+    ///
+    /// The namespace is the base URL of the CHC service, under which each cell
+    /// has its own path.
+    pub fn new(namespace: &str, cell_id: &CellId) -> ChcResult<Self> {
+        let base_url = Url::parse(&format!(
+            "{}/{}/{}",
+            namespace.trim_end_matches('/'),
+            cell_id.dna_hash(),
+            cell_id.agent_pubkey()
+        ))
+        .map_err(|e| ChcError::InvalidUrl(format!("{}: {}", namespace, e)))?;
+        if base_url.cannot_be_a_base() {
+            return Err(ChcError::InvalidUrl(namespace.to_string()));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(CHC_REMOTE_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ChcError::ServiceUnreachable(e.to_string()))?;
+        Ok(Self {
+            actions: ChcRemoteClient {
+                base_url: base_url.clone(),
+                client: client.clone(),
+            },
+            entries: ChcRemoteClient { base_url, client },
+        })
     }
 }
 
 /// Client for a single CHC server
 pub struct ChcRemoteClient {
-    base_url: url::Url,
+    base_url: Url,
+    client: reqwest::Client,
 }
 
 impl ChcRemoteClient {
     fn url(&self, path: &str) -> Url {
-        assert!(path.chars().nth(0) == Some('/'));
+        assert!(path.starts_with('/'));
         Url::parse(&format!("{}{}", self.base_url, path)).expect("invalid URL")
     }
 
     async fn get(&self, path: &str) -> ChcResult<Bytes> {
-        let bytes = reqwest::get(self.url(path))
-            .await
-            .map_err(extract_string)?
-            .bytes()
-            .await
-            .map_err(extract_string)?;
-        Ok(bytes)
+        self.with_retries(|| self.client.get(self.url(path))).await
     }
 
     async fn post(&self, path: &str, body: Vec<u8>) -> ChcResult<Bytes> {
-        let client = reqwest::Client::new();
-        let response = client
-            .post(self.url(path))
-            .body(body)
-            .send()
+        self.with_retries(|| self.client.post(self.url(path)).body(body.clone()))
             .await
-            .map_err(extract_string)?;
-        Ok(response.bytes().await.map_err(extract_string)?)
+    }
+
+    /// Send a request which must not be retried, as the service may have
+    /// applied it even though the response was an error. Resending actions
+    /// which were already added would be rejected as an invalid chain.
+    async fn post_once(&self, path: &str, body: Vec<u8>) -> ChcResult<Bytes> {
+        send(self.client.post(self.url(path)).body(body)).await
+    }
+
+    /// Send a request, retrying with exponential backoff if the service is
+    /// unreachable or responds with a server error. Any other error
+    /// is returned immediately.
+    async fn with_retries(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> ChcResult<Bytes> {
+        let mut backoff = CHC_REMOTE_RETRY_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            match send(request()).await {
+                Err(e) if attempt < CHC_REMOTE_MAX_RETRIES && is_retryable(&e) => {
+                    tracing::warn!(?e, attempt, "Retrying CHC request");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+async fn send(request: reqwest::RequestBuilder) -> ChcResult<Bytes> {
+    let response = request.send().await.map_err(extract_string)?;
+    let status = response.status();
+    let bytes = response.bytes().await.map_err(extract_string)?;
+    if status.is_success() {
+        Ok(bytes)
+    } else {
+        Err(error_from_response(status, bytes))
+    }
+}
+
+/// Map an unsuccessful response from the CHC service to a [`ChcError`].
+fn error_from_response(status: StatusCode, body: Bytes) -> ChcError {
+    match status {
+        StatusCode::CONFLICT => {
+            let invalid: Result<(Option<u32>, String), _> = decode(&body);
+            if let Ok((seq, msg)) = invalid {
+                return ChcError::InvalidChain(seq, msg);
+            }
+        }
+        StatusCode::NOT_FOUND => {
+            let missing: Result<HashSet<EntryHash>, _> = decode(&body);
+            if let Ok(missing) = missing {
+                return ChcError::MissingEntries(missing);
+            }
+        }
+        _ => (),
+    }
+    ChcError::ServiceError(status.as_u16(), String::from_utf8_lossy(&body).into())
+}
+
+fn is_retryable(e: &ChcError) -> bool {
+    match e {
+        ChcError::ServiceUnreachable(_) => true,
+        ChcError::ServiceError(status, _) => *status >= 500,
+        _ => false,
     }
 }
 
 fn extract_string(e: reqwest::Error) -> ChcError {
     ChcError::ServiceUnreachable(e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use holochain_conductor_api::conductor::ConductorConfig;
+
+    use super::*;
+    use crate::conductor::chc::chc_server::run_chc_server;
+    use crate::sweettest::*;
+    use ::fixt::prelude::*;
+    use holochain_types::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_chc_sync() {
+        use holochain::test_utils::inline_zomes::{simple_crud_zome, AppString};
+
+        let (addr, _server) = run_chc_server(([127, 0, 0, 1], 0)).await.unwrap();
+
+        let mut config = ConductorConfig::default();
+        config.chc_namespace = Some(format!("http://{}", addr));
+        let mut conductors = SweetConductorBatch::from_configs([config.clone(), config]).await;
+
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+        let (agent, _) = SweetAgents::alice_and_bob();
+
+        let (c0,) = conductors[0]
+            .setup_app_for_agent("app", agent.clone(), [&dna_file])
+            .await
+            .unwrap()
+            .into_tuple();
+        let cell_id = c0.cell_id().clone();
+
+        let chc = ChcRemote::new(&format!("http://{}", addr), &cell_id).unwrap();
+        assert_eq!(chc.get_actions_since_hash(None).await.unwrap().len(), 3);
+
+        // The second device can't run genesis, since the CHC already has a chain
+        assert!(conductors[1]
+            .setup_app_for_agent("app", agent.clone(), [&dna_file])
+            .await
+            .is_err());
+
+        conductors[1]
+            .raw_handle()
+            .chc_sync(cell_id.clone(), None)
+            .await
+            .unwrap();
+        conductors[1]
+            .setup_app_for_agent("app", agent.clone(), [&dna_file])
+            .await
+            .unwrap();
+
+        let _: ActionHash = conductors[0]
+            .call(
+                &c0.zome(SweetInlineZomes::COORDINATOR),
+                "create_string",
+                AppString::new("zero"),
+            )
+            .await;
+        assert_eq!(chc.get_actions_since_hash(None).await.unwrap().len(), 5);
+
+        conductors[1]
+            .raw_handle()
+            .chc_sync(cell_id.clone(), None)
+            .await
+            .unwrap();
+
+        let dump0 = conductors[0]
+            .dump_full_cell_state(&cell_id, None)
+            .await
+            .unwrap();
+        let dump1 = conductors[1]
+            .dump_full_cell_state(&cell_id, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            dump0.source_chain_dump.records,
+            dump1.source_chain_dump.records
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_chc_missing_entries() {
+        let (addr, _server) = run_chc_server(([127, 0, 0, 1], 0)).await.unwrap();
+        let cell_id = fixt!(CellId);
+        let chc = ChcRemote::new(&format!("http://{}", addr), &cell_id).unwrap();
+
+        let entry = EntryHashed::from_content_sync(fixt!(Entry));
+        let missing = EntryHashed::from_content_sync(fixt!(Entry));
+        chc.add_entries(vec![entry.clone()]).await.unwrap();
+
+        let found = chc
+            .get_entries([entry.as_hash()].into_iter().collect())
+            .await
+            .unwrap();
+        assert_eq!(found.get(entry.as_hash()), Some(entry.as_content()));
+
+        let err = chc
+            .get_entries([entry.as_hash(), missing.as_hash()].into_iter().collect())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ChcError::MissingEntries(hashes) if hashes.contains(missing.as_hash()))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_chc_unreachable() {
+        // Nothing is listening on port 1
+        let chc = ChcRemote::new("http://127.0.0.1:1", &fixt!(CellId)).unwrap();
+        assert!(matches!(
+            chc.head().await,
+            Err(ChcError::ServiceUnreachable(_))
+        ));
+        assert!(matches!(
+            ChcRemote::new("not a url", &fixt!(CellId)),
+            Err(ChcError::InvalidUrl(_))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn only_idempotent_requests_are_retried() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+        use warp::Filter;
+
+        let requests = Arc::new(AtomicU32::new(0));
+        let route = warp::any().map({
+            let requests = requests.clone();
+            move || {
+                requests.fetch_add(1, Ordering::SeqCst);
+                warp::reply::with_status("down", warp::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        });
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        let _server = tokio::task::spawn(server);
+        let chc = ChcRemote::new(&format!("http://{}", addr), &fixt!(CellId)).unwrap();
+
+        assert!(matches!(
+            chc.head().await,
+            Err(ChcError::ServiceError(500, _))
+        ));
+        assert_eq!(
+            requests.swap(0, Ordering::SeqCst),
+            1 + CHC_REMOTE_MAX_RETRIES
+        );
+
+        // The actions may have been added before the error, so they are
+        // only sent once.
+        assert!(matches!(
+            chc.add_actions(vec![fixt!(SignedActionHashed)]).await,
+            Err(ChcError::ServiceError(500, _))
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invalid_chc_namespace_is_an_error() {
+        use holochain::test_utils::inline_zomes::simple_crud_zome;
+
+        let mut config = ConductorConfig::default();
+        config.chc_namespace = Some("not a url".to_string());
+        let mut conductor = SweetConductor::from_config(config).await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+
+        // Genesis fails instead of the conductor panicking
+        assert!(conductor.setup_app("app", [&dna_file]).await.is_err());
+    }
+}
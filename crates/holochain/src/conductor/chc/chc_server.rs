@@ -0,0 +1,121 @@
+//! A stand-in HTTP CHC server, backed by [`ChcLocal`] instances.
+//!
+//! This is for testing the [`ChcRemote`](super::ChcRemote) client, and implements
+//! the same API which it expects. State is kept in memory only.
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use holochain_serialized_bytes::{decode, encode};
+use holochain_types::prelude::*;
+use warp::http::{Response, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::Filter;
+
+use super::ChcLocal;
+
+/// No reason to accept a request body larger than 16MB.
+const SIZE_LIMIT: u64 = 16 * 1024 * 1024;
+
+type ChcMap = Arc<parking_lot::Mutex<HashMap<(String, String), Arc<ChcLocal>>>>;
+
+/// Run a CHC server on the given address. Returns the address actually bound
+/// and a handle to the task driving the server.
+pub async fn run_chc_server(
+    addr: impl Into<SocketAddr> + 'static,
+) -> Result<(SocketAddr, tokio::task::JoinHandle<()>), String> {
+    let chcs: ChcMap = Default::default();
+
+    let with_chc = warp::any().map(move || chcs.clone());
+
+    let head = warp::get()
+        .and(warp::path!(String / String / "head"))
+        .and(with_chc.clone())
+        .and_then(|dna: String, agent: String, chcs: ChcMap| async move {
+            let chc = get_chc(&chcs, dna, agent);
+            respond(chc.head().await)
+        });
+
+    let post = |op: &'static str| {
+        warp::post()
+            .and(warp::path::param::<String>())
+            .and(warp::path::param::<String>())
+            .and(warp::path(op))
+            .and(warp::path::end())
+            .and(warp::body::content_length_limit(SIZE_LIMIT))
+            .and(warp::body::bytes())
+            .and(with_chc.clone())
+    };
+
+    let add_actions = post("add_actions").and_then(
+        |dna: String, agent: String, body: Bytes, chcs: ChcMap| async move {
+            let chc = get_chc(&chcs, dna, agent);
+            let actions: Vec<SignedActionHashed> = decode(&body).map_err(|_| warp::reject())?;
+            respond(chc.add_actions(actions).await)
+        },
+    );
+
+    let add_entries = post("add_entries").and_then(
+        |dna: String, agent: String, body: Bytes, chcs: ChcMap| async move {
+            let chc = get_chc(&chcs, dna, agent);
+            let entries: Vec<EntryHashed> = decode(&body).map_err(|_| warp::reject())?;
+            respond(chc.add_entries(entries).await)
+        },
+    );
+
+    let get_actions_since_hash = post("get_actions_since_hash").and_then(
+        |dna: String, agent: String, body: Bytes, chcs: ChcMap| async move {
+            let chc = get_chc(&chcs, dna, agent);
+            let hash: Option<ActionHash> = decode(&body).map_err(|_| warp::reject())?;
+            respond(chc.get_actions_since_hash(hash).await)
+        },
+    );
+
+    let get_entries = post("get_entries").and_then(
+        |dna: String, agent: String, body: Bytes, chcs: ChcMap| async move {
+            let chc = get_chc(&chcs, dna, agent);
+            let hashes: HashSet<EntryHash> = decode(&body).map_err(|_| warp::reject())?;
+            respond(chc.get_entries(hashes.iter().collect()).await)
+        },
+    );
+
+    let routes = head
+        .or(add_actions)
+        .or(add_entries)
+        .or(get_actions_since_hash)
+        .or(get_entries);
+
+    let (addr, server) = warp::serve(routes)
+        .try_bind_ephemeral(addr)
+        .map_err(|e| format!("Failed to bind socket: {:?}", e))?;
+    Ok((addr, tokio::task::spawn(server)))
+}
+
+fn get_chc(chcs: &ChcMap, dna: String, agent: String) -> Arc<ChcLocal> {
+    chcs.lock()
+        .entry((dna, agent))
+        .or_insert_with(|| Arc::new(ChcLocal::new()))
+        .clone()
+}
+
+/// Encode a CHC result as a response, using the status codes which
+/// [`ChcRemote`](super::ChcRemote) expects.
+fn respond<T: serde::Serialize>(
+    result: ChcResult<T>,
+) -> Result<Response<Vec<u8>>, warp::Rejection> {
+    let (status, body) = match result {
+        Ok(r) => (StatusCode::OK, encode(&r)),
+        Err(ChcError::InvalidChain(seq, msg)) => (StatusCode::CONFLICT, encode(&(seq, msg))),
+        Err(ChcError::MissingEntries(hashes)) => (StatusCode::NOT_FOUND, encode(&hashes)),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Ok(e.to_string().into_bytes()),
+        ),
+    };
+    let body = body.map_err(|_| warp::reject())?;
+    Response::builder()
+        .status(status)
+        .body(body)
+        .map_err(|_| warp::reject())
+}
@@ -33,9 +33,14 @@ pub use self::share::RwShare;
 use super::api::RealAppInterfaceApi;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::AllowedOrigins;
 use super::config::InterfaceDriver;
 use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
+use super::interface::authentication::read_bootstrap_token;
+use super::interface::authentication::AuthenticationTokens;
+use super::interface::authentication::InterfaceKind;
+use super::interface::error::AuthenticationError;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_task;
 use super::interface::websocket::spawn_app_interface_task;
@@ -82,13 +87,19 @@ use futures::future::FutureExt;
 use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
+use holochain_conductor_api::authentication::AuthenticationScope;
+use holochain_conductor_api::authentication::AuthenticationToken;
+use holochain_conductor_api::authentication::AuthenticationTokenIssued;
+use holochain_conductor_api::authentication::IssueAuthenticationTokenPayload;
 use holochain_conductor_api::conductor::KeystoreConfig;
+use holochain_conductor_api::source_chain_archive::SourceChainArchive;
 use holochain_conductor_api::AppInfo;
 use holochain_conductor_api::AppStatusFilter;
 use holochain_conductor_api::FullIntegrationStateDump;
 use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
 use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::ZomeFunctions;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
 use holochain_keystore::test_keystore::spawn_test_keystore;
@@ -111,6 +122,8 @@ use kitsune_p2p::agent_store::AgentInfoSigned;
 use kitsune_p2p_types::config::JOIN_NETWORK_TIMEOUT;
 use rusqlite::Transaction;
 use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tokio::sync::mpsc::error::SendError;
@@ -130,6 +143,7 @@ pub use chc::*;
 pub use accessor_impls::*;
 pub use app_impls::*;
 pub use app_status_impls::*;
+pub use backup_impls::*;
 pub use cell_impls::*;
 pub use clone_cell_impls::*;
 pub use dna_impls::*;
@@ -141,6 +155,7 @@ pub use startup_shutdown_impls::*;
 pub use state_impls::*;
 
 mod graft_records_onto_source_chain;
+mod source_chain_archive;
 
 /// A list of Cells which failed to start, and why
 pub type CellStartupErrors = Vec<(CellId, CellError)>;
@@ -209,6 +224,9 @@ pub struct Conductor {
     /// Collection app interface data, keyed by id
     app_interfaces: RwShare<HashMap<AppInterfaceId, AppInterfaceRuntime>>,
 
+    /// The tokens issued for authenticating connections to interfaces
+    authentication_tokens: RwShare<AuthenticationTokens>,
+
     /// The channels and handles needed to interact with the task_manager task.
     /// If this is None, then the task manager has not yet been initialized.
     pub(crate) task_manager: RwShare<Option<TaskManagerClient>>,
@@ -273,6 +291,7 @@ mod startup_shutdown_impls {
                 config,
                 shutting_down: Arc::new(AtomicBool::new(false)),
                 app_interfaces: RwShare::new(HashMap::new()),
+                authentication_tokens: RwShare::new(AuthenticationTokens::default()),
                 task_manager: RwShare::new(None),
                 admin_websocket_ports: RwShare::new(Vec::new()),
                 scheduler: Arc::new(parking_lot::Mutex::new(None)),
@@ -350,6 +369,7 @@ mod startup_shutdown_impls {
                 ));
             });
 
+            self.load_authentication_tokens().await?;
             self.clone().add_admin_interfaces(admin_configs).await?;
             self.clone().startup_app_interfaces().await?;
 
@@ -382,24 +402,32 @@ mod interface_impls {
             });
 
             // Closure to process each admin config item
-            let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
+            let spawn_from_config = |AdminInterfaceConfig {
+                                         driver,
+                                         require_authentication,
+                                     }| {
                 let admin_api = admin_api.clone();
                 let stop_tx = stop_tx.clone();
                 async move {
-                    match driver {
-                        InterfaceDriver::Websocket { port } => {
-                            let (listener_handle, listener) =
-                                spawn_websocket_listener(port).await?;
+                    let (listener_handle, listener) = spawn_websocket_listener(&driver).await?;
+                    let (port, name) = match driver {
+                        InterfaceDriver::Websocket { port, .. } => {
                             let port = listener_handle.local_addr().port().unwrap_or(port);
-                            let handle: ManagedTaskHandle = spawn_admin_interface_task(
-                                listener_handle,
-                                listener,
-                                admin_api.clone(),
-                                stop_tx.subscribe(),
-                            )?;
-                            InterfaceResult::Ok((port, handle))
+                            (Some(port), format!("admin interface, port {}", port))
                         }
-                    }
+                        InterfaceDriver::UnixSocket { path } => (
+                            None,
+                            format!("admin interface, unix socket {}", path.display()),
+                        ),
+                    };
+                    let handle: ManagedTaskHandle = spawn_admin_interface_task(
+                        listener_handle,
+                        listener,
+                        admin_api.clone(),
+                        require_authentication,
+                        stop_tx.subscribe(),
+                    )?;
+                    InterfaceResult::Ok((port, name, handle))
                 }
             };
 
@@ -425,13 +453,10 @@ mod interface_impls {
                 .await?;
 
                 // Now that tasks are spawned, register them with the TaskManager
-                for (port, handle) in handles {
-                    ports.push(port);
-                    self.manage_task(ManagedTaskAdd::ignore(
-                        handle,
-                        &format!("admin interface, port {}", port),
-                    ))
-                    .await?
+                for (port, name, handle) in handles {
+                    ports.extend(port);
+                    self.manage_task(ManagedTaskAdd::ignore(handle, &name))
+                        .await?
                 }
                 for p in ports {
                     self.add_admin_port(p);
@@ -442,18 +467,62 @@ mod interface_impls {
 
         /// Spawn a new app interface task, register it with the TaskManager,
         /// and modify the conductor accordingly, based on the config passed in
-        /// which is just a networking port number (or 0 to auto-select one).
+        /// which is a networking port number (or 0 to auto-select one),
+        /// the address to bind to, the origins browsers may connect from
+        /// and whether connections must authenticate.
         /// Returns the given or auto-chosen port number if giving an Ok Result
         pub async fn add_app_interface(
             self: Arc<Self>,
             port: either::Either<u16, AppInterfaceId>,
+            bind_address: IpAddr,
+            allowed_origins: AllowedOrigins,
+            require_authentication: bool,
         ) -> ConductorResult<u16> {
             let interface_id = match port {
                 either::Either::Left(port) => AppInterfaceId::new(port),
                 either::Either::Right(id) => id,
             };
-            let port = interface_id.port();
-            tracing::debug!("Attaching interface {}", port);
+            let driver = InterfaceDriver::Websocket {
+                port: interface_id.port(),
+                bind_address,
+                allowed_origins,
+            };
+            let driver = self
+                .start_app_interface(interface_id, driver, require_authentication)
+                .await?;
+            Ok(driver
+                .port()
+                .expect("A websocket interface always has a port"))
+        }
+
+        /// Spawn a new app interface task listening on a unix domain socket
+        /// at the given path, register it with the TaskManager,
+        /// and modify the conductor accordingly.
+        /// Only the user running the conductor can connect to the socket.
+        pub async fn add_app_unix_socket_interface(
+            self: Arc<Self>,
+            path: PathBuf,
+            require_authentication: bool,
+        ) -> ConductorResult<()> {
+            self.start_app_interface(
+                AppInterfaceId::default(),
+                InterfaceDriver::UnixSocket { path },
+                require_authentication,
+            )
+            .await?;
+            Ok(())
+        }
+
+        /// Start an app interface with the given driver and record it in
+        /// the conductor state under this id.
+        /// Returns the driver the interface is listening on.
+        async fn start_app_interface(
+            self: Arc<Self>,
+            interface_id: AppInterfaceId,
+            driver: InterfaceDriver,
+            require_authentication: bool,
+        ) -> ConductorResult<InterfaceDriver> {
+            tracing::debug!("Attaching interface {:?}", driver);
             let app_api = RealAppInterfaceApi::new(self.clone());
             // This receiver is thrown away because we can produce infinite new
             // receivers from the Sender
@@ -464,15 +533,26 @@ mod interface_impls {
                     .task_stop_broadcaster()
                     .subscribe()
             });
-            let (port, task) = spawn_app_interface_task(port, app_api, signal_tx.clone(), stop_rx)
-                .await
-                .map_err(Box::new)?;
+            let (driver, task) = spawn_app_interface_task(
+                &driver,
+                app_api,
+                require_authentication,
+                signal_tx.clone(),
+                stop_rx,
+            )
+            .await
+            .map_err(Box::new)?;
+            let name = match &driver {
+                InterfaceDriver::Websocket { port, .. } => {
+                    format!("app interface, port {}", port)
+                }
+                InterfaceDriver::UnixSocket { path } => {
+                    format!("app interface, unix socket {}", path.display())
+                }
+            };
             // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
-            self.manage_task(ManagedTaskAdd::ignore(
-                task,
-                &format!("app interface, port {}", port),
-            ))
-            .await?;
+            self.manage_task(ManagedTaskAdd::ignore(task, &name))
+                .await?;
             let interface = AppInterfaceRuntime::Websocket { signal_tx };
 
             self.app_interfaces.share_mut(|app_interfaces| {
@@ -485,14 +565,18 @@ mod interface_impls {
                 app_interfaces.insert(interface_id.clone(), interface);
                 Ok(())
             })?;
-            let config = AppInterfaceConfig::websocket(port);
+            let config = AppInterfaceConfig {
+                signal_subscriptions: HashMap::new(),
+                driver: driver.clone(),
+                require_authentication,
+            };
             self.update_state(|mut state| {
                 state.app_interfaces.insert(interface_id, config);
                 Ok(state)
             })
             .await?;
-            tracing::debug!("App interface added at port: {}", port);
-            Ok(port)
+            tracing::debug!("App interface added: {:?}", driver);
+            Ok(driver)
         }
 
         /// Returns a port which is guaranteed to have a websocket listener with an Admin interface
@@ -501,27 +585,119 @@ mod interface_impls {
             self.admin_websocket_ports.share_ref(|p| p.get(0).copied())
         }
 
-        /// Give a list of networking ports taken up as running app interface tasks
+        /// Give a list of networking ports taken up as running app interface tasks.
+        /// Interfaces listening on unix domain sockets aren't included.
         pub async fn list_app_interfaces(&self) -> ConductorResult<Vec<u16>> {
             Ok(self
                 .get_state()
                 .await?
                 .app_interfaces
                 .values()
-                .map(|config| config.driver.port())
+                .filter_map(|config| config.driver.port())
                 .collect())
         }
 
         /// Start all app interfaces currently in state.
         /// This should only be run at conductor initialization.
-        #[allow(irrefutable_let_patterns)]
         pub(crate) async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()> {
-            for id in self.get_state().await?.app_interfaces.keys().cloned() {
+            for (id, config) in self.get_state().await?.app_interfaces {
                 tracing::debug!("Starting up app interface: {:?}", id);
-                let _ = self.clone().add_app_interface(either::Right(id)).await?;
+                // A websocket interface is restarted on the port it was
+                // asked for, which may be 0, rather than the one it was given.
+                let driver = match config.driver {
+                    InterfaceDriver::Websocket {
+                        bind_address,
+                        allowed_origins,
+                        ..
+                    } => InterfaceDriver::Websocket {
+                        port: id.port(),
+                        bind_address,
+                        allowed_origins,
+                    },
+                    driver @ InterfaceDriver::UnixSocket { .. } => driver,
+                };
+                let _ = self
+                    .clone()
+                    .start_app_interface(id, driver, config.require_authentication)
+                    .await?;
             }
             Ok(())
         }
+
+        /// Load the tokens issued before the conductor was started, along
+        /// with the bootstrap token if one is configured.
+        async fn load_authentication_tokens(&self) -> ConductorResult<()> {
+            let mut tokens = self.get_state().await?.authentication_tokens;
+            if let Some(config) = &self.config.admin_bootstrap_token {
+                let token = read_bootstrap_token(config)?;
+                tokens.insert(token, AuthenticationScope::Admin, None);
+            }
+            self.authentication_tokens.share_mut(|t| *t = tokens);
+            Ok(())
+        }
+
+        /// Issue a token which authenticates connections to interfaces
+        /// that require authentication.
+        pub async fn issue_authentication_token(
+            &self,
+            payload: IssueAuthenticationTokenPayload,
+        ) -> ConductorResult<AuthenticationTokenIssued> {
+            let IssueAuthenticationTokenPayload {
+                scope,
+                expiry_seconds,
+            } = payload;
+            let (_, issued) = self
+                .update_state_prime({
+                    let scope = scope.clone();
+                    move |mut state| {
+                        if let AuthenticationScope::App { installed_app_ids } = &scope {
+                            for installed_app_id in installed_app_ids {
+                                state.get_app(installed_app_id)?;
+                            }
+                        }
+                        let issued = state.authentication_tokens.issue(
+                            scope,
+                            expiry_seconds,
+                            Timestamp::now(),
+                        );
+                        Ok((state, issued))
+                    }
+                })
+                .await?;
+            self.authentication_tokens
+                .share_mut(|tokens| tokens.insert(issued.token.clone(), scope, issued.expires_at));
+            Ok(issued)
+        }
+
+        /// Revoke an authentication token, so that connections authenticated
+        /// with it are no longer served.
+        pub async fn revoke_authentication_token(
+            &self,
+            token: &AuthenticationToken,
+        ) -> ConductorResult<()> {
+            self.update_state({
+                let token = token.clone();
+                move |mut state| {
+                    state.authentication_tokens.revoke(&token);
+                    Ok(state)
+                }
+            })
+            .await?;
+            self.authentication_tokens
+                .share_mut(|tokens| tokens.revoke(token));
+            Ok(())
+        }
+
+        /// Check that a token is valid for authenticating a connection to
+        /// this kind of interface, and get what the connection may access.
+        pub fn authenticate(
+            &self,
+            token: &AuthenticationToken,
+            kind: InterfaceKind,
+        ) -> Result<AuthenticationScope, AuthenticationError> {
+            self.authentication_tokens
+                .share_mut(|tokens| tokens.authenticate(token, kind, Timestamp::now()))
+        }
     }
 }
 
@@ -551,6 +727,27 @@ mod dna_impls {
             self.ribosome_store().share_ref(|ds| ds.get_entry_def(key))
         }
 
+        /// List the functions exported by each zome of a DNA, separating the
+        /// callbacks from the zome functions which can be called by a client.
+        pub fn list_zome_functions(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorResult<Vec<ZomeFunctions>> {
+            let ribosome = self.get_ribosome(dna_hash)?;
+            let zome_functions = ribosome
+                .dna_def()
+                .all_zomes()
+                .map(|(zome_name, _)| {
+                    Ok(ZomeFunctions {
+                        zome_name: zome_name.clone(),
+                        zome_fns: ribosome.list_zome_fns(zome_name)?,
+                        callbacks: ribosome.list_callbacks(zome_name)?,
+                    })
+                })
+                .collect::<ConductorResult<Vec<_>>>()?;
+            Ok(zome_functions)
+        }
+
         /// Create a hash map of all existing DNA definitions, mapped to cell
         /// ids.
         pub fn get_dna_definitions(
@@ -853,6 +1050,70 @@ mod network_impls {
             .collect::<Result<Vec<_>, _>>()
         }
 
+        /// The agents whose peer info is withheld from the network because
+        /// they have been blocked by [`Self::block_warranted_agents`].
+        fn blocked_agents(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorResult<HashSet<Arc<kitsune_p2p::KitsuneAgent>>> {
+            Ok(self
+                .spaces
+                .blocked_agents(dna_hash)?
+                .share_ref(|blocked| blocked.clone()))
+        }
+
+        /// Block every agent with a verified warrant against them in a DNA's
+        /// network, if `block_warranted_agents` is set in the conductor config.
+        ///
+        /// Their peer info is withheld from the network, and the network
+        /// refuses to communicate with the nodes hosting them. An agent whose
+        /// info hasn't been received yet is blocked once it arrives.
+        pub(crate) async fn block_warranted_agents(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorResult<()> {
+            use holochain_p2p::AgentPubKeyExt;
+
+            if !self.config.block_warranted_agents {
+                return Ok(());
+            }
+            let agents = self
+                .get_or_create_dht_db(dna_hash)?
+                .async_reader(|txn| holochain_state::warrants::list_warranted_agents(&txn))
+                .await?;
+            let blocked = self.blocked_agents(dna_hash)?;
+            let mut result = Ok(());
+            for agent in agents {
+                if blocked.contains(&agent.to_kitsune()) {
+                    continue;
+                }
+                // Only agents which the network has really blocked are
+                // marked as blocked, so that the others are tried again.
+                match self
+                    .holochain_p2p
+                    .block(dna_hash.clone(), agent.clone())
+                    .await
+                {
+                    Ok(true) => {
+                        tracing::info!(?agent, "Blocked an agent with a verified warrant");
+                        self.spaces
+                            .blocked_agents(dna_hash)?
+                            .share_mut(|blocked| blocked.insert(agent.to_kitsune()));
+                    }
+                    Ok(false) => {
+                        tracing::debug!(?agent, "Blocking a warranted agent once its info arrives");
+                    }
+                    Err(e) => {
+                        tracing::warn!(?agent, ?e, "Failed to block a warranted agent");
+                        if result.is_ok() {
+                            result = Err(e.into());
+                        }
+                    }
+                }
+            }
+            result
+        }
+
         #[instrument(skip(self))]
         pub(crate) async fn dispatch_holochain_p2p_event(
             &self,
@@ -863,8 +1124,13 @@ mod network_impls {
             trace!(dispatch_event = ?event);
             match event {
                 PutAgentInfoSigned {
-                    peer_data, respond, ..
+                    mut peer_data,
+                    respond,
+                    ..
                 } => {
+                    let blocked = self.blocked_agents(&dna_hash)?;
+                    peer_data.retain(|info| !blocked.contains(&info.agent));
+                    let any_stored = !peer_data.is_empty();
                     let sender = self.p2p_batch_sender(&dna_hash);
                     let (result_sender, response) = tokio::sync::oneshot::channel();
                     let _ = sender
@@ -877,7 +1143,15 @@ mod network_impls {
                         Ok(r) => r.map_err(holochain_p2p::HolochainP2pError::other),
                         Err(e) => Err(holochain_p2p::HolochainP2pError::other(e)),
                     };
+                    let stored = any_stored && res.is_ok();
                     respond.respond(Ok(async move { res }.boxed().into()));
+                    // The info of a warranted agent may have just arrived,
+                    // which is needed to find the nodes to block.
+                    if stored {
+                        if let Err(e) = self.block_warranted_agents(&dna_hash).await {
+                            tracing::warn!(?e, "Failed to block warranted agents");
+                        }
+                    }
                 }
                 QueryAgentInfoSigned {
                     kitsune_space,
@@ -885,15 +1159,20 @@ mod network_impls {
                     respond,
                     ..
                 } => {
+                    let blocked = self.blocked_agents(&dna_hash)?;
                     let db = { self.p2p_agents_db(&dna_hash) };
                     let res = list_all_agent_info(db.into(), kitsune_space)
                         .await
-                        .map(|infos| match agents {
-                            Some(agents) => infos
+                        .map(|infos| {
+                            infos
                                 .into_iter()
-                                .filter(|info| agents.contains(&info.agent))
-                                .collect(),
-                            None => infos,
+                                .filter(|info| {
+                                    agents
+                                        .as_ref()
+                                        .map_or(true, |agents| agents.contains(&info.agent))
+                                        && !blocked.contains(&info.agent)
+                                })
+                                .collect()
                         })
                         .map_err(holochain_p2p::HolochainP2pError::other);
                     respond.respond(Ok(async move { res }.boxed().into()));
@@ -906,6 +1185,7 @@ mod network_impls {
                     ..
                 } => {
                     use holochain_sqlite::db::AsP2pAgentStoreConExt;
+                    let blocked = self.blocked_agents(&dna_hash)?;
                     let db = { self.p2p_agents_db(&dna_hash) };
                     let permit = db.conn_permit().await;
                     let res = tokio::task::spawn_blocking(move || {
@@ -915,7 +1195,11 @@ mod network_impls {
                     .await;
                     let res = res
                         .map_err(holochain_p2p::HolochainP2pError::other)
-                        .and_then(|r| r.map_err(holochain_p2p::HolochainP2pError::other));
+                        .and_then(|r| r.map_err(holochain_p2p::HolochainP2pError::other))
+                        .map(|mut infos| {
+                            infos.retain(|info| !blocked.contains(&info.agent));
+                            infos
+                        });
                     respond.respond(Ok(async move { res }.boxed().into()));
                 }
                 QueryAgentInfoSignedNearBasis {
@@ -925,6 +1209,7 @@ mod network_impls {
                     respond,
                     ..
                 } => {
+                    let blocked = self.blocked_agents(&dna_hash)?;
                     let db = { self.p2p_agents_db(&dna_hash) };
                     let res = list_all_agent_info_signed_near_basis(
                         db.into(),
@@ -933,6 +1218,10 @@ mod network_impls {
                         limit,
                     )
                     .await
+                    .map(|mut infos| {
+                        infos.retain(|info| !blocked.contains(&info.agent));
+                        infos
+                    })
                     .map_err(holochain_p2p::HolochainP2pError::other);
                     respond.respond(Ok(async move { res }.boxed().into()));
                 }
@@ -973,6 +1262,7 @@ mod network_impls {
                 | Get { .. }
                 | GetMeta { .. }
                 | GetLinks { .. }
+                | CountLinks { .. }
                 | GetAgentActivity { .. }
                 | MustGetAgentActivity { .. }
                 | ValidationReceiptReceived { .. } => {
@@ -1005,6 +1295,23 @@ mod network_impls {
                     .instrument(debug_span!("handle_publish"))
                     .await;
                 }
+                PublishWarrants {
+                    dna_hash,
+                    respond,
+                    warrants,
+                    ..
+                } => {
+                    async {
+                        let res = self
+                            .spaces
+                            .handle_publish_warrants(&dna_hash, warrants)
+                            .await
+                            .map_err(holochain_p2p::HolochainP2pError::other);
+                        respond.respond(Ok(async move { res }.boxed().into()));
+                    }
+                    .instrument(debug_span!("handle_publish_warrants"))
+                    .await;
+                }
                 FetchOpData {
                     respond,
                     query,
@@ -1101,10 +1408,17 @@ mod app_impls {
                 installed_app_id,
                 membrane_proofs,
                 network_seed,
+                trusted_publishers,
             } = payload;
 
             let bundle: AppBundle = {
-                let original_bundle = source.resolve().await?;
+                let mut original_bundle = source.resolve().await?;
+                // Signatures only cover the bundle as published, so they must
+                // be checked before the manifest is modified.
+                if let Some(trusted_publishers) = trusted_publishers {
+                    original_bundle =
+                        original_bundle.with_verified_signatures(&trusted_publishers)?;
+                }
                 if let Some(network_seed) = network_seed {
                     let mut manifest = original_bundle.manifest().to_owned();
                     manifest.set_network_seed(network_seed);
@@ -1113,11 +1427,28 @@ mod app_impls {
                     original_bundle
                 }
             };
+            // Remote DNAs and zomes pinned by hash are cached alongside the
+            // databases, so that the app can be installed again offline.
+            let bundle = {
+                let root_db_dir: std::path::PathBuf = self.root_db_dir().clone().into();
+                bundle.with_cache_dir(root_db_dir.join("bundle_cache"))
+            };
 
             let installed_app_id =
                 installed_app_id.unwrap_or_else(|| bundle.manifest().app_name().to_owned());
+            // Cells of any other installed app may be shared with this app,
+            // as determined by the provisioning strategy of each role.
+            let gamut = {
+                let state = self.get_state().await?;
+                DnaGamut::new(
+                    state
+                        .installed_apps()
+                        .iter()
+                        .flat_map(|(_, app)| app.all_cells().cloned().collect::<Vec<_>>()),
+                )
+            };
             let ops = bundle
-                .resolve_cells(agent_key.clone(), DnaGamut::placeholder(), membrane_proofs)
+                .resolve_cells(agent_key.clone(), gamut, membrane_proofs)
                 .await?;
 
             let cells_to_create = ops.cells_to_create();
@@ -1125,6 +1456,10 @@ mod app_impls {
             for (dna, _) in ops.dnas_to_register {
                 self.clone().register_dna(dna).await?;
             }
+            // Roles which only provision clones still need their DNA registered
+            for dna in ops.dnas_to_register_without_cells {
+                self.clone().register_dna(dna).await?;
+            }
 
             crate::conductor::conductor::genesis_cells(self.clone(), cells_to_create).await?;
 
@@ -1345,13 +1680,13 @@ mod clone_cell_impls {
             }
             let state = self.get_state().await?;
             let app = state.get_app(&app_id)?;
-            app.provisioned_cells()
-                .find(|(app_role_name, _)| **app_role_name == role_name)
-                .ok_or_else(|| {
-                    ConductorError::CloneCellError(
-                        "no base cell found for provided role id".to_string(),
-                    )
-                })?;
+            // Roles with the Disabled provisioning strategy have no provisioned
+            // Cell, but clones are still made from the DNA of their base Cell.
+            app.role(&role_name).map_err(|_| {
+                ConductorError::CloneCellError(
+                    "no base cell found for provided role id".to_string(),
+                )
+            })?;
 
             // add cell to app
             let installed_clone_cell = self
@@ -1444,6 +1779,256 @@ mod clone_cell_impls {
     }
 }
 
+/// Methods related to migrating source chains between DNAs
+mod chain_migration_impls {
+    use super::*;
+    use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+    use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+    use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+    use holochain_zome_types::builder;
+
+    impl Conductor {
+        /// Migrate the source chain of an app's provisioned cell to a new DNA.
+        ///
+        /// The old cell's chain is closed with a `CloseChain` action and a new
+        /// cell is created in the new DNA, whose chain is opened with an
+        /// `OpenChain` action. The new cell replaces the old one as the
+        /// provisioned cell of the role, and the old cell keeps running so
+        /// its data can still be read.
+        ///
+        /// Both DNAs' callbacks are run before anything is written, and the
+        /// `OpenChain` action is written as part of the new cell's genesis.
+        /// If the old chain can't be closed the app is left unchanged.
+        ///
+        /// # Returns
+        ///
+        /// The info of the app after the migration.
+        pub async fn migrate_chain(
+            self: Arc<Self>,
+            payload: MigrateChainPayload,
+        ) -> ConductorResult<AppInfo> {
+            let MigrateChainPayload {
+                app_id,
+                role_name,
+                new_dna_hash,
+                membrane_proof,
+            } = payload;
+            let state = self.get_state().await?;
+            let app = state.get_app(&app_id)?;
+            if !app.status().is_running() {
+                return Err(ConductorError::AppNotRunning(app_id));
+            }
+            let prev_cell_id = app
+                .role(&role_name)?
+                .provisioned_cell()
+                .cloned()
+                .ok_or_else(|| AppError::RoleNotProvisioned(role_name.clone()))?;
+            let prev_dna_hash = prev_cell_id.dna_hash().clone();
+            if prev_dna_hash == new_dna_hash {
+                return Err(ConductorError::ChainMigrationError(
+                    "a chain can't be migrated to the DNA it is already in".to_string(),
+                ));
+            }
+            let new_cell_id =
+                CellId::new(new_dna_hash.clone(), prev_cell_id.agent_pubkey().clone());
+            if app.all_cells().any(|cell_id| *cell_id == new_cell_id) {
+                return Err(AppError::DuplicateCellId(new_cell_id).into());
+            }
+
+            // Nothing more can be written to a closed chain, including the
+            // init marker, so the old cell's zomes must be initialized first.
+            self.cell_by_id(&prev_cell_id)?
+                .check_or_run_zome_init()
+                .await?;
+
+            // Every zome of the old DNA must agree to close the chain
+            // before anything is written.
+            let prev_workspace = self.chain_migration_workspace(&prev_cell_id).await?;
+            if let Some(hash) = prev_workspace.source_chain().new_dna_hash().await? {
+                return Err(ConductorError::ChainMigrationError(format!(
+                    "the chain of cell {:?} has already been migrated to {}",
+                    prev_cell_id, hash
+                )));
+            }
+            self.run_migrate_agent(&prev_cell_id, &prev_workspace, MigrateAgent::Close)?;
+
+            // Every zome of the new DNA must agree to open the chain, which
+            // is asked before its genesis so that a refusal leaves nothing
+            // behind.
+            let ribosome = self.get_ribosome(&new_dna_hash)?;
+            let space = self.get_or_create_space(&new_dna_hash)?;
+            let new_workspace = SourceChainWorkspace::raw_empty(
+                space.authored_db.clone(),
+                space.dht_db.clone(),
+                space.dht_query_cache.clone(),
+                space.cache_db.clone(),
+                self.keystore.clone(),
+                new_cell_id.agent_pubkey().clone(),
+                Arc::new(ribosome.dna_def().as_content().clone()),
+            )
+            .await?;
+            // A chain left behind by an earlier failed migration from the
+            // same cell is reused, any other chain is refused.
+            let new_chain = new_workspace.source_chain();
+            if !new_chain
+                .query(ChainQueryFilter::new().action_type(ActionType::Dna))
+                .await?
+                .is_empty()
+                && new_chain.prev_dna_hash().await?.as_ref() != Some(&prev_dna_hash)
+            {
+                return Err(ConductorError::ChainMigrationError(format!(
+                    "cell {:?} already has a source chain",
+                    new_cell_id
+                )));
+            }
+            self.run_migrate_agent(&new_cell_id, &new_workspace, MigrateAgent::Open)?;
+
+            prev_workspace
+                .source_chain()
+                .put(
+                    builder::CloseChain {
+                        new_dna_hash: new_dna_hash.clone(),
+                    },
+                    None,
+                    ChainTopOrdering::Strict,
+                )
+                .await?;
+
+            // The open action is written together with the rest of genesis.
+            let chc = self.chc(&new_cell_id)?;
+            Cell::genesis(
+                new_cell_id.clone(),
+                self.clone(),
+                space.authored_db,
+                space.dht_db,
+                space.dht_query_cache,
+                ribosome,
+                membrane_proof,
+                chc,
+                Some(prev_dna_hash),
+            )
+            .await
+            .map_err(|e| ConductorError::GenesisFailed { errors: vec![e] })?;
+
+            self.update_state({
+                let app_id = app_id.clone();
+                let role_name = role_name.clone();
+                let new_cell_id = new_cell_id.clone();
+                move |mut state| {
+                    state
+                        .get_app_mut(&app_id)?
+                        .migrate_provisioned_cell(&role_name, new_cell_id)?;
+                    Ok(state)
+                }
+            })
+            .await?;
+
+            // Closing the old chain is the last step which can fail, as the
+            // chain may have moved on since the callbacks were run.
+            if let Err(e) = self
+                .flush_chain_migration(&prev_cell_id, &prev_workspace)
+                .await
+            {
+                self.update_state({
+                    let app_id = app_id.clone();
+                    move |mut state| {
+                        state
+                            .get_app_mut(&app_id)?
+                            .revert_migrated_provisioned_cell(&role_name)?;
+                        Ok(state)
+                    }
+                })
+                .await?;
+                return Err(e);
+            }
+
+            self.create_and_add_initialized_cells_for_running_apps(Some(&app_id))
+                .await?;
+
+            self.get_app_info(&app_id)
+                .await?
+                .ok_or(ConductorError::AppNotInstalled(app_id))
+        }
+
+        async fn chain_migration_workspace(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorResult<SourceChainWorkspace> {
+            let space = self.get_or_create_space(cell_id.dna_hash())?;
+            let ribosome = self.get_ribosome(cell_id.dna_hash())?;
+            Ok(SourceChainWorkspace::new(
+                space.authored_db,
+                space.dht_db,
+                space.dht_query_cache,
+                space.cache_db,
+                self.keystore.clone(),
+                cell_id.agent_pubkey().clone(),
+                Arc::new(ribosome.dna_def().as_content().clone()),
+            )
+            .await?)
+        }
+
+        fn run_migrate_agent(
+            &self,
+            cell_id: &CellId,
+            workspace: &SourceChainWorkspace,
+            migrate_agent: MigrateAgent,
+        ) -> ConductorResult<()> {
+            let ribosome = self.get_ribosome(cell_id.dna_hash())?;
+            let host_access = MigrateAgentHostAccess::new(workspace.clone().into());
+            let invocation =
+                MigrateAgentInvocation::new(ribosome.dna_def().as_content().clone(), migrate_agent);
+            match ribosome.run_migrate_agent(host_access, invocation)? {
+                MigrateAgentResult::Pass => Ok(()),
+                MigrateAgentResult::Fail(zome_name, reason) => {
+                    Err(ConductorError::ChainMigrationError(format!(
+                        "zome {} of cell {:?} refused the migration: {}",
+                        zome_name, cell_id, reason
+                    )))
+                }
+            }
+        }
+
+        async fn flush_chain_migration(
+            &self,
+            cell_id: &CellId,
+            workspace: &SourceChainWorkspace,
+        ) -> ConductorResult<()> {
+            let cell = self.cell_by_id(cell_id)?;
+            workspace
+                .source_chain()
+                .flush(cell.holochain_p2p_dna())
+                .await?;
+            cell.triggers()
+                .integrate_dht_ops
+                .trigger(&"flush_chain_migration");
+            cell.triggers()
+                .publish_dht_ops
+                .trigger(&"flush_chain_migration");
+            Ok(())
+        }
+    }
+}
+
+/// Methods related to backing up the conductor's databases
+mod backup_impls {
+    use super::*;
+    use holochain_conductor_api::backup::BackupManifest;
+
+    impl Conductor {
+        /// Write a backup of every database of this conductor to a new tar
+        /// archive at `path`, while the conductor keeps running.
+        ///
+        /// The keystore is in use while the conductor runs, so its files are
+        /// left out. See [`crate::conductor::backup`] for the contents of the
+        /// archive.
+        pub async fn create_backup(&self, path: PathBuf) -> ConductorResult<BackupManifest> {
+            crate::conductor::backup::create_backup(self.root_db_dir().clone().into(), None, path)
+                .await
+        }
+    }
+}
+
 /// Methods related to management of app and cell status
 mod app_status_impls {
     use super::*;
@@ -1988,6 +2573,26 @@ mod misc_impls {
             .await
         }
 
+        /// Export the source chain of a cell as a portable archive,
+        /// optionally encrypting its private entries.
+        pub async fn export_source_chain(
+            &self,
+            cell_id: CellId,
+            encrypt_private_entries: bool,
+        ) -> ConductorApiResult<SourceChainArchive> {
+            source_chain_archive::export_source_chain(self, cell_id, encrypt_private_entries).await
+        }
+
+        /// Verify the source chain in an archive made by [`Conductor::export_source_chain`]
+        /// and insert it into the empty source chain of the same cell on this conductor.
+        pub async fn import_source_chain(
+            self: Arc<Self>,
+            archive: SourceChainArchive,
+            validate: bool,
+        ) -> ConductorApiResult<()> {
+            source_chain_archive::import_source_chain(self, archive, validate).await
+        }
+
         /// Update coordinator zomes on an existing dna.
         pub async fn update_coordinators(
             &self,
@@ -2004,7 +2609,6 @@ mod misc_impls {
                     None => Err(DnaError::DnaMissing(hash.to_owned())),
                 })?;
             let _old_wasms = ribosome
-                .dna_file
                 .update_coordinators(coordinator_zomes.clone(), wasms.clone())
                 .await?;
 
@@ -2247,8 +2851,10 @@ impl Conductor {
             let handle = self.clone();
             let managed_task_add_sender = managed_task_add_sender.clone();
             let managed_task_stop_broadcaster = managed_task_stop_broadcaster.clone();
-            let chc = handle.chc(cell_id);
             async move {
+                let chc = handle
+                    .chc(cell_id)
+                    .map_err(|e| (cell_id.clone(), CellError::ConductorError(Box::new(e))))?;
                 let holochain_p2p_cell =
                     handle.holochain_p2p.to_dna(cell_id.dna_hash().clone(), chc);
 
@@ -2498,7 +3104,7 @@ pub(crate) async fn genesis_cells(
             let dht_db = space.dht_db;
             let dht_db_cache = space.dht_query_cache;
             let conductor = conductor.clone();
-            let chc = conductor.chc(&cell_id);
+            let chc = conductor.chc(&cell_id).map_err(Box::new)?;
             let cell_id_inner = cell_id.clone();
             let ribosome = conductor
                 .get_ribosome(cell_id.dna_hash())
@@ -2513,6 +3119,7 @@ pub(crate) async fn genesis_cells(
                     ribosome,
                     proof,
                     chc,
+                    None,
                 )
                 .await
             })
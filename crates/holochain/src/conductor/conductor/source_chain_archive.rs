@@ -0,0 +1,192 @@
+use holochain_conductor_api::source_chain_archive::*;
+use holochain_types::prelude::ChainItem;
+
+use super::*;
+use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::api::error::SerializationError;
+
+fn archive_error(e: impl std::fmt::Display) -> ConductorApiError {
+    ConductorError::SourceChainArchiveError(e.to_string()).into()
+}
+
+pub(crate) async fn export_source_chain(
+    conductor: &Conductor,
+    cell_id: CellId,
+    encrypt_private_entries: bool,
+) -> ConductorApiResult<SourceChainArchive> {
+    if conductor.get_dna_def(cell_id.dna_hash()).is_none() {
+        return Err(DnaError::DnaMissing(cell_id.dna_hash().clone()).into());
+    }
+    let space = conductor.get_or_create_space(cell_id.dna_hash())?;
+    let keystore = conductor.keystore().clone();
+    let mut records = space
+        .source_chain(keystore.clone(), cell_id.agent_pubkey().clone())
+        .await?
+        .query(ChainQueryFilter::new().include_entries(true))
+        .await?;
+    if records.is_empty() {
+        return Err(archive_error(format!(
+            "The source chain of cell {:?} is empty",
+            cell_id
+        )));
+    }
+    records.sort_unstable_by_key(|r| r.action().action_seq());
+
+    // Private entries are "box"ed from and to the agent itself.
+    let x25519_key = if encrypt_private_entries {
+        Some(
+            keystore
+                .agent_x25519_pub_key(cell_id.agent_pubkey().clone())
+                .await
+                .map_err(archive_error)?,
+        )
+    } else {
+        None
+    };
+
+    let mut archived = Vec::with_capacity(records.len());
+    for record in records {
+        let is_private = matches!(
+            record.action().entry_type().map(|t| t.visibility()),
+            Some(EntryVisibility::Private)
+        );
+        let (signed_action, entry) = record.into_inner();
+        let entry = match (entry.into_option(), &x25519_key) {
+            (None, _) => ArchivedEntry::Absent,
+            (Some(entry), Some(key)) if is_private => {
+                let data =
+                    holochain_serialized_bytes::encode(&entry).map_err(SerializationError::from)?;
+                let (nonce, cipher) = keystore
+                    .crypto_box_xsalsa(key.clone(), key.clone(), data.into())
+                    .await
+                    .map_err(archive_error)?;
+                ArchivedEntry::Encrypted(XSalsa20Poly1305EncryptedData::new(
+                    nonce.into(),
+                    cipher.to_vec(),
+                ))
+            }
+            (Some(entry), _) => ArchivedEntry::Present(entry),
+        };
+        archived.push(ArchivedRecord {
+            signed_action: signed_action.into(),
+            entry,
+        });
+    }
+
+    Ok(SourceChainArchive {
+        version: SOURCE_CHAIN_ARCHIVE_VERSION,
+        cell_id,
+        exported_at: Timestamp::now(),
+        records: archived,
+    })
+}
+
+pub(crate) async fn import_source_chain(
+    handle: ConductorHandle,
+    archive: SourceChainArchive,
+    validate: bool,
+) -> ConductorApiResult<()> {
+    let SourceChainArchive {
+        version,
+        cell_id,
+        exported_at: _,
+        records,
+    } = archive;
+    if version != SOURCE_CHAIN_ARCHIVE_VERSION {
+        return Err(archive_error(format!(
+            "Unsupported source chain archive version {}, expected {}",
+            version, SOURCE_CHAIN_ARCHIVE_VERSION
+        )));
+    }
+    if handle.get_dna_def(cell_id.dna_hash()).is_none() {
+        return Err(DnaError::DnaMissing(cell_id.dna_hash().clone()).into());
+    }
+
+    let space = handle.get_or_create_space(cell_id.dna_hash())?;
+    let keystore = handle.keystore().clone();
+    let existing = space
+        .source_chain(keystore.clone(), cell_id.agent_pubkey().clone())
+        .await?
+        .query(ChainQueryFilter::new())
+        .await?;
+    if !existing.is_empty() {
+        return Err(archive_error(format!(
+            "Cell {:?} already has a source chain",
+            cell_id
+        )));
+    }
+
+    let mut x25519_key = None;
+    let mut imported = Vec::with_capacity(records.len());
+    for ArchivedRecord {
+        signed_action,
+        entry,
+    } in records
+    {
+        let signed_action = SignedActionHashed::from_content_sync(signed_action);
+        let action = signed_action.action();
+        if action.author() != cell_id.agent_pubkey() {
+            return Err(StateMutationError::AuthorsMustMatch.into());
+        }
+        signed_action.validate().await?;
+
+        let entry = match entry {
+            ArchivedEntry::Absent => None,
+            ArchivedEntry::Present(entry) => Some(entry),
+            ArchivedEntry::Encrypted(encrypted) => {
+                let key = match &x25519_key {
+                    Some(key) => key.clone(),
+                    None => {
+                        let key = keystore
+                            .agent_x25519_pub_key(cell_id.agent_pubkey().clone())
+                            .await
+                            .map_err(archive_error)?;
+                        x25519_key.insert(key).clone()
+                    }
+                };
+                let mut nonce = [0; 24];
+                nonce.copy_from_slice(encrypted.as_nonce_ref().as_ref());
+                let data = keystore
+                    .crypto_box_xsalsa_open(
+                        key.clone(),
+                        key,
+                        nonce,
+                        encrypted.as_encrypted_data_ref().to_vec().into(),
+                    )
+                    .await
+                    .map_err(archive_error)?;
+                Some(
+                    holochain_serialized_bytes::decode::<_, Entry>(&data[..])
+                        .map_err(SerializationError::from)?,
+                )
+            }
+        };
+        match (&entry, action.entry_hash()) {
+            (Some(entry), Some(entry_hash)) => {
+                if EntryHash::with_data_sync(entry) != *entry_hash {
+                    return Err(archive_error(format!(
+                        "The entry of action {} doesn't match its entry hash",
+                        signed_action.as_hash()
+                    )));
+                }
+            }
+            (Some(_), None) => {
+                return Err(archive_error(format!(
+                    "Action {} has an entry but doesn't reference one",
+                    signed_action.as_hash()
+                )))
+            }
+            (None, _) => (),
+        }
+        imported.push(Record::new(signed_action, entry));
+    }
+
+    // The archive must hold a whole chain, starting at its root.
+    imported.sort_unstable_by_key(|r| r.signed_action().seq());
+    crate::core::validate_chain(imported.iter().map(|r| r.signed_action()), &None)
+        .map_err(|e| SourceChainError::InvalidCommit(e.to_string()))?;
+
+    handle
+        .graft_records_onto_source_chain(cell_id, validate, imported)
+        .await
+}
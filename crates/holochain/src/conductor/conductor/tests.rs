@@ -213,18 +213,11 @@ async fn common_genesis_test_app(
 ) -> ConductorApiResult<SweetApp> {
     let hardcoded_zome = InlineIntegrityZome::new_unique(Vec::new(), 0);
 
-    // Just a strong reminder that we need to be careful once we start using existing Cells:
-    // When a Cell panics or fails validation in general, we want to disable all Apps touching that Cell.
+    // NB: when a Cell panics or fails validation in general, we want to disable all Apps touching that Cell.
     // However, if the panic/failure happens during Genesis, we want to completely
     // destroy the app which is attempting to Create that Cell, but *NOT* any other apps
-    // which might be touching that Cell.
-    //
-    // It probably works out to be the same either way, since if we are creating a Cell,
-    // no other app could be possibly referencing it, but just in case we have some kind of complex
-    // behavior like installing two apps which reference each others' Cells at the same time,
-    // we need to be aware of this distinction.
-    holochain_types::app::we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution(
-    );
+    // which might be touching that Cell. Genesis only ever runs for newly created Cells,
+    // so existing Cells shared with this app are never affected by a failure here.
 
     // Create one DNA which always works, and another from a zome that gets passed in
     let (dna_hardcoded, _, _) = mk_dna(("hardcoded", hardcoded_zome)).await;
@@ -937,3 +930,43 @@ async fn test_init_concurrency() {
     assert_eq!(num_calls_clone.fetch_add(0, Ordering::SeqCst), 100);
     assert_eq!(num_inits_clone.fetch_add(0, Ordering::SeqCst), 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_list_zome_functions() {
+    observability::test_run().ok();
+    let zome = InlineZomeSet::new_unique_single("integrity", "zome", vec![], 0)
+        .function("integrity", "validate", |_, _op: Op| {
+            Ok(ValidateCallbackResult::Valid)
+        })
+        .function("zome", "init", |_, ()| Ok(InitCallbackResult::Pass))
+        .function("zome", "migrate_agent_open", |_, ()| {
+            Ok(MigrateAgentCallbackResult::Pass)
+        })
+        .function("zome", "zomefunc", |_, ()| Ok(()))
+        .function("zome", "validated", |_, ()| Ok(()));
+    let (dna, _, _) = mk_dna(zome).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    conductor.register_dna(dna.clone()).await.unwrap();
+
+    let zome_functions = conductor
+        .raw_handle()
+        .list_zome_functions(dna.dna_hash())
+        .unwrap();
+    assert_eq!(zome_functions.len(), 2);
+
+    let integrity = &zome_functions[0];
+    assert_eq!(integrity.zome_name, "integrity".into());
+    assert!(integrity.zome_fns.is_empty());
+    assert!(integrity.callbacks.contains(&"validate".into()));
+
+    let coordinator = &zome_functions[1];
+    assert_eq!(coordinator.zome_name, "zome".into());
+    assert_eq!(
+        coordinator.zome_fns,
+        vec![FunctionName::from("validated"), "zomefunc".into()]
+    );
+    assert_eq!(
+        coordinator.callbacks,
+        vec![FunctionName::from("init"), "migrate_agent_open".into()]
+    );
+}
@@ -40,9 +40,21 @@ pub enum ConductorError {
     #[error("Error while cloning cell: {0}")]
     CloneCellError(String),
 
+    #[error("Error while migrating a source chain: {0}")]
+    ChainMigrationError(String),
+
+    #[error("Error while backing up or restoring databases: {0}")]
+    BackupError(String),
+
+    #[error("Error while exporting or importing a source chain: {0}")]
+    SourceChainArchiveError(String),
+
     #[error(transparent)]
     ConductorConfigError(#[from] ConductorConfigError),
 
+    #[error("Invalid chc_namespace in conductor config: {0}")]
+    InvalidChcNamespace(holochain_types::chc::ChcError),
+
     #[error("Configuration consistency error: {0}")]
     ConfigError(String),
 
@@ -8,10 +8,17 @@
 use crate::conductor::api::*;
 use error::InterfaceError;
 use error::InterfaceResult;
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_conductor_api::signal_subscription::SignalSubscription;
+use holochain_types::prelude::*;
 use holochain_types::signal::Signal;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryInto;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 
+pub mod authentication;
 #[allow(missing_docs)]
 pub mod error;
 pub mod websocket;
@@ -98,4 +105,48 @@ impl SignalBroadcaster {
     }
 }
 
+/// The signal subscriptions made over a single app interface connection.
+///
+/// Each subscription applies to the Cells of the app it was made for, as they
+/// were at the time of subscribing. Signals from Cells which are not covered
+/// by any subscription are always let through.
+#[derive(Clone, Debug, Default)]
+pub struct SignalSubscriptions(
+    Arc<parking_lot::RwLock<HashMap<InstalledAppId, (HashSet<CellId>, SignalFilterSet)>>>,
+);
+
+impl SignalSubscriptions {
+    /// Add or replace the subscription for an app
+    pub(crate) fn subscribe(&self, subscription: SignalSubscription, cells: HashSet<CellId>) {
+        self.0.write().insert(
+            subscription.installed_app_id().clone(),
+            (cells, subscription.filters().clone()),
+        );
+    }
+
+    /// Whether a signal should be sent over this connection.
+    ///
+    /// If a Cell is shared by several subscribed apps, its signals are let
+    /// through if any of those subscriptions allows them. System signals are
+    /// let through if any subscription allows them.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        let subscriptions = self.0.read();
+        if subscriptions.is_empty() {
+            return true;
+        }
+        match signal {
+            Signal::App { cell_id, .. } => {
+                let mut relevant = subscriptions
+                    .values()
+                    .filter(|(cells, _)| cells.contains(cell_id))
+                    .peekable();
+                relevant.peek().is_none() || relevant.any(|(_, filters)| filters.allows(signal))
+            }
+            Signal::System(_) => subscriptions
+                .values()
+                .any(|(_, filters)| filters.allows(signal)),
+        }
+    }
+}
+
 pub use holochain_conductor_api::config::InterfaceDriver;
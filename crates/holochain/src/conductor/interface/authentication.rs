@@ -0,0 +1,247 @@
+//! The tokens a conductor has issued for authenticating connections
+//! to its admin and app interfaces.
+
+use super::error::AuthenticationError;
+use holochain_conductor_api::authentication::*;
+use holochain_types::prelude::*;
+use rand::RngCore;
+use std::collections::HashMap;
+
+/// Number of random bytes in an [`AuthenticationToken`].
+const TOKEN_LEN: usize = 32;
+
+/// Read the bootstrap token, creating its file with a new token if needed.
+///
+/// A token file which other users may access is refused.
+pub fn read_bootstrap_token(config: &BootstrapTokenConfig) -> std::io::Result<AuthenticationToken> {
+    let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+    let encoded = match config {
+        BootstrapTokenConfig::Token { token } => token.clone(),
+        BootstrapTokenConfig::File { path } => match std::fs::metadata(path) {
+            Ok(metadata) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    if metadata.permissions().mode() & 0o077 != 0 {
+                        return Err(invalid(format!(
+                            "bootstrap token file {} may be accessed by other users",
+                            path.display()
+                        )));
+                    }
+                }
+                #[cfg(not(unix))]
+                let _ = metadata;
+                std::fs::read_to_string(path)?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let encoded = base64::encode_config(
+                    AuthenticationTokens::generate_token().0,
+                    base64::URL_SAFE_NO_PAD,
+                );
+                let mut options = std::fs::OpenOptions::new();
+                options.write(true).create_new(true);
+                #[cfg(unix)]
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+                std::io::Write::write_all(&mut options.open(path)?, encoded.as_bytes())?;
+                encoded
+            }
+            Err(e) => return Err(e),
+        },
+    };
+    base64::decode_config(encoded.trim(), base64::URL_SAFE_NO_PAD)
+        .ok()
+        .filter(|bytes| !bytes.is_empty())
+        .map(AuthenticationToken)
+        .ok_or_else(|| invalid("the bootstrap token is not valid base64".to_string()))
+}
+
+/// The kinds of interface a token can authenticate connections to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterfaceKind {
+    /// An admin interface
+    Admin,
+    /// An app interface
+    App,
+}
+
+impl std::fmt::Display for InterfaceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterfaceKind::Admin => write!(f, "admin"),
+            InterfaceKind::App => write!(f, "app"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct IssuedToken {
+    scope: AuthenticationScope,
+    expires_at: Option<Timestamp>,
+}
+
+impl IssuedToken {
+    fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+/// The tokens which have been issued and not yet revoked.
+///
+/// They are persisted in the [`ConductorState`](crate::conductor::state::ConductorState)
+/// and kept in memory by the conductor, so that authenticating a request
+/// doesn't need to read the state.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AuthenticationTokens(HashMap<AuthenticationToken, IssuedToken>);
+
+impl AuthenticationTokens {
+    /// Generate a new random token.
+    pub fn generate_token() -> AuthenticationToken {
+        let mut bytes = vec![0; TOKEN_LEN];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        AuthenticationToken(bytes)
+    }
+
+    /// Issue a new random token, which expires `expiry_seconds` after `now`.
+    /// Tokens which have expired by `now` are forgotten.
+    pub fn issue(
+        &mut self,
+        scope: AuthenticationScope,
+        expiry_seconds: Option<u64>,
+        now: Timestamp,
+    ) -> AuthenticationTokenIssued {
+        self.0.retain(|_, issued| !issued.is_expired(now));
+        let token = Self::generate_token();
+        let expires_at =
+            expiry_seconds.map(|s| now.saturating_add(&std::time::Duration::from_secs(s)));
+        self.insert(token.clone(), scope, expires_at);
+        AuthenticationTokenIssued { token, expires_at }
+    }
+
+    /// Add a token which was issued elsewhere, such as the bootstrap token
+    /// or a token issued in another copy of these tokens.
+    pub fn insert(
+        &mut self,
+        token: AuthenticationToken,
+        scope: AuthenticationScope,
+        expires_at: Option<Timestamp>,
+    ) {
+        self.0.insert(token, IssuedToken { scope, expires_at });
+    }
+
+    /// Revoke a token. Returns whether the token was known.
+    pub fn revoke(&mut self, token: &AuthenticationToken) -> bool {
+        self.0.remove(token).is_some()
+    }
+
+    /// Check that a token may be used to authenticate a connection to this
+    /// kind of interface at `now`, and return its scope.
+    /// Expired tokens are forgotten.
+    pub fn authenticate(
+        &mut self,
+        token: &AuthenticationToken,
+        kind: InterfaceKind,
+        now: Timestamp,
+    ) -> Result<AuthenticationScope, AuthenticationError> {
+        let issued = self.0.get(token).ok_or(AuthenticationError::UnknownToken)?;
+        if issued.is_expired(now) {
+            self.0.remove(token);
+            return Err(AuthenticationError::Expired);
+        }
+        match (&issued.scope, kind) {
+            (AuthenticationScope::Admin, InterfaceKind::Admin)
+            | (AuthenticationScope::App { .. }, InterfaceKind::App) => Ok(issued.scope.clone()),
+            _ => Err(AuthenticationError::WrongScope(kind)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn bootstrap_token_file_is_created_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BootstrapTokenConfig::File {
+            path: dir.path().join("token"),
+        };
+        let token = read_bootstrap_token(&config).unwrap();
+        assert_eq!(token.0.len(), TOKEN_LEN);
+        // The same token is read back.
+        assert_eq!(read_bootstrap_token(&config).unwrap(), token);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = dir.path().join("token");
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            // A token file others can read is refused.
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(read_bootstrap_token(&config).is_err());
+        }
+
+        let config = BootstrapTokenConfig::Token {
+            token: "not base64!".to_string(),
+        };
+        assert!(read_bootstrap_token(&config).is_err());
+    }
+
+    #[test]
+    fn issue_authenticate_and_revoke_tokens() {
+        let mut tokens = AuthenticationTokens::default();
+        let now = Timestamp::now();
+        let app_scope = AuthenticationScope::App {
+            installed_app_ids: vec!["app".to_string()],
+        };
+
+        let admin = tokens.issue(AuthenticationScope::Admin, None, now);
+        let app = tokens.issue(app_scope.clone(), Some(60), now);
+        assert_ne!(admin.token, app.token);
+        assert_eq!(admin.expires_at, None);
+        assert_eq!(
+            app.expires_at,
+            Some((now + Duration::from_secs(60)).unwrap())
+        );
+
+        assert_eq!(
+            tokens.authenticate(&admin.token, InterfaceKind::Admin, now),
+            Ok(AuthenticationScope::Admin)
+        );
+        assert_eq!(
+            tokens.authenticate(&app.token, InterfaceKind::App, now),
+            Ok(app_scope)
+        );
+
+        // Tokens only work for the kind of interface they were issued for.
+        assert_eq!(
+            tokens.authenticate(&admin.token, InterfaceKind::App, now),
+            Err(AuthenticationError::WrongScope(InterfaceKind::App))
+        );
+        assert_eq!(
+            tokens.authenticate(&app.token, InterfaceKind::Admin, now),
+            Err(AuthenticationError::WrongScope(InterfaceKind::Admin))
+        );
+
+        // Expired tokens are forgotten.
+        let later = (now + Duration::from_secs(60)).unwrap();
+        assert_eq!(
+            tokens.authenticate(&app.token, InterfaceKind::App, later),
+            Err(AuthenticationError::Expired)
+        );
+        assert_eq!(
+            tokens.authenticate(&app.token, InterfaceKind::App, now),
+            Err(AuthenticationError::UnknownToken)
+        );
+
+        assert!(tokens.revoke(&admin.token));
+        assert!(!tokens.revoke(&admin.token));
+        assert_eq!(
+            tokens.authenticate(&admin.token, InterfaceKind::Admin, now),
+            Err(AuthenticationError::UnknownToken)
+        );
+    }
+}
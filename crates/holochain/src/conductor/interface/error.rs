@@ -1,6 +1,10 @@
+use super::authentication::InterfaceKind;
 use crate::conductor::error::ConductorError;
+use holo_hash::DnaHash;
 use holochain_serialized_bytes::SerializedBytesError;
+use holochain_types::app::InstalledAppId;
 use holochain_types::signal::Signal;
+use holochain_zome_types::cell::CellId;
 
 /// Interface Error Type
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +31,31 @@ pub enum InterfaceError {
     WebsocketError(#[from] holochain_websocket::WebsocketError),
     #[error("Failed to find free port")]
     PortError,
+    #[error("Unix domain socket interfaces are not supported on this platform")]
+    UnixSocketUnsupported,
+    #[error("Connection rejected: {0}")]
+    AuthenticationFailed(#[from] AuthenticationError),
+}
+
+/// Why a connection to an interface which requires authentication was rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuthenticationError {
+    #[error("The first message over the connection was not an authentication request")]
+    NotAuthenticated,
+    #[error("No authentication request was sent over the connection in time")]
+    TimedOut,
+    #[error("The authentication token is unknown or has been revoked")]
+    UnknownToken,
+    #[error("The authentication token has expired")]
+    Expired,
+    #[error("The authentication token is not valid for {0} interfaces")]
+    WrongScope(InterfaceKind),
+    #[error("The connection is not authorized to access app {0}")]
+    AppNotAuthorized(InstalledAppId),
+    #[error("The connection is not authorized to access cell {0:?}")]
+    CellNotAuthorized(CellId),
+    #[error("The connection is not authorized to access DNA {0}")]
+    DnaNotAuthorized(DnaHash),
 }
 
 impl From<String> for InterfaceError {
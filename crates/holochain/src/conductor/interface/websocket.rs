@@ -1,22 +1,30 @@
 //! Module for establishing Websocket-based Interfaces,
 //! i.e. those configured with `InterfaceDriver::Websocket`
+//! or `InterfaceDriver::UnixSocket`
 
+use super::error::AuthenticationError;
 use super::error::InterfaceError;
 use super::error::InterfaceResult;
 use crate::conductor::conductor::StopReceiver;
 use crate::conductor::interface::*;
 use crate::conductor::manager::ManagedTaskHandle;
 use crate::conductor::manager::ManagedTaskResult;
+use futures::stream::BoxStream;
+use holochain_conductor_api::authentication::AuthenticationRequest;
+use holochain_conductor_api::authentication::AuthenticationResponse;
+use holochain_conductor_api::ExternalApiWireError;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_types::signal::Signal;
 use holochain_websocket::ListenerHandle;
 use holochain_websocket::ListenerItem;
+use holochain_websocket::OriginFilter;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketMessage;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
 use std::convert::TryFrom;
+use std::net::SocketAddr;
 
 use std::sync::atomic::AtomicIsize;
 use std::sync::atomic::Ordering;
@@ -32,29 +40,61 @@ use url2::url2;
 pub(crate) const SIGNAL_BUFFER_SIZE: usize = 50;
 const MAX_CONNECTIONS: isize = 400;
 
+/// How long a client has to send its authentication request after
+/// connecting to an interface which requires authentication.
+pub const AUTHENTICATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// File permissions for the unix domain sockets which interfaces listen on.
+/// Only the user running the conductor may connect.
+#[cfg(unix)]
+pub const UNIX_SOCKET_MODE: u32 = 0o600;
+
 /// Create a WebsocketListener to be used in interfaces
 pub async fn spawn_websocket_listener(
-    port: u16,
-) -> InterfaceResult<(
-    ListenerHandle,
-    impl futures::stream::Stream<Item = ListenerItem>,
-)> {
+    driver: &InterfaceDriver,
+) -> InterfaceResult<(ListenerHandle, BoxStream<'static, ListenerItem>)> {
     trace!("Initializing Admin interface");
-    let listener = WebsocketListener::bind_with_handle(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
-    trace!("LISTENING AT: {}", listener.0.local_addr());
-    Ok(listener)
+    let (handle, listener) = match driver {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address,
+            allowed_origins,
+        } => {
+            let (handle, listener) = WebsocketListener::bind_with_handle(
+                url2!("ws://{}", SocketAddr::new(*bind_address, *port)),
+                Arc::new(WebsocketConfig::default().origin_filter(OriginFilter::new({
+                    let allowed_origins = allowed_origins.clone();
+                    move |origin| allowed_origins.allows(origin)
+                }))),
+            )
+            .await?;
+            (handle, Box::pin(listener) as BoxStream<_>)
+        }
+        #[cfg(unix)]
+        InterfaceDriver::UnixSocket { path } => {
+            let (handle, listener) = WebsocketListener::bind_unix_with_handle(
+                path,
+                UNIX_SOCKET_MODE,
+                Arc::new(WebsocketConfig::default()),
+            )
+            .await?;
+            (handle, Box::pin(listener) as BoxStream<_>)
+        }
+        #[cfg(not(unix))]
+        InterfaceDriver::UnixSocket { .. } => return Err(InterfaceError::UnixSocketUnsupported),
+    };
+    trace!("LISTENING AT: {}", handle.local_addr());
+    Ok((handle, listener))
 }
 
 /// Create an Admin Interface, which only receives AdminRequest messages
-/// from the external client
+/// from the external client.
+/// If `require_authentication` is set, each connection must first authenticate.
 pub fn spawn_admin_interface_task<A: InterfaceApi>(
     handle: ListenerHandle,
     listener: impl futures::stream::Stream<Item = ListenerItem> + Send + 'static,
     api: A,
+    require_authentication: bool,
     mut stop_rx: StopReceiver,
 ) -> InterfaceResult<ManagedTaskHandle> {
     Ok(tokio::task::spawn(async move {
@@ -77,6 +117,7 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
                     tokio::task::spawn(recv_incoming_admin_msgs(
                         api.clone(),
                         rx_from_iface,
+                        require_authentication,
                         num_connections.clone(),
                     ));
                 }
@@ -90,24 +131,34 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task<A: InterfaceApi>(
-    port: u16,
-    api: A,
+/// from Cells via a broadcast channel.
+/// If `require_authentication` is set, each connection must first authenticate.
+/// Returns the driver the interface is listening on, which for a websocket
+/// on port 0 holds the port that was chosen by the OS.
+pub async fn spawn_app_interface_task(
+    driver: &InterfaceDriver,
+    api: RealAppInterfaceApi,
+    require_authentication: bool,
     signal_broadcaster: broadcast::Sender<Signal>,
     mut stop_rx: StopReceiver,
-) -> InterfaceResult<(u16, ManagedTaskHandle)> {
+) -> InterfaceResult<(InterfaceDriver, ManagedTaskHandle)> {
     trace!("Initializing App interface");
-    let (handle, mut listener) = WebsocketListener::bind_with_handle(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
-    trace!("LISTENING AT: {}", handle.local_addr());
-    let port = handle
-        .local_addr()
-        .port()
-        .ok_or(InterfaceError::PortError)?;
+    let (handle, mut listener) = spawn_websocket_listener(driver).await?;
+    let driver = match driver {
+        InterfaceDriver::Websocket {
+            bind_address,
+            allowed_origins,
+            ..
+        } => InterfaceDriver::Websocket {
+            port: handle
+                .local_addr()
+                .port()
+                .ok_or(InterfaceError::PortError)?,
+            bind_address: *bind_address,
+            allowed_origins: allowed_origins.clone(),
+        },
+        InterfaceDriver::UnixSocket { .. } => driver.clone(),
+    };
     // Task that will kill the listener and all child connections.
     tokio::task::spawn(
         handle.close_on(async move { stop_rx.recv().await.map(|_| true).unwrap_or(true) }),
@@ -116,14 +167,29 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
         // establish a new connection to a client
         while let Some(connection) = listener.next().await {
             match connection {
-                Ok((tx_to_iface, rx_from_iface)) => {
-                    let rx_from_cell = signal_broadcaster.subscribe();
-                    spawn_recv_incoming_msgs_and_outgoing_signals(
-                        api.clone(),
-                        rx_from_iface,
-                        rx_from_cell,
-                        tx_to_iface,
-                    );
+                Ok((tx_to_iface, mut rx_from_iface)) => {
+                    let api = api.clone();
+                    let signal_broadcaster = signal_broadcaster.clone();
+                    tokio::task::spawn(async move {
+                        let api = if require_authentication {
+                            match authenticate_connection(&api, &mut rx_from_iface).await {
+                                Ok(api) => api,
+                                Err(err) => {
+                                    warn!(?err, "App interface connection rejected");
+                                    return;
+                                }
+                            }
+                        } else {
+                            api.for_connection()
+                        };
+                        let rx_from_cell = signal_broadcaster.subscribe();
+                        spawn_recv_incoming_msgs_and_outgoing_signals(
+                            api,
+                            rx_from_iface,
+                            rx_from_cell,
+                            tx_to_iface,
+                        );
+                    });
                 }
                 Err(err) => {
                     warn!("Admin socket connection failed: {}", err);
@@ -133,18 +199,32 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
 
         ManagedTaskResult::Ok(())
     });
-    Ok((port, task))
+    Ok((driver, task))
 }
 
 /// Polls for messages coming in from the external client.
 /// Used by Admin interface.
 async fn recv_incoming_admin_msgs<A: InterfaceApi>(
     api: A,
-    rx_from_iface: WebsocketReceiver,
+    mut rx_from_iface: WebsocketReceiver,
+    require_authentication: bool,
     num_connections: Arc<AtomicIsize>,
 ) {
     use futures::stream::StreamExt;
 
+    let api = if require_authentication {
+        match authenticate_connection(&api, &mut rx_from_iface).await {
+            Ok(api) => api,
+            Err(err) => {
+                warn!(?err, "Admin interface connection rejected");
+                num_connections.fetch_sub(1, Ordering::SeqCst);
+                return;
+            }
+        }
+    } else {
+        api
+    };
+
     rx_from_iface
         .for_each_concurrent(4096, move |msg| {
             let api = api.clone();
@@ -160,9 +240,10 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
 
 /// Polls for messages coming in from the external client while simultaneously
 /// polling for signals being broadcast from the Cells associated with this
-/// App interface.
-fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
-    api: A,
+/// App interface. Signals are filtered by the subscriptions made over this
+/// connection.
+fn spawn_recv_incoming_msgs_and_outgoing_signals(
+    api: RealAppInterfaceApi,
     rx_from_iface: WebsocketReceiver,
     rx_from_cell: broadcast::Receiver<Signal>,
     tx_to_iface: WebsocketSender,
@@ -179,9 +260,15 @@ fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
         }
     });
 
+    let signal_api = api.clone();
     tokio::task::spawn(rx_from_cell.for_each_concurrent(4096, move |signal| {
         let mut tx_to_iface = tx_to_iface.clone();
+        let allowed = signal_api.allows_signal(&signal);
         async move {
+            if !allowed {
+                trace!(msg = "Signal filtered out by subscriptions", ?signal);
+                return;
+            }
             trace!(msg = "Sending signal!", ?signal);
             if let Err(err) = async move {
                 let bytes = SerializedBytes::try_from(signal)?;
@@ -205,6 +292,34 @@ fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
     }));
 }
 
+/// Waits for the first message over a connection to an interface which
+/// requires authentication, and authenticates the connection with the token
+/// it carries. The client is told whether the token was accepted.
+/// If it wasn't, or nothing was sent within [`AUTHENTICATION_TIMEOUT`], the
+/// caller closes the connection by dropping it.
+async fn authenticate_connection<A: InterfaceApi>(
+    api: &A,
+    rx_from_iface: &mut WebsocketReceiver,
+) -> InterfaceResult<A> {
+    let (bytes, respond) = tokio::time::timeout(AUTHENTICATION_TIMEOUT, rx_from_iface.next())
+        .await
+        .map_err(|_| AuthenticationError::TimedOut)?
+        .ok_or(InterfaceError::Closed)?;
+    let result = match AuthenticationRequest::try_from(bytes) {
+        Ok(AuthenticationRequest { token }) => api.authenticate(token).await,
+        Err(_) => Err(AuthenticationError::NotAuthenticated.into()),
+    };
+    let response = match &result {
+        Ok(_) => AuthenticationResponse::Authenticated,
+        Err(InterfaceError::AuthenticationFailed(e)) => {
+            AuthenticationResponse::Error(e.clone().into())
+        }
+        Err(e) => AuthenticationResponse::Error(ExternalApiWireError::internal(e)),
+    };
+    respond.respond(response.try_into()?).await?;
+    result
+}
+
 /// Handles messages on all interfaces
 async fn handle_incoming_message<A>(ws_msg: WebsocketMessage, api: A) -> InterfaceResult<()>
 where
@@ -235,6 +350,7 @@ pub mod test {
     use crate::test_utils::conductor_setup::ConductorTestData;
     use ::fixt::prelude::*;
     use futures::future::FutureExt;
+    use holochain_conductor_api::AllowedOrigins;
     use holochain_p2p::{AgentPubKeyExt, DnaHashExt};
     use holochain_serialized_bytes::prelude::*;
     use holochain_sqlite::prelude::*;
@@ -469,6 +585,68 @@ pub mod test {
         shutdown.await.unwrap().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signal_subscription_filters_connection() {
+        use holochain_conductor_api::signal_subscription::*;
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let cell_id = CellId::from((dna.dna_hash().clone(), fake_agent_pubkey_1()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let (_tmpdir, app_api, handle) = setup_app(vec![dna], vec![(installed_cell, None)]).await;
+        let connection_api = app_api.for_connection();
+
+        let signal = |zome_name: &str| Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(()).unwrap()),
+        };
+        let other_cell_signal = Signal::App {
+            cell_id: fixt!(CellId),
+            zome_name: "foo".into(),
+            signal: AppSignal::new(ExternIO::encode(()).unwrap()),
+        };
+
+        let filters = SignalFilterSet::Include(
+            [(cell_id.clone(), SignalFilter::empty().zome_name("foo"))]
+                .into_iter()
+                .collect(),
+        );
+        let msg = AppRequest::SignalSubscription(SignalSubscription::new(
+            "test app".to_string(),
+            filters,
+        ));
+        let msg = msg.try_into().unwrap();
+        let respond = |bytes: SerializedBytes| {
+            let response: AppResponse = bytes.try_into().unwrap();
+            assert_matches!(response, AppResponse::SignalSubscribed);
+            async { Ok(()) }.boxed().into()
+        };
+        let respond = Respond::Request(Box::new(respond));
+        handle_incoming_message((msg, respond), connection_api.clone())
+            .await
+            .unwrap();
+
+        let subscriptions = connection_api.signal_subscriptions();
+        assert!(subscriptions.allows(&signal("foo")));
+        assert!(!subscriptions.allows(&signal("bar")));
+        // Cells outside the subscribed app are unaffected
+        assert!(subscriptions.allows(&other_cell_signal));
+        // Other connections are unaffected
+        assert!(app_api
+            .for_connection()
+            .signal_subscriptions()
+            .allows(&signal("bar")));
+
+        let shutdown = handle.take_shutdown_handle().unwrap();
+        handle.shutdown();
+        shutdown.await.unwrap().unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn enable_disable_app() {
         observability::test_run().ok();
@@ -597,7 +775,12 @@ pub mod test {
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let shutdown = conductor_handle.take_shutdown_handle().unwrap();
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            bind_address: None,
+            allowed_origins: AllowedOrigins::Any,
+            require_authentication: false,
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
@@ -611,6 +794,66 @@ pub mod test {
         shutdown.await.unwrap().unwrap();
     }
 
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn attach_app_unix_socket_interface() {
+        use std::os::unix::fs::PermissionsExt;
+
+        observability::test_run().ok();
+        let (tmpdir, conductor_handle) = setup_admin().await;
+        let shutdown = conductor_handle.take_shutdown_handle().unwrap();
+        let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
+        let path = tmpdir.path().join("app.sock");
+        let msg = AdminRequest::AttachAppUnixSocketInterface {
+            path: path.clone(),
+            require_authentication: false,
+        };
+        let msg = msg.try_into().unwrap();
+        let respond = |bytes: SerializedBytes| {
+            let response: AdminResponse = bytes.try_into().unwrap();
+            assert_matches!(response, AdminResponse::AppUnixSocketInterfaceAttached);
+            async { Ok(()) }.boxed().into()
+        };
+        let respond = Respond::Request(Box::new(respond));
+        handle_incoming_message((msg, respond), admin_api)
+            .await
+            .unwrap();
+
+        // Only the conductor's user can connect
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, UNIX_SOCKET_MODE);
+        // Unix socket interfaces have no port to list
+        assert!(conductor_handle
+            .list_app_interfaces()
+            .await
+            .unwrap()
+            .is_empty());
+
+        let (mut app_tx, _) =
+            holochain_websocket::connect_unix(&path, Arc::new(WebsocketConfig::default()))
+                .await
+                .unwrap();
+        let response: AppResponse = app_tx
+            .request(AppRequest::AppInfo {
+                installed_app_id: "no such app".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_matches!(response, AppResponse::AppInfo(None));
+
+        conductor_handle.shutdown();
+        shutdown.await.unwrap().unwrap();
+
+        // The socket file is removed when the interface stops
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while path.exists() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn dump_state() {
         observability::test_run().ok();
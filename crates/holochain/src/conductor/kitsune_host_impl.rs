@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use super::{ribosome_store::RibosomeStore, space::Spaces};
 use futures::FutureExt;
-use holo_hash::DnaHash;
+use holo_hash::{AgentPubKey, DnaHash};
 use holochain_p2p::{
     dht::{spacetime::Topology, ArqStrat},
     DnaHashExt,
@@ -19,8 +19,10 @@ use holochain_types::{
     share::RwShare,
 };
 use kitsune_p2p::{
-    agent_store::AgentInfoSigned, dependencies::kitsune_p2p_fetch::OpHashSized,
-    event::GetAgentInfoSignedEvt, KitsuneHost, KitsuneHostResult,
+    agent_store::AgentInfoSigned,
+    dependencies::kitsune_p2p_fetch::{FetchContext, FetchPriority, OpHashSized},
+    event::GetAgentInfoSignedEvt,
+    KitsuneHost, KitsuneHostResult,
 };
 use kitsune_p2p_types::{config::KitsuneP2pTuningParams, KOpData, KOpHash};
 
@@ -229,4 +231,51 @@ impl KitsuneHost for KitsuneHostImpl {
         .boxed()
         .into()
     }
+
+    fn fetch_priority(
+        &self,
+        space: Arc<kitsune_p2p::KitsuneSpace>,
+        author: Arc<kitsune_p2p::KitsuneAgent>,
+        context: FetchContext,
+    ) -> KitsuneHostResult<FetchPriority> {
+        use holochain_p2p::{AgentPubKeyExt, FetchContextExt};
+
+        async move {
+            // A countersigning session is waiting for the op to be validated.
+            if context.has_countersigning_session() {
+                return Ok(FetchPriority::High);
+            }
+
+            // Ops by the same author are the most likely dependencies of
+            // this author's ops which are waiting in validation limbo.
+            let author = AgentPubKey::from_kitsune(&author);
+            let db = self.spaces.dht_db(&DnaHash::from_kitsune(&space))?;
+            let awaiting_deps = db
+                .async_reader(move |txn| {
+                    txn.query_row(
+                        "
+                        SELECT EXISTS(
+                            SELECT 1 FROM DhtOp
+                            JOIN Action ON DhtOp.action_hash = Action.hash
+                            WHERE Action.author = ?
+                            AND DhtOp.when_integrated IS NULL
+                            AND DhtOp.validation_stage IN (0, 2)
+                        )
+                        ",
+                        [&author],
+                        |row| row.get::<_, bool>(0),
+                    )
+                    .map_err(holochain_sqlite::prelude::DatabaseError::from)
+                })
+                .await?;
+
+            Ok(if awaiting_deps {
+                FetchPriority::High
+            } else {
+                FetchPriority::Normal
+            })
+        }
+        .boxed()
+        .into()
+    }
 }
@@ -1,7 +1,11 @@
 //! This module contains data and functions for running operations
 //! at the level of a [`DnaHash`] space.
 //! Multiple [`Cell`](crate::conductor::Cell)'s could share the same space.
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use holo_hash::{AgentPubKey, DhtOpHash, DnaHash};
 use holochain_conductor_api::conductor::{ConductorConfig, DatabaseRootPath};
@@ -28,19 +32,20 @@ use holochain_sqlite::{
     prelude::{DatabaseError, DatabaseResult},
 };
 use holochain_state::{
-    mutations,
+    mutations::{self, StateMutationResult},
     prelude::{from_blob, StateQueryResult},
     query::{map_sql_dht_op_common, StateQueryError},
     source_chain::{SourceChain, SourceChainResult},
+    warrants::verify_warrant,
 };
 use holochain_types::{
     db_cache::DhtDbQueryCache,
     dht_op::{DhtOp, DhtOpType},
 };
-use holochain_zome_types::{Entry, EntryVisibility, SignedAction, Timestamp};
+use holochain_zome_types::{Entry, EntryVisibility, SignedAction, SignedWarrant, Timestamp};
 use kitsune_p2p::{
     event::{TimeWindow, TimeWindowInclusive},
-    KitsuneP2pConfig,
+    KitsuneAgent, KitsuneP2pConfig,
 };
 use rusqlite::{named_params, OptionalExtension};
 use tracing::instrument;
@@ -125,6 +130,10 @@ pub struct Space {
 
     /// Incoming ops batch for this space.
     pub incoming_ops_batch: IncomingOpsBatch,
+
+    /// The agents who are blocked in this space because of a verified
+    /// warrant against them.
+    pub blocked_agents: RwShare<HashSet<Arc<KitsuneAgent>>>,
 }
 
 #[cfg(test)]
@@ -287,6 +296,14 @@ impl Spaces {
         self.get_or_create_space_ref(dna_hash, |space| space.p2p_metrics_db.clone())
     }
 
+    /// Get the agents blocked in a space (this will create the space if it doesn't already exist).
+    pub fn blocked_agents(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorResult<RwShare<HashSet<Arc<KitsuneAgent>>>> {
+        self.get_or_create_space_ref(dna_hash, |space| space.blocked_agents.clone())
+    }
+
     /// Get the batch sender (this will create the space if it doesn't already exist).
     pub fn p2p_batch_sender(
         &self,
@@ -632,6 +649,49 @@ impl Spaces {
         Ok(())
     }
 
+    /// Store warrants published to us by other agents.
+    ///
+    /// Warrants with a valid signature are stored as unverified. Anyone can
+    /// sign a warrant against an op they didn't author, so the warrant isn't
+    /// surfaced until app validation has run on its proof and found the op
+    /// to be invalid.
+    pub async fn handle_publish_warrants(
+        &self,
+        dna_hash: &DnaHash,
+        warrants: Vec<SignedWarrant>,
+    ) -> ConductorResult<()> {
+        let mut signed = Vec::with_capacity(warrants.len());
+        for warrant in warrants {
+            if verify_warrant(&warrant).await {
+                signed.push(warrant);
+            } else {
+                tracing::warn!(?warrant, "Dropping a warrant with an invalid signature");
+            }
+        }
+        if signed.is_empty() {
+            return Ok(());
+        }
+        let space = self.get_or_create_space(dna_hash)?;
+        space
+            .dht_db
+            .async_commit(move |txn| {
+                for warrant in signed {
+                    mutations::insert_warrant(txn, warrant, false)?;
+                }
+                StateMutationResult::Ok(())
+            })
+            .await?;
+
+        // App validation checks the proofs of unverified warrants.
+        if let Some(trigger) = self
+            .queue_consumer_map
+            .app_validation_trigger(space.dna_hash.clone())
+        {
+            trigger.trigger(&"handle_publish_warrants");
+        }
+        Ok(())
+    }
+
     /// Get the recent_threshold based on the kitsune network config
     pub fn recent_threshold(&self) -> Duration {
         self.network_config
@@ -704,6 +764,7 @@ impl Space {
             incoming_ops_batch,
             dht_query_cache,
             conductor_db,
+            blocked_agents: RwShare::new(HashSet::new()),
         };
         Ok(r)
     }
@@ -10,6 +10,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::error::{ConductorError, ConductorResult};
+use super::interface::authentication::AuthenticationTokens;
 
 /// Unique conductor tag / identifier.
 #[derive(Clone, Deserialize, Serialize, Debug, SerializedBytes)]
@@ -40,6 +41,9 @@ pub struct ConductorState {
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub(crate) app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
+    /// Tokens issued for authenticating connections to interfaces.
+    #[serde(default)]
+    pub(crate) authentication_tokens: AuthenticationTokens,
 }
 
 /// A unique identifier used to refer to an App Interface internally.
@@ -199,9 +203,7 @@ impl ConductorState {
 /// GUIs, browser based web UIs, local native UIs, other local applications and scripts.
 /// We currently have:
 /// * websockets
-///
-/// We will also soon develop
-/// * Unix domain sockets
+/// * websockets over unix domain sockets
 ///
 /// The cells (referenced by ID) that are to be made available via that interface should be listed.
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -210,8 +212,13 @@ pub struct AppInterfaceConfig {
     /// The signal subscription settings for each App
     pub signal_subscriptions: HashMap<InstalledAppId, SignalSubscription>,
 
-    /// The driver for the interface, e.g. Websocket
+    /// The driver for the interface, e.g. Websocket or UnixSocket
     pub driver: InterfaceDriver,
+
+    /// Whether connections must authenticate with an app scoped token
+    /// before making requests
+    #[serde(default)]
+    pub require_authentication: bool,
 }
 
 impl AppInterfaceConfig {
@@ -219,7 +226,8 @@ impl AppInterfaceConfig {
     pub fn websocket(port: u16) -> Self {
         Self {
             signal_subscriptions: HashMap::new(),
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::websocket(port),
+            require_authentication: false,
         }
     }
 }
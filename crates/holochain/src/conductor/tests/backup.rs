@@ -0,0 +1,153 @@
+use super::conductor_with_chain;
+use crate::conductor::backup::create_backup;
+use crate::conductor::backup::restore_backup;
+use crate::sweettest::*;
+use holochain_sqlite::backup::*;
+use holochain_sqlite::db::DbKindT;
+use holochain_sqlite::db::DbKindWasm;
+use std::path::Path;
+
+fn authored_databases(manifest_databases: &[std::path::PathBuf]) -> Vec<&Path> {
+    manifest_databases
+        .iter()
+        .filter(|db| db.starts_with("authored"))
+        .map(|db| db.as_path())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn backup_and_restore_roundtrip() {
+    let (conductor, _, _) = conductor_with_chain().await;
+    let archive_dir = tempfile::tempdir().unwrap();
+    let archive = archive_dir.path().join("backup.tar");
+
+    let manifest = conductor.create_backup(archive.clone()).await.unwrap();
+    assert_eq!(authored_databases(&manifest.databases).len(), 1);
+    assert!(manifest.keystore.is_empty());
+    assert!(manifest.databases.contains(&DbKindWasm.filename()));
+
+    // An existing archive is never overwritten.
+    assert!(conductor.create_backup(archive.clone()).await.is_err());
+
+    // Restoring over existing databases is refused.
+    assert!(
+        restore_backup(archive.clone(), conductor.db_path().to_owned(), None)
+            .await
+            .is_err()
+    );
+
+    let restore_dir = tempfile::tempdir().unwrap();
+    let restored = restore_backup(archive, restore_dir.path().to_owned(), None)
+        .await
+        .unwrap();
+    assert_eq!(restored, manifest);
+    assert_eq!(
+        find_database_files(restore_dir.path()).unwrap(),
+        manifest.databases
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restore_rejects_broken_source_chain() {
+    let (conductor, _, _) = conductor_with_chain().await;
+    let archive_dir = tempfile::tempdir().unwrap();
+    let archive = archive_dir.path().join("backup.tar");
+    let manifest = conductor.create_backup(archive.clone()).await.unwrap();
+
+    // Remove an action from the middle of the chain and repack the archive.
+    let unpacked = tempfile::tempdir().unwrap();
+    tar::Archive::new(std::fs::File::open(&archive).unwrap())
+        .unpack(unpacked.path())
+        .unwrap();
+    let authored = unpacked
+        .path()
+        .join(authored_databases(&manifest.databases)[0]);
+    open_database_file(&authored)
+        .unwrap()
+        .execute_batch("PRAGMA foreign_keys = OFF; DELETE FROM Action WHERE seq = 1;")
+        .unwrap();
+    let tampered = archive_dir.path().join("tampered.tar");
+    let mut builder = tar::Builder::new(std::fs::File::create(&tampered).unwrap());
+    builder.append_dir_all(".", unpacked.path()).unwrap();
+    builder.finish().unwrap();
+
+    let restore_dir = tempfile::tempdir().unwrap();
+    assert!(
+        restore_backup(tampered, restore_dir.path().to_owned(), None)
+            .await
+            .is_err()
+    );
+    // Nothing was restored.
+    assert!(find_database_files(restore_dir.path()).unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn backup_and_restore_keystore() {
+    let (conductor, _, _) = conductor_with_chain().await;
+    let keystore_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        keystore_dir.path().join("lair-keystore-config.yaml"),
+        "config",
+    )
+    .unwrap();
+    std::fs::write(keystore_dir.path().join("store_file"), "keys").unwrap();
+    let archive_dir = tempfile::tempdir().unwrap();
+    let archive = archive_dir.path().join("backup.tar");
+
+    // A keystore which is still running is not copied.
+    let lair = std::os::unix::net::UnixListener::bind(keystore_dir.path().join("socket")).unwrap();
+    assert!(create_backup(
+        conductor.db_path().to_owned(),
+        Some(keystore_dir.path().to_owned()),
+        archive.clone(),
+    )
+    .await
+    .is_err());
+    assert!(!archive.exists());
+    drop(lair);
+
+    let manifest = create_backup(
+        conductor.db_path().to_owned(),
+        Some(keystore_dir.path().to_owned()),
+        archive.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        manifest.keystore,
+        vec![
+            std::path::PathBuf::from("lair-keystore-config.yaml"),
+            std::path::PathBuf::from("store_file")
+        ]
+    );
+    // Nothing is left behind next to the archive.
+    assert_eq!(std::fs::read_dir(archive_dir.path()).unwrap().count(), 1);
+
+    // Restoring over an existing keystore is refused, and restores nothing.
+    let restore_dir = tempfile::tempdir().unwrap();
+    assert!(restore_backup(
+        archive.clone(),
+        restore_dir.path().to_owned(),
+        Some(keystore_dir.path().to_owned())
+    )
+    .await
+    .is_err());
+    assert!(find_database_files(restore_dir.path()).unwrap().is_empty());
+
+    let restored_keystore = restore_dir.path().join("keystore");
+    restore_backup(
+        archive,
+        restore_dir.path().to_owned(),
+        Some(restored_keystore.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        find_database_files(restore_dir.path()).unwrap(),
+        manifest.databases
+    );
+    assert_eq!(
+        std::fs::read_to_string(restored_keystore.join("store_file")).unwrap(),
+        "keys"
+    );
+}
@@ -0,0 +1,245 @@
+use super::app_bundle_from_dnas;
+use crate::conductor::error::{ConductorError, ConductorResult};
+use crate::sweettest::*;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+use mr_bundle::{
+    error::{BundleError, MrBundleError},
+    BundleSignature, Location, Manifest, PublisherKey, ResourceHash, SigningKey,
+};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// The fields of an encoded bundle, so that a signed bundle can be tampered with
+#[derive(serde::Serialize, serde::Deserialize)]
+struct EncodedBundle {
+    manifest: AppManifest,
+    resources: BTreeMap<PathBuf, Vec<u8>>,
+    signatures: Vec<BundleSignature>,
+    #[serde(with = "serde_bytes")]
+    signed_manifest: Vec<u8>,
+}
+
+/// An app manifest which does not check the bundles nested in it, so that a
+/// bundle can be signed even though one of its DNAs has an unpinned resource
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+struct UncheckedAppManifest(AppManifest);
+
+impl Manifest for UncheckedAppManifest {
+    fn locations(&self) -> Vec<Location> {
+        self.0.locations()
+    }
+
+    fn resource_hash(&self, location: &Location) -> Option<ResourceHash> {
+        self.0.resource_hash(location)
+    }
+
+    fn path() -> PathBuf {
+        AppManifest::path()
+    }
+
+    fn bundle_extension() -> &'static str {
+        AppManifest::bundle_extension()
+    }
+}
+
+async fn bundle(dna: &DnaFile) -> mr_bundle::Bundle<AppManifest> {
+    app_bundle_from_dnas(
+        "app",
+        [(
+            "role".to_string(),
+            dna.clone(),
+            CellProvisioning::Create { deferred: false },
+            0,
+        )],
+    )
+    .await
+    .into_inner()
+}
+
+/// The bundle of `dna` with its coordinator zome pointed at an unpinned URL
+async fn with_unpinned_coordinator(dna: &DnaFile, remote: &Location) -> DnaBundle {
+    let dna_bundle = DnaBundle::from_dna_file(dna.clone()).await.unwrap();
+    let mut dna_manifest = DnaManifest::clone(dna_bundle.manifest());
+    let mut resources = dna_bundle.bundled_resources().clone();
+    match &mut dna_manifest {
+        DnaManifest::V1(m) => {
+            let zome = &mut m.coordinator.zomes[0];
+            if let Location::Bundled(path) = &zome.location {
+                resources.remove(path);
+            }
+            zome.location = remote.clone();
+            zome.resource_hash = None;
+        }
+    }
+    DnaBundle::new(
+        dna_manifest.try_into().unwrap(),
+        resources.into_iter().collect(),
+        PathBuf::from("."),
+    )
+    .unwrap()
+}
+
+async fn install(
+    conductor: &SweetConductor,
+    agent: &AgentPubKey,
+    bundle: mr_bundle::Bundle<AppManifest>,
+    trusted_publishers: &[PublisherKey],
+) -> ConductorResult<StoppedApp> {
+    conductor
+        .raw_handle()
+        .install_app_bundle(InstallAppPayload {
+            source: AppBundleSource::Bundle(bundle.into()),
+            agent_key: agent.clone(),
+            installed_app_id: Some("app".to_string()),
+            membrane_proofs: Default::default(),
+            network_seed: None,
+            trusted_publishers: Some(trusted_publishers.to_vec()),
+        })
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_bundles_signed_by_trusted_publishers_are_installed() {
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let (other_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let publisher = SigningKey::from_seed(&[1; 32]);
+    let untrusted = SigningKey::from_seed(&[2; 32]);
+    let trusted = [publisher.publisher_key()];
+
+    let unsigned = bundle(&dna).await;
+    matches::assert_matches!(
+        install(&conductor, &alice, unsigned, &trusted).await,
+        Err(ConductorError::MrBundleError(MrBundleError::BundleError(
+            BundleError::UntrustedBundle(_)
+        )))
+    );
+
+    let signed_by_untrusted = bundle(&dna).await.sign(&untrusted).unwrap();
+    matches::assert_matches!(
+        install(&conductor, &alice, signed_by_untrusted, &trusted).await,
+        Err(ConductorError::MrBundleError(MrBundleError::BundleError(
+            BundleError::UntrustedBundle(_)
+        )))
+    );
+
+    // Swap the signed DNA for another one, keeping the signatures
+    let signed = bundle(&dna).await.sign(&publisher).unwrap();
+    let mut encoded: EncodedBundle = mr_bundle::decode(&signed.encode().unwrap()).unwrap();
+    encoded.resources.insert(
+        PathBuf::from("role.dna"),
+        DnaBundle::from_dna_file(other_dna)
+            .await
+            .unwrap()
+            .encode()
+            .unwrap(),
+    );
+    let tampered = mr_bundle::Bundle::decode(&mr_bundle::encode(&encoded).unwrap()).unwrap();
+    matches::assert_matches!(
+        install(&conductor, &alice, tampered, &trusted).await,
+        Err(ConductorError::MrBundleError(MrBundleError::BundleError(
+            BundleError::InvalidSignature(key)
+        ))) if key == publisher.publisher_key()
+    );
+
+    // Nothing was installed until the bundle was signed by a trusted publisher
+    assert!(conductor
+        .raw_handle()
+        .list_apps(None)
+        .await
+        .unwrap()
+        .is_empty());
+    install(&conductor, &alice, signed, &trusted).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signed_bundles_with_nested_unpinned_resources_are_not_installed() {
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let publisher = SigningKey::from_seed(&[1; 32]);
+    let remote = Location::Url("http://127.0.0.1:1/coordinator.wasm".into());
+    let dna_bundle = with_unpinned_coordinator(&dna, &remote).await;
+
+    // A publisher cannot sign an app containing that DNA
+    let app_bundle = bundle(&dna).await;
+    let app_manifest = app_bundle.manifest().clone();
+    let mut resources = app_bundle.bundled_resources().clone();
+    resources.insert(PathBuf::from("role.dna"), dna_bundle.encode().unwrap());
+    let app_bundle =
+        mr_bundle::Bundle::new_unchecked(app_manifest.clone(), resources.clone()).unwrap();
+    matches::assert_matches!(
+        app_bundle.sign(&publisher),
+        Err(MrBundleError::BundleError(BundleError::UnpinnedResource(location)))
+            if location == remote
+    );
+
+    // Nor is such a bundle installed when it was signed without the check
+    let signed = mr_bundle::Bundle::new_unchecked(UncheckedAppManifest(app_manifest), resources)
+        .unwrap()
+        .sign(&publisher)
+        .unwrap();
+    let signed = mr_bundle::Bundle::decode(&signed.encode().unwrap()).unwrap();
+    matches::assert_matches!(
+        install(&conductor, &alice, signed, &[publisher.publisher_key()]).await,
+        Err(ConductorError::MrBundleError(MrBundleError::BundleError(
+            BundleError::UnpinnedResource(location)
+        ))) if location == remote
+    );
+    assert!(conductor
+        .raw_handle()
+        .list_apps(None)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signed_bundles_with_url_pinned_dnas_must_pin_their_zomes() {
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let publisher = SigningKey::from_seed(&[1; 32]);
+    let remote = Location::Url("http://127.0.0.1:1/coordinator.wasm".into());
+    let dna_bytes = with_unpinned_coordinator(&dna, &remote)
+        .await
+        .encode()
+        .unwrap();
+
+    // The DNA is pinned by its hash, and found in the conductor's cache
+    // rather than downloaded.
+    let dna_hash = ResourceHash::of(&dna_bytes);
+    let cache_dir = conductor.db_path().join("bundle_cache");
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    std::fs::write(cache_dir.join(dna_hash.to_string()), &dna_bytes).unwrap();
+
+    let mut app_manifest = bundle(&dna).await.manifest().clone();
+    match &mut app_manifest {
+        AppManifest::V1(m) => {
+            m.roles[0].dna.location = Some(Location::Url("http://127.0.0.1:1/role.dna".into()));
+            m.roles[0].dna.resource_hash = Some(dna_hash);
+        }
+    }
+
+    // The app pins the DNA, so it can be signed, but the DNA doesn't pin
+    // its coordinator zome, so it isn't installed.
+    let signed = mr_bundle::Bundle::new_unchecked(app_manifest, BTreeMap::new())
+        .unwrap()
+        .sign(&publisher)
+        .unwrap();
+    let signed = mr_bundle::Bundle::decode(&signed.encode().unwrap()).unwrap();
+    matches::assert_matches!(
+        install(&conductor, &alice, signed, &[publisher.publisher_key()]).await,
+        Err(ConductorError::AppBundleError(AppBundleError::MrBundleError(
+            MrBundleError::BundleError(BundleError::UnpinnedResource(location))
+        ))) if location == remote
+    );
+    assert!(conductor
+        .raw_handle()
+        .list_apps(None)
+        .await
+        .unwrap()
+        .is_empty());
+}
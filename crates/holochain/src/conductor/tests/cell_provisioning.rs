@@ -0,0 +1,142 @@
+use super::app_bundle_from_dnas;
+use crate::conductor::error::{ConductorError, ConductorResult};
+use crate::sweettest::*;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+
+async fn install(
+    conductor: &SweetConductor,
+    app_id: &str,
+    agent: &AgentPubKey,
+    dna: &DnaFile,
+    provisioning: CellProvisioning,
+    clone_limit: u32,
+) -> ConductorResult<StoppedApp> {
+    let bundle = app_bundle_from_dnas(
+        app_id,
+        [("role".to_string(), dna.clone(), provisioning, clone_limit)],
+    )
+    .await;
+    conductor
+        .raw_handle()
+        .install_app_bundle(InstallAppPayload {
+            source: AppBundleSource::Bundle(bundle),
+            agent_key: agent.clone(),
+            installed_app_id: Some(app_id.to_string()),
+            membrane_proofs: Default::default(),
+            network_seed: None,
+            trusted_publishers: None,
+        })
+        .await
+}
+
+fn role_cell(app: &StoppedApp) -> CellId {
+    app.role(&"role".to_string()).unwrap().cell_id().clone()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cells_are_shared_across_apps() {
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let (alice, bob) = SweetAgents::two(conductor.keystore()).await;
+    let alice_cell = CellId::new(dna.dna_hash().clone(), alice.clone());
+
+    let created = install(
+        &conductor,
+        "created",
+        &alice,
+        &dna,
+        CellProvisioning::Create { deferred: false },
+        0,
+    )
+    .await
+    .unwrap();
+    assert_eq!(role_cell(&created), alice_cell);
+
+    // Both strategies reuse the Cell which alice already has
+    for (app_id, provisioning) in [
+        (
+            "existing",
+            CellProvisioning::UseExisting { deferred: false },
+        ),
+        (
+            "if_not_exists",
+            CellProvisioning::CreateIfNotExists { deferred: false },
+        ),
+    ] {
+        let app = install(&conductor, app_id, &alice, &dna, provisioning, 0)
+            .await
+            .unwrap();
+        assert_eq!(role_cell(&app), alice_cell);
+    }
+
+    // Neither strategy shares alice's Cell with bob: bob can't use an
+    // existing Cell, and gets a Cell of their own if one may be created
+    matches::assert_matches!(
+        install(
+            &conductor,
+            "bobs_existing",
+            &bob,
+            &dna,
+            CellProvisioning::UseExisting { deferred: false },
+            0,
+        )
+        .await,
+        Err(ConductorError::AppBundleError(
+            AppBundleError::CellResolutionFailure(_)
+        ))
+    );
+    let bobs = install(
+        &conductor,
+        "bobs",
+        &bob,
+        &dna,
+        CellProvisioning::CreateIfNotExists { deferred: false },
+        0,
+    )
+    .await
+    .unwrap();
+    assert_eq!(role_cell(&bobs), CellId::new(dna.dna_hash().clone(), bob));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clones_can_be_made_for_a_disabled_role() {
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    // The DNA is not known to the conductor before the app is installed
+    let app = install(
+        &conductor,
+        "app",
+        &alice,
+        &dna,
+        CellProvisioning::Disabled,
+        1,
+    )
+    .await
+    .unwrap();
+    assert!(app
+        .role(&"role".to_string())
+        .unwrap()
+        .provisioned_cell()
+        .is_none());
+
+    conductor
+        .raw_handle()
+        .enable_app("app".to_string())
+        .await
+        .unwrap();
+    let clone = conductor
+        .raw_handle()
+        .create_clone_cell(CreateCloneCellPayload {
+            app_id: "app".to_string(),
+            role_name: "role".to_string(),
+            modifiers: DnaModifiersOpt::none().with_network_seed("clone".to_string()),
+            membrane_proof: None,
+            name: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(clone.as_id().agent_pubkey(), &alice);
+}
@@ -0,0 +1,210 @@
+use crate::sweettest::*;
+use holochain_conductor_api::{CellInfo, ChainMigration};
+use holochain_state::source_chain::chain_head_db;
+use holochain_types::prelude::{DnaFile, MigrateChainPayload, RoleName};
+use holochain_wasm_test_utils::TestWasm;
+use holochain_zome_types::{CellId, DnaHash};
+use std::sync::Arc;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_links_old_and_new_cells() {
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (dna_2, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let role_name: RoleName = "role".into();
+    let app = conductor
+        .setup_app_for_agent("app", alice.clone(), [&(role_name.clone(), dna_1.clone())])
+        .await
+        .unwrap();
+    let (prev_cell,) = app.into_tuple();
+    conductor.register_dna(dna_2.clone()).await.unwrap();
+
+    let app_info = conductor
+        .clone()
+        .migrate_chain(MigrateChainPayload {
+            app_id: "app".into(),
+            role_name: role_name.clone(),
+            new_dna_hash: dna_2.dna_hash().clone(),
+            membrane_proof: None,
+        })
+        .await
+        .unwrap();
+
+    let new_cell_id = CellId::new(dna_2.dna_hash().clone(), alice.clone());
+    assert_eq!(
+        app_info.chain_migrations.get(&role_name).unwrap(),
+        &vec![ChainMigration {
+            prev_cell_id: prev_cell.cell_id().clone(),
+            new_cell_id: new_cell_id.clone(),
+        }]
+    );
+    // The new cell is now the provisioned cell of the role
+    assert!(matches!(
+        &app_info.cell_info.get(&role_name).unwrap()[0],
+        CellInfo::Provisioned(cell) if cell.cell_id == new_cell_id
+    ));
+
+    // Both chains know where they were migrated from and to
+    let new_cell = conductor.get_sweet_cell(new_cell_id).unwrap();
+    let prev_dna_hash: Option<DnaHash> = conductor
+        .call(
+            &new_cell.zome(TestWasm::MigrateAgentPass),
+            "prev_dna_hash",
+            (),
+        )
+        .await;
+    assert_eq!(prev_dna_hash, Some(dna_1.dna_hash().clone()));
+    let new_dna_hash: Option<DnaHash> = conductor
+        .call(
+            &prev_cell.zome(TestWasm::MigrateAgentPass),
+            "new_dna_hash",
+            (),
+        )
+        .await;
+    assert_eq!(new_dna_hash, Some(dna_2.dna_hash().clone()));
+
+    // The old cell stays in the app, so the chain can't be migrated back to it
+    let result = conductor
+        .clone()
+        .migrate_chain(MigrateChainPayload {
+            app_id: "app".into(),
+            role_name: role_name.clone(),
+            new_dna_hash: dna_1.dna_hash().clone(),
+            membrane_proof: None,
+        })
+        .await;
+    assert!(result.is_err());
+
+    // The migration survives a restart
+    conductor.shutdown().await;
+    conductor.startup().await;
+    let app_info_after_restart = conductor
+        .get_app_info(&"app".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        app_info.chain_migrations,
+        app_info_after_restart.chain_migrations
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_refused_by_zome_changes_nothing() {
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentFail]).await;
+    let (dna_2, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let role_name: RoleName = "role".into();
+    conductor
+        .setup_app_for_agent("app", alice.clone(), [&(role_name.clone(), dna_1.clone())])
+        .await
+        .unwrap();
+    conductor.register_dna(dna_2.clone()).await.unwrap();
+    let app_info_before = conductor
+        .get_app_info(&"app".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = conductor
+        .clone()
+        .migrate_chain(MigrateChainPayload {
+            app_id: "app".into(),
+            role_name,
+            new_dna_hash: dna_2.dna_hash().clone(),
+            membrane_proof: None,
+        })
+        .await;
+    assert!(result.is_err());
+
+    let app_info_after = conductor
+        .get_app_info(&"app".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(app_info_before, app_info_after);
+    assert!(app_info_after.chain_migrations.is_empty());
+}
+
+/// Try to migrate a chain from `dna_1` to `dna_2`, which must fail without
+/// touching either chain.
+async fn assert_failed_migration_leaves_no_trace(dna_1: DnaFile, dna_2: DnaFile) {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let role_name: RoleName = "role".into();
+    let app = conductor
+        .setup_app_for_agent("app", alice.clone(), [&(role_name.clone(), dna_1.clone())])
+        .await
+        .unwrap();
+    let (prev_cell,) = app.into_tuple();
+    conductor.register_dna(dna_2.clone()).await.unwrap();
+    let app_info_before = conductor
+        .get_app_info(&"app".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = conductor
+        .clone()
+        .migrate_chain(MigrateChainPayload {
+            app_id: "app".into(),
+            role_name,
+            new_dna_hash: dna_2.dna_hash().clone(),
+            membrane_proof: None,
+        })
+        .await;
+    assert!(result.is_err());
+
+    let app_info_after = conductor
+        .get_app_info(&"app".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(app_info_before, app_info_after);
+
+    // The old chain is still open
+    let new_dna_hash: Option<DnaHash> = conductor
+        .call(
+            &prev_cell.zome(TestWasm::MigrateAgentPass),
+            "new_dna_hash",
+            (),
+        )
+        .await;
+    assert_eq!(new_dna_hash, None);
+
+    // No genesis was run in the new DNA
+    let chain_head = conductor
+        .get_authored_db(dna_2.dna_hash())
+        .unwrap()
+        .async_reader(move |txn| chain_head_db(&txn, Arc::new(alice)))
+        .await
+        .unwrap();
+    assert_eq!(chain_head, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_refused_by_new_dna_leaves_no_trace() {
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (dna_2, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentFail]).await;
+    assert_failed_migration_leaves_no_trace(dna_1, dna_2).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_with_failed_genesis_leaves_no_trace() {
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (dna_2, _, _) = SweetDnaFile::unique_from_test_wasms(vec![
+        TestWasm::MigrateAgentPass,
+        TestWasm::GenesisSelfCheckInvalid,
+    ])
+    .await;
+    assert_failed_migration_leaves_no_trace(dna_1, dna_2).await;
+}
@@ -1,4 +1,60 @@
 mod app_info;
+mod backup;
+mod bundle_signatures;
 mod cell_cloning;
+mod cell_provisioning;
+mod chain_migration;
 mod request_dna_def;
 mod signed_zome_call;
+mod source_chain_archive;
+
+use crate::sweettest::*;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+use std::path::PathBuf;
+
+/// A conductor running a single app whose cell has committed a public and a
+/// private entry, along with the app's DNA and cell.
+pub(crate) async fn conductor_with_chain() -> (SweetConductor, DnaFile, SweetCell) {
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &[dna.clone()]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let zome = cell.zome(TestWasm::Create);
+    let _: ActionHash = conductor.call(&zome, "create_entry", ()).await;
+    let _: ActionHash = conductor.call(&zome, "create_priv_msg", ()).await;
+    (conductor, dna, cell)
+}
+
+/// Build an app bundle with a role for each DNA, which is bundled alongside
+/// the manifest. Each role uses the given provisioning strategy and clone limit.
+pub(crate) async fn app_bundle_from_dnas(
+    name: &str,
+    roles: impl IntoIterator<Item = (RoleName, DnaFile, CellProvisioning, u32)>,
+) -> AppBundle {
+    let mut role_manifests = Vec::new();
+    let mut resources = Vec::new();
+    for (role_name, dna, provisioning, clone_limit) in roles {
+        let path = PathBuf::from(format!("{}.dna", role_name));
+        role_manifests.push(AppRoleManifest {
+            name: role_name,
+            provisioning: Some(provisioning),
+            dna: AppRoleDnaManifest {
+                location: Some(mr_bundle::Location::Bundled(path.clone())),
+                resource_hash: None,
+                modifiers: DnaModifiersOpt::none(),
+                version: Some(DnaVersionFlexible::Singleton(dna.dna_hash().clone().into())),
+                clone_limit,
+            },
+        });
+        resources.push((path, DnaBundle::from_dna_file(dna).await.unwrap()));
+    }
+    let manifest = AppManifestCurrent {
+        name: name.to_string(),
+        description: None,
+        roles: role_manifests,
+    };
+    AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+        .await
+        .unwrap()
+}
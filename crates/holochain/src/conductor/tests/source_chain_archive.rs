@@ -0,0 +1,107 @@
+use super::conductor_with_chain;
+use crate::conductor::Conductor;
+use crate::sweettest::*;
+use holochain_conductor_api::source_chain_archive::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn export_and_import_source_chain() {
+    let (conductor, dna, cell) = conductor_with_chain().await;
+    let cell_id = cell.cell_id().clone();
+
+    let archive = conductor
+        .export_source_chain(cell_id.clone(), true)
+        .await
+        .unwrap();
+    assert!(archive
+        .records
+        .iter()
+        .any(|r| matches!(r.entry, ArchivedEntry::Encrypted(_))));
+    let archive = SourceChainArchive::decode(&archive.encode().unwrap()).unwrap();
+
+    // A fresh conductor sharing the keystore, so it can decrypt the private entries.
+    let other = SweetConductor::from_builder(
+        Conductor::builder()
+            .config(standard_config())
+            .with_keystore(conductor.keystore()),
+    )
+    .await;
+    other.register_dna(dna.clone()).await.unwrap();
+    other
+        .clone()
+        .import_source_chain(archive.clone(), true)
+        .await
+        .unwrap();
+
+    let original = conductor
+        .export_source_chain(cell_id.clone(), false)
+        .await
+        .unwrap();
+    let imported = other.export_source_chain(cell_id, false).await.unwrap();
+    assert_eq!(imported.records, original.records);
+
+    // The chain is no longer empty, so it can't be imported again.
+    assert!(other
+        .clone()
+        .import_source_chain(archive, false)
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_rejects_broken_or_unreadable_chains() {
+    let (conductor, dna, cell) = conductor_with_chain().await;
+    let archive = conductor
+        .export_source_chain(cell.cell_id().clone(), true)
+        .await
+        .unwrap();
+
+    // Without the agent's seed the private entries can't be decrypted.
+    let stranger = SweetConductor::from_standard_config().await;
+    stranger.register_dna(dna.clone()).await.unwrap();
+    assert!(stranger
+        .clone()
+        .import_source_chain(archive.clone(), false)
+        .await
+        .is_err());
+
+    let other = SweetConductor::from_builder(
+        Conductor::builder()
+            .config(standard_config())
+            .with_keystore(conductor.keystore()),
+    )
+    .await;
+    other.register_dna(dna.clone()).await.unwrap();
+
+    // A gap in the hash chain is rejected.
+    let mut gap = archive.clone();
+    gap.records.remove(1);
+    assert!(other.clone().import_source_chain(gap, false).await.is_err());
+
+    // An entry which doesn't match its action is rejected.
+    let mut swapped = archive.clone();
+    let present = swapped
+        .records
+        .iter()
+        .filter(|r| matches!(r.entry, ArchivedEntry::Present(_)))
+        .map(|r| r.entry.clone())
+        .collect::<Vec<_>>();
+    let index = swapped
+        .records
+        .iter()
+        .position(|r| matches!(r.entry, ArchivedEntry::Present(_)))
+        .unwrap();
+    swapped.records[index].entry = present.last().unwrap().clone();
+    assert_ne!(swapped, archive);
+    assert!(other
+        .clone()
+        .import_source_chain(swapped, false)
+        .await
+        .is_err());
+
+    // Nothing was imported by the failed attempts.
+    other
+        .clone()
+        .import_source_chain(archive, false)
+        .await
+        .unwrap();
+}
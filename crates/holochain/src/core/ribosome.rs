@@ -610,18 +610,28 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
     /// values without needing to make holochain a dependency.
     fn get_const_fn(&self, zome: &Zome, name: &str) -> Result<Option<i32>, RibosomeError>;
 
-    /// @todo list out all the available callbacks and maybe cache them somewhere
-    fn list_callbacks(&self) {
-        unimplemented!()
-        // pseudocode
-        // self.instance().exports().filter(|e| e.is_callback())
+    /// List all the functions exported by a zome, sorted by name.
+    ///
+    /// This includes both callbacks and zome functions, but not the
+    /// functions used internally by the host and guest to share memory.
+    fn list_exports(&self, zome_name: &ZomeName) -> RibosomeResult<Vec<FunctionName>>;
+
+    /// List all the callbacks implemented by a zome, e.g. `init` or `validate`.
+    fn list_callbacks(&self, zome_name: &ZomeName) -> RibosomeResult<Vec<FunctionName>> {
+        Ok(self
+            .list_exports(zome_name)?
+            .into_iter()
+            .filter(|f| is_callback(f))
+            .collect())
     }
 
-    /// @todo list out all the available zome functions and maybe cache them somewhere
-    fn list_zome_fns(&self) {
-        unimplemented!()
-        // pseudocode
-        // self.instance().exports().filter(|e| !e.is_callback())
+    /// List all the functions of a zome which can be called with a zome call.
+    fn list_zome_fns(&self, zome_name: &ZomeName) -> RibosomeResult<Vec<FunctionName>> {
+        Ok(self
+            .list_exports(zome_name)?
+            .into_iter()
+            .filter(|f| !is_callback(f))
+            .collect())
     }
 
     fn run_genesis_self_check(
@@ -679,6 +689,27 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
     fn zome_types(&self) -> &Arc<GlobalZomeTypes>;
 }
 
+/// The names of all the callbacks which the host may call on a zome,
+/// including the names built from several components, as with
+/// `migrate_agent_open`.
+pub const CALLBACK_NAMES: &[&str] = &[
+    "entry_defs",
+    "genesis_self_check",
+    "init",
+    "migrate_agent",
+    "migrate_agent_close",
+    "migrate_agent_open",
+    "post_commit",
+    "validate",
+    "validation_package",
+];
+
+/// Whether an exported function is a callback rather than a zome function.
+pub fn is_callback(function_name: &FunctionName) -> bool {
+    let name: &str = function_name.as_ref();
+    CALLBACK_NAMES.contains(&name)
+}
+
 /// Placeholder for weighing. Currently produces zero weight.
 pub fn weigh_placeholder() -> EntryRateWeight {
     EntryRateWeight::default()
@@ -686,6 +717,7 @@ pub fn weigh_placeholder() -> EntryRateWeight {
 
 #[cfg(test)]
 pub mod wasm_test {
+    use crate::core::ribosome::is_callback;
     use crate::core::ribosome::FnComponents;
     use crate::core::ribosome::ZomeCall;
     use crate::sweettest::SweetAgents;
@@ -789,6 +821,16 @@ pub mod wasm_test {
         assert_eq!(fn_components.into_iter().collect::<Vec<String>>(), expected,);
     }
 
+    #[test]
+    fn callbacks_are_matched_by_name() {
+        for name in ["init", "validate", "post_commit", "migrate_agent_open"] {
+            assert!(is_callback(&name.into()), "{}", name);
+        }
+        for name in ["init_profile", "validate_email", "post_commit_hook"] {
+            assert!(!is_callback(&name.into()), "{}", name);
+        }
+    }
+
     pub struct RibosomeTestFixture {
         pub conductor: SweetConductor,
         pub alice_pubkey: AgentPubKey,
@@ -1,13 +1,47 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
 
-/// lists all the local claims filtered by tag
+/// lists all the local claims filtered by tag and grantor
+/// this is only the current claims as per local CRUD
 pub fn capability_claims(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapClaimQuery,
+) -> Result<Vec<CapClaim>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let claims = tokio_helper::block_forever_on(async move {
+                call_context
+                    .host_context
+                    .workspace()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if read_workspace access is given")
+                    .live_cap_claims()
+                    .await
+            })
+            .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
+            Ok(claims
+                .into_iter()
+                .filter(|claim| input.check(claim))
+                .collect())
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_claims".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
 }
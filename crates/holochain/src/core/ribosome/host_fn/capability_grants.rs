@@ -1,16 +1,53 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
 
-/// list all the grants stored locally in the chain filtered by tag
+/// list all the grants stored locally in the chain filtered by tag, grantee and function
 /// this is only the current grants as per local CRUD
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapGrantQuery,
+) -> Result<Vec<CapGrantInfo>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let grants = tokio_helper::block_forever_on(async move {
+                call_context
+                    .host_context
+                    .workspace()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if read_workspace access is given")
+                    .live_cap_grants()
+                    .await
+            })
+            .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
+            Ok(grants
+                .into_iter()
+                .filter(|(_, cap_grant)| input.check(cap_grant))
+                .map(|(action_hash, cap_grant)| CapGrantInfo {
+                    action_hash,
+                    cap_grant,
+                })
+                .collect())
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_grants".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +95,92 @@ pub mod wasm_test {
         assert_eq!(entry_secret, secret);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_grants_and_claims() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            bob,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Capability).await;
+
+        // init commits an unrestricted grant
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&alice, "list_cap_grants", CapGrantQuery::default())
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].cap_grant.access, CapAccess::Unrestricted);
+
+        let secret: CapSecret = conductor.call(&alice, "cap_secret", ()).await;
+        let action_hash: ActionHash = conductor
+            .call(&alice, "transferable_cap_grant", secret)
+            .await;
+
+        let needs_cap_claim = CapGrantQuery::default()
+            .function((TestWasm::Capability.into(), "needs_cap_claim".into()));
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&alice, "list_cap_grants", needs_cap_claim.clone())
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].action_hash, action_hash);
+
+        // rolling the grant replaces it
+        let new_action_hash: ActionHash = conductor
+            .call(&alice, "roll_cap_grant", action_hash)
+            .await;
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&alice, "list_cap_grants", needs_cap_claim.clone())
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].action_hash, new_action_hash);
+
+        // deleting the grant removes it
+        let _: ActionHash = conductor
+            .call(&alice, "delete_cap_grant", new_action_hash)
+            .await;
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&alice, "list_cap_grants", needs_cap_claim)
+            .await;
+        assert!(grants.is_empty());
+
+        // alice assigns a grant to bob and sends him the claim
+        let _: () = conductor
+            .call(&alice, "send_assigned_cap_claim", bob_pubkey.clone())
+            .await;
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(
+                &alice,
+                "list_cap_grants",
+                CapGrantQuery::default().grantee(bob_pubkey.clone()),
+            )
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].cap_grant.tag, "has_cap_claim");
+
+        let claims: Vec<CapClaim> = conductor
+            .call(
+                &bob,
+                "list_cap_claims",
+                CapClaimQuery::default().grantor(alice_pubkey.clone()),
+            )
+            .await;
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].tag, "has_cap_claim");
+        let claims: Vec<CapClaim> = conductor
+            .call(&bob, "list_cap_claims", CapClaimQuery::default().tag("nope"))
+            .await;
+        assert!(claims.is_empty());
+
+        // a call by the author is authorized by the implicit author grant
+        let info: CapabilityInfo = conductor.call(&alice, "capability_info", ()).await;
+        assert_eq!(info.provenance, alice_pubkey);
+        assert_eq!(info.cap_grant, CapGrant::ChainAuthor(alice_pubkey));
+        assert_eq!(info.action_hash, None);
+    }
+
     // MAYBE: [ B-03669 ] can move this to an integration test (may need to switch to using a RibosomeStore)
     #[tokio::test(flavor = "multi_thread")]
     async fn ribosome_authorized_call() -> anyhow::Result<()> {
@@ -1,14 +1,59 @@
+use crate::core::ribosome::host_fn::call_info::call_cap_grant;
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
 
 /// return the access info used for this call
 /// also return who is originated the call (pubkey)
 pub fn capability_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+) -> Result<CapabilityInfo, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            bindings: Permission::Allow,
+            ..
+        } => {
+            let (provenance, cap_grant) = call_cap_grant(&call_context)?;
+            let action_hash = match &cap_grant {
+                CapGrant::ChainAuthor(_) => None,
+                CapGrant::RemoteAgent(zome_call_cap_grant) => {
+                    let zome_call_cap_grant = zome_call_cap_grant.clone();
+                    tokio_helper::block_forever_on(async move {
+                        call_context
+                            .host_context
+                            .workspace()
+                            .source_chain()
+                            .as_ref()
+                            .expect("Must have source chain if bindings access is given")
+                            .live_cap_grants()
+                            .await
+                    })
+                    .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?
+                    .into_iter()
+                    .find(|(_, grant)| *grant == zome_call_cap_grant)
+                    .map(|(action_hash, _)| action_hash)
+                }
+            };
+            Ok(CapabilityInfo {
+                provenance,
+                cap_grant,
+                action_hash,
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_info".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
 }
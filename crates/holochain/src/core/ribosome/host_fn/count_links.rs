@@ -0,0 +1,67 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::Cascade;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+
+#[tracing::instrument(skip(_ribosome, call_context), fields(?call_context.zome, function = ?call_context.function_name))]
+pub fn count_links(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetLinksInput,
+) -> Result<usize, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let GetLinksInput {
+                base_address,
+                link_type,
+                tag_prefix,
+                filter,
+            } = input;
+
+            // The limit and cursor of the filter don't apply to counting.
+            let query = WireLinkQuery {
+                base: base_address,
+                type_query: link_type,
+                tag: tag_prefix,
+                author: filter.author,
+                after: filter.after,
+                before: filter.before,
+            };
+
+            // Get the network from the context
+            let network = call_context.host_context.network().clone();
+
+            tokio_helper::block_forever_on(async move {
+                let workspace = call_context.host_context.workspace();
+                let mut cascade = Cascade::from_workspace_and_network(&workspace, network);
+                let count = cascade
+                    .dht_count_links(query)
+                    .await
+                    .map_err(|cascade_error| {
+                        wasm_error!(WasmErrorInner::Host(cascade_error.to_string()))
+                    })?;
+                Ok(count)
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "count_links".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+// The slow tests for get_links show that count_links agrees with get_links
+// for an authority, see get_links.rs. The cascade tests for get_links cover
+// an agent counting its own links without being an authority.
@@ -30,12 +30,14 @@ pub fn get_links<'a>(
                             base_address,
                             link_type,
                             tag_prefix,
+                            filter,
                         } = input;
 
                         let key = WireLinkKey {
                             base: base_address,
                             type_query: link_type,
                             tag: tag_prefix,
+                            filter,
                         };
                         Ok(Cascade::from_workspace_and_network(
                             &call_context.host_context.workspace(),
@@ -273,6 +275,28 @@ pub mod slow_tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn count_links_matches_get_links() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let count: usize = conductor.call(&alice, "count_links", ()).await;
+        assert_eq!(count, 0);
+
+        let _: ActionHash = conductor.call(&alice, "create_link", ()).await;
+        let _: ActionHash = conductor.call(&alice, "create_link", ()).await;
+        let links: Vec<Link> = conductor.call(&alice, "get_links", ()).await;
+        let count: usize = conductor.call(&alice, "count_links", ()).await;
+        assert_eq!(count, links.len());
+        assert_eq!(count, 2);
+
+        let _: () = conductor.call(&alice, "delete_all_links", ()).await;
+        let count: usize = conductor.call(&alice, "count_links", ()).await;
+        assert_eq!(count, 0);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn dup_path_test() {
         observability::test_run().ok();
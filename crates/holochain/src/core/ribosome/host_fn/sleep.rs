@@ -1,12 +1,106 @@
+use crate::conductor::api::ZOME_CALL_TIMEOUT;
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_types::access::Permission;
 use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The longest a single call to `sleep` may sleep for, which is the
+/// [`ZOME_CALL_TIMEOUT`] of a zome call made over an app interface.
+/// A zome call sleeping any longer would time out before it could return
+/// to the client that made it.
+pub fn sleep_max_duration() -> Duration {
+    ZOME_CALL_TIMEOUT
+}
 
 pub fn sleep(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: Duration,
 ) -> Result<(), RuntimeError> {
-    unimplemented!()
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            non_determinism: Permission::Allow,
+            ..
+        } => {
+            let max = sleep_max_duration();
+            if input > max {
+                return Err(wasm_error!(WasmErrorInner::Host(format!(
+                    "Cannot sleep for {:?}, the maximum is {:?}",
+                    input, max
+                )))
+                .into());
+            }
+            // Host functions are called synchronously from wasm, so the
+            // calling thread waits for the whole duration. Zome calls run on
+            // the blocking pool, and otherwise `block_in_place` hands the
+            // tasks of this runtime worker to another thread first, so no
+            // runtime worker is parked while sleeping.
+            tokio::task::block_in_place(|| std::thread::sleep(input));
+            Ok(())
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "sleep".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use super::*;
+    use crate::core::ribosome::HostContext;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::RealRibosomeFixturator;
+    use crate::fixt::ValidateHostAccessFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+
+    fn call_context(host_context: HostContext) -> Arc<CallContext> {
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        call_context.host_context = host_context;
+        Arc::new(call_context)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sleep_test() {
+        let ribosome = Arc::new(
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+                .next()
+                .unwrap(),
+        );
+        let zome_call = call_context(HostContext::ZomeCall(fixt!(ZomeCallHostAccess)));
+
+        let start = std::time::Instant::now();
+        sleep(
+            ribosome.clone(),
+            zome_call.clone(),
+            Duration::from_millis(100),
+        )
+        .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        // Sleeping beyond the limit is an error.
+        assert!(sleep(
+            ribosome.clone(),
+            zome_call,
+            sleep_max_duration() + Duration::from_millis(1),
+        )
+        .is_err());
+
+        // Sleeping is not allowed during validation.
+        let validate = call_context(HostContext::Validate(fixt!(ValidateHostAccess)));
+        assert!(sleep(ribosome, validate, Duration::from_millis(1)).is_err());
+    }
 }
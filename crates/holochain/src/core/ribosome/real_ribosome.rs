@@ -32,6 +32,7 @@ use crate::core::ribosome::host_fn::call_info::call_info;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
+use crate::core::ribosome::host_fn::count_links::count_links;
 use crate::core::ribosome::host_fn::create::create;
 use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
@@ -106,6 +107,9 @@ pub struct RealRibosome {
 
     /// Dependencies for every zome.
     pub zome_dependencies: Arc<HashMap<ZomeName, Vec<ZomeIndex>>>,
+
+    /// The functions exported by each zome, filled in as they are listed.
+    zome_exports: Arc<parking_lot::RwLock<HashMap<ZomeName, Vec<FunctionName>>>>,
 }
 
 struct HostFnBuilder {
@@ -241,6 +245,7 @@ impl RealRibosome {
             dna_file,
             zome_types: Default::default(),
             zome_dependencies: Default::default(),
+            zome_exports: Default::default(),
         };
 
         // Collect the number of entry and link types
@@ -326,6 +331,7 @@ impl RealRibosome {
             dna_file: ribosome.dna_file,
             zome_types,
             zome_dependencies: Arc::new(zome_dependencies),
+            zome_exports: ribosome.zome_exports,
         })
     }
 
@@ -335,9 +341,28 @@ impl RealRibosome {
             dna_file,
             zome_types: Default::default(),
             zome_dependencies: Default::default(),
+            zome_exports: Default::default(),
         }
     }
 
+    /// Replace the coordinator zomes of this ribosome's DNA, returning the
+    /// hashes of the replaced wasms. The cached zome exports are dropped,
+    /// since the exports of the new coordinators may differ.
+    pub async fn update_coordinators(
+        &mut self,
+        coordinator_zomes: CoordinatorZomes,
+        wasms: Vec<DnaWasm>,
+    ) -> Result<Vec<WasmHash>, DnaError> {
+        let old_wasms = self
+            .dna_file
+            .update_coordinators(coordinator_zomes, wasms)
+            .await?;
+        // Clones of this ribosome share the cache, so a new one is needed
+        // rather than clearing it.
+        self.zome_exports = Default::default();
+        Ok(old_wasms)
+    }
+
     pub fn module(&self, zome_name: &ZomeName) -> RibosomeResult<Arc<Module>> {
         if holochain_wasmer_host::module::SERIALIZED_MODULE_CACHE
             .get()
@@ -570,6 +595,7 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__get_details", get_details)
             .with_host_function(&mut ns, "__get_links", get_links)
             .with_host_function(&mut ns, "__get_link_details", get_link_details)
+            .with_host_function(&mut ns, "__count_links", count_links)
             .with_host_function(&mut ns, "__get_agent_activity", get_agent_activity)
             .with_host_function(&mut ns, "__must_get_entry", must_get_entry)
             .with_host_function(&mut ns, "__must_get_action", must_get_action)
@@ -606,6 +632,28 @@ impl RealRibosome {
             .get(zome_name)
             .ok_or_else(|| ZomeTypesError::MissingDependenciesForZome(zome_name.clone()))?)
     }
+
+    /// All the exports of a zome except the const fns, sorted by name.
+    pub fn extern_fns(&self, zome: &Zome) -> RibosomeResult<Vec<FunctionName>> {
+        match zome.zome_def() {
+            ZomeDef::Wasm(_) => {
+                let module = self.module(zome.zome_name())?;
+
+                let mut extern_fns: Vec<FunctionName> = module
+                    .info()
+                    .exports
+                    .iter()
+                    .filter(|(name, _)| {
+                        name.as_str() != "__num_entry_types" && name.as_str() != "__num_link_types"
+                    })
+                    .map(|(name, _index)| FunctionName::new(name))
+                    .collect();
+                extern_fns.sort();
+                Ok(extern_fns)
+            }
+            ZomeDef::Inline { inline_zome, .. } => Ok(inline_zome.0.functions()),
+        }
+    }
 }
 
 /// General purpose macro which relies heavily on various impls of the form:
@@ -690,27 +738,7 @@ impl RibosomeT for RealRibosome {
                     }
                 }
             },
-            extern_fns: {
-                match zome.zome_def() {
-                    ZomeDef::Wasm(_) => {
-                        let module = self.module(zome.zome_name())?;
-
-                        let mut extern_fns: Vec<FunctionName> = module
-                            .info()
-                            .exports
-                            .iter()
-                            .filter(|(name, _)| {
-                                name.as_str() != "__num_entry_types"
-                                    && name.as_str() != "__num_link_types"
-                            })
-                            .map(|(name, _index)| FunctionName::new(name))
-                            .collect();
-                        extern_fns.sort();
-                        extern_fns
-                    }
-                    ZomeDef::Inline { inline_zome, .. } => inline_zome.0.functions(),
-                }
-            },
+            extern_fns: self.extern_fns(&zome)?,
             zome_types,
         })
     }
@@ -834,6 +862,31 @@ impl RibosomeT for RealRibosome {
         }
     }
 
+    fn list_exports(&self, zome_name: &ZomeName) -> RibosomeResult<Vec<FunctionName>> {
+        if let Some(exports) = self.zome_exports.read().get(zome_name) {
+            return Ok(exports.clone());
+        }
+        let zome = self
+            .dna_def()
+            .get_zome(zome_name)
+            .map_err(|_| RibosomeError::ZomeNotExists(zome_name.clone()))?;
+        let exports: Vec<FunctionName> = self
+            .extern_fns(&zome)?
+            .into_iter()
+            // Exports prefixed with `__` are used internally by the host and
+            // guest, e.g. for allocation, and the memory and metering points
+            // are not functions.
+            .filter(|f| {
+                let name: &str = f.as_ref();
+                !name.starts_with("__") && name != "memory" && !name.starts_with("wasmer_metering_")
+            })
+            .collect();
+        self.zome_exports
+            .write()
+            .insert(zome_name.clone(), exports.clone());
+        Ok(exports)
+    }
+
     fn call_iterator<I: crate::core::ribosome::Invocation>(
         &self,
         host_context: HostContext,
@@ -257,6 +257,16 @@ pub fn check_prev_seq(action: &Action, prev_action: &Action) -> SysValidationRes
     }
 }
 
+/// Check the previous action didn't close the chain, as a
+/// [`Action::CloseChain`] must be the last action on a source chain
+pub fn check_prev_not_closed(prev_action: &Action) -> SysValidationResult<()> {
+    match prev_action {
+        Action::CloseChain(_) => Err(PrevActionError::ChainClosed(prev_action.action_seq()))
+            .map_err(|e| ValidationOutcome::from(e).into()),
+        _ => Ok(()),
+    }
+}
+
 /// Check the entry variant matches the variant in the actions entry type
 pub fn check_entry_type(entry_type: &EntryType, entry: &Entry) -> SysValidationResult<()> {
     match (entry_type, entry) {
@@ -154,6 +154,8 @@ impl ValidationOutcome {
 
 #[derive(Error, Debug)]
 pub enum PrevActionError {
+    #[error("The source chain was closed at seq {0}, so no action can follow it")]
+    ChainClosed(u32),
     #[error("The previous action hash specified in an action doesn't match the actual previous action. Seq: {0}")]
     HashMismatch(u32),
     #[error("Root of source chain must be Dna")]
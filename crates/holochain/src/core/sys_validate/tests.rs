@@ -192,6 +192,20 @@ async fn check_previous_seq() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn check_previous_not_closed() {
+    let prev_action = fixt!(CreateLink);
+    assert_matches!(check_prev_not_closed(&prev_action.into()), Ok(()));
+
+    let prev_action = fixt!(CloseChain);
+    assert_matches!(
+        check_prev_not_closed(&prev_action.into()),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::PrevActionError(PrevActionError::ChainClosed(_)),
+        ),)
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn check_entry_type_test() {
     let entry_fixt = EntryFixturator::new(Predictable);
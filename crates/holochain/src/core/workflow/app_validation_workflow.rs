@@ -48,6 +48,23 @@ pub mod validation_package;
 
 const NUM_CONCURRENT_OPS: usize = 50;
 
+/// An op which is still missing its app validation dependencies this long
+/// after app validation first tried it is abandoned and integrated with
+/// [`ValidationStatus::Abandoned`].
+pub const ABANDON_AFTER_AWAITING_APP_DEPS: std::time::Duration =
+    std::time::Duration::from_secs(60 * 60);
+
+/// How long an abandoned op is kept before it is given another chance to
+/// validate, in case its dependencies have since become available.
+pub const ABANDONED_OP_RETRY_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(60 * 60);
+
+/// A received warrant whose proof still can't be validated this long after
+/// we first tried is dropped, so that warrants against ops whose
+/// dependencies never arrive aren't validated again forever.
+pub const DROP_UNVERIFIED_WARRANT_AFTER: std::time::Duration =
+    std::time::Duration::from_secs(10 * 60);
+
 #[instrument(skip(
     workspace,
     trigger_integration,
@@ -86,6 +103,19 @@ async fn app_validation_workflow_inner(
     network: &HolochainP2pDna,
     dht_query_cache: DhtDbQueryCache,
 ) -> WorkflowResult<WorkComplete> {
+    // Give any ops which were abandoned long enough ago another try.
+    let abandoned_before = Timestamp::now().saturating_sub(&ABANDONED_OP_RETRY_INTERVAL);
+    let retried = workspace
+        .dht_db
+        .async_commit(move |txn| WorkflowResult::Ok(retry_abandoned_ops(txn, abandoned_before)?))
+        .await?;
+    if retried > 0 {
+        tracing::debug!("retrying {} abandoned ops", retried);
+    }
+
+    verify_received_warrants(dna_hash.clone(), &conductor, &workspace, network).await?;
+    conductor.block_warranted_agents(&dna_hash).await?;
+
     let db = workspace.dht_db.clone().into();
     let sorted_ops = validation_query::get_ops_to_app_validate(&db).await?;
     let start_len = sorted_ops.len();
@@ -97,6 +127,8 @@ async fn app_validation_workflow_inner(
     let iter = sorted_ops.into_iter().map({
         let network = network.clone();
         let workspace = workspace.clone();
+        let conductor = conductor.clone();
+        let dna_hash = dna_hash.clone();
         move |so| {
             let network = network.clone();
             let conductor = conductor.clone();
@@ -120,13 +152,16 @@ async fn app_validation_workflow_inner(
 
                 // Validate this op
                 let mut cascade = workspace.full_cascade(network.clone());
-                let r = match dhtop_to_op(op, &mut cascade).await {
+                let (r, op) = match dhtop_to_op(op, &mut cascade).await {
                     Ok(op) => {
-                        validate_op_outer(dna_hash, &op, &conductor, &(*workspace), &network).await
+                        let r =
+                            validate_op_outer(dna_hash, &op, &conductor, &(*workspace), &network)
+                                .await;
+                        (r, Some(op))
                     }
-                    Err(e) => Err(e),
+                    Err(e) => (Err(e), None),
                 };
-                (op_hash, dependency, op_light, r, activity)
+                (op_hash, dependency, op_light, r, activity, op)
             }
         }
     });
@@ -164,15 +199,17 @@ async fn app_validation_workflow_inner(
             "Committing {} ops",
             chunk.iter().map(|c| c.len()).sum::<usize>()
         );
-        let (t, a, r, activity) = workspace
+        let (t, a, r, ab, activity, invalid_ops) = workspace
             .dht_db
             .async_commit(move |txn| {
                 let mut total = 0;
                 let mut awaiting = 0;
                 let mut rejected = 0;
+                let mut abandoned = 0;
                 let mut agent_activity = Vec::new();
+                let mut invalid_ops = Vec::new();
                 for outcome in chunk.into_iter().flatten() {
-                    let (op_hash, dependency, op_light, outcome, activity) = outcome;
+                    let (op_hash, dependency, op_light, outcome, activity, op) = outcome;
                     // Get the outcome or return the error
                     let outcome = outcome.or_else(|outcome_or_err| outcome_or_err.try_into())?;
 
@@ -201,13 +238,16 @@ async fn app_validation_workflow_inner(
                             }
                         }
                         Outcome::AwaitingDeps(deps) => {
-                            awaiting += 1;
-                            let status = ValidationLimboStatus::AwaitingAppDeps(deps);
-                            put_validation_limbo(txn, &op_hash, status)?;
+                            if put_awaiting_app_deps(txn, &op_hash, deps, Timestamp::now())? {
+                                awaiting += 1;
+                            } else {
+                                abandoned += 1;
+                            }
                         }
                         Outcome::Rejected(_) => {
                             rejected += 1;
-                            tracing::warn!("Received invalid op! Warrants aren't implemented yet, so we can't do anything about this right now, but be warned that somebody on the network has maliciously hacked their node.\nOp: {:?}", op_light);
+                            tracing::warn!("Received invalid op! A warrant will be issued against its author.\nOp: {:?}", op_light);
+                            invalid_ops.extend(op);
                             if let Dependency::Null = dependency {
                                 put_integrated(txn, &op_hash, ValidationStatus::Rejected)?;
                             } else {
@@ -216,9 +256,19 @@ async fn app_validation_workflow_inner(
                         }
                     }
                 }
-                WorkflowResult::Ok((total, awaiting, rejected, agent_activity))
+                WorkflowResult::Ok((
+                    total,
+                    awaiting,
+                    rejected,
+                    abandoned,
+                    agent_activity,
+                    invalid_ops,
+                ))
             })
             .await?;
+        if !invalid_ops.is_empty() {
+            issue_warrants(&dna_hash, invalid_ops, &conductor, &workspace).await?;
+        }
         // Once the database transaction is committed, add agent activity to the cache
         // that is ready for integration.
         for (author, seq, has_no_dependency) in activity {
@@ -248,10 +298,11 @@ async fn app_validation_workflow_inner(
             );
         }
         tracing::debug!(
-            "{} committed, {} awaiting sys dep, {} rejected. {} committed this round",
+            "{} committed, {} awaiting sys dep, {} rejected, {} abandoned. {} committed this round",
             t,
             a,
             r,
+            ab,
             total
         );
     }
@@ -264,6 +315,145 @@ async fn app_validation_workflow_inner(
     })
 }
 
+/// Warrant the authors of ops which failed validation and store the
+/// warrants. The publish workflow of the issuing cell publishes them to the
+/// authorities for each author's agent activity.
+async fn issue_warrants(
+    dna_hash: &DnaHash,
+    invalid_ops: Vec<Op>,
+    conductor: &ConductorHandle,
+    workspace: &AppValidationWorkspace,
+) -> WorkflowResult<()> {
+    // Any of our agents in this DNA can vouch for the validation we just ran.
+    let warrantor = match conductor
+        .running_cell_ids()
+        .into_iter()
+        .find(|cell_id| cell_id.dna_hash() == dna_hash)
+    {
+        Some(cell_id) => cell_id,
+        None => {
+            tracing::warn!("No running cell to issue warrants for {:?}", dna_hash);
+            return Ok(());
+        }
+    };
+
+    let mut warrants = Vec::with_capacity(invalid_ops.len());
+    for op in invalid_ops {
+        if op.author() == warrantor.agent_pubkey() {
+            continue;
+        }
+        let warrant = Warrant::new(
+            WarrantProof::InvalidOp(Box::new(op)),
+            warrantor.agent_pubkey().clone(),
+            Timestamp::now(),
+        );
+        warrants.push(sign_warrant(warrant, conductor.keystore()).await?);
+    }
+    if warrants.is_empty() {
+        return Ok(());
+    }
+
+    workspace
+        .dht_db
+        .async_commit({
+            let warrants = warrants.clone();
+            move |txn| {
+                for warrant in warrants {
+                    insert_warrant(txn, warrant, true)?;
+                }
+                WorkflowResult::Ok(())
+            }
+        })
+        .await?;
+
+    // The authored database holds what our agents still need to publish.
+    conductor
+        .get_authored_db(dna_hash)
+        .map_err(Box::new)?
+        .async_commit(move |txn| {
+            for warrant in warrants {
+                insert_warrant(txn, warrant, true)?;
+            }
+            WorkflowResult::Ok(())
+        })
+        .await?;
+    conductor
+        .get_cell_triggers(&warrantor)
+        .map_err(Box::new)?
+        .publish_dht_ops
+        .trigger(&"issue_warrants");
+    conductor.block_warranted_agents(dna_hash).await?;
+    Ok(())
+}
+
+/// Check the proofs of warrants which other agents have published to us.
+///
+/// A warrant is only verified once the op it carries has failed app
+/// validation here too. Warrants against valid ops are deleted, and warrants
+/// whose ops can't be validated yet are kept to be checked again next time,
+/// until [`DROP_UNVERIFIED_WARRANT_AFTER`] has passed.
+async fn verify_received_warrants(
+    dna_hash: Arc<DnaHash>,
+    conductor: &ConductorHandle,
+    workspace: &AppValidationWorkspace,
+    network: &HolochainP2pDna,
+) -> WorkflowResult<()> {
+    let warrants = workspace
+        .dht_db
+        .async_reader(|txn| list_unverified_warrants(&txn))
+        .await?;
+    if warrants.is_empty() {
+        return Ok(());
+    }
+
+    let mut verified = Vec::new();
+    let mut disproved = Vec::new();
+    let mut pending = Vec::new();
+    for warrant in warrants {
+        let WarrantProof::InvalidOp(op) = &warrant.warrant().proof;
+        let outcome = validate_op_outer(dna_hash.clone(), op, conductor, workspace, network)
+            .await
+            .or_else(|outcome_or_err| outcome_or_err.try_into());
+        match outcome {
+            Ok(Outcome::Rejected(_)) => verified.push(warrant),
+            Ok(Outcome::Accepted) => {
+                tracing::warn!(
+                    ?warrant,
+                    "Dropping a warrant against an op which passed app validation"
+                );
+                disproved.push(warrant);
+            }
+            Ok(Outcome::AwaitingDeps(_)) => pending.push(warrant),
+            Err(e) => {
+                tracing::info!(?warrant, failed_to_verify_warrant = ?e);
+                pending.push(warrant);
+            }
+        }
+    }
+
+    let now = Timestamp::now();
+    workspace
+        .dht_db
+        .async_commit(move |txn| {
+            for warrant in &verified {
+                set_warrant_verified(txn, warrant)?;
+            }
+            for warrant in &disproved {
+                delete_warrant(txn, warrant)?;
+            }
+            for warrant in &pending {
+                let first_attempt = set_warrant_first_verify_attempt(txn, warrant, now)?;
+                if first_attempt <= now.saturating_sub(&DROP_UNVERIFIED_WARRANT_AFTER) {
+                    tracing::info!(?warrant, "Dropping a warrant which could not be verified");
+                    delete_warrant(txn, warrant)?;
+                }
+            }
+            WorkflowResult::Ok(())
+        })
+        .await?;
+    Ok(())
+}
+
 pub async fn record_to_op(
     record: Record,
     op_type: DhtOpType,
@@ -711,6 +901,28 @@ impl AppValidationWorkspace {
     }
 }
 
+/// Put an op which is missing app validation dependencies back in the
+/// validation limbo, or abandon it if it has been waiting on them for longer
+/// than [`ABANDON_AFTER_AWAITING_APP_DEPS`].
+/// Returns whether the op is still awaiting its dependencies.
+pub fn put_awaiting_app_deps(
+    txn: &mut Transaction<'_>,
+    hash: &DhtOpHash,
+    deps: Vec<AnyDhtHash>,
+    now: Timestamp,
+) -> WorkflowResult<bool> {
+    let first_attempt = set_first_app_validation_attempt(txn, hash, now)?;
+    if first_attempt > now.saturating_sub(&ABANDON_AFTER_AWAITING_APP_DEPS) {
+        put_validation_limbo(txn, hash, ValidationLimboStatus::AwaitingAppDeps(deps))?;
+        Ok(true)
+    } else {
+        // The dependencies may never arrive so this is integrated directly
+        // rather than waiting on its integration dependency.
+        put_integrated(txn, hash, ValidationStatus::Abandoned)?;
+        Ok(false)
+    }
+}
+
 pub fn put_validation_limbo(
     txn: &mut Transaction<'_>,
     hash: &DhtOpHash,
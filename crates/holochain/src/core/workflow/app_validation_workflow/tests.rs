@@ -1,3 +1,5 @@
+use super::put_awaiting_app_deps;
+use super::ABANDON_AFTER_AWAITING_APP_DEPS;
 use crate::conductor::ConductorHandle;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::sweettest::SweetConductorBatch;
@@ -6,15 +8,27 @@ use crate::test_utils::host_fn_caller::*;
 use crate::test_utils::new_invocation;
 use crate::test_utils::new_zome_call;
 use crate::test_utils::wait_for_integration;
+use ::fixt::prelude::*;
 use holo_hash::ActionHash;
 use holo_hash::AnyDhtHash;
 use holo_hash::EntryHash;
+use holo_hash::HasHash;
+use holo_hash::HashableContentExtSync;
 use holochain_state::prelude::fresh_reader_test;
 use holochain_state::prelude::from_blob;
+use holochain_state::prelude::insert_op;
+use holochain_state::prelude::list_unverified_warrants;
+use holochain_state::prelude::list_warrants;
+use holochain_state::prelude::retry_abandoned_ops;
+use holochain_state::prelude::set_validation_stage;
+use holochain_state::prelude::sign_warrant;
+use holochain_state::prelude::verify_warrant;
 use holochain_state::prelude::StateQueryResult;
+use holochain_state::validation_db::ValidationLimboStatus;
 use holochain_types::prelude::*;
 use holochain_wasm_test_utils::TestWasm;
 
+use holochain_zome_types::fixt::*;
 use holochain_zome_types::Entry;
 use holochain_zome_types::ValidationStatus;
 use rusqlite::named_params;
@@ -62,6 +76,61 @@ async fn app_validation_workflow_test() {
     .await;
 }
 
+#[test]
+fn ops_awaiting_app_deps_are_abandoned_and_retried() {
+    let db = holochain_state::prelude::test_dht_db();
+    let op = DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+        fixt!(Signature),
+        fixt!(Action),
+    ));
+    let hash = op.as_hash().clone();
+    let deps = vec![AnyDhtHash::from(fixt!(ActionHash))];
+    let start = Timestamp::now();
+    let status = |txn: &Transaction| -> (Option<ValidationStatus>, Option<u32>, bool) {
+        txn.query_row(
+            "SELECT validation_status, validation_stage, when_integrated IS NOT NULL
+            FROM DhtOp WHERE hash = :hash",
+            named_params! {
+                ":hash": hash,
+            },
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap()
+    };
+
+    db.to_db().test_commit(|txn| {
+        insert_op(txn, &op).unwrap();
+
+        // Sys validation attempts don't count towards abandoning the op.
+        for _ in 0..200 {
+            set_validation_stage(txn, &hash, ValidationLimboStatus::SysValidated).unwrap();
+        }
+        assert!(put_awaiting_app_deps(txn, &hash, deps.clone(), start).unwrap());
+        assert_eq!(status(txn), (None, Some(2), false));
+
+        // Still waiting just before the limit.
+        let abandoned_at = start.saturating_add(&ABANDON_AFTER_AWAITING_APP_DEPS);
+        let almost = abandoned_at.saturating_sub(&Duration::from_secs(1));
+        assert!(put_awaiting_app_deps(txn, &hash, deps.clone(), almost).unwrap());
+        assert_eq!(status(txn), (None, Some(2), false));
+
+        // Abandoned once the limit has passed since the first attempt.
+        assert!(!put_awaiting_app_deps(txn, &hash, deps.clone(), abandoned_at).unwrap());
+        assert_eq!(status(txn), (Some(ValidationStatus::Abandoned), None, true));
+
+        // Abandoned ops are only retried once the retry interval has passed.
+        assert_eq!(retry_abandoned_ops(txn, start).unwrap(), 0);
+        assert_eq!(status(txn), (Some(ValidationStatus::Abandoned), None, true));
+        let retry_at = Timestamp::now().saturating_add(&Duration::from_secs(1));
+        assert_eq!(retry_abandoned_ops(txn, retry_at).unwrap(), 1);
+        assert_eq!(status(txn), (None, Some(2), false));
+
+        // The retried op gets the full time to find its dependencies again.
+        assert!(put_awaiting_app_deps(txn, &hash, deps, abandoned_at).unwrap());
+        assert_eq!(status(txn), (None, Some(2), false));
+    });
+}
+
 const SELECT: &'static str = "SELECT count(hash) FROM DhtOp WHERE";
 
 // These are the expected invalid ops
@@ -252,6 +321,80 @@ async fn run_test(
         assert_eq!(num_valid(&txn), expected_count - 1);
     });
 
+    // Alice issues a warrant against bob for the invalid op.
+    let alice_db = conductors[0].get_dht_db(&alice_cell_id.dna_hash()).unwrap();
+    let mut warrants = Vec::new();
+    for _ in 0..num_attempts {
+        warrants = fresh_reader_test(alice_db.clone(), |txn| {
+            list_warrants(&txn, bob_cell_id.agent_pubkey()).unwrap()
+        });
+        if !warrants.is_empty() {
+            break;
+        }
+        tokio::time::sleep(delay_per_attempt).await;
+    }
+    assert!(warrants
+        .iter()
+        .any(|w| w.warrant().author == *alice_cell_id.agent_pubkey()));
+    assert!(verify_warrant(&warrants[0]).await);
+
+    // A warrant against a valid op is signed just as well as a real one.
+    let warrant = warrants[0].clone();
+    let forged = match &warrant.warrant().proof {
+        WarrantProof::InvalidOp(op) => match op.as_ref() {
+            Op::StoreEntry(StoreEntry { action, .. }) => {
+                Op::RegisterAgentActivity(RegisterAgentActivity {
+                    action: SignedActionHashed::with_presigned(
+                        ActionHashed::from_content_sync(action.hashed.content.clone().into()),
+                        action.signature.clone(),
+                    ),
+                    cached_entry: None,
+                })
+            }
+            op => panic!("Unexpected warrant proof {:?}", op),
+        },
+    };
+    let forged = sign_warrant(
+        Warrant::new(
+            WarrantProof::InvalidOp(Box::new(forged)),
+            alice_cell_id.agent_pubkey().clone(),
+            Timestamp::now(),
+        ),
+        conductors[0].raw_handle().keystore(),
+    )
+    .await
+    .unwrap();
+    assert!(verify_warrant(&forged).await);
+
+    // Bob's conductor only surfaces the warrant whose op fails its own
+    // app validation, and drops the forged one.
+    conductors[1]
+        .raw_handle()
+        .spaces
+        .handle_publish_warrants(
+            bob_cell_id.dna_hash(),
+            vec![warrant.clone(), forged.clone()],
+        )
+        .await
+        .unwrap();
+    let bob_db = conductors[1].get_dht_db(bob_cell_id.dna_hash()).unwrap();
+    let mut unverified = vec![forged.clone()];
+    for _ in 0..num_attempts {
+        unverified = fresh_reader_test(bob_db.clone(), |txn| {
+            list_unverified_warrants(&txn).unwrap()
+        });
+        if unverified.is_empty() {
+            break;
+        }
+        tokio::time::sleep(delay_per_attempt).await;
+    }
+    assert!(unverified.is_empty());
+    let bob_warrants = fresh_reader_test(bob_db, |txn| {
+        list_warrants(&txn, bob_cell_id.agent_pubkey()).unwrap()
+    });
+    assert!(bob_warrants.contains(&warrant));
+    assert!(!bob_warrants.contains(&forged));
+
     let invocation = new_zome_call(
         conductors[1].raw_handle().keystore(),
         &bob_cell_id,
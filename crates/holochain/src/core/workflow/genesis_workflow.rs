@@ -2,6 +2,7 @@
 //! - Dna
 //! - AgentValidationPkg
 //! - AgentId
+//! - OpenChain, if the chain is migrated from another DNA
 //!
 
 use std::sync::Arc;
@@ -33,6 +34,8 @@ where
     ribosome: Ribosome,
     dht_db_cache: DhtDbQueryCache,
     chc: Option<ChcImpl>,
+    /// The DNA a migrated chain is opened from
+    prev_dna_hash: Option<DnaHash>,
 }
 
 // #[instrument(skip(workspace, api, args))]
@@ -63,6 +66,7 @@ where
         ribosome,
         dht_db_cache,
         chc,
+        prev_dna_hash,
     } = args;
 
     if workspace.has_genesis(agent_pubkey.clone()).await? {
@@ -108,7 +112,7 @@ where
         return Err(WorkflowError::AgentInvalid(agent_pubkey.clone()));
     }
 
-    source_chain::genesis(
+    source_chain::genesis_with_open_chain(
         workspace.vault.clone(),
         workspace.dht_db.clone(),
         &dht_db_cache,
@@ -117,6 +121,7 @@ where
         agent_pubkey,
         membrane_proof,
         chc,
+        prev_dna_hash,
     )
     .await?;
 
@@ -207,6 +212,7 @@ pub mod tests {
                 ribosome,
                 dht_db_cache: dht_db_cache.clone(),
                 chc: None,
+                prev_dna_hash: None,
             };
             let _: () = genesis_workflow(workspace, api, args).await.unwrap();
         }
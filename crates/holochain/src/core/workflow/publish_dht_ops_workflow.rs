@@ -31,6 +31,11 @@ pub const DEFAULT_RECEIPT_BUNDLE_SIZE: u8 = 5;
 /// flooding the network with spurious publishes.
 pub const MIN_PUBLISH_INTERVAL: time::Duration = time::Duration::from_secs(60 * 5);
 
+/// Warrants don't collect validation receipts, so the warrants an agent
+/// issues are published again (at most once every [`MIN_PUBLISH_INTERVAL`])
+/// until they are this old.
+pub const WARRANT_PUBLISH_PERIOD: time::Duration = time::Duration::from_secs(60 * 60 * 24);
+
 #[instrument(skip(db, network, trigger_self))]
 pub async fn publish_dht_ops_workflow(
     db: DbWrite<DbKindAuthored>,
@@ -72,6 +77,22 @@ pub async fn publish_dht_ops_workflow(
     }
 
     tracing::info!("published {} ops", success.len());
+
+    // Publish the warrants this agent has issued.
+    let mut published_warrants = Vec::new();
+    for warrant in publish_query::get_warrants_to_publish(agent.clone(), &db.clone().into()).await?
+    {
+        let basis = warrant.warrant().offender().clone().into();
+        match network.publish_warrants(basis, vec![warrant.clone()]).await {
+            Err(e) => {
+                tracing::warn!(failed_to_publish_warrant = ?e);
+            }
+            Ok(()) => {
+                published_warrants.push(warrant);
+            }
+        }
+    }
+
     let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?;
     let continue_publish = db
         .async_commit(move |writer| {
@@ -80,7 +101,13 @@ pub async fn publish_dht_ops_workflow(
                 let hash = DhtOpHash::from_kitsune(hash.data_ref());
                 mutations::set_last_publish_time(writer, &hash, now)?;
             }
-            WorkflowResult::Ok(publish_query::num_still_needing_publish(writer)? > 0)
+            for warrant in published_warrants {
+                mutations::set_warrant_last_publish_time(writer, &warrant, now)?;
+            }
+            WorkflowResult::Ok(
+                publish_query::num_still_needing_publish(writer)? > 0
+                    || publish_query::num_warrants_still_needing_publish(writer)? > 0,
+            )
         })
         .await?;
 
@@ -6,6 +6,7 @@ use holo_hash::DhtOpHash;
 use holochain_p2p::DhtOpHashExt;
 use holochain_sqlite::db::DbKindAuthored;
 use holochain_state::query::prelude::*;
+use holochain_state::warrants::list_warrants_to_publish;
 use holochain_types::db::DbRead;
 use holochain_types::dht_op::DhtOp;
 use holochain_types::dht_op::DhtOpType;
@@ -13,6 +14,8 @@ use holochain_types::prelude::OpBasis;
 use holochain_zome_types::Entry;
 use holochain_zome_types::EntryVisibility;
 use holochain_zome_types::SignedAction;
+use holochain_zome_types::SignedWarrant;
+use holochain_zome_types::Timestamp;
 use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
 use rusqlite::named_params;
 use rusqlite::Transaction;
@@ -20,6 +23,7 @@ use rusqlite::Transaction;
 use crate::core::workflow::error::WorkflowResult;
 
 use super::MIN_PUBLISH_INTERVAL;
+use super::WARRANT_PUBLISH_PERIOD;
 
 /// Get all dht ops on an agents chain that need to be published.
 /// - Don't publish private entries.
@@ -29,12 +33,7 @@ pub async fn get_ops_to_publish(
     agent: AgentPubKey,
     db: &DbRead<DbKindAuthored>,
 ) -> WorkflowResult<Vec<(OpBasis, OpHashSized, DhtOp)>> {
-    let recency_threshold = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .ok()
-        .and_then(|epoch| epoch.checked_sub(MIN_PUBLISH_INTERVAL))
-        .map(|t| t.as_secs())
-        .unwrap_or(0);
+    let recency_threshold = recency_threshold();
 
     let results = db
         .async_reader(move |txn| {
@@ -126,6 +125,55 @@ pub fn num_still_needing_publish(txn: &Transaction) -> WorkflowResult<usize> {
     Ok(count)
 }
 
+/// Get all warrants issued by an agent that need to be published again.
+/// - Only get warrants that haven't been published within the minimum publish interval
+/// - Only get warrants issued within the warrant publish period
+pub async fn get_warrants_to_publish(
+    agent: AgentPubKey,
+    db: &DbRead<DbKindAuthored>,
+) -> WorkflowResult<Vec<SignedWarrant>> {
+    let recency_threshold = recency_threshold();
+    let issued_since = Timestamp::now().saturating_sub(&WARRANT_PUBLISH_PERIOD);
+    let results = db
+        .async_reader(move |txn| {
+            list_warrants_to_publish(&txn, &agent, issued_since, recency_threshold)
+        })
+        .await?;
+    Ok(results)
+}
+
+/// Get the number of warrants that might need to publish again in the future.
+pub fn num_warrants_still_needing_publish(txn: &Transaction) -> WorkflowResult<usize> {
+    let issued_since = Timestamp::now().saturating_sub(&WARRANT_PUBLISH_PERIOD);
+    let count = txn.query_row(
+        "
+        SELECT
+        COUNT(Warrant.rowid) as num_warrants
+        FROM Warrant
+        WHERE
+        Warrant.verified = 1
+        AND
+        Warrant.timestamp >= :issued_since
+        ",
+        named_params! {
+            ":issued_since": issued_since,
+        },
+        |row| row.get("num_warrants"),
+    )?;
+    Ok(count)
+}
+
+/// Anything last published before this time (in seconds since the UNIX
+/// epoch) is due to be published again.
+fn recency_threshold() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|epoch| epoch.checked_sub(MIN_PUBLISH_INTERVAL))
+        .map(|t| t.as_secs())
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use fixt::prelude::*;
@@ -599,6 +599,7 @@ async fn store_record(
             .ok_or_else(|| ValidationOutcome::DepMissingFromDht(prev_action_hash.clone().into()))?;
         check_prev_timestamp(action, prev_action.action())?;
         check_prev_seq(action, prev_action.action())?;
+        check_prev_not_closed(prev_action.action())?;
     }
     Ok(())
 }
@@ -32,7 +32,8 @@ impl SweetConductorConfig {
         Self(ConductorConfig {
             network: Some(network),
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::websocket(0),
+                require_authentication: false,
             }]),
             ..Default::default()
         })
@@ -238,6 +238,9 @@ fn cache_data(in_memory: bool, data: &MockNetworkData, is_cached: bool) -> Conne
     holochain_sqlite::schema::SCHEMA_CELL
         .initialize(&mut conn, None)
         .unwrap();
+    // Both schemas share this database but the schema version is stored
+    // per database, so it must be reset before adding the second schema.
+    conn.pragma_update(None, "user_version", &0u16).unwrap();
     holochain_sqlite::schema::SCHEMA_P2P_STATE
         .initialize(&mut conn, None)
         .unwrap();
@@ -92,6 +92,57 @@ async fn test_coordinator_zome_update() {
     println!("Success! Success! Success! ");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_list_zome_functions_after_coordinator_update() {
+    let conductor = SweetConductor::from_standard_config().await;
+    let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+        vec![TestIntegrityWasm::IntegrityZome],
+        vec![TestCoordinatorWasm::CoordinatorZome],
+        vec![
+            DnaWasm::from(TestIntegrityWasm::IntegrityZome),
+            DnaWasm::from(TestCoordinatorWasm::CoordinatorZome),
+        ],
+    )
+    .await;
+    let dna_hash = dna.dna_hash().clone();
+    conductor.register_dna(dna).await.unwrap();
+
+    let coordinator_fns = |conductor: &SweetConductor| {
+        conductor
+            .raw_handle()
+            .list_zome_functions(&dna_hash)
+            .unwrap()
+            .into_iter()
+            .find(|z| z.zome_name == TestCoordinatorWasm::CoordinatorZome.into())
+            .unwrap()
+            .zome_fns
+    };
+
+    // Listing the functions fills the export cache.
+    assert!(coordinator_fns(&conductor).contains(&"create_entry".into()));
+
+    // Replace the coordinator with a different wasm under the same name.
+    let wasm_hash =
+        WasmHash::with_data(&DnaWasm::from(TestCoordinatorWasm::CoordinatorZomeUpdate)).await;
+    let new_coordinator: CoordinatorZomeDef = ZomeDef::Wasm(WasmZome {
+        wasm_hash,
+        dependencies: vec![TestIntegrityWasm::IntegrityZome.into()],
+    })
+    .into();
+    conductor
+        .update_coordinators(
+            &dna_hash,
+            vec![(TestCoordinatorWasm::CoordinatorZome.into(), new_coordinator)],
+            vec![TestCoordinatorWasm::CoordinatorZomeUpdate.into()],
+        )
+        .await
+        .unwrap();
+
+    let zome_fns = coordinator_fns(&conductor);
+    assert!(zome_fns.contains(&"create_post".into()));
+    assert!(!zome_fns.contains(&"create_entry".into()));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_coordinator_zome_update_multi_integrity() {
     let mut conductor = SweetConductor::from_standard_config().await;
@@ -246,6 +297,7 @@ async fn test_update_admin_interface() {
             name: TestCoordinatorWasm::CoordinatorZomeUpdate.into(),
             hash: None,
             location: ZomeLocation::Bundled(TestCoordinatorWasm::CoordinatorZomeUpdate.into()),
+            resource_hash: None,
             dependencies: Some(vec![ZomeDependency {
                 name: TestIntegrityWasm::IntegrityZome.into(),
             }]),
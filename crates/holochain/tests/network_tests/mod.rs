@@ -514,6 +514,7 @@ async fn run_fixt_network(
                             deletes: btreeset! {},
                             updates: btreeset! {},
                             invalid_actions: btreeset! {},
+                            links: btreeset! {},
                             entry_dht_status: None,
                         };
                         respond.respond(Ok(async move { Ok(metadata.try_into().unwrap()) }
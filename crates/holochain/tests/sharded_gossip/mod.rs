@@ -92,6 +92,44 @@ async fn fullsync_sharded_gossip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn full_sync_gossip_strategy() -> anyhow::Result<()> {
+    let _g = observability::test_run().ok();
+    const NUM_CONDUCTORS: usize = 2;
+
+    let mut config = make_config(false, false, None);
+    config.network = config.network.map(|network| {
+        network.tune(|mut tp| {
+            tp.gossip_strategy = "full-sync".to_string();
+            tp
+        })
+    });
+    let mut conductors = SweetConductorBatch::from_config(NUM_CONDUCTORS, config).await;
+
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(("simple", simple_create_read_zome())).await;
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+
+    // Publish is disabled, so Bob can only get the data by gossip.
+    consistency_10s([&alice, &bobbo]).await;
+    let record: Option<Record> = conductors[1]
+        .call(&bobbo.zome("simple"), "read", hash)
+        .await;
+    let record = record.expect("Record was None: bobbo couldn't `get` it");
+    assert_eq!(record.action().author(), alice.agent_pubkey());
+
+    Ok(())
+}
+
 #[cfg(feature = "test_utils")]
 #[tokio::test(flavor = "multi_thread")]
 async fn fullsync_sharded_gossip_high_data() -> anyhow::Result<()> {
@@ -471,6 +509,9 @@ async fn mock_network_sharded_gossip() {
                         holochain_p2p::WireMessage::PublishCountersign { .. } => {
                             debug!("PublishCountersign")
                         }
+                        holochain_p2p::WireMessage::PublishWarrants { .. } => {
+                            debug!("PublishWarrants")
+                        }
                         /* (david.b) TODO - this has been replaced by
                          *                  combined `receive_ops`
                         holochain_p2p::WireMessage::Publish { ops, .. } => {
@@ -511,6 +552,7 @@ async fn mock_network_sharded_gossip() {
                         }
                         holochain_p2p::WireMessage::GetMeta { .. } => debug!("get_meta"),
                         holochain_p2p::WireMessage::GetLinks { .. } => debug!("get_links"),
+                        holochain_p2p::WireMessage::CountLinks { .. } => debug!("count_links"),
                         holochain_p2p::WireMessage::GetAgentActivity { .. } => {
                             debug!("get_agent_activity")
                         }
@@ -1027,6 +1069,7 @@ async fn mock_network_sharding() {
                         }
                         holochain_p2p::WireMessage::GetMeta { .. } => debug!("get_meta"),
                         holochain_p2p::WireMessage::GetLinks { .. } => debug!("get_links"),
+                        holochain_p2p::WireMessage::CountLinks { .. } => debug!("count_links"),
                         holochain_p2p::WireMessage::GetAgentActivity { .. } => {
                             debug!("get_agent_activity")
                         }
@@ -1039,6 +1082,9 @@ async fn mock_network_sharding() {
                         holochain_p2p::WireMessage::PublishCountersign { .. } => {
                             debug!("publish_countersign")
                         }
+                        holochain_p2p::WireMessage::PublishWarrants { .. } => {
+                            debug!("publish_warrants")
+                        }
                     },
                     HolochainP2pMockMsg::CallResp(_) => debug!("CallResp"),
                     HolochainP2pMockMsg::MetricExchange(_) => debug!("MetricExchange"),
@@ -24,6 +24,7 @@ use holochain::conductor::api::AdminResponse;
 use holochain::conductor::api::AppRequest;
 use holochain::conductor::api::AppResponse;
 use holochain::conductor::api::ZomeCall;
+use holochain::conductor::config::AllowedOrigins;
 use holochain::test_utils::setup_app;
 use holochain_state::nonce::fresh_nonce;
 use holochain_wasm_test_utils::TestZomes;
@@ -177,7 +178,12 @@ async fn speed_test(n: Option<usize>) -> Arc<TempDir> {
 
     // Setup websocket handle and app interface
     let (mut client, _) = websocket_client(&handle).await.unwrap();
-    let request = AdminRequest::AttachAppInterface { port: None };
+    let request = AdminRequest::AttachAppInterface {
+        port: None,
+        bind_address: None,
+        allowed_origins: AllowedOrigins::Any,
+        require_authentication: false,
+    };
     let response = client.request(request);
     let response = response.await.unwrap();
     let app_port = match response {
@@ -28,6 +28,7 @@ use futures::Future;
 use holochain_conductor_api::conductor::ConductorConfig;
 use holochain_conductor_api::conductor::KeystoreConfig;
 use holochain_conductor_api::AdminInterfaceConfig;
+use holochain_conductor_api::AllowedOrigins;
 use holochain_conductor_api::InterfaceDriver;
 use matches::assert_matches;
 use serde::Serialize;
@@ -158,7 +159,12 @@ pub async fn call_zome_fn<S>(
 }
 
 pub async fn attach_app_interface(client: &mut WebsocketSender, port: Option<u16>) -> u16 {
-    let request = AdminRequest::AttachAppInterface { port };
+    let request = AdminRequest::AttachAppInterface {
+        port,
+        bind_address: None,
+        allowed_origins: AllowedOrigins::Any,
+        require_authentication: false,
+    };
     let response = client.request(request);
     let response = check_timeout(response, 3000).await;
     match response {
@@ -315,13 +321,16 @@ pub async fn check_started(holochain: &mut Child) {
 pub fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
     ConductorConfig {
         admin_interfaces: Some(vec![AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::websocket(port),
+            require_authentication: false,
         }]),
         environment_path: environment_path.into(),
         network: None,
         dpki: None,
         keystore: KeystoreConfig::DangerTestKeystore,
         db_sync_strategy: DbSyncStrategy::default(),
+        block_warranted_agents: false,
+        admin_bootstrap_token: None,
         chc_namespace: None,
     }
 }
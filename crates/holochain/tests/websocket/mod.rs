@@ -8,12 +8,14 @@ use holochain::sweettest::SweetConductorBatch;
 use holochain::sweettest::SweetDnaFile;
 use holochain::{
     conductor::{
-        api::{AdminRequest, AdminResponse},
+        api::{AdminRequest, AdminResponse, AppRequest, AppResponse, ExternalApiWireError},
         error::ConductorError,
         Conductor,
     },
     fixt::*,
 };
+use holochain_conductor_api::authentication::*;
+use holochain_conductor_api::AllowedOrigins;
 use holochain_types::{
     prelude::*,
     test_utils::{fake_dna_zomes, write_fake_dna_file},
@@ -294,7 +296,7 @@ async fn remote_signals() -> anyhow::Result<()> {
     for mut rx in rxs {
         let r = rx.try_recv();
         // Each handle should recv a signal
-        assert_matches!(r, Ok(Signal::App(_, a)) if a == signal);
+        assert_matches!(r, Ok(Signal::App { signal: a, .. }) if a == signal);
     }
 
     Ok(())
@@ -562,6 +564,190 @@ async fn too_many_open() {
     conductor_handle.shutdown();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn interfaces_require_authentication() {
+    observability::test_run().ok();
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = create_config(0, tmp_dir.path().to_path_buf());
+    config.admin_interfaces.as_mut().unwrap()[0].require_authentication = true;
+    let conductor_handle = Conductor::builder().config(config).build().await.unwrap();
+    let port = admin_port(&conductor_handle).await;
+
+    let issue = |scope| {
+        let conductor_handle = conductor_handle.clone();
+        async move {
+            conductor_handle
+                .issue_authentication_token(IssueAuthenticationTokenPayload {
+                    scope,
+                    expiry_seconds: None,
+                })
+                .await
+                .unwrap()
+                .token
+        }
+    };
+    let admin_token = issue(AuthenticationScope::Admin).await;
+    let app_token = issue(AuthenticationScope::App {
+        installed_app_ids: vec![],
+    })
+    .await;
+
+    // An app token can't be issued for an app which isn't installed.
+    assert_matches!(
+        conductor_handle
+            .issue_authentication_token(IssueAuthenticationTokenPayload {
+                scope: AuthenticationScope::App {
+                    installed_app_ids: vec!["missing".to_string()],
+                },
+                expiry_seconds: None,
+            })
+            .await,
+        Err(ConductorError::AppNotInstalled(_))
+    );
+
+    // A client which doesn't authenticate is rejected.
+    let (mut client, _rx) = websocket_client_by_port(port).await.unwrap();
+    let response: AdminResponse = client
+        .request(AdminRequest::ListAppInterfaces)
+        .await
+        .unwrap();
+    assert_matches!(
+        response,
+        AdminResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+    );
+
+    // So is a client which authenticates with an app token.
+    let (mut client, _rx) = websocket_client_by_port(port).await.unwrap();
+    let response: AuthenticationResponse = client
+        .request(AuthenticationRequest {
+            token: app_token.clone(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(
+        response,
+        AuthenticationResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+    );
+
+    let (mut client, _rx) = websocket_client_by_port(port).await.unwrap();
+    let response: AuthenticationResponse = client
+        .request(AuthenticationRequest {
+            token: admin_token.clone(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AuthenticationResponse::Authenticated);
+    let response: AdminResponse = client
+        .request(AdminRequest::AttachAppInterface {
+            port: None,
+            bind_address: None,
+            allowed_origins: AllowedOrigins::Any,
+            require_authentication: true,
+        })
+        .await
+        .unwrap();
+    let app_port = match response {
+        AdminResponse::AppInterfaceAttached { port } => port,
+        _ => panic!("Attach app interface failed: {:?}", response),
+    };
+
+    // An app token only gives access to the apps it was issued for.
+    let (mut app_client, _rx) = websocket_client_by_port(app_port).await.unwrap();
+    let response: AuthenticationResponse = app_client
+        .request(AuthenticationRequest { token: app_token })
+        .await
+        .unwrap();
+    assert_matches!(response, AuthenticationResponse::Authenticated);
+    let response: AppResponse = app_client
+        .request(AppRequest::AppInfo {
+            installed_app_id: "other".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(
+        response,
+        AppResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+    );
+
+    // Connections authenticated with a revoked token are no longer served.
+    let response: AdminResponse = client
+        .request(AdminRequest::RevokeAuthenticationToken(admin_token))
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::AuthenticationTokenRevoked);
+    let response: AdminResponse = client
+        .request(AdminRequest::ListAppInterfaces)
+        .await
+        .unwrap();
+    assert_matches!(
+        response,
+        AdminResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+    );
+
+    conductor_handle.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn authentication_tokens_survive_restart() {
+    observability::test_run().ok();
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = create_config(0, tmp_dir.path().to_path_buf());
+    config.admin_interfaces.as_mut().unwrap()[0].require_authentication = true;
+    let bootstrap_token = AuthenticationToken(vec![1; 32]);
+    config.admin_bootstrap_token = Some(BootstrapTokenConfig::Token {
+        token: base64::encode_config(&bootstrap_token.0, base64::URL_SAFE_NO_PAD),
+    });
+
+    let authenticate = |port: u16, token: AuthenticationToken| async move {
+        let (mut client, rx) = websocket_client_by_port(port).await.unwrap();
+        let response: AuthenticationResponse = client
+            .request(AuthenticationRequest { token })
+            .await
+            .unwrap();
+        (response, client, rx)
+    };
+
+    // The bootstrap token is accepted when every admin interface
+    // requires authentication.
+    let conductor_handle = Conductor::builder()
+        .config(config.clone())
+        .build()
+        .await
+        .unwrap();
+    let port = admin_port(&conductor_handle).await;
+    let (response, mut client, _rx) = authenticate(port, bootstrap_token).await;
+    assert_matches!(response, AuthenticationResponse::Authenticated);
+    let response: AdminResponse = client
+        .request(AdminRequest::IssueAuthenticationToken(Box::new(
+            IssueAuthenticationTokenPayload {
+                scope: AuthenticationScope::Admin,
+                expiry_seconds: None,
+            },
+        )))
+        .await
+        .unwrap();
+    let issued = match response {
+        AdminResponse::AuthenticationTokenIssued(issued) => issued.token,
+        _ => panic!("Issuing a token failed: {:?}", response),
+    };
+    let shutdown = conductor_handle.take_shutdown_handle().unwrap();
+    conductor_handle.shutdown();
+    shutdown.await.unwrap().unwrap();
+
+    // Issued tokens are still accepted after a restart.
+    let conductor_handle = Conductor::builder().config(config).build().await.unwrap();
+    let port = admin_port(&conductor_handle).await;
+    let (response, _client, _rx) = authenticate(port, issued).await;
+    assert_matches!(response, AuthenticationResponse::Authenticated);
+    let (response, _client, _rx) = authenticate(port, AuthenticationToken(vec![2; 32])).await;
+    assert_matches!(
+        response,
+        AuthenticationResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+    );
+
+    conductor_handle.shutdown();
+}
+
 // #[tokio::test(flavor = "multi_thread")]
 // #[cfg(feature = "slow_tests")]
 // TODO: duplicate/rewrite this to also test happ bundles in addition to dna
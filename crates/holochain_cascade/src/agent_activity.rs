@@ -22,17 +22,20 @@ fn merge_hashes(
     let mut valid = HashSet::new();
     let mut rejected = HashSet::new();
     let mut merged_highest_observed = None;
+    let mut merged_warrants = Vec::new();
     for result in results {
         let AgentActivityResponse {
             agent: the_agent,
             highest_observed,
             valid_activity,
             rejected_activity,
+            warrants,
             ..
         } = result;
         if the_agent != agent {
             continue;
         }
+        merge_warrants(&mut merged_warrants, warrants);
 
         match (merged_highest_observed.take(), highest_observed) {
             (None, None) => {}
@@ -84,6 +87,38 @@ fn merge_hashes(
         valid_activity,
         rejected_activity,
         highest_observed: merged_highest_observed,
+        warrants: merged_warrants,
+    }
+}
+
+/// Remove the warrants which are not signed by their author, or whose
+/// offending action is not signed by the offender, so that a remote
+/// authority can't attribute a warrant to somebody else.
+///
+/// The proof of a warrant can't be validated without the DNA, so the
+/// authorities are still trusted to only return warrants whose offending
+/// op they have found invalid.
+pub(crate) async fn drop_unverified_warrants(results: &mut [AgentActivityResponse<ActionHash>]) {
+    for result in results {
+        let mut verified = Vec::with_capacity(result.warrants.len());
+        for warrant in result.warrants.drain(..) {
+            if holochain_state::warrants::verify_warrant(&warrant).await {
+                verified.push(warrant);
+            } else {
+                tracing::warn!(?warrant, "Dropping a remote warrant which failed to verify");
+            }
+        }
+        result.warrants = verified;
+    }
+}
+
+/// Different authorities may hold some of the same warrants,
+/// so only add the ones which haven't been seen yet.
+fn merge_warrants(merged: &mut Vec<SignedWarrant>, warrants: Vec<SignedWarrant>) {
+    for warrant in warrants {
+        if !merged.contains(&warrant) {
+            merged.push(warrant);
+        }
     }
 }
 
@@ -147,16 +182,19 @@ fn merge_status_only(
 ) -> AgentActivityResponse<ActionHash> {
     let mut merged_status = None;
     let mut merged_highest_observed = None;
+    let mut merged_warrants = Vec::new();
     for result in results {
         let AgentActivityResponse {
             status,
             agent: the_agent,
             highest_observed,
+            warrants,
             ..
         } = result;
         if the_agent != agent {
             continue;
         }
+        merge_warrants(&mut merged_warrants, warrants);
         match (merged_highest_observed.take(), highest_observed) {
             (None, None) => {}
             (Some(h), None) | (None, Some(h)) => {
@@ -234,5 +272,6 @@ fn merge_status_only(
         valid_activity: ChainItems::NotRequested,
         rejected_activity: ChainItems::NotRequested,
         highest_observed: merged_highest_observed,
+        warrants: merged_warrants,
     }
 }
@@ -4,6 +4,7 @@ use self::get_agent_activity_query::hashes::GetAgentActivityQuery;
 use self::get_agent_activity_query::must_get_agent_activity::must_get_agent_activity;
 use self::get_entry_ops_query::GetEntryOpsQuery;
 use self::get_links_ops_query::GetLinksOpsQuery;
+use self::get_meta_query::GetMetaQuery;
 use self::{
     get_agent_activity_query::deterministic::DeterministicGetAgentActivityQuery,
     get_record_query::GetRecordOpsQuery,
@@ -12,8 +13,11 @@ use self::{
 use super::error::CascadeResult;
 use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
+use holochain_state::query::link::GetLinksQuery;
 use holochain_state::query::Query;
+use holochain_state::query::StateQueryResult;
 use holochain_state::query::Txn;
+use holochain_state::warrants::list_warrants;
 use holochain_types::prelude::*;
 use holochain_zome_types::agent_activity::DeterministicGetAgentActivityFilter;
 use tracing::*;
@@ -24,6 +28,7 @@ mod test;
 pub(crate) mod get_agent_activity_query;
 pub(crate) mod get_entry_ops_query;
 pub(crate) mod get_links_ops_query;
+pub(crate) mod get_meta_query;
 pub(crate) mod get_record_query;
 
 /// Handler for get_entry query to an Entry authority
@@ -54,6 +59,20 @@ pub async fn handle_get_record(
     Ok(results)
 }
 
+/// Handler for get_meta query to an authority for the given basis
+#[instrument(skip(db))]
+pub async fn handle_get_meta(
+    db: DbRead<DbKindDht>,
+    hash: AnyDhtHash,
+    options: holochain_p2p::event::GetMetaOptions,
+) -> CascadeResult<MetadataSet> {
+    let query = GetMetaQuery::new(hash, options.metadata_request);
+    let results = db
+        .async_reader(move |txn| query.run(Txn::from(&txn)))
+        .await?;
+    Ok(results)
+}
+
 /// Handler for get_agent_activity query to an Activity authority
 #[instrument(skip(env))]
 pub async fn handle_get_agent_activity(
@@ -62,9 +81,13 @@ pub async fn handle_get_agent_activity(
     query: ChainQueryFilter,
     options: holochain_p2p::event::GetActivityOptions,
 ) -> CascadeResult<AgentActivityResponse<ActionHash>> {
-    let query = GetAgentActivityQuery::new(agent, query, options);
+    let query = GetAgentActivityQuery::new(agent.clone(), query, options);
     let results = env
-        .async_reader(move |txn| query.run(Txn::from(&txn)))
+        .async_reader(move |txn| {
+            let mut results = query.run(Txn::from(&txn))?;
+            results.warrants = list_warrants(&txn, &agent)?;
+            StateQueryResult::Ok(results)
+        })
         .await?;
     Ok(results)
 }
@@ -107,3 +130,22 @@ pub async fn handle_get_links(
         .await?;
     Ok(results)
 }
+
+/// Handler for count_links query to a Record/Entry authority
+#[instrument(skip(env))]
+pub async fn handle_count_links(
+    env: DbRead<DbKindDht>,
+    query: WireLinkQuery,
+) -> CascadeResult<CountLinksResponse> {
+    let filter = query.filter();
+    let query = GetLinksQuery::new(query.base, query.type_query, query.tag).with_filter(filter);
+    let links = env
+        .async_reader(move |txn| query.run(Txn::from(&txn)))
+        .await?;
+    Ok(CountLinksResponse::new(
+        links
+            .into_iter()
+            .map(|link| link.create_link_hash)
+            .collect(),
+    ))
+}
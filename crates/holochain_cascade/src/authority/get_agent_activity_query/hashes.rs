@@ -168,6 +168,9 @@ impl Query for GetAgentActivityQuery {
             rejected_activity,
             status,
             highest_observed,
+            // Warrants are held separately from the chain,
+            // so the authority adds them after running this query.
+            warrants: Vec::new(),
         })
     }
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use holo_hash::AnyLinkableHash;
 use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::Row;
+use holochain_state::query::link::LinksQuery;
 use holochain_state::query::prelude::*;
 use holochain_state::query::StateQueryError;
 use holochain_types::dht_op::DhtOpType;
@@ -11,11 +12,13 @@ use holochain_types::link::WireDeleteLink;
 use holochain_types::link::WireLinkOps;
 use holochain_types::sql::ToSqlStatement;
 use holochain_zome_types::Action;
+use holochain_zome_types::GetLinksFilter;
 use holochain_zome_types::HasValidationStatus;
 use holochain_zome_types::Judged;
 use holochain_zome_types::LinkTag;
 use holochain_zome_types::LinkTypeFilter;
 use holochain_zome_types::SignedAction;
+use holochain_zome_types::ValidationStatus;
 
 use super::WireLinkKey;
 
@@ -24,6 +27,7 @@ pub struct GetLinksOpsQuery {
     base: Arc<AnyLinkableHash>,
     type_query: LinkTypeFilter,
     tag: Option<Arc<LinkTag>>,
+    filter: GetLinksFilter,
 }
 
 impl GetLinksOpsQuery {
@@ -32,6 +36,7 @@ impl GetLinksOpsQuery {
             base: Arc::new(key.base),
             type_query: key.type_query,
             tag: key.tag.map(Arc::new),
+            filter: key.filter,
         }
     }
     pub fn tag_to_hex(tag: &LinkTag) -> String {
@@ -88,11 +93,36 @@ impl Query for GetLinksOpsQuery {
             "
             {}
             {}
+            {}
             ",
             common_query,
             self.type_query.to_sql_statement(),
+            LinksQuery::filter_to_sql(&self.filter),
         );
-        let create_query = format!("{}{}", create, common_query);
+        let mut create_query = format!("{}{}", create, common_query);
+        if self.filter.limit.is_some() {
+            // Only links which are valid and not deleted count towards the limit,
+            // so the requester gets as many live links as it asked for.
+            create_query = format!(
+                "
+                SELECT action_blob, dht_type, status FROM (
+                    {}
+                    AND DhtOp.validation_status = :valid
+                    AND NOT EXISTS (
+                        SELECT 1 FROM DhtOp AS DeleteOp
+                        JOIN Action AS DeleteAction ON DeleteOp.action_hash = DeleteAction.hash
+                        WHERE DeleteOp.type = :delete
+                        AND DeleteAction.create_link_hash = Action.hash
+                        AND DeleteOp.validation_status = :valid
+                        AND DeleteOp.when_integrated IS NOT NULL
+                    )
+                    ORDER BY DhtOp.authored_timestamp, Action.hash
+                    LIMIT :limit
+                )
+                ",
+                create_query
+            );
+        }
         let sub_create_query = format!("{}{}", sub_create, common_query);
         let delete_query = format!(
             "
@@ -112,12 +142,19 @@ impl Query for GetLinksOpsQuery {
     }
 
     fn params(&self) -> Vec<Params> {
-        named_params! {
+        let mut params = named_params! {
             ":create": DhtOpType::RegisterAddLink,
             ":delete": DhtOpType::RegisterRemoveLink,
             ":base_hash": self.base,
         }
-        .to_vec()
+        .to_vec();
+        if self.filter.limit.is_some() {
+            params.extend(named_params! {
+                ":valid": ValidationStatus::Valid,
+            });
+        }
+        params.extend(LinksQuery::filter_params(&self.filter));
+        params
     }
 
     fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
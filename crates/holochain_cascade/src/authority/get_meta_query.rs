@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use holo_hash::ActionHash;
+use holo_hash::AnyDhtHash;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Row;
+use holochain_state::query::prelude::*;
+use holochain_state::query::StateQueryError;
+use holochain_types::dht_op::DhtOpType;
+use holochain_types::metadata::MetadataSet;
+use holochain_types::metadata::TimedActionHash;
+use holochain_zome_types::Action;
+use holochain_zome_types::EntryDhtStatus;
+use holochain_zome_types::Judged;
+use holochain_zome_types::MetadataRequest;
+use holochain_zome_types::SignedAction;
+use holochain_zome_types::ValidationStatus;
+
+#[derive(Debug, Clone)]
+pub struct GetMetaQuery(AnyDhtHash, MetadataRequest);
+
+impl GetMetaQuery {
+    pub fn new(hash: AnyDhtHash, request: MetadataRequest) -> Self {
+        Self(hash, request)
+    }
+}
+
+pub struct Item {
+    op_type: DhtOpType,
+    action: SignedAction,
+    hash: ActionHash,
+}
+
+#[derive(Debug, Default)]
+pub struct State {
+    meta: MetadataSet,
+    is_entry: bool,
+    /// The actions which have been deleted by a valid Delete
+    deleted: HashSet<ActionHash>,
+    /// The links which have been deleted by a valid DeleteLink
+    deleted_links: HashSet<ActionHash>,
+}
+
+impl Query for GetMetaQuery {
+    type Item = Judged<Item>;
+    type State = State;
+    type Output = MetadataSet;
+
+    fn query(&self) -> String {
+        "
+        SELECT Action.blob AS action_blob, Action.hash AS action_hash,
+        DhtOp.type AS dht_type, DhtOp.validation_status AS status
+        FROM DhtOp
+        JOIN Action On DhtOp.action_hash = Action.hash
+        WHERE DhtOp.type IN (
+            :store_entry, :store_record, :delete_entry, :delete_record,
+            :update_content, :update_record, :create_link, :delete_link
+        )
+        AND
+        DhtOp.basis_hash = :basis_hash
+        AND
+        DhtOp.when_integrated IS NOT NULL
+        "
+        .into()
+    }
+
+    fn params(&self) -> Vec<Params> {
+        let params = named_params! {
+            ":store_entry": DhtOpType::StoreEntry,
+            ":store_record": DhtOpType::StoreRecord,
+            ":delete_entry": DhtOpType::RegisterDeletedEntryAction,
+            ":delete_record": DhtOpType::RegisterDeletedBy,
+            ":update_content": DhtOpType::RegisterUpdatedContent,
+            ":update_record": DhtOpType::RegisterUpdatedRecord,
+            ":create_link": DhtOpType::RegisterAddLink,
+            ":delete_link": DhtOpType::RegisterRemoveLink,
+            ":basis_hash": self.0,
+        };
+        params.to_vec()
+    }
+
+    fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
+        let f = |row: &Row| {
+            let action =
+                from_blob::<SignedAction>(row.get(row.as_ref().column_index("action_blob")?)?)?;
+            let hash = row.get(row.as_ref().column_index("action_hash")?)?;
+            let op_type = row.get(row.as_ref().column_index("dht_type")?)?;
+            let validation_status = row.get(row.as_ref().column_index("status")?)?;
+            Ok(Judged::raw(
+                Item {
+                    op_type,
+                    action,
+                    hash,
+                },
+                validation_status,
+            ))
+        };
+        Arc::new(f)
+    }
+
+    fn init_fold(&self) -> StateQueryResult<Self::State> {
+        Ok(Default::default())
+    }
+
+    fn fold(&self, mut state: Self::State, dht_op: Self::Item) -> StateQueryResult<Self::State> {
+        let request = &self.1;
+        let status = dht_op.status;
+        let Item {
+            op_type,
+            action,
+            hash,
+        } = dht_op.data;
+        let SignedAction(action, _) = action;
+        let timed = TimedActionHash {
+            timestamp: action.timestamp(),
+            action_hash: hash,
+        };
+        match status {
+            Some(ValidationStatus::Valid) => (),
+            Some(ValidationStatus::Rejected) => {
+                if request.all_invalid_actions {
+                    state.meta.invalid_actions.insert(timed);
+                }
+                return Ok(state);
+            }
+            _ => return Ok(state),
+        }
+        match op_type {
+            DhtOpType::StoreEntry => {
+                state.is_entry = true;
+                state.meta.actions.insert(timed);
+            }
+            DhtOpType::StoreRecord => {
+                state.meta.actions.insert(timed);
+            }
+            DhtOpType::RegisterDeletedEntryAction | DhtOpType::RegisterDeletedBy => {
+                if let Action::Delete(delete) = &action {
+                    state.deleted.insert(delete.deletes_address.clone());
+                }
+                state.meta.deletes.insert(timed);
+            }
+            DhtOpType::RegisterUpdatedContent | DhtOpType::RegisterUpdatedRecord => {
+                state.meta.updates.insert(timed);
+            }
+            DhtOpType::RegisterAddLink => {
+                state.meta.links.insert(timed);
+            }
+            DhtOpType::RegisterRemoveLink => {
+                if let Action::DeleteLink(delete_link) = &action {
+                    state
+                        .deleted_links
+                        .insert(delete_link.link_add_address.clone());
+                }
+            }
+            _ => return Err(StateQueryError::UnexpectedOp(op_type)),
+        }
+        Ok(state)
+    }
+
+    fn render<S>(&self, state: Self::State, _stores: S) -> StateQueryResult<Self::Output>
+    where
+        S: Store,
+    {
+        let request = &self.1;
+        let State {
+            mut meta,
+            is_entry,
+            deleted,
+            deleted_links,
+        } = state;
+        if request.entry_dht_status && is_entry {
+            let live = meta
+                .actions
+                .iter()
+                .any(|a| !deleted.contains(&a.action_hash));
+            meta.entry_dht_status = Some(if live {
+                EntryDhtStatus::Live
+            } else {
+                EntryDhtStatus::Dead
+            });
+        }
+        if !request.all_valid_actions {
+            meta.actions.clear();
+        }
+        if !request.all_deletes {
+            meta.deletes.clear();
+        }
+        if !request.all_updates {
+            meta.updates.clear();
+        }
+        if request.all_links {
+            meta.links
+                .retain(|link| !deleted_links.contains(&link.action_hash));
+        } else {
+            meta.links.clear();
+        }
+        Ok(meta)
+    }
+}
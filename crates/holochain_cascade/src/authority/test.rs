@@ -189,6 +189,69 @@ async fn get_links() {
     assert_eq!(result, expected);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn get_meta() {
+    observability::test_run().ok();
+    let db = test_dht_db();
+
+    let td = EntryTestData::create();
+    let hashes = |set: &std::collections::BTreeSet<TimedActionHash>| {
+        set.iter()
+            .map(|t| t.action_hash.clone())
+            .collect::<Vec<_>>()
+    };
+
+    fill_db(&db.to_db(), td.store_entry_op.clone());
+    fill_db(&db.to_db(), td.update_content_op.clone());
+    fill_db(&db.to_db(), td.create_link_op.clone());
+    let options = actor::GetMetaOptions {
+        metadata_request: MetadataRequest {
+            entry_dht_status: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let result = handle_get_meta(db.to_db().into(), td.hash.clone().into(), (&options).into())
+        .await
+        .unwrap();
+    assert_eq!(hashes(&result.actions), vec![td.create_hash.clone()]);
+    assert_eq!(hashes(&result.updates), vec![td.update_hash.clone()]);
+    assert!(result.deletes.is_empty());
+    assert_eq!(
+        hashes(&result.links),
+        vec![td.create_link_action.as_hash().clone()]
+    );
+    assert_eq!(result.entry_dht_status, Some(EntryDhtStatus::Live));
+
+    fill_db(&db.to_db(), td.delete_entry_action_op.clone());
+    fill_db(&db.to_db(), td.delete_link_op.clone());
+
+    let result = handle_get_meta(db.to_db().into(), td.hash.clone().into(), (&options).into())
+        .await
+        .unwrap();
+    assert_eq!(hashes(&result.deletes), vec![td.delete_hash.clone()]);
+    assert!(result.links.is_empty());
+    assert_eq!(result.entry_dht_status, Some(EntryDhtStatus::Dead));
+
+    // Only the parts of the metadata which were requested are returned.
+    let options = actor::GetMetaOptions {
+        metadata_request: MetadataRequest {
+            all_valid_actions: false,
+            all_updates: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result = handle_get_meta(db.to_db().into(), td.hash.clone().into(), (&options).into())
+        .await
+        .unwrap();
+    assert!(result.actions.is_empty());
+    assert!(result.updates.is_empty());
+    assert_eq!(hashes(&result.deletes), vec![td.delete_hash.clone()]);
+    assert_eq!(result.entry_dht_status, None);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_agent_activity() {
     observability::test_run().ok();
@@ -224,6 +287,7 @@ async fn get_agent_activity() {
         rejected_activity: ChainItems::NotRequested,
         status: ChainStatus::Valid(td.chain_head.clone()),
         highest_observed: Some(td.highest_observed.clone()),
+        warrants: Vec::new(),
     };
     assert_eq!(result, expected);
 
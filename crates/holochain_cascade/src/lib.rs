@@ -23,6 +23,7 @@
 //!
 #![warn(missing_docs)]
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use error::CascadeResult;
@@ -33,6 +34,7 @@ use holo_hash::AnyDhtHash;
 use holo_hash::EntryHash;
 use holochain_p2p::actor::GetActivityOptions;
 use holochain_p2p::actor::GetLinksOptions;
+use holochain_p2p::actor::GetMetaOptions;
 use holochain_p2p::actor::GetOptions as NetworkGetOptions;
 use holochain_p2p::HolochainP2pDna;
 use holochain_p2p::HolochainP2pDnaT;
@@ -387,6 +389,15 @@ where
         Ok(())
     }
 
+    #[instrument(skip(self))]
+    async fn fetch_link_counts(
+        &mut self,
+        query: WireLinkQuery,
+    ) -> CascadeResult<Vec<CountLinksResponse>> {
+        let network = some_or_return!(self.network.as_mut(), Vec::with_capacity(0));
+        Ok(network.count_links(query).await?)
+    }
+
     #[instrument(skip(self, options))]
     async fn fetch_agent_activity(
         &mut self,
@@ -846,11 +857,44 @@ where
         if !authority {
             self.fetch_links(key.clone(), options).await?;
         }
-        let query = GetLinksQuery::new(key.base, key.type_query, key.tag);
+        let query = GetLinksQuery::new(key.base, key.type_query, key.tag).with_filter(key.filter);
         let results = self.cascading(query).await?;
         Ok(results)
     }
 
+    #[instrument(skip(self))]
+    /// Count the links on a base which haven't been deleted.
+    /// An authority counts the links in its local databases. Otherwise the
+    /// links counted by the authorities are merged with the links which the
+    /// agent created itself, which may not have reached the authorities yet,
+    /// and the links deleted locally are removed.
+    /// Any other local create without a delete may have been deleted
+    /// remotely, so it is not added to the authorities' count.
+    pub async fn dht_count_links(&mut self, query: WireLinkQuery) -> CascadeResult<usize> {
+        let authority = self.am_i_an_authority(query.base.clone()).await?;
+        let own_agent = self.private_data.clone();
+        let mut create_link_actions = HashSet::new();
+        if !authority {
+            for response in self.fetch_link_counts(query.clone()).await? {
+                create_link_actions.extend(response.into_create_link_actions());
+            }
+        }
+        let filter = query.filter();
+        let local_query =
+            GetLinkDetailsQuery::new(query.base, query.type_query, query.tag).with_filter(filter);
+        for (create, deletes) in self.cascading(local_query).await? {
+            let own_create = own_agent
+                .as_deref()
+                .map_or(false, |agent| create.action().author() == agent);
+            if !deletes.is_empty() {
+                create_link_actions.remove(create.as_hash());
+            } else if authority || own_create {
+                create_link_actions.insert(create.as_hash().clone());
+            }
+        }
+        Ok(create_link_actions.len())
+    }
+
     #[instrument(skip(self, key, options))]
     /// Return all CreateLink actions
     /// and DeleteLink actions ordered by time.
@@ -863,11 +907,53 @@ where
         if !authority {
             self.fetch_links(key.clone(), options).await?;
         }
-        let query = GetLinkDetailsQuery::new(key.base, key.type_query, key.tag);
+        let query =
+            GetLinkDetailsQuery::new(key.base, key.type_query, key.tag).with_filter(key.filter);
         let results = self.cascading(query).await?;
         Ok(results)
     }
 
+    #[instrument(skip(self, options))]
+    /// Get the metadata for a basis hash, as described by the
+    /// [`MetadataRequest`] in the options.
+    /// Local stores are always checked. If we are not an authority for the
+    /// hash then the metadata returned by the network is merged in.
+    pub async fn get_meta(
+        &mut self,
+        hash: AnyDhtHash,
+        options: GetMetaOptions,
+    ) -> CascadeResult<MetadataSet> {
+        let query = authority::get_meta_query::GetMetaQuery::new(
+            hash.clone(),
+            options.metadata_request.clone(),
+        );
+        let conns = self.get_databases().await;
+        let mut meta = tokio::task::spawn_blocking(move || {
+            let mut conns = conns
+                .into_iter()
+                .map(|(permit, conn)| conn.with_permit(permit))
+                .collect::<DatabaseResult<Vec<_>>>()?;
+            let mut txns = Vec::with_capacity(conns.len());
+            for conn in &mut conns {
+                let txn = conn.transaction().map_err(StateQueryError::from)?;
+                txns.push(txn);
+            }
+            let txns_ref: Vec<_> = txns.iter().collect();
+            CascadeResult::Ok(query.run(Txns::from(&txns_ref[..]))?)
+        })
+        .await??;
+
+        let authority = self.am_i_an_authority(hash.clone().into()).await?;
+        if !authority {
+            if let Some(network) = self.network.as_mut() {
+                for other in network.get_meta(hash, options).await? {
+                    meta.merge(other);
+                }
+            }
+        }
+        Ok(meta)
+    }
+
     /// Request a hash bounded chain query.
     pub async fn must_get_agent_activity(
         &mut self,
@@ -947,9 +1033,10 @@ where
         // DESIGN: Evaluate if it's ok to **not** go to another authority for agent activity?
         let authority = self.am_i_an_authority(agent.clone().into()).await?;
         let merged_response = if !authority {
-            let results = self
+            let mut results = self
                 .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
                 .await?;
+            agent_activity::drop_unverified_warrants(&mut results).await;
             let merged_response: AgentActivityResponse<ActionHash> =
                 agent_activity::merge_activities(agent.clone(), &options, results)?;
             merged_response
@@ -994,6 +1081,7 @@ where
             rejected_activity,
             status,
             highest_observed,
+            warrants,
         } = merged_response;
         let valid_activity = match valid_activity {
             ChainItems::Hashes(hashes) => {
@@ -1048,6 +1136,7 @@ where
             rejected_activity,
             status,
             highest_observed,
+            warrants,
         };
         Ok(r)
     }
@@ -51,6 +51,7 @@ use holochain_zome_types::zome_io::Nonce256Bits;
 use holochain_zome_types::ActionRefMut;
 use holochain_zome_types::QueryFilter;
 use holochain_zome_types::Signature;
+use holochain_zome_types::SignedWarrant;
 use holochain_zome_types::Timestamp;
 use holochain_zome_types::ValidationStatus;
 use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
@@ -139,10 +140,17 @@ impl HolochainP2pDnaT for PassThroughNetwork {
 
     async fn get_meta(
         &self,
-        _dht_hash: holo_hash::AnyDhtHash,
-        _options: actor::GetMetaOptions,
+        dht_hash: holo_hash::AnyDhtHash,
+        options: actor::GetMetaOptions,
     ) -> actor::HolochainP2pResult<Vec<MetadataSet>> {
-        todo!()
+        let mut out = Vec::new();
+        for env in &self.envs {
+            let r = authority::handle_get_meta(env.clone(), dht_hash.clone(), (&options).into())
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
     }
 
     async fn get_links(
@@ -160,6 +168,20 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         Ok(out)
     }
 
+    async fn count_links(
+        &self,
+        query: WireLinkQuery,
+    ) -> actor::HolochainP2pResult<Vec<CountLinksResponse>> {
+        let mut out = Vec::new();
+        for env in &self.envs {
+            let r = authority::handle_count_links(env.clone(), query.clone())
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
     async fn get_agent_activity(
         &self,
         agent: AgentPubKey,
@@ -247,6 +269,14 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         todo!()
     }
 
+    async fn publish_warrants(
+        &self,
+        _basis_hash: holo_hash::OpBasis,
+        _warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn send_validation_receipt(
         &self,
         _to_agent: AgentPubKey,
@@ -327,6 +357,20 @@ pub fn fill_db_rejected<Db: DbKindT + DbKindOp>(env: &DbWrite<Db>, op: DhtOpHash
         .unwrap();
 }
 
+/// Insert ops directly into the database and mark integrated as abandoned
+pub fn fill_db_abandoned<Db: DbKindT + DbKindOp>(env: &DbWrite<Db>, op: DhtOpHashed) {
+    env.conn()
+        .unwrap()
+        .with_commit_sync(|txn| {
+            let hash = op.as_hash();
+            insert_op(txn, &op).unwrap();
+            set_validation_status(txn, hash, ValidationStatus::Abandoned).unwrap();
+            set_when_integrated(txn, hash, Timestamp::now()).unwrap();
+            DatabaseResult::Ok(())
+        })
+        .unwrap();
+}
+
 /// Insert ops directly into the database and mark valid and pending integration
 pub fn fill_db_pending<Db: DbKindT + DbKindOp>(env: &DbWrite<Db>, op: DhtOpHashed) {
     env.conn()
@@ -377,6 +421,13 @@ impl HolochainP2pDnaT for MockNetwork {
         self.0.lock().await.get_links(link_key, options).await
     }
 
+    async fn count_links(
+        &self,
+        query: WireLinkQuery,
+    ) -> actor::HolochainP2pResult<Vec<CountLinksResponse>> {
+        self.0.lock().await.count_links(query).await
+    }
+
     async fn get_agent_activity(
         &self,
         agent: AgentPubKey,
@@ -449,6 +500,14 @@ impl HolochainP2pDnaT for MockNetwork {
         todo!()
     }
 
+    async fn publish_warrants(
+        &self,
+        _basis_hash: holo_hash::OpBasis,
+        _warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn send_validation_receipt(
         &self,
         _to_agent: AgentPubKey,
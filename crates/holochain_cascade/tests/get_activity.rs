@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use ::fixt::prelude::*;
 use ghost_actor::dependencies::observability;
 use holo_hash::AgentPubKey;
 use holo_hash::DnaHash;
@@ -8,15 +9,28 @@ use holochain_cascade::Cascade;
 use holochain_sqlite::db::DbKindAuthored;
 use holochain_sqlite::db::DbKindCache;
 use holochain_sqlite::db::DbKindDht;
+use holochain_state::mutations::insert_warrant;
 use holochain_state::prelude::test_cache_db;
 use holochain_state::prelude::test_dht_db;
+use holochain_state::prelude::test_keystore;
 use holochain_state::scratch::Scratch;
+use holochain_state::warrants::sign_warrant;
 use holochain_types::activity::*;
 use holochain_types::chain::MustGetAgentActivityResponse;
+use holochain_types::prelude::AgentPubKeyExt;
 use holochain_types::test_utils::chain::*;
+use holochain_zome_types::fixt::*;
+use holochain_zome_types::op::Op;
+use holochain_zome_types::op::RegisterAgentActivity;
+use holochain_zome_types::warrant::Warrant;
+use holochain_zome_types::warrant::WarrantProof;
+use holochain_zome_types::Action;
+use holochain_zome_types::ActionHashed;
 use holochain_zome_types::ChainFilter;
 use holochain_zome_types::ChainQueryFilter;
 use holochain_zome_types::ChainStatus;
+use holochain_zome_types::SignedActionHashed;
+use holochain_zome_types::Timestamp;
 use test_case::test_case;
 
 #[tokio::test(flavor = "multi_thread")]
@@ -64,6 +78,7 @@ async fn get_activity() {
         rejected_activity: ChainItems::NotRequested,
         status: ChainStatus::Valid(td.chain_head.clone()),
         highest_observed: Some(td.highest_observed.clone()),
+        warrants: Vec::new(),
     };
     assert_eq!(r, expected);
 }
@@ -140,3 +155,52 @@ async fn test_must_get_agent_activity(
         .await
         .unwrap()
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forged_remote_warrants_are_dropped() {
+    observability::test_run().ok();
+
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+    let keystore = test_keystore();
+    let validator = keystore.new_sign_keypair_random().await.unwrap();
+    let offender = keystore.new_sign_keypair_random().await.unwrap();
+
+    let mut create = fixt!(Create);
+    create.author = offender.clone();
+    let action = Action::Create(create);
+    let signature = offender.sign(&keystore, &action).await.unwrap();
+    let op = Op::RegisterAgentActivity(RegisterAgentActivity {
+        action: SignedActionHashed::with_presigned(
+            ActionHashed::from_content_sync(action),
+            signature,
+        ),
+        cached_entry: None,
+    });
+    let warrant = Warrant::new(
+        WarrantProof::InvalidOp(Box::new(op)),
+        validator,
+        Timestamp::now(),
+    );
+    let signed = sign_warrant(warrant, &keystore).await.unwrap();
+
+    // The authority claims that somebody else issued the same warrant
+    let mut forged = signed.clone();
+    forged.warrant.author = fixt!(AgentPubKey);
+    authority.to_db().test_commit(|txn| {
+        insert_warrant(txn, signed.clone(), true).unwrap();
+        insert_warrant(txn, forged, true).unwrap();
+    });
+
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+    let mut cascade = Cascade::empty().with_network(network, cache.to_db());
+    let r = cascade
+        .get_agent_activity(
+            offender,
+            ChainQueryFilter::new(),
+            holochain_p2p::actor::GetActivityOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(r.warrants, vec![signed]);
+}
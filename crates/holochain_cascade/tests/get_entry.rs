@@ -58,6 +58,7 @@ async fn assert_can_get<N: HolochainP2pDnaT + Clone + Send + 'static>(
             .clone()
             .into_action(td_entry.entry.entry_type.clone(), td_entry.hash.clone())],
         rejected_actions: vec![],
+        abandoned_actions: vec![],
         deletes: vec![],
         updates: vec![],
         entry_dht_status: EntryDhtStatus::Live,
@@ -156,6 +157,7 @@ async fn assert_rejected<N: HolochainP2pDnaT + Clone + Send + 'static>(
             .data
             .clone()
             .into_action(td_entry.entry.entry_type.clone(), td_entry.hash.clone())],
+        abandoned_actions: vec![],
         deletes: vec![],
         updates: vec![],
         entry_dht_status: EntryDhtStatus::Dead,
@@ -179,6 +181,59 @@ async fn assert_rejected<N: HolochainP2pDnaT + Clone + Send + 'static>(
     assert_eq!(r, expected);
 }
 
+async fn assert_abandoned<N: HolochainP2pDnaT + Clone + Send + 'static>(
+    td_entry: &EntryTestData,
+    td_record: &RecordTestData,
+    cascade: &mut Cascade<N>,
+    options: GetOptions,
+) {
+    // - Abandoned data is not live
+    let r = cascade
+        .dht_get(td_entry.hash.clone().into(), options.clone())
+        .await
+        .unwrap();
+
+    assert!(r.is_none());
+
+    // - But it is surfaced in the details
+    let r = cascade
+        .get_details(td_entry.hash.clone().into(), Default::default())
+        .await
+        .unwrap()
+        .expect("Failed to get entry");
+
+    let expected = Details::Entry(EntryDetails {
+        entry: td_entry.entry.entry.clone(),
+        actions: vec![],
+        rejected_actions: vec![],
+        abandoned_actions: vec![td_entry
+            .wire_create
+            .data
+            .clone()
+            .into_action(td_entry.entry.entry_type.clone(), td_entry.hash.clone())],
+        deletes: vec![],
+        updates: vec![],
+        entry_dht_status: EntryDhtStatus::Abandoned,
+    });
+
+    assert_eq!(r, expected);
+
+    let r = cascade
+        .get_details(td_record.any_action_hash.clone().into(), Default::default())
+        .await
+        .unwrap()
+        .expect("Failed to get record");
+
+    let expected = Details::Record(RecordDetails {
+        record: td_record.any_record.clone(),
+        validation_status: ValidationStatus::Abandoned,
+        deletes: vec![],
+        updates: vec![],
+    });
+
+    assert_eq!(r, expected);
+}
+
 async fn assert_can_retrieve<N: HolochainP2pDnaT + Clone + Send + 'static>(
     td_entry: &EntryTestData,
     cascade: &mut Cascade<N>,
@@ -448,6 +503,28 @@ async fn check_can_handle_rejected_ops_in_cache() {
     assert_rejected(&td_entry, &td_record, &mut cascade, GetOptions::latest()).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn abandoned_ops() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_record = RecordTestData::create();
+    fill_db_abandoned(&authority.to_db(), td_entry.store_entry_op.clone());
+    fill_db_abandoned(&authority.to_db(), td_record.any_store_record_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.to_db());
+    assert_abandoned(&td_entry, &td_record, &mut cascade, GetOptions::latest()).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore = "todo"]
 async fn check_all_queries_still_work() {
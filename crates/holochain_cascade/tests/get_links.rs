@@ -7,8 +7,10 @@ use holochain_state::prelude::test_authored_db;
 use holochain_state::prelude::test_cache_db;
 use holochain_state::prelude::test_dht_db;
 use holochain_state::scratch::Scratch;
+use holochain_types::link::CountLinksResponse;
 use holochain_types::link::WireLinkOps;
 use holochain_zome_types::ChainTopOrdering;
+use std::sync::Arc;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn links_not_authority() {
@@ -173,6 +175,177 @@ async fn links_authoring() {
     assert!(r.is_empty());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn count_links_not_authority() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td = EntryTestData::create();
+    fill_db(&authority.to_db(), td.store_entry_op.clone());
+    fill_db(&authority.to_db(), td.create_link_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![
+        authority.to_db().clone().into(),
+        authority.to_db().clone().into(),
+    ]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.to_db());
+
+    // Both authorities count the same link.
+    let r = cascade
+        .dht_count_links(td.link_key_tag.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(r, 1);
+
+    fill_db(&authority.to_db(), td.delete_link_op.clone());
+
+    let r = cascade
+        .dht_count_links(td.link_key.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(r, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn count_links_merges_local_links() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cache_db();
+    let mut scratch = Scratch::new();
+
+    // Data
+    let td = EntryTestData::create();
+    insert_op_scratch(
+        &mut scratch,
+        td.create_link_op.clone(),
+        ChainTopOrdering::default(),
+    )
+    .unwrap();
+
+    // Network
+    // - The authority knows the link being authored and one other link.
+    let other_link = holo_hash::ActionHash::from_raw_36(vec![1; 36]);
+    let mut mock = MockHolochainP2pDnaT::new();
+    mock.expect_authority_for_hash().returning(|_| Ok(false));
+    let known = vec![td.links[0].create_link_hash.clone(), other_link];
+    mock.expect_count_links()
+        .returning(move |_| Ok(vec![CountLinksResponse::new(known.clone())]));
+    let mock = MockNetwork::new(mock);
+
+    // Cascade
+    let mut cascade = Cascade::empty()
+        .with_network(mock.clone(), cache.to_db())
+        .with_scratch(scratch.clone().into_sync());
+
+    let r = cascade
+        .dht_count_links(td.link_key.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(r, 2);
+
+    insert_op_scratch(
+        &mut scratch,
+        td.delete_link_op.clone(),
+        ChainTopOrdering::default(),
+    )
+    .unwrap();
+
+    let mut cascade = Cascade::empty()
+        .with_network(mock, cache.to_db())
+        .with_scratch(scratch.into_sync());
+
+    // The link deleted locally isn't counted.
+    let r = cascade
+        .dht_count_links(td.link_key.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(r, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn count_links_counts_own_links_not_authority() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cache_db();
+    let vault = test_authored_db();
+    let authority = test_dht_db();
+
+    // Data
+    // - The link was just authored, so the authority doesn't have it yet.
+    let td = EntryTestData::create();
+    let author = Arc::new(td.create_link_action.action().author().clone());
+    fill_db(&vault.to_db(), td.store_entry_op.clone());
+    fill_db(&vault.to_db(), td.create_link_op.clone());
+    fill_db(&authority.to_db(), td.store_entry_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty()
+        .with_network(network.clone(), cache.to_db())
+        .with_authored(vault.to_db().into())
+        .with_private_data(author.clone());
+
+    let links = cascade
+        .dht_get_links(td.link_key_tag.clone(), Default::default())
+        .await
+        .unwrap();
+    let r = cascade
+        .dht_count_links(td.link_key_tag.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(links, td.links);
+    assert_eq!(r, links.len());
+
+    // The same applies to a link which is still in the scratch.
+    let mut scratch = Scratch::new();
+    insert_op_scratch(
+        &mut scratch,
+        td.create_link_op.clone(),
+        ChainTopOrdering::default(),
+    )
+    .unwrap();
+    let mut cascade = Cascade::empty()
+        .with_network(network.clone(), cache.to_db())
+        .with_scratch(scratch.into_sync())
+        .with_private_data(author.clone());
+
+    let r = cascade
+        .dht_count_links(td.link_key_tag.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(r, 1);
+
+    fill_db(&vault.to_db(), td.delete_link_op.clone());
+    let mut cascade = Cascade::empty()
+        .with_network(network, cache.to_db())
+        .with_authored(vault.to_db().into())
+        .with_private_data(author);
+
+    let r = cascade
+        .dht_count_links(td.link_key.clone().into())
+        .await
+        .unwrap();
+
+    assert_eq!(r, 0);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore = "todo"]
 async fn test_links_can_match_a_partial_tag() {
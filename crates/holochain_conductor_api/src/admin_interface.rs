@@ -2,8 +2,13 @@ use holo_hash::*;
 use holochain_types::prelude::*;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use std::net::IpAddr;
+use std::path::PathBuf;
 
-use crate::{AppInfo, FullStateDump};
+use crate::authentication::*;
+use crate::backup::BackupManifest;
+use crate::source_chain_archive::SourceChainArchive;
+use crate::{AllowedOrigins, AppInfo, FullStateDump};
 
 /// Represents the available conductor functions to call over an admin interface.
 ///
@@ -43,6 +48,16 @@ pub enum AdminRequest {
     /// [`AdminResponse::DnaDefinitionReturned`]
     GetDnaDefinition(Box<DnaHash>),
 
+    /// List the functions exported by each zome of a DNA.
+    ///
+    /// Callbacks such as `init` and `validate` are listed separately from
+    /// the zome functions which can be called by a client.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ZomeFunctionsListed`]
+    ListZomeFunctions(Box<DnaHash>),
+
     /// Update coordinator zomes for an already installed DNA.
     ///
     /// Replaces any installed coordinator zomes with the same zome name.
@@ -166,10 +181,48 @@ pub enum AdminRequest {
     /// a free port is chosen by the conductor.
     /// The response will contain the port chosen by the conductor if `None` was passed.
     ///
+    /// The interface binds to `bind_address`, or [`DEFAULT_BIND_ADDRESS`]
+    /// if it is `None`, and only accepts browser connections from
+    /// `allowed_origins`, which defaults to origins on this machine.
+    ///
+    /// If `require_authentication` is `true`, every connection to the interface
+    /// must first authenticate with a token issued by
+    /// [`AdminRequest::IssueAuthenticationToken`] for the apps it will access.
+    ///
     /// [`AppRequest`]: super::AppRequest
+    /// [`DEFAULT_BIND_ADDRESS`]: crate::config::DEFAULT_BIND_ADDRESS
     AttachAppInterface {
         /// Optional port number
         port: Option<u16>,
+        /// Optional address to bind to
+        #[serde(default)]
+        bind_address: Option<IpAddr>,
+        /// The HTTP `Origin`s which browsers may connect from
+        #[serde(default)]
+        allowed_origins: AllowedOrigins,
+        /// Whether connections must authenticate before making requests
+        #[serde(default)]
+        require_authentication: bool,
+    },
+
+    /// Open up a new websocket interface for processing [`AppRequest`]s,
+    /// listening on a unix domain socket at `path`.
+    ///
+    /// Only the user running the conductor can connect to the socket.
+    /// A stale socket file at `path` is replaced, but attaching fails if
+    /// something is still listening on it. Only available on unix platforms.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppUnixSocketInterfaceAttached`]
+    ///
+    /// [`AppRequest`]: super::AppRequest
+    AttachAppUnixSocketInterface {
+        /// Path of the socket file to listen on
+        path: PathBuf,
+        /// Whether connections must authenticate before making requests
+        #[serde(default)]
+        require_authentication: bool,
     },
 
     /// List all the app interfaces currently attached with [`AttachAppInterface`].
@@ -177,9 +230,11 @@ pub enum AdminRequest {
     /// # Returns
     ///
     /// [`AdminResponse::AppInterfacesListed`], a list of websocket ports that can
-    /// process [`AppRequest`]s.
+    /// process [`AppRequest`]s. Interfaces attached with
+    /// [`AttachAppUnixSocketInterface`] aren't listed.
     ///
     /// [`AttachAppInterface`]: AdminRequest::AttachAppInterface
+    /// [`AttachAppUnixSocketInterface`]: AdminRequest::AttachAppUnixSocketInterface
     /// [`AppRequest`]: super::AppRequest
     ListAppInterfaces,
 
@@ -325,6 +380,108 @@ pub enum AdminRequest {
     ///
     /// [`AdminResponse::CloneCellDeleted`]
     DeleteCloneCell(Box<DeleteCloneCellPayload>),
+
+    /// Migrate the source chain of an app's provisioned cell to a new DNA,
+    /// which must already be registered.
+    ///
+    /// The old cell's chain is closed with a `CloseChain` action pointing
+    /// at the new DNA. A new cell is then created for the same agent in the
+    /// new DNA, and its chain is opened with an `OpenChain` action pointing
+    /// back at the old DNA. The new cell becomes the provisioned cell of the
+    /// role, while the old cell keeps running so its data can still be read.
+    ///
+    /// The app must be running, and every zome of both DNAs must agree
+    /// to the migration through their `migrate_agent` callbacks.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ChainMigrated`]
+    MigrateChain(Box<MigrateChainPayload>),
+
+    /// Issue a token which authenticates connections to admin or app interfaces
+    /// that require authentication.
+    ///
+    /// An admin scoped token is only accepted by admin interfaces. An app
+    /// scoped token is only accepted by app interfaces, and a connection
+    /// authenticated with it may only access the apps it was issued for.
+    /// All of those apps must be installed.
+    ///
+    /// Tokens are stored in the conductor's state, so they can still be used
+    /// after the conductor restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AuthenticationTokenIssued`]
+    IssueAuthenticationToken(Box<IssueAuthenticationTokenPayload>),
+
+    /// Revoke a token issued with [`AdminRequest::IssueAuthenticationToken`].
+    ///
+    /// Connections which were authenticated with the token are no longer served.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AuthenticationTokenRevoked`]
+    RevokeAuthenticationToken(AuthenticationToken),
+
+    /// Write a backup of every database of the conductor to a tar archive
+    /// at the given path on the conductor's file system.
+    ///
+    /// Each database is copied with SQLite's online backup API, so the
+    /// conductor keeps running while the backup is made. Each database in the
+    /// archive is consistent on its own. The databases are snapshotted one
+    /// after another before any is copied, so a write made during that short
+    /// window may be in some of them but not in others.
+    /// The keystore is in use while the conductor runs, so its files are not
+    /// included. Back them up with `holochain --create-backup` while the
+    /// conductor is stopped.
+    ///
+    /// The archive can be restored into an empty environment directory by
+    /// starting the `holochain` binary with `--restore-backup`.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::BackupCreated`]
+    CreateBackup {
+        /// Where to write the archive. Must not exist yet.
+        path: PathBuf,
+    },
+
+    /// Export the source chain of a cell as a [`SourceChainArchive`], which
+    /// can be imported into another conductor with [`AdminRequest::ImportSourceChain`].
+    ///
+    /// The archive contains every action of the chain with its signature and
+    /// entry. Private entries are included too, so they can optionally be
+    /// encrypted with a key derived from the agent's seed. Only a keystore
+    /// which holds the same agent's seed can then import the archive.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::SourceChainExported`]
+    ExportSourceChain {
+        /// The cell whose source chain to export.
+        cell_id: CellId,
+        /// Whether to encrypt the private entries in the archive.
+        encrypt_private_entries: bool,
+    },
+
+    /// Import a [`SourceChainArchive`] created with [`AdminRequest::ExportSourceChain`]
+    /// into the source chain of the same cell on this conductor, which must be empty.
+    ///
+    /// The DNA of the cell must already be registered, but the cell doesn't need
+    /// to be installed. Before anything is written, the hash and signature of
+    /// every action, the hash of every entry and the order of the hash chain
+    /// are verified. The records are then inserted as with [`AdminRequest::GraftRecords`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::SourceChainImported`]
+    ImportSourceChain {
+        /// The archive to import.
+        archive: SourceChainArchive,
+        /// If this is `true`, the records are also validated by the
+        /// DNA's validation rules before they are inserted.
+        validate: bool,
+    },
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -349,6 +506,12 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::GetDnaDefinition`]
     DnaDefinitionReturned(DnaDef),
 
+    /// The successful response to an [`AdminRequest::ListZomeFunctions`].
+    ///
+    /// Contains the functions of every zome in the DNA, in the order the
+    /// zomes are defined.
+    ZomeFunctionsListed(Vec<ZomeFunctions>),
+
     /// The successful response to an [`AdminRequest::UpdateCoordinators`]
     CoordinatorsUpdated,
 
@@ -399,6 +562,11 @@ pub enum AdminResponse {
         port: u16,
     },
 
+    /// The successful response to an [`AdminRequest::AttachAppUnixSocketInterface`].
+    ///
+    /// `AppInterfaceApi` successfully attached and listening on the socket.
+    AppUnixSocketInterfaceAttached,
+
     /// The list of attached app interfaces.
     AppInterfacesListed(Vec<u16>),
 
@@ -462,6 +630,30 @@ pub enum AdminResponse {
 
     /// The successful response to an [`AdminRequest::DeleteCloneCell`].
     CloneCellDeleted,
+
+    /// The successful response to an [`AdminRequest::MigrateChain`].
+    ///
+    /// The resulting [`AppInfo`] lists the new cell as the provisioned cell
+    /// of the role, and the link between the old and new cells in
+    /// [`AppInfo::chain_migrations`].
+    ChainMigrated(AppInfo),
+
+    /// The successful response to an [`AdminRequest::IssueAuthenticationToken`].
+    AuthenticationTokenIssued(AuthenticationTokenIssued),
+
+    /// The successful response to an [`AdminRequest::RevokeAuthenticationToken`].
+    AuthenticationTokenRevoked,
+
+    /// The successful response to an [`AdminRequest::CreateBackup`].
+    ///
+    /// Contains the manifest which was written to the archive.
+    BackupCreated(BackupManifest),
+
+    /// The successful response to an [`AdminRequest::ExportSourceChain`].
+    SourceChainExported(SourceChainArchive),
+
+    /// The successful response to an [`AdminRequest::ImportSourceChain`].
+    SourceChainImported,
 }
 
 /// Error type that goes over the websocket wire.
@@ -487,6 +679,9 @@ pub enum ExternalApiWireError {
     ZomeCallUnauthorized(String),
     /// A countersigning session has failed.
     CountersigningSessionError(String),
+    /// The connection could not be authenticated, or is not allowed to make
+    /// the request it made.
+    AuthenticationFailed(String),
 }
 
 impl ExternalApiWireError {
@@ -498,6 +693,18 @@ impl ExternalApiWireError {
     }
 }
 
+/// The functions exported by a single zome.
+/// Returned by [`AdminRequest::ListZomeFunctions`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct ZomeFunctions {
+    /// The name of the zome.
+    pub zome_name: ZomeName,
+    /// The functions which can be called with a zome call, sorted by name.
+    pub zome_fns: Vec<FunctionName>,
+    /// The callbacks which the zome implements, sorted by name.
+    pub callbacks: Vec<FunctionName>,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
 /// Filter for [`AdminRequest::ListApps`].
 pub enum AppStatusFilter {
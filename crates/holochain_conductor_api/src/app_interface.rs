@@ -71,8 +71,12 @@ pub enum AppRequest {
     /// Info about networking processes
     NetworkInfo(Box<NetworkInfoRequestPayload>),
 
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`].
+    /// Set the signal filters for an app, for this connection only.
+    /// Replaces any previous subscription for the same app.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalSubscribed`]
     SignalSubscription(SignalSubscription),
 }
 
@@ -119,6 +123,11 @@ pub enum AppResponse {
 
     /// NetworkInfo is returned
     NetworkInfo(Vec<NetworkInfo>),
+
+    /// The successful response to an [`AppRequest::SignalSubscription`].
+    ///
+    /// Signals on this connection are now filtered accordingly.
+    SignalSubscribed,
 }
 
 /// The data provided over an app interface in order to make a zome call
@@ -269,6 +278,18 @@ pub struct Cell {
     pub enabled: bool,
 }
 
+/// The link between two cells of a role whose source chain was migrated
+/// from one DNA to another.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChainMigration {
+    /// The cell whose chain was closed with a `CloseChain` action
+    /// pointing at the DNA of `new_cell_id`.
+    pub prev_cell_id: CellId,
+    /// The cell whose chain was opened with an `OpenChain` action
+    /// pointing back at the DNA of `prev_cell_id`.
+    pub new_cell_id: CellId,
+}
+
 /// Info about an installed app, returned as part of [`AppResponse::AppInfo`]
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct AppInfo {
@@ -278,6 +299,11 @@ pub struct AppInfo {
     /// and contain first the provisioned cell, then enabled clone cells and
     /// finally disabled clone cells.
     pub cell_info: HashMap<RoleName, Vec<CellInfo>>,
+    /// The chain migrations of the provisioned cell of each role which has
+    /// been migrated, oldest first. The last migration of a role ends at its
+    /// current provisioned cell.
+    #[serde(default)]
+    pub chain_migrations: HashMap<RoleName, Vec<ChainMigration>>,
     /// The app's current status, in an API-friendly format
     pub status: AppInfoStatus,
 }
@@ -291,6 +317,7 @@ impl AppInfo {
         let status = app.status().clone().into();
 
         let mut cell_info: HashMap<RoleName, Vec<CellInfo>> = HashMap::new();
+        let mut chain_migrations: HashMap<RoleName, Vec<ChainMigration>> = HashMap::new();
         app.roles().iter().for_each(|(role_name, role_assignment)| {
             // create a vector with info of all cells for this role
             let mut cell_info_for_role: Vec<CellInfo> = Vec::new();
@@ -352,11 +379,29 @@ impl AppInfo {
             }
 
             cell_info.insert(role_name.clone(), cell_info_for_role);
+
+            // link each cell the role was migrated from to its successor
+            if !role_assignment.previous_cells().is_empty() {
+                let cells: Vec<&CellId> = role_assignment
+                    .previous_cells()
+                    .iter()
+                    .chain(std::iter::once(role_assignment.cell_id()))
+                    .collect();
+                let migrations = cells
+                    .windows(2)
+                    .map(|pair| ChainMigration {
+                        prev_cell_id: pair[0].clone(),
+                        new_cell_id: pair[1].clone(),
+                    })
+                    .collect();
+                chain_migrations.insert(role_name.clone(), migrations);
+            }
         });
 
         Self {
             installed_app_id,
             cell_info,
+            chain_migrations,
             status,
         }
     }
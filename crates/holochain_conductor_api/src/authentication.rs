@@ -0,0 +1,109 @@
+//! Types for authenticating connections to admin and app interfaces.
+//!
+//! An interface which requires authentication expects the first message sent
+//! over each connection to be an [`AuthenticationRequest`] carrying a token
+//! issued with [`AdminRequest::IssueAuthenticationToken`]. The conductor
+//! replies with an [`AuthenticationResponse`], and closes the connection
+//! if the token was rejected.
+//!
+//! [`AdminRequest::IssueAuthenticationToken`]: crate::AdminRequest::IssueAuthenticationToken
+
+use crate::ExternalApiWireError;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::app::InstalledAppId;
+use holochain_zome_types::Timestamp;
+use std::path::PathBuf;
+
+/// A secret which authenticates a connection to an interface.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct AuthenticationToken(pub Vec<u8>);
+
+/// Where the conductor gets its bootstrap token, an admin scoped token which
+/// is accepted from startup and never expires. It allows the first tokens to
+/// be issued when every admin interface requires authentication.
+///
+/// The token is written as URL safe base64 without padding.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum BootstrapTokenConfig {
+    /// The token is written in the config.
+    Token {
+        /// The encoded token
+        token: String,
+    },
+    /// The token is read from a file, which only the user running the
+    /// conductor may read or write (mode 0600). If the file doesn't exist
+    /// it is created holding a new random token.
+    File {
+        /// The path of the file
+        path: PathBuf,
+    },
+}
+
+/// What a connection authenticated with a token is allowed to do.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum AuthenticationScope {
+    /// Use admin interfaces.
+    Admin,
+    /// Use app interfaces, but only to make requests about these apps
+    /// and to receive signals from their cells.
+    App {
+        /// The apps the connection may access.
+        installed_app_ids: Vec<InstalledAppId>,
+    },
+}
+
+/// The payload of [`AdminRequest::IssueAuthenticationToken`].
+///
+/// [`AdminRequest::IssueAuthenticationToken`]: crate::AdminRequest::IssueAuthenticationToken
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct IssueAuthenticationTokenPayload {
+    /// What connections authenticated with the token are allowed to do.
+    pub scope: AuthenticationScope,
+    /// How many seconds the token can be used for, after which connections
+    /// authenticated with it are no longer served.
+    /// The token never expires if this is `None`.
+    #[serde(default)]
+    pub expiry_seconds: Option<u64>,
+}
+
+/// The token issued by [`AdminRequest::IssueAuthenticationToken`].
+///
+/// [`AdminRequest::IssueAuthenticationToken`]: crate::AdminRequest::IssueAuthenticationToken
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AuthenticationTokenIssued {
+    /// The token to send in an [`AuthenticationRequest`].
+    pub token: AuthenticationToken,
+    /// When the token expires, if ever.
+    pub expires_at: Option<Timestamp>,
+}
+
+/// The first message a client must send over a connection to an interface
+/// which requires authentication.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AuthenticationRequest {
+    /// A token issued with [`AdminRequest::IssueAuthenticationToken`].
+    ///
+    /// [`AdminRequest::IssueAuthenticationToken`]: crate::AdminRequest::IssueAuthenticationToken
+    pub token: AuthenticationToken,
+}
+
+/// The reply to an [`AuthenticationRequest`].
+///
+/// A rejection is serialized in the same way as an [`AdminResponse::Error`]
+/// or [`AppResponse::Error`], so a client which didn't expect to authenticate
+/// still sees why its first request failed.
+///
+/// [`AdminResponse::Error`]: crate::AdminResponse::Error
+/// [`AppResponse::Error`]: crate::AppResponse::Error
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum AuthenticationResponse {
+    /// The connection is authenticated, and requests can now be sent over it.
+    Authenticated,
+    /// The token was rejected, with an [`ExternalApiWireError::AuthenticationFailed`].
+    Error(ExternalApiWireError),
+}
@@ -0,0 +1,37 @@
+//! Types describing a backup of all of a conductor's databases.
+//!
+//! A backup is created with [`AdminRequest::CreateBackup`] while the conductor
+//! runs, or by the `holochain` binary while it is stopped, and written as a
+//! tar archive containing a copy of every database file in the conductor's
+//! environment directory, along with a `backup.yaml` [`BackupManifest`].
+//! A backup made while the conductor is stopped also holds the files of its
+//! keystore.
+//!
+//! [`AdminRequest::CreateBackup`]: crate::AdminRequest::CreateBackup
+
+use holochain_serialized_bytes::prelude::*;
+use holochain_zome_types::Timestamp;
+use std::path::PathBuf;
+
+/// The name of the manifest file at the root of a backup archive.
+pub const BACKUP_MANIFEST_FILE_NAME: &str = "backup.yaml";
+
+/// The version of the backup archive format written by this conductor.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Describes the contents of a backup archive.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct BackupManifest {
+    /// The version of the archive format.
+    pub format_version: u32,
+    /// When the backup was started.
+    pub created_at: Timestamp,
+    /// The database files in the archive, relative to the environment
+    /// directory they were copied from, sorted.
+    pub databases: Vec<PathBuf>,
+    /// The files of the keystore in the archive's `keystore` directory,
+    /// sorted. Empty if the backup was made while the conductor was running,
+    /// or if the conductor's keystore files aren't on its machine.
+    #[serde(default)]
+    pub keystore: Vec<PathBuf>,
+}
@@ -46,9 +46,10 @@ pub struct ConductorConfig {
     /// Optional config for the network module.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
 
-    /// **PLACEHOLDER**: Optional specification of the Cloudflare namespace to use in Chain Head Coordination
-    /// service URLs. This is a placeholder for future work and may even go away.
-    /// Setting this to anything other than `None` will surely lead to no good.
+    /// Optional base URL of a Chain Head Coordination service, e.g. `http://localhost:40000`.
+    /// Each cell's chain is coordinated under `{chc_namespace}/{dna_hash}/{agent_pubkey}`.
+    /// The magic string `#LOCAL#` uses an in-memory CHC, for testing only.
+    /// Requires the `chc` feature to be enabled.
     #[serde(default)]
     pub chc_namespace: Option<String>,
 
@@ -62,6 +63,20 @@ pub struct ConductorConfig {
     /// [sqlite documentation]: https://www.sqlite.org/pragma.html#pragma_synchronous
     #[serde(default)]
     pub db_sync_strategy: DbSyncStrategy,
+
+    /// Stop talking to agents who have a verified warrant against them.
+    ///
+    /// When enabled, any agent whose invalid op has failed this conductor's
+    /// own app validation is blocked. Its peer info is neither stored nor
+    /// handed to the network, and connections with the nodes at its urls are
+    /// closed and refused, which also blocks any other agent on those nodes.
+    #[serde(default)]
+    pub block_warranted_agents: bool,
+
+    /// Optional bootstrap token for admin interfaces which require authentication.
+    /// See [`BootstrapTokenConfig`](crate::authentication::BootstrapTokenConfig).
+    #[serde(default)]
+    pub admin_bootstrap_token: Option<crate::authentication::BootstrapTokenConfig>,
     //
     //
     // Which signals to emit
@@ -133,6 +148,8 @@ pub mod tests {
                 keystore: KeystoreConfig::DangerTestKeystore,
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::default(),
+                block_warranted_agents: false,
+                admin_bootstrap_token: None,
                 chc_namespace: None,
             }
         );
@@ -220,10 +237,13 @@ pub mod tests {
                 }),
                 keystore: KeystoreConfig::LairServerInProc { lair_root: None },
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port: 1234 }
+                    driver: InterfaceDriver::websocket(1234),
+                    require_authentication: false,
                 }]),
                 network: Some(network_config),
                 db_sync_strategy: DbSyncStrategy::Fast,
+                block_warranted_agents: false,
+                admin_bootstrap_token: None,
                 chc_namespace: None,
             }
         );
@@ -251,8 +271,54 @@ pub mod tests {
                 },
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::Fast,
+                block_warranted_agents: false,
+                admin_bootstrap_token: None,
                 chc_namespace: None,
             }
         );
     }
+
+    #[test]
+    fn test_config_websocket_interface_options() {
+        let yaml = r#"---
+    environment_path: /path/to/env
+
+    keystore:
+      type: danger_test_keystore
+
+    admin_interfaces:
+      - driver:
+          type: websocket
+          port: 1234
+          bind_address: "0.0.0.0"
+          allowed_origins:
+            origins:
+              - http://localhost:8888
+      - driver:
+          type: unix_socket
+          path: /run/holochain/admin.sock
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.admin_interfaces,
+            Some(vec![
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::Websocket {
+                        port: 1234,
+                        bind_address: std::net::Ipv4Addr::UNSPECIFIED.into(),
+                        allowed_origins: AllowedOrigins::Origins(
+                            ["http://localhost:8888".to_string()].into_iter().collect()
+                        ),
+                    },
+                    require_authentication: false,
+                },
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::UnixSocket {
+                        path: PathBuf::from("/run/holochain/admin.sock"),
+                    },
+                    require_authentication: false,
+                },
+            ])
+        );
+    }
 }
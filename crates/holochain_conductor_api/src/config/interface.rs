@@ -1,15 +1,89 @@
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+
+/// The address websocket interfaces bind to unless another is configured.
+/// Only processes on the same machine can connect to it.
+pub const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+fn default_bind_address() -> IpAddr {
+    DEFAULT_BIND_ADDRESS
+}
+
+/// The HTTP `Origin`s a websocket interface accepts handshakes from.
+///
+/// Browsers always send an `Origin` header with a websocket handshake,
+/// so this stops web pages served from other origins from connecting.
+/// Handshakes without an `Origin` header, which come from clients that
+/// aren't browsers, are always accepted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowedOrigins {
+    /// Only accept handshakes from pages served by this machine, i.e. from
+    /// origins whose host is `localhost` or a loopback address.
+    Localhost,
+    /// Accept handshakes from any origin.
+    Any,
+    /// Only accept handshakes from these origins,
+    /// e.g. `http://localhost:8888`.
+    /// If the set is empty no browser can connect.
+    Origins(HashSet<String>),
+}
+
+impl Default for AllowedOrigins {
+    fn default() -> Self {
+        AllowedOrigins::Localhost
+    }
+}
+
+impl AllowedOrigins {
+    /// Whether a handshake from this origin is accepted.
+    pub fn allows(&self, origin: &str) -> bool {
+        match self {
+            AllowedOrigins::Localhost => origin_host(origin).map_or(false, |host| {
+                host == "localhost"
+                    || host.ends_with(".localhost")
+                    || host.parse::<IpAddr>().map_or(false, |ip| ip.is_loopback())
+            }),
+            AllowedOrigins::Any => true,
+            AllowedOrigins::Origins(origins) => origins.contains(origin),
+        }
+    }
+}
+
+/// The host of a serialized origin such as `http://[::1]:8888`,
+/// without the brackets around an IPv6 address.
+fn origin_host(origin: &str) -> Option<&str> {
+    let (_scheme, authority) = origin.split_once("://")?;
+    match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split_once(']').map(|(host, _port)| host),
+        None => authority.split(':').next(),
+    }
+}
 
 /// Information neeeded to spawn an admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means the interface will be exposed.
-    /// Currently the only option is a local websocket running on a configurable port.
+    /// Either a websocket running on a configurable address and port,
+    /// or a unix domain socket at a configurable path.
     pub driver: InterfaceDriver,
-    // How long will this interface be accessible between authentications?
-    // TODO: implement once we have authentication
-    // _session_duration_seconds: Option<u32>,
+    /// Whether every connection must authenticate with a token issued by
+    /// [`AdminRequest::IssueAuthenticationToken`] before making requests.
+    /// How long a connection stays authenticated is set by the token's expiry.
+    ///
+    /// Tokens can only be issued over an admin interface, so the first token
+    /// is issued either over an admin interface which doesn't require
+    /// authentication, or over a connection authenticated with the
+    /// conductor's [`admin_bootstrap_token`].
+    ///
+    /// [`AdminRequest::IssueAuthenticationToken`]: crate::AdminRequest::IssueAuthenticationToken
+    /// [`admin_bootstrap_token`]: crate::config::conductor::ConductorConfig::admin_bootstrap_token
+    #[serde(default)]
+    pub require_authentication: bool,
 }
 
 /// Configuration for interfaces, specifying the means by which an interface
@@ -28,14 +102,60 @@ pub enum InterfaceDriver {
     Websocket {
         /// The port on which to establish the WebsocketListener
         port: u16,
+        /// The address to bind the WebsocketListener to.
+        /// Defaults to [`DEFAULT_BIND_ADDRESS`].
+        #[serde(default = "default_bind_address")]
+        bind_address: IpAddr,
+        /// The HTTP `Origin`s which browsers may connect from.
+        /// Defaults to [`AllowedOrigins::Localhost`].
+        #[serde(default)]
+        allowed_origins: AllowedOrigins,
+    },
+    /// An interface implemented via websockets over a unix domain socket.
+    ///
+    /// Access is controlled by the permissions of the socket file, which
+    /// the conductor creates so that only its own user can connect.
+    /// Any stale socket file left at the path is replaced.
+    /// Only available on unix platforms.
+    UnixSocket {
+        /// The path of the socket file to listen on
+        path: PathBuf,
     },
 }
 
 impl InterfaceDriver {
-    /// Get the port for this driver.
-    pub fn port(&self) -> u16 {
+    /// A websocket driver on this port, bound to [`DEFAULT_BIND_ADDRESS`]
+    /// and only allowing origins on this machine.
+    pub fn websocket(port: u16) -> Self {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address: DEFAULT_BIND_ADDRESS,
+            allowed_origins: AllowedOrigins::Localhost,
+        }
+    }
+
+    /// Get the port for this driver, if it listens on one.
+    pub fn port(&self) -> Option<u16> {
         match self {
-            InterfaceDriver::Websocket { port } => *port,
+            InterfaceDriver::Websocket { port, .. } => Some(*port),
+            InterfaceDriver::UnixSocket { .. } => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_origins_default_to_localhost() {
+        let allowed = AllowedOrigins::default();
+        assert!(allowed.allows("http://localhost:8888"));
+        assert!(allowed.allows("http://ui.localhost"));
+        assert!(allowed.allows("http://127.0.0.1"));
+        assert!(allowed.allows("https://[::1]:8888"));
+        assert!(!allowed.allows("http://example.com"));
+        assert!(!allowed.allows("http://localhost.example.com"));
+        assert!(!allowed.allows("null"));
+    }
+}
@@ -1,7 +1,10 @@
 mod admin_interface;
 mod app_interface;
+pub mod authentication;
+pub mod backup;
 pub mod config;
 pub mod signal_subscription;
+pub mod source_chain_archive;
 pub mod state_dump;
 
 pub use admin_interface::*;
@@ -1,7 +1,9 @@
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::Signal;
 use holochain_zome_types::cell::CellId;
-use std::collections::HashMap;
+use holochain_zome_types::prelude::{AppSignal, ZomeName};
+use std::collections::{HashMap, HashSet};
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
@@ -13,6 +15,26 @@ pub struct SignalSubscription {
     filters: SignalFilterSet,
 }
 
+impl SignalSubscription {
+    /// Constructor
+    pub fn new(installed_app_id: InstalledAppId, filters: SignalFilterSet) -> Self {
+        Self {
+            installed_app_id,
+            filters,
+        }
+    }
+
+    /// Accessor
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        &self.installed_app_id
+    }
+
+    /// Accessor
+    pub fn filters(&self) -> &SignalFilterSet {
+        &self.filters
+    }
+}
+
 /// Associate a SignalFilter with each Cell in an App.
 /// The filtering can be interpreted as inclusive or exclusive,
 /// depending on the use case.
@@ -46,11 +68,64 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
+
+    /// Whether a signal should be let through by this filter set.
+    ///
+    /// System signals are not associated with any Cell, so they are checked
+    /// against the filters of every Cell in the set.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        let filters = match self {
+            SignalFilterSet::Include(filters) | SignalFilterSet::Exclude(filters) => filters,
+        };
+        let matched = match signal {
+            Signal::App { cell_id, .. } => filters
+                .get(cell_id)
+                .map(|filter| filter.matches(signal))
+                .unwrap_or(false),
+            Signal::System(_) => filters.values().any(|filter| filter.matches(signal)),
+        };
+        match self {
+            SignalFilterSet::Include(_) => matched,
+            SignalFilterSet::Exclude(_) => !matched,
+        }
+    }
 }
 
-/// Specifies fine-grained filter controls for the signals
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-pub struct SignalFilter;
+/// The two kinds of [`Signal`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalKind {
+    /// Signals emitted by a Cell via `emit_signal`
+    App,
+    /// Signals emitted by Holochain itself
+    System,
+}
+
+impl From<&Signal> for SignalKind {
+    fn from(signal: &Signal) -> Self {
+        match signal {
+            Signal::App { .. } => SignalKind::App,
+            Signal::System(_) => SignalKind::System,
+        }
+    }
+}
+
+/// Specifies fine-grained filter controls for the signals.
+///
+/// A filter matches a signal if the signal meets every criterion which is set.
+/// A filter with no criteria set matches every signal.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct SignalFilter {
+    /// Only match signals of this kind.
+    pub kind: Option<SignalKind>,
+    /// Only match App signals emitted by one of these zomes.
+    /// System signals never match if this is set.
+    pub zome_names: Option<HashSet<ZomeName>>,
+    /// Only match App signals whose payload is a serialized enum with one of
+    /// these variant names, using serde's default (externally tagged)
+    /// representation. System signals never match if this is set.
+    pub discriminants: Option<HashSet<String>>,
+}
 
 impl Default for SignalFilter {
     fn default() -> Self {
@@ -60,8 +135,201 @@ impl Default for SignalFilter {
 }
 
 impl SignalFilter {
-    /// A passthrough filter which filters nothing
+    /// A passthrough filter which matches every signal
     pub fn empty() -> Self {
-        SignalFilter
+        SignalFilter {
+            kind: None,
+            zome_names: None,
+            discriminants: None,
+        }
+    }
+
+    /// Only match signals of this kind
+    pub fn kind(mut self, kind: SignalKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only match App signals emitted by this zome.
+    /// May be called multiple times to match several zomes.
+    pub fn zome_name(mut self, zome_name: impl Into<ZomeName>) -> Self {
+        self.zome_names
+            .get_or_insert_with(HashSet::new)
+            .insert(zome_name.into());
+        self
+    }
+
+    /// Only match App signals with this payload discriminant.
+    /// May be called multiple times to match several discriminants.
+    pub fn discriminant(mut self, discriminant: impl Into<String>) -> Self {
+        self.discriminants
+            .get_or_insert_with(HashSet::new)
+            .insert(discriminant.into());
+        self
+    }
+
+    /// Check whether a signal meets all the criteria of this filter
+    pub fn matches(&self, signal: &Signal) -> bool {
+        if let Some(kind) = self.kind {
+            if kind != SignalKind::from(signal) {
+                return false;
+            }
+        }
+        match signal {
+            Signal::App {
+                zome_name, signal, ..
+            } => {
+                if let Some(zome_names) = &self.zome_names {
+                    if !zome_names.contains(zome_name) {
+                        return false;
+                    }
+                }
+                if let Some(discriminants) = &self.discriminants {
+                    match payload_discriminant(signal) {
+                        Some(d) if discriminants.contains(&d) => (),
+                        _ => return false,
+                    }
+                }
+                true
+            }
+            Signal::System(_) => self.zome_names.is_none() && self.discriminants.is_none(),
+        }
+    }
+}
+
+/// Get the variant name of an App signal payload, if it is a serialized enum.
+fn payload_discriminant(signal: &AppSignal) -> Option<String> {
+    let discriminant: Result<PayloadDiscriminant, _> = signal.clone().into_inner().decode();
+    discriminant.ok().map(|d| d.0)
+}
+
+/// Deserializes only the variant name of an externally tagged enum:
+/// either a bare string for a unit variant, or the key of a single-entry map.
+#[derive(Debug)]
+struct PayloadDiscriminant(String);
+
+impl<'de> serde::Deserialize<'de> for PayloadDiscriminant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = PayloadDiscriminant;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a serialized enum")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(PayloadDiscriminant(v.to_string()))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let variant: String = map
+                    .next_key()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+                let _: serde::de::IgnoredAny = map.next_value()?;
+                if map.next_key::<serde::de::IgnoredAny>()?.is_some() {
+                    return Err(serde::de::Error::invalid_length(2, &self));
+                }
+                Ok(PayloadDiscriminant(variant))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::signal::SystemSignal;
+    use holochain_zome_types::ExternIO;
+
+    #[derive(Debug, serde::Serialize)]
+    enum TestSignal {
+        Ping,
+        Message(String),
+        Update { count: u32 },
+    }
+
+    fn app_signal(cell_id: &CellId, zome_name: &str, payload: TestSignal) -> Signal {
+        Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(payload).unwrap()),
+        }
+    }
+
+    fn cell_id(n: u8) -> CellId {
+        CellId::new(
+            holo_hash::DnaHash::from_raw_36(vec![n; 36]),
+            holo_hash::AgentPubKey::from_raw_36(vec![n; 36]),
+        )
+    }
+
+    #[test]
+    fn filter_matches_zome_kind_and_discriminant() {
+        let cell = cell_id(1);
+        let ping = app_signal(&cell, "a", TestSignal::Ping);
+        let message = app_signal(&cell, "a", TestSignal::Message("hi".into()));
+        let update = app_signal(&cell, "b", TestSignal::Update { count: 1 });
+        let system: Signal = SystemSignal::Test("test".into()).into();
+
+        let all = SignalFilter::empty();
+        assert!([&ping, &message, &update, &system]
+            .iter()
+            .all(|s| all.matches(s)));
+
+        let zome_a = SignalFilter::empty().zome_name("a");
+        assert!(zome_a.matches(&ping));
+        assert!(zome_a.matches(&message));
+        assert!(!zome_a.matches(&update));
+        assert!(!zome_a.matches(&system));
+
+        let system_only = SignalFilter::empty().kind(SignalKind::System);
+        assert!(!system_only.matches(&ping));
+        assert!(system_only.matches(&system));
+
+        let discriminants = SignalFilter::empty()
+            .discriminant("Ping")
+            .discriminant("Update");
+        assert!(discriminants.matches(&ping));
+        assert!(!discriminants.matches(&message));
+        assert!(discriminants.matches(&update));
+        assert!(!discriminants.matches(&system));
+    }
+
+    #[test]
+    fn filter_set_include_and_exclude() {
+        let (cell1, cell2) = (cell_id(1), cell_id(2));
+        let from1 = app_signal(&cell1, "a", TestSignal::Ping);
+        let from2 = app_signal(&cell2, "a", TestSignal::Ping);
+        let system: Signal = SystemSignal::Test("test".into()).into();
+
+        assert!(SignalFilterSet::allow_all().allows(&from1));
+        assert!(SignalFilterSet::allow_all().allows(&system));
+        assert!(!SignalFilterSet::block_all().allows(&from1));
+        assert!(!SignalFilterSet::block_all().allows(&system));
+
+        let include = SignalFilterSet::Include(
+            [(cell1.clone(), SignalFilter::empty().kind(SignalKind::App))]
+                .into_iter()
+                .collect(),
+        );
+        assert!(include.allows(&from1));
+        assert!(!include.allows(&from2));
+        assert!(!include.allows(&system));
+
+        let exclude = SignalFilterSet::Exclude(
+            [(cell1, SignalFilter::empty().zome_name("a"))]
+                .into_iter()
+                .collect(),
+        );
+        assert!(!exclude.allows(&from1));
+        assert!(exclude.allows(&from2));
+        assert!(exclude.allows(&system));
     }
 }
@@ -0,0 +1,107 @@
+//! A portable, versioned archive of a single cell's source chain.
+//!
+//! An archive is created with [`AdminRequest::ExportSourceChain`] and can be
+//! imported into another conductor with [`AdminRequest::ImportSourceChain`].
+//! [`SourceChainArchive::encode`] and [`SourceChainArchive::decode`] convert
+//! it to and from the msgpack bytes which are stored in an archive file.
+//!
+//! [`AdminRequest::ExportSourceChain`]: crate::AdminRequest::ExportSourceChain
+//! [`AdminRequest::ImportSourceChain`]: crate::AdminRequest::ImportSourceChain
+
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+use holochain_zome_types::cell::CellId;
+
+/// The version of the archive format written by this conductor.
+pub const SOURCE_CHAIN_ARCHIVE_VERSION: u32 = 1;
+
+/// The records of a cell's source chain, with everything needed to verify them.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SourceChainArchive {
+    /// The version of the archive format.
+    pub version: u32,
+    /// The cell whose source chain this is.
+    pub cell_id: CellId,
+    /// When the chain was exported.
+    pub exported_at: Timestamp,
+    /// Every record of the chain, in sequence order.
+    pub records: Vec<ArchivedRecord>,
+}
+
+/// A single signed action of an archived source chain and its entry.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ArchivedRecord {
+    /// The action and the author's signature of it.
+    pub signed_action: SignedAction,
+    /// The entry of the action, if any.
+    pub entry: ArchivedEntry,
+}
+
+/// The entry of an [`ArchivedRecord`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum ArchivedEntry {
+    /// The action has no entry, or the entry isn't stored on the exporting conductor.
+    Absent,
+    /// The entry in plain text.
+    Present(Entry),
+    /// A private entry, encrypted so that only a keystore holding the author's
+    /// key can read it. The entry is msgpack encoded before encryption.
+    Encrypted(XSalsa20Poly1305EncryptedData),
+}
+
+/// Just the version of an archive, so it can be checked before decoding the rest.
+#[derive(Debug, serde::Deserialize)]
+struct ArchiveVersion {
+    version: u32,
+}
+
+impl SourceChainArchive {
+    /// Encode the archive to msgpack bytes.
+    pub fn encode(&self) -> Result<Vec<u8>, SerializedBytesError> {
+        holochain_serialized_bytes::encode(self)
+    }
+
+    /// Decode an archive from msgpack bytes, failing if it was written
+    /// in an unsupported version of the format.
+    pub fn decode(bytes: &[u8]) -> Result<Self, SerializedBytesError> {
+        let ArchiveVersion { version } = holochain_serialized_bytes::decode(bytes)?;
+        if version != SOURCE_CHAIN_ARCHIVE_VERSION {
+            return Err(SerializedBytesError::Deserialize(format!(
+                "Unsupported source chain archive version {}, expected {}",
+                version, SOURCE_CHAIN_ARCHIVE_VERSION
+            )));
+        }
+        holochain_serialized_bytes::decode(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(version: u32) -> SourceChainArchive {
+        SourceChainArchive {
+            version,
+            cell_id: CellId::new(
+                DnaHash::from_raw_32(vec![0; 32]),
+                AgentPubKey::from_raw_32(vec![1; 32]),
+            ),
+            exported_at: Timestamp::from_micros(1),
+            records: vec![],
+        }
+    }
+
+    #[test]
+    fn archive_roundtrip() {
+        let original = archive(SOURCE_CHAIN_ARCHIVE_VERSION);
+        let bytes = original.encode().unwrap();
+        assert_eq!(SourceChainArchive::decode(&bytes).unwrap(), original);
+    }
+
+    #[test]
+    fn unknown_archive_version_is_rejected() {
+        let bytes = archive(SOURCE_CHAIN_ARCHIVE_VERSION + 1).encode().unwrap();
+        assert!(SourceChainArchive::decode(&bytes).is_err());
+    }
+}
@@ -240,6 +240,32 @@ impl MetaLairClient {
         }
     }
 
+    /// Get the x25519 public key derived from the same seed as the signing
+    /// key of `agent`, so data can be "box"ed to and from that agent.
+    /// Fails if the agent's seed is not in the keystore.
+    pub fn agent_x25519_pub_key(
+        &self,
+        agent: AgentPubKey,
+    ) -> impl Future<Output = LairResult<X25519PubKey>> + 'static + Send {
+        let (client, esnd) = self.cli();
+        async move {
+            let entries = echk!(esnd, client.list_entries().await);
+            entries
+                .into_iter()
+                .find_map(|lair_entry_info| match lair_entry_info {
+                    LairEntryInfo::Seed { tag: _, seed_info }
+                        if seed_info.ed25519_pub_key.to_vec() == agent.get_raw_32() =>
+                    {
+                        Some(seed_info.x25519_pub_key)
+                    }
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    one_err::OneErr::new(format!("No seed for agent {} in the keystore", agent))
+                })
+        }
+    }
+
     /// Import a shared secret to be indentified by `tag` using box decryption.
     pub fn shared_secret_import(
         &self,
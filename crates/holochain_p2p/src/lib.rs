@@ -94,6 +94,13 @@ pub trait HolochainP2pDnaT {
         op: DhtOp,
     ) -> actor::HolochainP2pResult<()>;
 
+    /// Publish warrants to the agent activity authorities of the offender.
+    async fn publish_warrants(
+        &self,
+        basis_hash: holo_hash::OpBasis,
+        warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()>;
+
     /// Get an entry from the DHT.
     async fn get(
         &self,
@@ -115,6 +122,12 @@ pub trait HolochainP2pDnaT {
         options: actor::GetLinksOptions,
     ) -> actor::HolochainP2pResult<Vec<WireLinkOps>>;
 
+    /// Count links on the DHT.
+    async fn count_links(
+        &self,
+        query: WireLinkQuery,
+    ) -> actor::HolochainP2pResult<Vec<CountLinksResponse>>;
+
     /// Get agent activity from the DHT.
     async fn get_agent_activity(
         &self,
@@ -288,6 +301,17 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    /// Publish warrants to the agent activity authorities of the offender.
+    async fn publish_warrants(
+        &self,
+        basis_hash: holo_hash::OpBasis,
+        warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .publish_warrants((*self.dna_hash).clone(), basis_hash, warrants)
+            .await
+    }
+
     /// Get [`DhtOp::StoreRecord`] or [`DhtOp::StoreEntry`] from the DHT.
     async fn get(
         &self,
@@ -322,6 +346,16 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    /// Count links on the DHT.
+    async fn count_links(
+        &self,
+        query: WireLinkQuery,
+    ) -> actor::HolochainP2pResult<Vec<CountLinksResponse>> {
+        self.sender
+            .count_links((*self.dna_hash).clone(), query)
+            .await
+    }
+
     /// Get agent activity from the DHT.
     async fn get_agent_activity(
         &self,
@@ -199,6 +199,18 @@ impl WrapEvtSender {
         )
     }
 
+    fn count_links(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        query: WireLinkQuery,
+    ) -> impl Future<Output = HolochainP2pResult<CountLinksResponse>> + 'static + Send {
+        timing_trace!(
+            { self.0.count_links(dna_hash, to_agent, query) },
+            "(hp2p:handle) count_links",
+        )
+    }
+
     fn get_agent_activity(
         &self,
         dna_hash: DnaHash,
@@ -459,6 +471,24 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming count_links request from a remote node
+    fn handle_incoming_count_links(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        query: WireLinkQuery,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender.count_links(dna_hash, to_agent, query).await;
+            res.and_then(|r| Ok(SerializedBytes::try_from(r)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+                .map(|res| UnsafeBytes::from(res).into())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming get_links request from a remote node
     fn handle_incoming_get_agent_activity(
         &mut self,
@@ -526,6 +556,21 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving incoming warrants from a remote node
+    fn handle_incoming_publish_warrants(
+        &mut self,
+        dna_hash: DnaHash,
+        warrants: Vec<SignedWarrant>,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            evt_sender.publish_warrants(dna_hash, warrants).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming validation receipt from a remote node
     fn handle_incoming_validation_receipt(
         &mut self,
@@ -724,6 +769,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::GetLinks { link_key, options } => {
                 self.handle_incoming_get_links(space, to_agent, link_key, options)
             }
+            crate::wire::WireMessage::CountLinks { query } => {
+                self.handle_incoming_count_links(space, to_agent, query)
+            }
             crate::wire::WireMessage::GetAgentActivity {
                 agent,
                 query,
@@ -743,6 +791,12 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 )
                 .into())
             }
+            crate::wire::WireMessage::PublishWarrants { .. } => {
+                Err(HolochainP2pError::invalid_p2p_message(
+                    "invalid: warrants are broadcast, not requests".to_string(),
+                )
+                .into())
+            }
         }
     }
 
@@ -765,6 +819,7 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::Get { .. }
             | crate::wire::WireMessage::GetMeta { .. }
             | crate::wire::WireMessage::GetLinks { .. }
+            | crate::wire::WireMessage::CountLinks { .. }
             | crate::wire::WireMessage::GetAgentActivity { .. }
             | crate::wire::WireMessage::MustGetAgentActivity { .. }
             | crate::wire::WireMessage::ValidationReceipt { .. } => {
@@ -830,6 +885,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::PublishCountersign { flag, op } => {
                 self.handle_incoming_publish(space, false, flag, vec![op])
             }
+            crate::wire::WireMessage::PublishWarrants { warrants } => {
+                self.handle_incoming_publish_warrants(space, warrants)
+            }
         }
     }
 
@@ -1151,6 +1209,30 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self, warrants), level = "trace")]
+    fn handle_publish_warrants(
+        &mut self,
+        dna_hash: DnaHash,
+        basis_hash: holo_hash::OpBasis,
+        warrants: Vec<SignedWarrant>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let basis = basis_hash.to_kitsune();
+        let timeout = self.tuning_params.implicit_timeout();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            let payload = crate::wire::WireMessage::publish_warrants(warrants).encode()?;
+
+            kitsune_p2p
+                .broadcast(space, basis, timeout, BroadcastData::User(payload))
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self, dna_hash, dht_hash, options), level = "trace")]
     fn handle_get(
         &mut self,
@@ -1252,6 +1334,37 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_count_links(
+        &mut self,
+        dna_hash: DnaHash,
+        query: WireLinkQuery,
+    ) -> HolochainP2pHandlerResult<Vec<CountLinksResponse>> {
+        let space = dna_hash.into_kitsune();
+        let basis = query.base.to_kitsune();
+
+        let payload = crate::wire::WireMessage::count_links(query).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        let tuning_params = self.tuning_params.clone();
+        Ok(async move {
+            // Unlike get_links this asks as many authorities as the tuning params
+            // allow, as the responses are cheap and are merged by the caller.
+            let input = kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            let result = kitsune_p2p.rpc_multi(input).await?;
+
+            let mut out = Vec::new();
+            for item in result {
+                let kitsune_p2p::actor::RpcMultiResponse { response, .. } = item;
+                out.push(SerializedBytes::from(UnsafeBytes::from(response)).try_into()?);
+            }
+
+            Ok(out)
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_get_agent_activity(
         &mut self,
@@ -1436,4 +1549,17 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .boxed()
         .into())
     }
+
+    fn handle_block(
+        &mut self,
+        dna_hash: DnaHash,
+        agent: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<bool> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent.into_kitsune();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move { Ok(kitsune_p2p.block(space, agent).await?) }
+            .boxed()
+            .into())
+    }
 }
@@ -312,6 +312,13 @@ ghost_actor::ghost_chan! {
             op: DhtOp,
         ) -> ();
 
+        /// Publish warrants to the agent activity authorities of the offender.
+        fn publish_warrants(
+            dna_hash: DnaHash,
+            basis_hash: holo_hash::OpBasis,
+            warrants: Vec<SignedWarrant>,
+        ) -> ();
+
         /// Get an entry from the DHT.
         fn get(
             dna_hash: DnaHash,
@@ -333,6 +340,12 @@ ghost_actor::ghost_chan! {
             options: GetLinksOptions,
         ) -> Vec<WireLinkOps>;
 
+        /// Count links on the DHT.
+        fn count_links(
+            dna_hash: DnaHash,
+            query: WireLinkQuery,
+        ) -> Vec<CountLinksResponse>;
+
         /// Get agent activity from the DHT.
         fn get_agent_activity(
             dna_hash: DnaHash,
@@ -371,6 +384,13 @@ ghost_actor::ghost_chan! {
 
         /// Get struct for diagnostic data
         fn get_diagnostics(dna_hash: DnaHash) -> KitsuneDiagnostics;
+
+        /// Stop communicating with the nodes hosting an agent, found from the
+        /// agent's info in a DNA's network. The nodes at the agent's urls are
+        /// refused all communication, in every network.
+        /// Returns false, without blocking anything, if there is no stored
+        /// info for the agent yet.
+        fn block(dna_hash: DnaHash, agent: AgentPubKey) -> bool;
     }
 }
 
@@ -136,6 +136,8 @@ pub enum HolochainP2pMockMsg {
 pub enum GossipProtocol {
     /// Sharded gossip wire protocol.
     Sharded(kitsune_p2p::gossip::sharded_gossip::ShardedGossipWire),
+    /// Full sync gossip wire protocol.
+    FullSync(kitsune_p2p::gossip::full_sync::FullSyncGossipWire),
 }
 
 /// This type allows a response to be sent to
@@ -344,13 +346,13 @@ impl HolochainP2pMockMsg {
                 | crate::wire::WireMessage::Get { .. }
                 | crate::wire::WireMessage::GetMeta { .. }
                 | crate::wire::WireMessage::GetLinks { .. }
+                | crate::wire::WireMessage::CountLinks { .. }
                 | crate::wire::WireMessage::GetAgentActivity { .. }
                 | crate::wire::WireMessage::PublishCountersign { .. }
                 | crate::wire::WireMessage::MustGetAgentActivity { .. } => next_msg_id().as_req(),
 
-                crate::wire::WireMessage::CountersigningSessionNegotiation { .. } => {
-                    MsgId::new_notify()
-                }
+                crate::wire::WireMessage::CountersigningSessionNegotiation { .. }
+                | crate::wire::WireMessage::PublishWarrants { .. } => MsgId::new_notify(),
             },
             HolochainP2pMockMsg::PeerGet(_) | HolochainP2pMockMsg::PeerQuery(_) => {
                 next_msg_id().as_req()
@@ -373,9 +375,11 @@ impl HolochainP2pMockMsg {
                     | crate::wire::WireMessage::Get { .. }
                     | crate::wire::WireMessage::GetMeta { .. }
                     | crate::wire::WireMessage::GetLinks { .. }
+                    | crate::wire::WireMessage::CountLinks { .. }
                     | crate::wire::WireMessage::GetAgentActivity { .. }
                     | crate::wire::WireMessage::MustGetAgentActivity { .. } => true,
                     crate::wire::WireMessage::PublishCountersign { .. }
+                    | crate::wire::WireMessage::PublishWarrants { .. }
                     | crate::wire::WireMessage::CountersigningSessionNegotiation { .. } => false,
                 };
                 let to_agent = to_agent.to_kitsune();
@@ -409,6 +413,7 @@ impl HolochainP2pMockMsg {
                 let space = dna.to_kitsune();
                 let data = match gossip {
                     GossipProtocol::Sharded(gossip) => gossip.encode_vec().unwrap().into(),
+                    GossipProtocol::FullSync(gossip) => gossip.encode_vec().unwrap().into(),
                 };
                 kwire::Wire::Gossip(kwire::Gossip {
                     space,
@@ -488,6 +493,13 @@ impl HolochainP2pMockMsg {
                             ShardedGossipWire::decode_ref(data.as_ref()).unwrap().1,
                         )
                     }
+                    GossipModuleType::FullSync => GossipProtocol::FullSync(
+                        kitsune_p2p::gossip::full_sync::FullSyncGossipWire::decode_ref(
+                            data.as_ref(),
+                        )
+                        .unwrap()
+                        .1,
+                    ),
                 };
                 let dna = holo_hash::DnaHash::from_kitsune(&space);
                 HolochainP2pMockMsg::Gossip {
@@ -64,6 +64,9 @@ pub enum WireMessage {
         link_key: WireLinkKey,
         options: event::GetLinksOptions,
     },
+    CountLinks {
+        query: WireLinkQuery,
+    },
     GetAgentActivity {
         agent: AgentPubKey,
         query: ChainQueryFilter,
@@ -80,6 +83,9 @@ pub enum WireMessage {
         flag: bool,
         op: DhtOp,
     },
+    PublishWarrants {
+        warrants: Vec<SignedWarrant>,
+    },
 }
 
 #[allow(missing_docs)]
@@ -96,6 +102,10 @@ impl WireMessage {
         Self::PublishCountersign { flag, op }
     }
 
+    pub fn publish_warrants(warrants: Vec<SignedWarrant>) -> WireMessage {
+        Self::PublishWarrants { warrants }
+    }
+
     /// For an outgoing remote call.
     #[allow(clippy::too_many_arguments)]
     pub fn call_remote(
@@ -166,6 +176,10 @@ impl WireMessage {
         Self::GetLinks { link_key, options }
     }
 
+    pub fn count_links(query: WireLinkQuery) -> WireMessage {
+        Self::CountLinks { query }
+    }
+
     pub fn get_agent_activity(
         agent: AgentPubKey,
         query: ChainQueryFilter,
@@ -0,0 +1,219 @@
+//! Online, consistent copies of the SQLite database files making up a
+//! conductor's environment.
+//!
+//! Backups are made with SQLite's [online backup API](https://www.sqlite.org/backup.html),
+//! so a database can be copied while the conductor is still reading from and
+//! writing to it. Each database is copied in a single step, which means the
+//! copy reflects one consistent snapshot of the source.
+//!
+//! To copy several databases with as little time between their snapshots as
+//! possible, [`DatabaseSnapshots`] opens a read transaction on every one of
+//! them before copying any.
+
+use crate::conn::initialize_connection;
+use crate::conn::DbSyncLevel;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
+use rusqlite::Connection;
+use rusqlite::OpenFlags;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// The file extension used by every database file, see [`crate::db::DbKindT::filename`].
+pub const DATABASE_FILE_EXTENSION: &str = "sqlite3";
+
+/// Open an existing database file, without creating it if it is missing.
+pub fn open_database_file(path: &Path) -> DatabaseResult<Connection> {
+    if !path.is_file() {
+        return Err(DatabaseError::DatabaseMissing(path.to_owned()));
+    }
+    let mut conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    initialize_connection(&mut conn, DbSyncLevel::default())?;
+    Ok(conn)
+}
+
+/// Copy the database at `source` to a new database file at `destination`,
+/// creating any missing parent directories.
+///
+/// The source database may be in use by other connections while the backup
+/// is running.
+pub fn backup_database(source: &Path, destination: &Path) -> DatabaseResult<()> {
+    copy_database(&open_database_file(source)?, destination)
+}
+
+/// Read transactions held open on a set of databases, so writes made after
+/// the snapshots were taken don't show up in their copies.
+///
+/// Each snapshot is consistent on its own, but they are taken one after
+/// another, so they don't show all the databases at the same moment.
+/// Databases are in WAL mode, so holding the snapshots doesn't block writers.
+pub struct DatabaseSnapshots {
+    snapshots: Vec<(PathBuf, Connection)>,
+}
+
+impl DatabaseSnapshots {
+    /// Open a read transaction on each of the `databases` below `root`,
+    /// all before any of them is copied.
+    pub fn take(root: &Path, databases: &[PathBuf]) -> DatabaseResult<Self> {
+        let snapshots = databases
+            .iter()
+            .map(|database| {
+                let conn = open_database_file(&root.join(database))?;
+                // A deferred transaction only takes its snapshot on the first read.
+                conn.execute_batch("BEGIN DEFERRED")?;
+                conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(()))?;
+                Ok((database.clone(), conn))
+            })
+            .collect::<DatabaseResult<_>>()?;
+        Ok(Self { snapshots })
+    }
+
+    /// Copy every snapshot to the same relative path below `destination`.
+    pub fn backup_to(&self, destination: &Path) -> DatabaseResult<()> {
+        for (database, conn) in self.snapshots.iter() {
+            copy_database(conn, &destination.join(database))?;
+        }
+        Ok(())
+    }
+}
+
+/// Copy the database open on `src`, within its current transaction if
+/// it has one, to a new database file at `destination`.
+fn copy_database(src: &Connection, destination: &Path) -> DatabaseResult<()> {
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut dst = Connection::open(destination)?;
+    initialize_connection(&mut dst, DbSyncLevel::default())?;
+    // Copying every page in one step holds a read lock on the source for the
+    // duration of the copy, which guarantees the result is a consistent snapshot.
+    rusqlite::backup::Backup::new(src, &mut dst)?.run_to_completion(
+        -1,
+        std::time::Duration::ZERO,
+        None,
+    )?;
+    Ok(())
+}
+
+/// Run SQLite's integrity check on the database file at `path`.
+pub fn check_database_integrity(path: &Path) -> DatabaseResult<()> {
+    let conn = open_database_file(path)?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let problems = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    if problems.len() == 1 && problems[0] == "ok" {
+        Ok(())
+    } else {
+        Err(DatabaseError::IntegrityCheckFailed(
+            path.to_owned(),
+            problems.join("; "),
+        ))
+    }
+}
+
+/// Find every database file below `root`.
+///
+/// The returned paths are relative to `root` and sorted.
+pub fn find_database_files(root: &Path) -> DatabaseResult<Vec<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, found: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                walk(root, &path, found)?;
+            } else if path.extension().and_then(|e| e.to_str()) == Some(DATABASE_FILE_EXTENSION) {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    found.push(relative.to_owned());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut found = Vec::new();
+    if root.is_dir() {
+        walk(root, root, &mut found)?;
+    }
+    found.sort();
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbKindT;
+    use crate::db::DbKindWasm;
+    use crate::db::DbWrite;
+    use tempfile::TempDir;
+
+    #[test]
+    fn backup_copies_a_live_database() {
+        let src_dir = TempDir::new().unwrap();
+        let dst_dir = TempDir::new().unwrap();
+        let db = DbWrite::test(src_dir.path(), DbKindWasm).unwrap();
+        db.test_commit(|txn| {
+            txn.execute(
+                "INSERT INTO Wasm (hash, blob) VALUES (?, ?)",
+                rusqlite::params![vec![1u8; 39], vec![2u8; 8]],
+            )
+            .unwrap();
+        });
+
+        let files = find_database_files(src_dir.path()).unwrap();
+        assert_eq!(files, vec![DbKindWasm.filename()]);
+
+        // The source connection pool is still open while the backup is taken.
+        let destination = dst_dir.path().join(&files[0]);
+        backup_database(&src_dir.path().join(&files[0]), &destination).unwrap();
+        check_database_integrity(&destination).unwrap();
+
+        let count: usize = open_database_file(&destination)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM Wasm", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn snapshots_ignore_later_writes() {
+        let src_dir = TempDir::new().unwrap();
+        let dst_dir = TempDir::new().unwrap();
+        let db = DbWrite::test(src_dir.path(), DbKindWasm).unwrap();
+        let insert = |byte: u8| {
+            db.test_commit(|txn| {
+                txn.execute(
+                    "INSERT INTO Wasm (hash, blob) VALUES (?, ?)",
+                    rusqlite::params![vec![byte; 39], vec![2u8; 8]],
+                )
+                .unwrap();
+            })
+        };
+        insert(1);
+
+        let files = find_database_files(src_dir.path()).unwrap();
+        let snapshots = DatabaseSnapshots::take(src_dir.path(), &files).unwrap();
+        insert(2);
+        snapshots.backup_to(dst_dir.path()).unwrap();
+
+        let count: usize = open_database_file(&dst_dir.path().join(&files[0]))
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM Wasm", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn integrity_check_rejects_garbage() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("garbage.sqlite3");
+        std::fs::write(&path, vec![7u8; 4096]).unwrap();
+        assert!(check_database_integrity(&path).is_err());
+        assert!(matches!(
+            check_database_integrity(&dir.path().join("missing.sqlite3")),
+            Err(DatabaseError::DatabaseMissing(_))
+        ));
+    }
+}
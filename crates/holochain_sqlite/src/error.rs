@@ -54,6 +54,25 @@ pub enum DatabaseError {
     #[error("transparent")]
     FailedToJoinBlocking(#[from] tokio::task::JoinError),
 
+    #[error("The {db_kind} database is at schema version {version}, but only versions up to {latest} are known. It must be migrated back by a newer version of Holochain first.")]
+    SchemaTooNew {
+        db_kind: String,
+        version: usize,
+        latest: usize,
+    },
+
+    #[error("Unknown schema version {version}, only versions up to {latest} are known")]
+    UnknownSchemaVersion { version: usize, latest: usize },
+
+    #[error("Schema migration {0} can't be undone")]
+    IrreversibleMigration(usize),
+
+    #[error("Schema migration failed verification: {0}")]
+    MigrationVerificationFailed(String),
+
+    #[error("Database {0} failed its integrity check: {1}")]
+    IntegrityCheckFailed(PathBuf, String),
+
     #[error(transparent)]
     Timestamp(TimestampError),
 
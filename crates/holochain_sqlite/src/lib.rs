@@ -4,6 +4,7 @@
 //!
 //! See [this hackmd](https://holo.hackmd.io/@holochain/SkuVLpqEL) for a diagram explaining the relationships between these building blocks and the higher abstractions
 
+pub mod backup;
 pub mod conn;
 pub mod db;
 pub mod error;
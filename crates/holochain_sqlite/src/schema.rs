@@ -1,15 +1,28 @@
 use once_cell::sync::Lazy;
 use rusqlite::Connection;
+use rusqlite::Transaction;
 
 use crate::db::DbKind;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
 use crate::sql::*;
 
+#[cfg(test)]
+mod tests;
+
 pub static SCHEMA_CELL: Lazy<Schema> = Lazy::new(|| {
     let migration_0 = Migration::initial(sql_cell::SCHEMA);
+    let migration_1 = Migration::new(
+        sql_cell::migrations::WARRANT_UP,
+        Some(sql_cell::migrations::WARRANT_DOWN),
+    );
+    let migration_2 = Migration::new(
+        sql_cell::migrations::FIRST_APP_VALIDATION_ATTEMPT_UP,
+        Some(sql_cell::migrations::FIRST_APP_VALIDATION_ATTEMPT_DOWN),
+    );
 
     Schema {
-        current_index: 0,
-        migrations: vec![migration_0],
+        migrations: vec![migration_0, migration_1, migration_2],
     }
 });
 
@@ -17,7 +30,6 @@ pub static SCHEMA_CONDUCTOR: Lazy<Schema> = Lazy::new(|| {
     let migration_0 = Migration::initial(sql_conductor::SCHEMA);
 
     Schema {
-        current_index: 0,
         migrations: vec![migration_0],
     }
 });
@@ -26,7 +38,6 @@ pub static SCHEMA_WASM: Lazy<Schema> = Lazy::new(|| {
     let migration_0 = Migration::initial(sql_wasm::SCHEMA);
 
     Schema {
-        current_index: 0,
         migrations: vec![migration_0],
     }
 });
@@ -35,7 +46,6 @@ pub static SCHEMA_P2P_STATE: Lazy<Schema> = Lazy::new(|| {
     let migration_0 = Migration::initial(sql_p2p_agent_store::SCHEMA);
 
     Schema {
-        current_index: 0,
         migrations: vec![migration_0],
     }
 });
@@ -44,94 +54,207 @@ pub static SCHEMA_P2P_METRICS: Lazy<Schema> = Lazy::new(|| {
     let migration_0 = Migration::initial(sql_p2p_metrics::SCHEMA);
 
     Schema {
-        current_index: 0,
         migrations: vec![migration_0],
     }
 });
 
+/// The ordered list of migrations for one kind of database.
+///
+/// The first migration is the initial schema, and each following migration
+/// changes the schema produced by the ones before it. The version of a
+/// database is the number of migrations which have been applied to it,
+/// and is stored in the `user_version` pragma.
+///
+/// To change a schema, append a new [`Migration`] to its list.
+/// Never edit a migration which has already been released.
 pub struct Schema {
-    current_index: usize,
     migrations: Vec<Migration>,
 }
 
+/// What was done, or would be done, by a call to [`Schema::migrate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The version of the database before migrating.
+    pub from: usize,
+    /// The version of the database after migrating.
+    pub to: usize,
+    /// Whether the migration was rolled back rather than committed.
+    pub dry_run: bool,
+}
+
 impl Schema {
+    /// Construct a schema from its ordered migrations,
+    /// the first of which must be the initial schema.
+    pub fn new(migrations: Vec<Migration>) -> Self {
+        assert!(
+            !migrations.is_empty(),
+            "a schema needs at least an initial migration"
+        );
+        Self { migrations }
+    }
+
+    /// The version a database is at once all migrations have been applied.
+    pub fn latest_version(&self) -> usize {
+        self.migrations.len()
+    }
+
     /// Determine if any database migrations need to run, and run them if so.
-    /// The decision is based on the difference between this Schema's
-    /// current_index and the user_version pragma value in the database itself.
-    /// NB: The current_index is 0-based, and the user_version is 1-based.
-    pub fn initialize(
-        &self,
-        conn: &mut Connection,
-        db_kind: Option<DbKind>,
-    ) -> rusqlite::Result<()> {
-        let user_version: u16 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    /// The decision is based on the difference between the number of
+    /// migrations in this Schema and the user_version pragma value in the
+    /// database itself.
+    ///
+    /// A database which is newer than this schema is an error, since only
+    /// the version of Holochain which wrote it knows how to migrate it back.
+    pub fn initialize(&self, conn: &mut Connection, db_kind: Option<DbKind>) -> DatabaseResult<()> {
+        let version = user_version(conn)?;
         let db_kind = db_kind
             .as_ref()
             .map(ToString::to_string)
             .unwrap_or_else(|| "<no name>".to_string());
 
-        if user_version == 0 {
-            // database just needs to be created / initialized
-            self.migrations[self.current_index].initialize(conn)?;
-            tracing::info!("database initialized: {}", db_kind);
-            return Ok(());
-        } else {
-            let current_index = user_version as usize - 1;
-            match current_index.cmp(&self.current_index) {
-                std::cmp::Ordering::Less => {
-                    // run forward migrations
-                    for v in current_index..self.current_index + 1 {
-                        self.migrations[v].run(conn)?;
-                    }
-                    // set the DB user_version so that next time we don't run
-                    // the same migration
-                    let new_user_version = (self.current_index + 1) as u16;
-                    conn.pragma_update(None, "user_version", &new_user_version)?;
+        match version.cmp(&self.latest_version()) {
+            std::cmp::Ordering::Less => {
+                // Databases created before migrations existed are at version 0
+                // but already have the initial schema. It only creates what is
+                // missing, so it is safe to run again for them.
+                self.migrate(conn, self.latest_version(), false)?;
+                if version == 0 {
+                    tracing::info!("database initialized: {}", db_kind);
+                } else {
                     tracing::info!(
                         "database forward migrated: {} from {} to {}",
                         db_kind,
-                        current_index,
-                        self.current_index
-                    );
-                }
-                std::cmp::Ordering::Equal => {
-                    tracing::debug!(
-                        "database needed no migration or initialization, good to go: {}",
-                        db_kind
+                        version,
+                        self.latest_version()
                     );
                 }
-                std::cmp::Ordering::Greater => {
-                    unimplemented!("backward migrations unimplemented");
-                }
+            }
+            std::cmp::Ordering::Equal => {
+                tracing::debug!(
+                    "database needed no migration or initialization, good to go: {}",
+                    db_kind
+                );
+            }
+            std::cmp::Ordering::Greater => {
+                return Err(DatabaseError::SchemaTooNew {
+                    db_kind,
+                    version,
+                    latest: self.latest_version(),
+                });
             }
         }
 
         Ok(())
     }
+
+    /// Migrate a database forward or backward to the target version.
+    ///
+    /// All steps are applied in a single transaction, so either every step
+    /// succeeds or the database is left as it was. When `dry_run` is set the
+    /// steps are applied and checked, then rolled back.
+    pub fn migrate(
+        &self,
+        conn: &mut Connection,
+        target: usize,
+        dry_run: bool,
+    ) -> DatabaseResult<MigrationReport> {
+        let latest = self.latest_version();
+        if target > latest {
+            return Err(DatabaseError::UnknownSchemaVersion {
+                version: target,
+                latest,
+            });
+        }
+        let txn = conn.transaction()?;
+        let from = user_version(&txn)?;
+        if from > latest {
+            return Err(DatabaseError::UnknownSchemaVersion {
+                version: from,
+                latest,
+            });
+        }
+        if from < target {
+            for migration in &self.migrations[from..target] {
+                migration.forward(&txn)?;
+            }
+        } else {
+            for (index, migration) in self.migrations[target..from].iter().enumerate().rev() {
+                migration.backward(&txn, target + index)?;
+            }
+        }
+        txn.pragma_update(None, "user_version", &(target as u16))?;
+        verify(&txn)?;
+        if dry_run {
+            txn.rollback()?;
+        } else {
+            txn.commit()?;
+        }
+        Ok(MigrationReport {
+            from,
+            to: target,
+            dry_run,
+        })
+    }
 }
 
+/// A single step in a [`Schema`].
 pub struct Migration {
-    schema: Sql,
-    _forward: Sql,
-    _backward: Option<Sql>,
+    forward: Sql,
+    backward: Option<Sql>,
 }
 
 impl Migration {
+    /// The initial schema, which creates everything that doesn't exist yet.
+    /// It can't be undone.
     pub fn initial(schema: &str) -> Self {
         Self {
-            schema: schema.into(),
-            _forward: "".into(),
-            _backward: None,
+            forward: schema.into(),
+            backward: None,
+        }
+    }
+
+    /// A change to the schema, along with the SQL to undo it if it can be undone.
+    pub fn new(forward: &str, backward: Option<&str>) -> Self {
+        Self {
+            forward: forward.into(),
+            backward: backward.map(Into::into),
         }
     }
 
-    pub fn initialize(&self, conn: &mut Connection) -> rusqlite::Result<()> {
-        conn.execute_batch(&self.schema)?;
+    fn forward(&self, txn: &Transaction) -> DatabaseResult<()> {
+        txn.execute_batch(&self.forward)?;
         Ok(())
     }
 
-    pub fn run(&self, _conn: &mut Connection) -> rusqlite::Result<()> {
-        unimplemented!("actual migrations not yet implemented")
+    fn backward(&self, txn: &Transaction, index: usize) -> DatabaseResult<()> {
+        match &self.backward {
+            Some(backward) => {
+                txn.execute_batch(backward)?;
+                Ok(())
+            }
+            None => Err(DatabaseError::IrreversibleMigration(index)),
+        }
+    }
+}
+
+fn user_version(conn: &Connection) -> DatabaseResult<usize> {
+    let version: u16 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    Ok(version as usize)
+}
+
+/// Check the migrated schema didn't leave any dangling references behind.
+fn verify(txn: &Transaction) -> DatabaseResult<()> {
+    let mut stmt = txn.prepare("PRAGMA foreign_key_check")?;
+    let violations = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(DatabaseError::MigrationVerificationFailed(format!(
+            "foreign key violations in tables: {:?}",
+            violations
+        )))
     }
 }
 
@@ -0,0 +1,157 @@
+use super::*;
+
+fn user_version(conn: &Connection) -> usize {
+    super::user_version(conn).unwrap()
+}
+
+fn table_exists(conn: &Connection, name: &str) -> bool {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+        [name],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+fn initial() -> Migration {
+    Migration::initial("CREATE TABLE IF NOT EXISTS Foo (id INTEGER PRIMARY KEY);")
+}
+
+fn add_bar() -> Migration {
+    Migration::new(
+        "CREATE TABLE Bar (id INTEGER PRIMARY KEY);",
+        Some("DROP TABLE Bar;"),
+    )
+}
+
+fn add_foo_name() -> Migration {
+    Migration::new("ALTER TABLE Foo ADD COLUMN name TEXT NULL;", None)
+}
+
+/// A database at version 1, with some data in it.
+fn fixture_v1() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    Schema::new(vec![initial()])
+        .initialize(&mut conn, None)
+        .unwrap();
+    conn.execute("INSERT INTO Foo (id) VALUES (1)", []).unwrap();
+    assert_eq!(user_version(&conn), 1);
+    conn
+}
+
+#[test]
+fn new_databases_are_initialized_to_the_latest_version() {
+    for schema in [
+        &SCHEMA_CELL,
+        &SCHEMA_CONDUCTOR,
+        &SCHEMA_WASM,
+        &SCHEMA_P2P_STATE,
+        &SCHEMA_P2P_METRICS,
+    ] {
+        let mut conn = Connection::open_in_memory().unwrap();
+        schema.initialize(&mut conn, None).unwrap();
+        assert_eq!(user_version(&conn), schema.latest_version());
+        // Initializing again is a no-op
+        schema.initialize(&mut conn, None).unwrap();
+        assert_eq!(user_version(&conn), schema.latest_version());
+    }
+}
+
+#[test]
+fn unversioned_databases_are_initialized() {
+    // Databases created before migrations existed have the initial
+    // schema but no version.
+    let mut conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(sql_cell::SCHEMA).unwrap();
+    assert_eq!(user_version(&conn), 0);
+
+    SCHEMA_CELL.initialize(&mut conn, None).unwrap();
+    assert_eq!(user_version(&conn), SCHEMA_CELL.latest_version());
+    assert!(table_exists(&conn, "DhtOp"));
+}
+
+#[test]
+fn forward_migration_keeps_data() {
+    let mut conn = fixture_v1();
+    let schema = Schema::new(vec![initial(), add_bar(), add_foo_name()]);
+    schema.initialize(&mut conn, None).unwrap();
+
+    assert_eq!(user_version(&conn), 3);
+    assert!(table_exists(&conn, "Bar"));
+    let name: Option<String> = conn
+        .query_row("SELECT name FROM Foo WHERE id = 1", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(name, None);
+}
+
+#[test]
+fn dry_run_leaves_database_unchanged() {
+    let mut conn = fixture_v1();
+    let schema = Schema::new(vec![initial(), add_bar(), add_foo_name()]);
+    let report = schema.migrate(&mut conn, 3, true).unwrap();
+
+    assert_eq!(
+        report,
+        MigrationReport {
+            from: 1,
+            to: 3,
+            dry_run: true
+        }
+    );
+    assert_eq!(user_version(&conn), 1);
+    assert!(!table_exists(&conn, "Bar"));
+}
+
+#[test]
+fn backward_migration() {
+    let mut conn = fixture_v1();
+    let schema = Schema::new(vec![initial(), add_bar(), add_foo_name()]);
+    schema.migrate(&mut conn, 2, false).unwrap();
+    assert!(table_exists(&conn, "Bar"));
+
+    schema.migrate(&mut conn, 1, false).unwrap();
+    assert_eq!(user_version(&conn), 1);
+    assert!(!table_exists(&conn, "Bar"));
+    assert!(table_exists(&conn, "Foo"));
+
+    // Migrations without a backward step can't be undone.
+    schema.migrate(&mut conn, 3, false).unwrap();
+    assert!(matches!(
+        schema.migrate(&mut conn, 1, false),
+        Err(DatabaseError::IrreversibleMigration(2))
+    ));
+    assert_eq!(user_version(&conn), 3);
+    assert!(table_exists(&conn, "Bar"));
+}
+
+#[test]
+fn failed_migration_is_rolled_back() {
+    let mut conn = fixture_v1();
+    let schema = Schema::new(vec![
+        initial(),
+        add_bar(),
+        Migration::new("ALTER TABLE Missing ADD COLUMN name TEXT NULL;", None),
+    ]);
+    assert!(schema.initialize(&mut conn, None).is_err());
+
+    assert_eq!(user_version(&conn), 1);
+    assert!(!table_exists(&conn, "Bar"));
+}
+
+#[test]
+fn newer_databases_are_an_error() {
+    let mut conn = fixture_v1();
+    Schema::new(vec![initial(), add_bar()])
+        .initialize(&mut conn, None)
+        .unwrap();
+
+    assert!(matches!(
+        Schema::new(vec![initial()]).initialize(&mut conn, None),
+        Err(DatabaseError::SchemaTooNew {
+            version: 2,
+            latest: 1,
+            ..
+        })
+    ));
+    assert_eq!(user_version(&conn), 2);
+}
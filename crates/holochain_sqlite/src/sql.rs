@@ -28,6 +28,15 @@ pub mod sql_cell {
 
     pub const FETCH_PUBLISHABLE_OP: &str = include_str!("sql/cell/fetch_publishable_op.sql");
 
+    pub(crate) mod migrations {
+        pub(crate) const WARRANT_UP: &str = include_str!("sql/cell/migrations/warrant_up.sql");
+        pub(crate) const WARRANT_DOWN: &str = include_str!("sql/cell/migrations/warrant_down.sql");
+        pub(crate) const FIRST_APP_VALIDATION_ATTEMPT_UP: &str =
+            include_str!("sql/cell/migrations/first_app_validation_attempt_up.sql");
+        pub(crate) const FIRST_APP_VALIDATION_ATTEMPT_DOWN: &str =
+            include_str!("sql/cell/migrations/first_app_validation_attempt_down.sql");
+    }
+
     pub mod must_get_agent_activity {
         pub const MUST_GET_AGENT_ACTIVITY: &str =
             include_str!("sql/cell/agent_activity/must_get_agent_activity.sql");
@@ -0,0 +1,3 @@
+-- no-sql-format --
+
+ALTER TABLE DhtOp DROP COLUMN first_app_validation_attempt;
@@ -0,0 +1,4 @@
+-- no-sql-format --
+
+-- When app validation first found the op to be missing dependencies.
+ALTER TABLE DhtOp ADD COLUMN first_app_validation_attempt INTEGER NULL;
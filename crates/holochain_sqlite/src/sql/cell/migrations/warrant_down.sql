@@ -0,0 +1,5 @@
+-- no-sql-format --
+
+DROP INDEX IF EXISTS Warrant_verified_idx;
+DROP INDEX IF EXISTS Warrant_offender_idx;
+DROP TABLE IF EXISTS Warrant;
@@ -0,0 +1,18 @@
+-- no-sql-format --
+
+-- Warrants against agents who authored invalid ops.
+-- A warrant received from another agent stays unverified until we have
+-- validated its proof ourselves.
+CREATE TABLE IF NOT EXISTS Warrant (
+    hash              BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    author            BLOB           NOT NULL,
+    offender          BLOB           NOT NULL,
+    timestamp         INTEGER        NOT NULL,
+    verified          INTEGER        NOT NULL DEFAULT 0,      -- BOOLEAN
+    last_publish_time INTEGER        NULL,    -- UNIX TIMESTAMP SECONDS
+    -- When we first failed to validate the proof of a received warrant.
+    first_verify_attempt INTEGER     NULL,
+    blob              BLOB           NOT NULL
+);
+CREATE INDEX IF NOT EXISTS Warrant_offender_idx ON Warrant ( offender );
+CREATE INDEX IF NOT EXISTS Warrant_verified_idx ON Warrant ( verified );
@@ -212,6 +212,113 @@ pub fn insert_validation_receipt(
     Ok(())
 }
 
+/// Insert a [`SignedWarrant`] into the database.
+///
+/// A warrant should only be inserted as `verified` once its proof has been
+/// validated by this conductor. Inserting a verified warrant which is
+/// already stored as unverified marks it as verified.
+pub fn insert_warrant(
+    txn: &mut Transaction,
+    warrant: SignedWarrant,
+    verified: bool,
+) -> StateMutationResult<()> {
+    let author = warrant.warrant.author.clone();
+    let offender = warrant.warrant.offender().clone();
+    let timestamp = warrant.warrant.timestamp;
+    let (hash, bytes) = warrant_hash_and_blob(warrant)?;
+    sql_insert!(txn, Warrant, {
+        "hash": hash,
+        "author": author,
+        "offender": offender,
+        "timestamp": timestamp,
+        "verified": verified,
+        "blob": bytes,
+    })?;
+    if verified {
+        txn.execute(
+            "UPDATE Warrant SET verified = 1 WHERE hash = :hash",
+            named_params! { ":hash": hash },
+        )?;
+    }
+    Ok(())
+}
+
+/// Mark a stored [`SignedWarrant`] as verified.
+pub fn set_warrant_verified(
+    txn: &mut Transaction,
+    warrant: &SignedWarrant,
+) -> StateMutationResult<()> {
+    let (hash, _) = warrant_hash_and_blob(warrant.clone())?;
+    txn.execute(
+        "UPDATE Warrant SET verified = 1 WHERE hash = :hash",
+        named_params! { ":hash": hash },
+    )?;
+    Ok(())
+}
+
+/// Set the last time a [`SignedWarrant`] was published.
+pub fn set_warrant_last_publish_time(
+    txn: &mut Transaction,
+    warrant: &SignedWarrant,
+    unix_epoch: std::time::Duration,
+) -> StateMutationResult<()> {
+    let (hash, _) = warrant_hash_and_blob(warrant.clone())?;
+    txn.execute(
+        "UPDATE Warrant SET last_publish_time = :last_publish_time WHERE hash = :hash",
+        named_params! {
+            ":hash": hash,
+            ":last_publish_time": unix_epoch.as_secs(),
+        },
+    )?;
+    Ok(())
+}
+
+/// Record when the proof of a received [`SignedWarrant`] could first not be
+/// validated, unless that has already been recorded.
+/// Returns the time of that first attempt.
+pub fn set_warrant_first_verify_attempt(
+    txn: &mut Transaction,
+    warrant: &SignedWarrant,
+    time: Timestamp,
+) -> StateMutationResult<Timestamp> {
+    let (hash, _) = warrant_hash_and_blob(warrant.clone())?;
+    txn.execute(
+        "
+        UPDATE Warrant
+        SET first_verify_attempt = IFNULL(first_verify_attempt, :time)
+        WHERE hash = :hash
+        ",
+        named_params! {
+            ":time": time,
+            ":hash": hash,
+        },
+    )?;
+    let first = txn.query_row(
+        "SELECT first_verify_attempt FROM Warrant WHERE hash = :hash",
+        named_params! {
+            ":hash": hash,
+        },
+        |row| row.get(0),
+    )?;
+    Ok(first)
+}
+
+/// Delete a [`SignedWarrant`] from the database.
+pub fn delete_warrant(txn: &mut Transaction, warrant: &SignedWarrant) -> StateMutationResult<()> {
+    let (hash, _) = warrant_hash_and_blob(warrant.clone())?;
+    txn.execute(
+        "DELETE FROM Warrant WHERE hash = :hash",
+        named_params! { ":hash": hash },
+    )?;
+    Ok(())
+}
+
+fn warrant_hash_and_blob(warrant: SignedWarrant) -> StateMutationResult<(Vec<u8>, Vec<u8>)> {
+    let bytes: UnsafeBytes = SerializedBytes::try_from(warrant)?.into();
+    let bytes: Vec<u8> = bytes.into();
+    Ok((blake2b_256(&bytes), bytes))
+}
+
 /// Insert a [`DnaWasm`](holochain_types::prelude::DnaWasm) into the database.
 pub fn insert_wasm(txn: &mut Transaction, wasm: DnaWasmHashed) -> StateMutationResult<()> {
     let (wasm, hash) = wasm.into_inner();
@@ -352,6 +459,65 @@ pub fn set_validation_stage(
     Ok(())
 }
 
+/// Return all [`DhtOp`](holochain_types::dht_op::DhtOp)s which were abandoned
+/// before the given time to app validation, so that they are validated again.
+/// Returns the number of ops which will be retried.
+pub fn retry_abandoned_ops(
+    txn: &mut Transaction,
+    abandoned_before: Timestamp,
+) -> StateMutationResult<usize> {
+    let retried = txn.execute(
+        "
+        UPDATE DhtOp
+        SET
+        validation_status = NULL,
+        validation_stage = :validation_stage,
+        when_integrated = NULL,
+        num_validation_attempts = 0,
+        first_app_validation_attempt = NULL
+        WHERE
+        DhtOp.validation_status = :abandoned
+        AND DhtOp.last_validation_attempt < :abandoned_before
+        ",
+        named_params! {
+            // Awaiting app validation dependencies.
+            ":validation_stage": 2,
+            ":abandoned": ValidationStatus::Abandoned,
+            ":abandoned_before": abandoned_before,
+        },
+    )?;
+    Ok(retried)
+}
+
+/// Record when app validation first found a [`DhtOp`](holochain_types::dht_op::DhtOp)
+/// to be missing dependencies, unless that has already been recorded.
+/// Returns the time of that first attempt.
+pub fn set_first_app_validation_attempt(
+    txn: &mut Transaction,
+    hash: &DhtOpHash,
+    time: Timestamp,
+) -> StateMutationResult<Timestamp> {
+    txn.execute(
+        "
+        UPDATE DhtOp
+        SET first_app_validation_attempt = IFNULL(first_app_validation_attempt, :time)
+        WHERE hash = :hash
+        ",
+        named_params! {
+            ":time": time,
+            ":hash": hash,
+        },
+    )?;
+    let first = txn.query_row(
+        "SELECT first_app_validation_attempt FROM DhtOp WHERE hash = :hash",
+        named_params! {
+            ":hash": hash,
+        },
+        |row| row.get(0),
+    )?;
+    Ok(first)
+}
+
 /// Set when a [`DhtOp`](holochain_types::dht_op::DhtOp) was integrated.
 pub fn set_when_integrated(
     txn: &mut Transaction,
@@ -20,6 +20,7 @@ impl GetEntryDetailsQuery {
 pub struct State {
     actions: HashSet<SignedActionHashed>,
     rejected_actions: HashSet<SignedActionHashed>,
+    abandoned_actions: HashSet<SignedActionHashed>,
     deletes: HashMap<ActionHash, SignedActionHashed>,
     updates: HashSet<SignedActionHashed>,
 }
@@ -96,6 +97,7 @@ impl Query for GetEntryDetailsQuery {
         Ok(State {
             actions: Default::default(),
             rejected_actions: Default::default(),
+            abandoned_actions: Default::default(),
             deletes: Default::default(),
             updates: Default::default(),
         })
@@ -110,7 +112,10 @@ impl Query for GetEntryDetailsQuery {
             Some(ValidationStatus::Rejected) => {
                 state.rejected_actions.insert(shh);
             }
-            _ => (),
+            Some(ValidationStatus::Abandoned) => {
+                state.abandoned_actions.insert(shh);
+            }
+            None => (),
         };
         match shh.action() {
             Action::Create(_) => add_action(&mut state, shh),
@@ -147,6 +152,7 @@ impl Query for GetEntryDetailsQuery {
             .actions
             .iter()
             .chain(state.rejected_actions.iter())
+            .chain(state.abandoned_actions.iter())
             .next();
         match action {
             Some(action) => {
@@ -163,6 +169,7 @@ impl Query for GetEntryDetailsQuery {
                             entry,
                             actions: state.actions.into_iter().collect(),
                             rejected_actions: state.rejected_actions.into_iter().collect(),
+                            abandoned_actions: state.abandoned_actions.into_iter().collect(),
                             deletes: state.deletes.into_iter().map(|(_, v)| v).collect(),
                             updates: state.updates.into_iter().collect(),
                             entry_dht_status,
@@ -183,6 +190,11 @@ fn compute_entry_status(state: &State) -> EntryDhtStatus {
         .count();
     if live_actions > 0 {
         EntryDhtStatus::Live
+    } else if state.actions.is_empty()
+        && state.rejected_actions.is_empty()
+        && !state.abandoned_actions.is_empty()
+    {
+        EntryDhtStatus::Abandoned
     } else {
         EntryDhtStatus::Dead
     }
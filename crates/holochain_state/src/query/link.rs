@@ -17,22 +17,38 @@ pub struct LinksQuery {
     pub base: Arc<AnyLinkableHash>,
     pub type_query: LinkTypeFilter,
     pub tag: Option<String>,
+    pub filter: GetLinksFilter,
     query: String,
 }
 
 impl LinksQuery {
     pub fn new(base: AnyLinkableHash, type_query: LinkTypeFilter, tag: Option<LinkTag>) -> Self {
         let tag = tag.map(|tag| Self::tag_to_hex(&tag));
-        let create_string = Self::create_query_string(&type_query, tag.clone());
-        let delete_string = Self::delete_query_string(&type_query, tag.clone());
+        let filter = GetLinksFilter::default();
+        let create_string = Self::create_query_string(&type_query, tag.clone(), &filter, true);
+        let delete_string = Self::delete_query_string(&type_query, tag.clone(), &filter);
         Self {
             base: Arc::new(base),
             type_query,
             tag,
+            filter,
             query: Self::create_query(create_string, delete_string),
         }
     }
 
+    /// Narrow down and page through the links.
+    ///
+    /// If `count_deleted` is false, links which have been deleted
+    /// don't count towards the limit of the filter.
+    pub fn with_filter(mut self, filter: GetLinksFilter, count_deleted: bool) -> Self {
+        let create_string =
+            Self::create_query_string(&self.type_query, self.tag.clone(), &filter, count_deleted);
+        let delete_string = Self::delete_query_string(&self.type_query, self.tag.clone(), &filter);
+        self.query = Self::create_query(create_string, delete_string);
+        self.filter = filter;
+        self
+    }
+
     pub fn tag_to_hex(tag: &LinkTag) -> String {
         use std::fmt::Write;
         let mut s = String::with_capacity(tag.0.len());
@@ -65,7 +81,12 @@ impl LinksQuery {
             AND DhtOp.when_integrated IS NOT NULL
         "
     }
-    fn create_query_string(type_query: &LinkTypeFilter, tag: Option<String>) -> String {
+    fn create_query_string(
+        type_query: &LinkTypeFilter,
+        tag: Option<String>,
+        filter: &GetLinksFilter,
+        count_deleted: bool,
+    ) -> String {
         let mut s = format!(
             "
             SELECT Action.blob AS action_blob FROM DhtOp
@@ -74,7 +95,29 @@ impl LinksQuery {
             Self::common_query_string()
         );
         s = Self::add_type_query(s, type_query);
-        Self::add_tag(s, tag)
+        s = Self::add_tag(s, tag);
+        s = format!("{} {}", s, Self::filter_to_sql(filter));
+        if filter.limit.is_some() {
+            if !count_deleted {
+                s = format!(
+                    "{}
+                    AND NOT EXISTS (
+                        SELECT 1 FROM DhtOp AS DeleteOp
+                        JOIN Action AS DeleteAction ON DeleteOp.action_hash = DeleteAction.hash
+                        WHERE DeleteOp.type = :delete
+                        AND DeleteAction.create_link_hash = Action.hash
+                        AND DeleteOp.validation_status = :status
+                        AND DeleteOp.when_integrated IS NOT NULL
+                    )",
+                    s
+                );
+            }
+            s = format!(
+                "SELECT action_blob FROM ({} ORDER BY DhtOp.authored_timestamp, Action.hash LIMIT :limit)",
+                s
+            );
+        }
+        s
     }
     fn add_tag(q: String, tag: Option<String>) -> String {
         match tag {
@@ -92,7 +135,11 @@ impl LinksQuery {
     fn add_type_query(q: String, type_query: &LinkTypeFilter) -> String {
         format!("{} {} ", q, type_query.to_sql_statement())
     }
-    fn delete_query_string(type_query: &LinkTypeFilter, tag: Option<String>) -> String {
+    fn delete_query_string(
+        type_query: &LinkTypeFilter,
+        tag: Option<String>,
+        filter: &GetLinksFilter,
+    ) -> String {
         let mut sub_create_query = format!(
             "
             SELECT Action.hash FROM DhtOp
@@ -102,6 +149,7 @@ impl LinksQuery {
         );
         sub_create_query = Self::add_type_query(sub_create_query, type_query);
         sub_create_query = Self::add_tag(sub_create_query, tag);
+        sub_create_query = format!("{} {}", sub_create_query, Self::filter_to_sql(filter));
         let delete_query = format!(
             "
             SELECT Action.blob AS action_blob FROM DhtOp
@@ -119,8 +167,53 @@ impl LinksQuery {
         delete_query
     }
 
+    /// The conditions a [`GetLinksFilter`] puts on the create link ops of a query
+    /// joining the `DhtOp` and `Action` tables. The limit isn't included.
+    pub fn filter_to_sql(filter: &GetLinksFilter) -> String {
+        let mut s = String::new();
+        if filter.author.is_some() {
+            s.push_str(" AND Action.author = :author");
+        }
+        if filter.after.is_some() {
+            s.push_str(" AND DhtOp.authored_timestamp > :after");
+        }
+        if filter.before.is_some() {
+            s.push_str(" AND DhtOp.authored_timestamp < :before");
+        }
+        if filter.cursor.is_some() {
+            s.push_str(
+                " AND (DhtOp.authored_timestamp > :cursor_timestamp
+                OR (DhtOp.authored_timestamp = :cursor_timestamp AND Action.hash > :cursor_hash))",
+            );
+        }
+        s
+    }
+
+    /// The parameters of the conditions from [`Self::filter_to_sql`],
+    /// and of the limit if there is one.
+    pub fn filter_params(filter: &GetLinksFilter) -> Vec<Params> {
+        let mut params: Vec<Params> = Vec::new();
+        if let Some(author) = &filter.author {
+            params.push((":author", author));
+        }
+        if let Some(after) = &filter.after {
+            params.push((":after", after));
+        }
+        if let Some(before) = &filter.before {
+            params.push((":before", before));
+        }
+        if let Some(cursor) = &filter.cursor {
+            params.push((":cursor_timestamp", cursor.timestamp()));
+            params.push((":cursor_hash", cursor.create_link_hash()));
+        }
+        if let Some(limit) = &filter.limit {
+            params.push((":limit", limit));
+        }
+        params
+    }
+
     pub fn params(&self) -> Vec<Params> {
-        {
+        let mut params = {
             named_params! {
                 ":create": DhtOpType::RegisterAddLink,
                 ":delete": DhtOpType::RegisterRemoveLink,
@@ -128,7 +221,9 @@ impl LinksQuery {
                 ":base_hash": self.base,
             }
         }
-        .to_vec()
+        .to_vec();
+        params.extend(Self::filter_params(&self.filter));
+        params
     }
 }
 
@@ -144,6 +239,14 @@ impl GetLinksQuery {
             query: LinksQuery::base(base, dependencies),
         }
     }
+
+    /// Narrow down and page through the links.
+    /// Deleted links don't count towards the limit.
+    pub fn with_filter(self, filter: GetLinksFilter) -> Self {
+        Self {
+            query: self.query.with_filter(filter, false),
+        }
+    }
 }
 
 impl Query for GetLinksQuery {
@@ -173,8 +276,11 @@ impl Query for GetLinksQuery {
         let base_filter = query.base.clone();
         let type_query_filter = query.type_query.clone();
         let tag_filter = query.tag.clone();
+        let links_filter = query.filter.clone();
         let f = move |action: &QueryData<Self>| match action.action() {
             Action::CreateLink(CreateLink {
+                author,
+                timestamp,
                 base_address,
                 tag,
                 zome_index,
@@ -186,6 +292,7 @@ impl Query for GetLinksQuery {
                     && tag_filter
                         .as_ref()
                         .map_or(true, |t| LinksQuery::tag_to_hex(tag).starts_with(&(**t)))
+                    && links_filter.matches(author, *timestamp, action.as_hash())
             }
             Action::DeleteLink(DeleteLink { base_address, .. }) => *base_address == *base_filter,
             _ => false,
@@ -219,7 +326,9 @@ impl Query for GetLinksQuery {
         S: Store,
     {
         let mut links: Self::Output = state.creates.into_iter().map(|(_, v)| v).collect();
-        links.sort_by_key(|l| l.timestamp);
+        self.query
+            .filter
+            .page(&mut links, |link| LinkCursor::from(link));
         Ok(links)
     }
 }
@@ -16,6 +16,14 @@ impl GetLinkDetailsQuery {
             query: LinksQuery::new(base, type_query, tag),
         }
     }
+
+    /// Narrow down and page through the links.
+    /// Deleted links count towards the limit, as they are returned with their deletes.
+    pub fn with_filter(self, filter: GetLinksFilter) -> Self {
+        Self {
+            query: self.query.with_filter(filter, true),
+        }
+    }
 }
 
 impl Query for GetLinkDetailsQuery {
@@ -45,8 +53,11 @@ impl Query for GetLinkDetailsQuery {
         let base_filter = query.base.clone();
         let type_query_filter = query.type_query.clone();
         let tag_filter = query.tag.clone();
+        let links_filter = query.filter.clone();
         let f = move |action: &QueryData<Self>| match action.action() {
             Action::CreateLink(CreateLink {
+                author,
+                timestamp,
                 base_address,
                 tag,
                 zome_index,
@@ -58,6 +69,7 @@ impl Query for GetLinkDetailsQuery {
                     && tag_filter
                         .as_ref()
                         .map_or(true, |t| LinksQuery::tag_to_hex(tag).starts_with(&(**t)))
+                    && links_filter.matches(author, *timestamp, action.as_hash())
             }
             Action::DeleteLink(DeleteLink { base_address, .. }) => *base_address == *base_filter,
             _ => false,
@@ -95,7 +107,7 @@ impl Query for GetLinkDetailsQuery {
     {
         // TODO: This could be done above by using BTMaps but deferring this optimization
         // because it's simpler .
-        // Order by timestamp.
+        // Order by timestamp, then by create link hash.
         let mut r = state
             .into_iter()
             .filter_map(|(_, (create, deletes))| {
@@ -106,7 +118,9 @@ impl Query for GetLinkDetailsQuery {
                 })
             })
             .collect::<Vec<_>>();
-        r.sort_by_key(|l| l.0.action().timestamp());
+        self.query
+            .filter
+            .page(&mut r, |l| LinkCursor::from_create_link(&l.0));
         Ok(r)
     }
 }
@@ -20,6 +20,7 @@ impl GetRecordDetailsQuery {
 pub struct State {
     action: Option<SignedActionHashed>,
     rejected_action: Option<SignedActionHashed>,
+    abandoned_action: Option<SignedActionHashed>,
     deletes: HashSet<SignedActionHashed>,
     updates: HashSet<SignedActionHashed>,
 }
@@ -91,6 +92,7 @@ impl Query for GetRecordDetailsQuery {
         Ok(State {
             action: Default::default(),
             rejected_action: Default::default(),
+            abandoned_action: Default::default(),
             deletes: Default::default(),
             updates: Default::default(),
         })
@@ -107,7 +109,11 @@ impl Query for GetRecordDetailsQuery {
                     Some(ValidationStatus::Rejected) => {
                         state.rejected_action = Some(shh);
                     }
-                    _ => (),
+                    // A valid or rejected status from another source takes precedence.
+                    Some(ValidationStatus::Abandoned) => {
+                        state.abandoned_action.get_or_insert(shh);
+                    }
+                    None => (),
                 }
             }
         } else {
@@ -137,12 +143,16 @@ impl Query for GetRecordDetailsQuery {
         let State {
             action,
             rejected_action,
+            abandoned_action,
             deletes,
             updates,
         } = state;
 
         let (action, validation_status) = match (action, rejected_action) {
-            (None, None) => return Ok(None),
+            (None, None) => match abandoned_action {
+                Some(h) => (h, ValidationStatus::Abandoned),
+                None => return Ok(None),
+            },
             (None, Some(h)) => (h, ValidationStatus::Rejected),
             (Some(h), None) => (h, ValidationStatus::Valid),
             (Some(_), Some(h)) => {
@@ -2,6 +2,7 @@ use super::*;
 use crate::here;
 use crate::prelude::mutations_helpers::insert_valid_integrated_op;
 use crate::prelude::*;
+use crate::query::link_details::GetLinkDetailsQuery;
 use holochain_types::db::DbWrite;
 use holochain_types::record::SignedActionHashedExt;
 use observability;
@@ -100,6 +101,22 @@ impl TestData {
         td
     }
 
+    /// Create the same test data with a new author and timestamp
+    fn with_author_and_timestamp(
+        mut td: Self,
+        author: AgentPubKey,
+        timestamp: holochain_zome_types::Timestamp,
+    ) -> Self {
+        td.link_add.author = author;
+        td.link_add.timestamp = timestamp;
+        let link_add_hash =
+            ActionHashed::from_content_sync(Action::CreateLink(td.link_add.clone())).into_hash();
+        td.link_remove.link_add_address = link_add_hash.clone();
+        td.expected_link.timestamp = timestamp;
+        td.expected_link.create_link_hash = link_add_hash;
+        td
+    }
+
     fn empty<'a>(&'a self, test: &'static str) {
         let val = fresh_reader_test(self.env.clone(), |txn| {
             self.query
@@ -922,3 +939,108 @@ async fn link_type_ranges() {
         here!("individual types"),
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn filtered_links() {
+    observability::test_run().ok();
+    let test_db = test_dht_db();
+    let env: DbWrite<DbKindDht> = test_db.to_db().into();
+
+    let td = fixtures(env.clone(), 1).pop().unwrap();
+    let alice = fixt!(AgentPubKey);
+    let bob = fixt!(AgentPubKey);
+    let mut links = (0..7)
+        .map(|i| {
+            let author = if i % 2 == 0 {
+                alice.clone()
+            } else {
+                bob.clone()
+            };
+            TestData::with_author_and_timestamp(
+                td.clone(),
+                author,
+                holochain_zome_types::Timestamp(i * 10),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut scratch = Scratch::new();
+    for d in &links[..6] {
+        d.add_link();
+    }
+    links[6].add_link_given_scratch(&mut scratch);
+    links[4].delete_link();
+    let links = links;
+
+    let base = td.base_hash.clone();
+    let zome_index = td.zome_index;
+    let get_links = |filter: GetLinksFilter| {
+        let query = GetLinksQuery::base(base.clone().into(), vec![zome_index]).with_filter(filter);
+        fresh_reader_test(env.clone(), |txn| {
+            query.run(DbScratch::new(&[&txn], &scratch)).unwrap()
+        })
+    };
+    let expected = |i: &[usize]| {
+        i.iter()
+            .map(|i| links[*i].expected_link.clone())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        get_links(GetLinksFilter::default()),
+        expected(&[0, 1, 2, 3, 5, 6])
+    );
+    assert_eq!(
+        get_links(GetLinksFilter::default().author(alice.clone())),
+        expected(&[0, 2, 6])
+    );
+    assert_eq!(
+        get_links(
+            GetLinksFilter::default()
+                .after(holochain_zome_types::Timestamp(10))
+                .before(holochain_zome_types::Timestamp(60))
+        ),
+        expected(&[2, 3, 5])
+    );
+
+    // Page through the links, the deleted link doesn't count towards the limit.
+    let mut pages = Vec::new();
+    let mut filter = GetLinksFilter::default().limit(2);
+    loop {
+        let page = get_links(filter.clone());
+        match page.last() {
+            Some(last) => filter = filter.cursor(LinkCursor::from(last)),
+            None => break,
+        }
+        pages.push(page);
+    }
+    assert_eq!(
+        pages,
+        vec![expected(&[0, 1]), expected(&[2, 3]), expected(&[5, 6])]
+    );
+
+    // Deleted links count towards the limit of the link details.
+    let query = GetLinkDetailsQuery::new(
+        base.clone().into(),
+        LinkTypeFilter::single_dep(zome_index),
+        None,
+    )
+    .with_filter(
+        GetLinksFilter::default()
+            .author(alice.clone())
+            .limit(2)
+            .cursor(LinkCursor::from(&links[0].expected_link)),
+    );
+    let details = fresh_reader_test(env.clone(), |txn| {
+        query.run(DbScratch::new(&[&txn], &scratch)).unwrap()
+    });
+    assert_eq!(
+        details
+            .iter()
+            .map(|(create, deletes)| (create.as_hash().clone(), deletes.len()))
+            .collect::<Vec<_>>(),
+        vec![
+            (links[2].expected_link.create_link_hash.clone(), 0),
+            (links[4].expected_link.create_link_hash.clone(), 1),
+        ]
+    );
+}
@@ -102,6 +102,13 @@ impl Scratch {
         })
     }
 
+    pub fn chain_head_action(&self) -> Option<&Action> {
+        self.chain_head
+            .as_ref()
+            .and_then(|(_, i)| self.actions.get(*i))
+            .map(|h| h.action())
+    }
+
     pub fn add_entry(&mut self, entry_hashed: EntryHashed, chain_top_ordering: ChainTopOrdering) {
         self.respect_chain_top_ordering(chain_top_ordering);
         let (entry, hash) = entry_hashed.into_inner();
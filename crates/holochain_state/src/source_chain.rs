@@ -39,6 +39,7 @@ use holochain_zome_types::ActionHashed;
 use holochain_zome_types::ActionType;
 use holochain_zome_types::ActionUnweighed;
 use holochain_zome_types::CapAccess;
+use holochain_zome_types::CapClaim;
 use holochain_zome_types::CapGrant;
 use holochain_zome_types::CapSecret;
 use holochain_zome_types::CellId;
@@ -58,6 +59,7 @@ use holochain_zome_types::Signature;
 use holochain_zome_types::SignedAction;
 use holochain_zome_types::SignedActionHashed;
 use holochain_zome_types::Timestamp;
+use holochain_zome_types::ZomeCallCapGrant;
 
 use crate::chain_lock::is_chain_locked;
 use crate::chain_lock::is_lock_expired;
@@ -72,6 +74,20 @@ pub use error::*;
 
 mod error;
 
+/// Counts the actions by the same author which update or delete `Action`.
+/// An entry is live if this is zero for the action which created it.
+const LIVE_ENTRY_NOT_REFERENCED_SQL: &str = "
+            SELECT COUNT(H_REF.hash)
+            FROM Action AS H_REF
+            JOIN DhtOp AS D_REF ON D_REF.action_hash = H_REF.hash
+            WHERE
+            H_REF.author = :author
+            AND
+            (H_REF.original_action_hash = Action.hash
+            OR
+            H_REF.deletes_action_hash = Action.hash)
+            ";
+
 #[derive(Clone)]
 pub struct SourceChain<AuthorDb = DbWrite<DbKindAuthored>, DhtDb = DbWrite<DbKindDht>> {
     scratch: SyncScratch,
@@ -83,6 +99,8 @@ pub struct SourceChain<AuthorDb = DbWrite<DbKindAuthored>, DhtDb = DbWrite<DbKin
     persisted_seq: u32,
     persisted_head: ActionHash,
     persisted_timestamp: Timestamp,
+    /// Set if the persisted chain head is an [`Action::CloseChain`].
+    persisted_new_dna_hash: Option<DnaHash>,
     public_only: bool,
     zomes_initialized: Arc<AtomicBool>,
 }
@@ -219,6 +237,9 @@ impl SourceChain {
         chain_top_ordering: ChainTopOrdering,
         weight: W,
     ) -> SourceChainResult<ActionHash> {
+        if let Some(new_dna_hash) = self.chain_head_new_dna_hash()? {
+            return Err(SourceChainError::ChainClosed(new_dna_hash));
+        }
         let (prev_action, chain_head_seq, chain_head_timestamp) = self.chain_head()?;
         let action_seq = chain_head_seq + 1;
 
@@ -398,6 +419,11 @@ impl SourceChain {
                         (*self.author).clone(),
                     )
                     .await?;
+                    // The chain may have been closed by the writes that
+                    // moved the head.
+                    if let Some(new_dna_hash) = child_chain.persisted_new_dna_hash.clone() {
+                        return Err(SourceChainError::ChainClosed(new_dna_hash));
+                    }
                     let rebased_actions = rebase_actions_on(
                         &keystore,
                         actions,
@@ -454,10 +480,14 @@ where
     ) -> SourceChainResult<Self> {
         let scratch = Scratch::new().into_sync();
         let author = Arc::new(author);
-        let (persisted_head, persisted_seq, persisted_timestamp) = vault
+        let ((persisted_head, persisted_seq, persisted_timestamp), persisted_new_dna_hash) = vault
             .async_reader({
                 let author = author.clone();
-                move |txn| chain_head_db_nonempty(&txn, author)
+                move |txn| {
+                    let head = chain_head_db_nonempty(&txn, author)?;
+                    let new_dna_hash = closed_chain_db(&txn, &head.0)?;
+                    SourceChainResult::Ok((head, new_dna_hash))
+                }
             })
             .await?;
         Ok(Self {
@@ -470,6 +500,7 @@ where
             persisted_seq,
             persisted_head,
             persisted_timestamp,
+            persisted_new_dna_hash,
             public_only: false,
             zomes_initialized: Arc::new(AtomicBool::new(false)),
         })
@@ -488,19 +519,25 @@ where
     ) -> SourceChainResult<Self> {
         let scratch = Scratch::new().into_sync();
         let author = Arc::new(author);
-        let (persisted_head, persisted_seq, persisted_timestamp) = vault
+        let ((persisted_head, persisted_seq, persisted_timestamp), persisted_new_dna_hash) = vault
             .async_reader({
                 let author = author.clone();
-                move |txn| chain_head_db(&txn, author)
+                move |txn| match chain_head_db(&txn, author)? {
+                    Some(head) => {
+                        let new_dna_hash = closed_chain_db(&txn, &head.0)?;
+                        SourceChainResult::Ok((head, new_dna_hash))
+                    }
+                    None => Ok((
+                        (
+                            ActionHash::from_raw_32(vec![0u8; 32]),
+                            0,
+                            Timestamp::from_micros(0),
+                        ),
+                        None,
+                    )),
+                }
             })
-            .await?
-            .unwrap_or_else(|| {
-                (
-                    ActionHash::from_raw_32(vec![0u8; 32]),
-                    0,
-                    Timestamp::from_micros(0),
-                )
-            });
+            .await?;
         Ok(Self {
             scratch,
             vault,
@@ -511,6 +548,7 @@ where
             persisted_seq,
             persisted_head,
             persisted_timestamp,
+            persisted_new_dna_hash,
             public_only: false,
             zomes_initialized: Arc::new(AtomicBool::new(false)),
         })
@@ -579,6 +617,44 @@ where
         Ok(zomes_initialized)
     }
 
+    /// The hash of the DNA this chain was migrated from,
+    /// if it was opened with an [`Action::OpenChain`].
+    pub async fn prev_dna_hash(&self) -> SourceChainResult<Option<DnaHash>> {
+        let records = self
+            .query(ChainQueryFilter::new().action_type(ActionType::OpenChain))
+            .await?;
+        Ok(records.iter().find_map(|record| match record.action() {
+            Action::OpenChain(open_chain) => Some(open_chain.prev_dna_hash.clone()),
+            _ => None,
+        }))
+    }
+
+    /// The hash of the DNA this chain was migrated to,
+    /// if it has been closed with an [`Action::CloseChain`].
+    /// Nothing more can be written to a closed chain.
+    pub async fn new_dna_hash(&self) -> SourceChainResult<Option<DnaHash>> {
+        let records = self
+            .query(ChainQueryFilter::new().action_type(ActionType::CloseChain))
+            .await?;
+        Ok(records.iter().find_map(|record| match record.action() {
+            Action::CloseChain(close_chain) => Some(close_chain.new_dna_hash.clone()),
+            _ => None,
+        }))
+    }
+
+    /// The hash of the DNA this chain was migrated to, if the current
+    /// chain head is an [`Action::CloseChain`]. Unlike [`Self::new_dna_hash`]
+    /// this does not hit the database.
+    fn chain_head_new_dna_hash(&self) -> SourceChainResult<Option<DnaHash>> {
+        Ok(self
+            .scratch
+            .apply(|scratch| match scratch.chain_head_action() {
+                Some(Action::CloseChain(close_chain)) => Some(close_chain.new_dna_hash.clone()),
+                Some(_) => None,
+                None => self.persisted_new_dna_hash.clone(),
+            })?)
+    }
+
     pub fn set_zomes_initialized(&self, value: bool) {
         self.zomes_initialized.store(value, Ordering::Relaxed);
     }
@@ -635,17 +711,6 @@ where
         let valid_cap_grant = self
             .vault
             .async_reader(move |txn| {
-                let not_referenced_action = "
-            SELECT COUNT(H_REF.hash)
-            FROM Action AS H_REF
-            JOIN DhtOp AS D_REF ON D_REF.action_hash = H_REF.hash
-            WHERE
-            H_REF.author = :author
-            AND
-            (H_REF.original_action_hash = Action.hash
-            OR
-            H_REF.deletes_action_hash = Action.hash)
-            ";
                 let sql = format!(
                     "
                 SELECT DISTINCT Entry.blob
@@ -659,7 +724,7 @@ where
                 AND
                 ({}) = 0
                 ",
-                    not_referenced_action
+                    LIVE_ENTRY_NOT_REFERENCED_SQL
                 );
                 txn.prepare(&sql)?
                     .query_and_then(
@@ -738,6 +803,92 @@ where
         Ok(valid_cap_grant)
     }
 
+    /// All capability grants committed to this source chain which have not
+    /// since been updated or deleted, along with the hash of the action which
+    /// created each of them.
+    pub async fn live_cap_grants(&self) -> SourceChainResult<Vec<(ActionHash, ZomeCallCapGrant)>> {
+        let author = self.author.clone();
+        let grants = self
+            .vault
+            .async_reader(move |txn| {
+                let sql = format!(
+                    "
+                SELECT DISTINCT Action.hash AS action_hash, Entry.blob
+                FROM Entry
+                JOIN Action ON Action.entry_hash = Entry.hash
+                JOIN DhtOp ON Action.hash = DhtOp.action_hash
+                WHERE
+                Action.author = :author
+                AND
+                Entry.access_type IS NOT NULL
+                AND
+                ({}) = 0
+                ORDER BY Action.seq ASC
+                ",
+                    LIVE_ENTRY_NOT_REFERENCED_SQL
+                );
+                txn.prepare(&sql)?
+                    .query_and_then(
+                        named_params! {
+                            ":author": author,
+                        },
+                        |row| {
+                            let action_hash: ActionHash = row.get("action_hash")?;
+                            let entry: Entry = from_blob(row.get("blob")?)?;
+                            Ok((action_hash, entry))
+                        },
+                    )?
+                    .filter_map(|result: StateQueryResult<(ActionHash, Entry)>| match result {
+                        Ok((action_hash, Entry::CapGrant(grant))) => Some(Ok((action_hash, grant))),
+                        Ok(_) => None,
+                        Err(e) => Some(Err(e)),
+                    })
+                    .collect::<StateQueryResult<Vec<_>>>()
+            })
+            .await?;
+        Ok(grants)
+    }
+
+    /// All capability claims committed to this source chain which have not
+    /// since been updated or deleted.
+    pub async fn live_cap_claims(&self) -> SourceChainResult<Vec<CapClaim>> {
+        let author = self.author.clone();
+        let claims = self
+            .vault
+            .async_reader(move |txn| {
+                let sql = format!(
+                    "
+                SELECT DISTINCT Entry.blob
+                FROM Entry
+                JOIN Action ON Action.entry_hash = Entry.hash
+                JOIN DhtOp ON Action.hash = DhtOp.action_hash
+                WHERE
+                Action.author = :author
+                AND
+                Entry.grantor IS NOT NULL
+                AND
+                ({}) = 0
+                ",
+                    LIVE_ENTRY_NOT_REFERENCED_SQL
+                );
+                txn.prepare(&sql)?
+                    .query_and_then(
+                        named_params! {
+                            ":author": author,
+                        },
+                        |row| from_blob(row.get("blob")?),
+                    )?
+                    .filter_map(|result: StateQueryResult<Entry>| match result {
+                        Ok(Entry::CapClaim(claim)) => Some(Ok(claim)),
+                        Ok(_) => None,
+                        Err(e) => Some(Err(e)),
+                    })
+                    .collect::<StateQueryResult<Vec<_>>>()
+            })
+            .await?;
+        Ok(claims)
+    }
+
     /// Query Actions in the source chain.
     /// This returns a Vec rather than an iterator because it is intended to be
     /// used by the `query` host function, which crosses the wasm boundary
@@ -1022,6 +1173,35 @@ pub async fn genesis(
     agent_pubkey: AgentPubKey,
     membrane_proof: Option<MembraneProof>,
     chc: Option<ChcImpl>,
+) -> SourceChainResult<()> {
+    genesis_with_open_chain(
+        authored,
+        dht_db,
+        dht_db_cache,
+        keystore,
+        dna_hash,
+        agent_pubkey,
+        membrane_proof,
+        chc,
+        None,
+    )
+    .await
+}
+
+/// Run genesis, and if `prev_dna_hash` is given also open the chain with an
+/// [`Action::OpenChain`] pointing at the chain it was migrated from.
+/// All genesis actions are written in a single transaction.
+#[allow(clippy::too_many_arguments)]
+pub async fn genesis_with_open_chain(
+    authored: DbWrite<DbKindAuthored>,
+    dht_db: DbWrite<DbKindDht>,
+    dht_db_cache: &DhtDbQueryCache,
+    keystore: MetaLairClient,
+    dna_hash: DnaHash,
+    agent_pubkey: AgentPubKey,
+    membrane_proof: Option<MembraneProof>,
+    chc: Option<ChcImpl>,
+    prev_dna_hash: Option<DnaHash>,
 ) -> SourceChainResult<()> {
     let dna_action = Action::Dna(action::Dna {
         author: agent_pubkey.clone(),
@@ -1069,6 +1249,29 @@ pub async fn genesis(
     let (agent_action, agent_entry) = record.into_inner();
     let agent_entry = agent_entry.into_option();
 
+    // open the chain if it was migrated from another DNA
+    let open_chain = match prev_dna_hash {
+        Some(prev_dna_hash) => {
+            let open_chain_action = Action::OpenChain(action::OpenChain {
+                author: agent_pubkey.clone(),
+                timestamp: std::cmp::max(
+                    Timestamp::now(),
+                    (agent_action.action().timestamp() + std::time::Duration::from_micros(1))?,
+                ),
+                action_seq: 3,
+                prev_action: agent_action.as_hash().clone(),
+                prev_dna_hash,
+            });
+            let open_chain_action = ActionHashed::from_content_sync(open_chain_action);
+            let open_chain_action = SignedActionHashed::sign(&keystore, open_chain_action).await?;
+            let record = Record::new(open_chain_action, None);
+            let open_chain_ops = produce_op_lights_from_records(vec![&record])?;
+            let (open_chain_action, _) = record.into_inner();
+            Some((open_chain_action, open_chain_ops))
+        }
+        None => None,
+    };
+
     let mut ops_to_integrate = Vec::new();
 
     if let Some(chc) = chc {
@@ -1077,14 +1280,13 @@ pub async fn genesis(
         ))])
         .await
         .map_err(SourceChainError::other)?;
-        match chc
-            .add_actions(vec![
-                dna_action.clone(),
-                agent_validation_action.clone(),
-                agent_action.clone(),
-            ])
-            .await
-        {
+        let mut actions = vec![
+            dna_action.clone(),
+            agent_validation_action.clone(),
+            agent_action.clone(),
+        ];
+        actions.extend(open_chain.as_ref().map(|(action, _)| action.clone()));
+        match chc.add_actions(actions).await {
             Err(e @ ChcError::InvalidChain(_, _)) => {
                 Err(SourceChainError::ChcHeadMoved("genesis".into(), e))
             }
@@ -1107,6 +1309,14 @@ pub async fn genesis(
                 agent_ops,
                 agent_entry,
             )?);
+            if let Some((open_chain_action, open_chain_ops)) = open_chain {
+                ops_to_integrate.extend(source_chain::put_raw(
+                    txn,
+                    open_chain_action,
+                    open_chain_ops,
+                    None,
+                )?);
+            }
             SourceChainResult::Ok(ops_to_integrate)
         })
         .await?;
@@ -1159,6 +1369,17 @@ pub fn chain_head_db(
     Ok(chain_head.run(Txn::from(txn))?)
 }
 
+/// If the action at `head` is an [`Action::CloseChain`], get the hash of
+/// the DNA the chain was migrated to.
+fn closed_chain_db(txn: &Transaction, head: &ActionHash) -> SourceChainResult<Option<DnaHash>> {
+    Ok(Txn::from(txn)
+        .get_action(head)?
+        .and_then(|action| match action.action() {
+            Action::CloseChain(close_chain) => Some(close_chain.new_dna_hash.clone()),
+            _ => None,
+        }))
+}
+
 /// Get the current chain head of the database.
 /// Error if the chain is empty.
 pub fn chain_head_db_nonempty(
@@ -1328,6 +1549,7 @@ impl From<SourceChain> for SourceChainRead {
             persisted_seq: chain.persisted_seq,
             persisted_head: chain.persisted_head,
             persisted_timestamp: chain.persisted_timestamp,
+            persisted_new_dna_hash: chain.persisted_new_dna_hash,
             public_only: chain.public_only,
             zomes_initialized: Arc::new(AtomicBool::new(false)),
         }
@@ -1436,6 +1658,81 @@ pub mod tests {
 
         Ok(())
     }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_closed_chain_rejects_writes() -> SourceChainResult<()> {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let keystore = test_keystore();
+        let db = test_db.to_db();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        mock.expect_chc().return_const(None);
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+
+        source_chain::genesis(
+            db.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let chain = SourceChain::new(
+            db.clone().into(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        assert_eq!(chain.prev_dna_hash().await?, None);
+        assert_eq!(chain.new_dna_hash().await?, None);
+
+        let new_dna_hash = fake_dna_hash(2);
+        chain
+            .put(
+                builder::CloseChain {
+                    new_dna_hash: new_dna_hash.clone(),
+                },
+                None,
+                ChainTopOrdering::Strict,
+            )
+            .await?;
+
+        // The scratch is checked before anything is flushed.
+        assert!(matches!(
+            chain
+                .put(builder::InitZomesComplete {}, None, ChainTopOrdering::Strict)
+                .await,
+            Err(SourceChainError::ChainClosed(hash)) if hash == new_dna_hash
+        ));
+        chain.flush(&mock).await?;
+
+        let chain = SourceChain::new(
+            db.clone().into(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        assert_eq!(chain.new_dna_hash().await?, Some(new_dna_hash.clone()));
+        assert!(matches!(
+            chain
+                .put(builder::InitZomesComplete {}, None, ChainTopOrdering::Strict)
+                .await,
+            Err(SourceChainError::ChainClosed(hash)) if hash == new_dna_hash
+        ));
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_relaxed_ordering_with_entry() -> SourceChainResult<()> {
         let test_db = test_authored_db();
@@ -0,0 +1,251 @@
+//! Module for issuing, checking and looking up warrants
+
+use holo_hash::AgentPubKey;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::MetaLairClient;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_zome_types::warrant::SignedWarrant;
+use holochain_zome_types::warrant::Warrant;
+use holochain_zome_types::Timestamp;
+
+use crate::prelude::from_blob;
+use crate::prelude::StateQueryResult;
+
+/// Sign a warrant as the agent who issued it.
+pub async fn sign_warrant(
+    warrant: Warrant,
+    keystore: &MetaLairClient,
+) -> holochain_keystore::LairResult<SignedWarrant> {
+    let signature = warrant.author.sign(keystore, &warrant).await?;
+    Ok(SignedWarrant::new(warrant, signature))
+}
+
+/// Check that a warrant was signed by the agent who issued it, and that
+/// the offending action it carries was really signed by the offender.
+///
+/// This does not check that the offending op is actually invalid,
+/// which needs the DNA to validate it.
+pub async fn verify_warrant(warrant: &SignedWarrant) -> bool {
+    let SignedWarrant { warrant, signature } = warrant;
+    if !warrant.author.verify_signature(signature, warrant).await {
+        return false;
+    }
+    let (action, signature) = warrant.proof.offending_action();
+    action.author().verify_signature(signature, &action).await
+}
+
+/// Get all verified warrants which have been issued against this agent.
+///
+/// Warrants received from other agents are only listed once their proof
+/// has been validated by this conductor.
+pub fn list_warrants(
+    txn: &Transaction,
+    offender: &AgentPubKey,
+) -> StateQueryResult<Vec<SignedWarrant>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT blob FROM Warrant WHERE offender = :offender AND verified = 1
+        ORDER BY timestamp ASC
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":offender": offender
+        },
+        |row| from_blob::<SignedWarrant>(row.get("blob")?),
+    )?;
+    iter.collect()
+}
+
+/// Get all warrants whose proof has not been validated yet.
+pub fn list_unverified_warrants(txn: &Transaction) -> StateQueryResult<Vec<SignedWarrant>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT blob FROM Warrant WHERE verified = 0
+        ORDER BY timestamp ASC
+        ",
+    )?;
+    let iter = stmt.query_and_then([], |row| from_blob::<SignedWarrant>(row.get("blob")?))?;
+    iter.collect()
+}
+
+/// Get all agents with at least one verified warrant against them.
+pub fn list_warranted_agents(txn: &Transaction) -> StateQueryResult<Vec<AgentPubKey>> {
+    let mut stmt = txn.prepare("SELECT DISTINCT offender FROM Warrant WHERE verified = 1")?;
+    let iter = stmt.query_and_then([], |row| StateQueryResult::Ok(row.get("offender")?))?;
+    iter.collect()
+}
+
+/// Get the warrants issued by this agent which are due to be published again.
+///
+/// Warrants issued after `issued_since` which have not been published
+/// since `published_before` (in seconds since the UNIX epoch) are returned.
+pub fn list_warrants_to_publish(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    issued_since: Timestamp,
+    published_before: u64,
+) -> StateQueryResult<Vec<SignedWarrant>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT blob FROM Warrant
+        WHERE author = :author
+        AND verified = 1
+        AND timestamp >= :issued_since
+        AND (last_publish_time IS NULL OR last_publish_time <= :published_before)
+        ORDER BY timestamp ASC
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":author": author,
+            ":issued_since": issued_since,
+            ":published_before": published_before,
+        },
+        |row| from_blob::<SignedWarrant>(row.get("blob")?),
+    )?;
+    iter.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::delete_warrant;
+    use crate::mutations::insert_warrant;
+    use crate::mutations::set_warrant_first_verify_attempt;
+    use crate::mutations::set_warrant_last_publish_time;
+    use crate::mutations::set_warrant_verified;
+    use ::fixt::prelude::*;
+    use holochain_zome_types::fixt::*;
+    use holochain_zome_types::op::Op;
+    use holochain_zome_types::op::RegisterAgentActivity;
+    use holochain_zome_types::warrant::WarrantProof;
+    use holochain_zome_types::Action;
+    use holochain_zome_types::ActionHashed;
+    use holochain_zome_types::SignedActionHashed;
+
+    async fn invalid_op(keystore: &MetaLairClient, offender: &AgentPubKey) -> Op {
+        let mut create = fixt!(Create);
+        create.author = offender.clone();
+        let action = Action::Create(create);
+        let signature = offender.sign(keystore, &action).await.unwrap();
+        Op::RegisterAgentActivity(RegisterAgentActivity {
+            action: SignedActionHashed::with_presigned(
+                ActionHashed::from_content_sync(action),
+                signature,
+            ),
+            cached_entry: None,
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sign_verify_and_list_warrants() {
+        let test_db = crate::test_utils::test_dht_db();
+        let db = test_db.to_db();
+        let keystore = crate::test_utils::test_keystore();
+        let validator = keystore.new_sign_keypair_random().await.unwrap();
+        let offender = keystore.new_sign_keypair_random().await.unwrap();
+
+        let proof = WarrantProof::InvalidOp(Box::new(invalid_op(&keystore, &offender).await));
+        let warrant = Warrant::new(proof, validator.clone(), Timestamp::now());
+        let signed = sign_warrant(warrant, &keystore).await.unwrap();
+        assert_eq!(signed.warrant().offender(), &offender);
+        assert!(verify_warrant(&signed).await);
+
+        // A warrant can't be attributed to somebody else.
+        let mut forged = signed.clone();
+        forged.warrant.author = fixt!(AgentPubKey);
+        assert!(!verify_warrant(&forged).await);
+
+        db.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                insert_warrant(txn, signed.clone(), true)?;
+                insert_warrant(txn, signed.clone(), true)
+            })
+            .unwrap();
+
+        let mut conn = db.conn().unwrap();
+        conn.with_reader_test(|txn| {
+            assert_eq!(
+                list_warrants(&txn, &offender).unwrap(),
+                vec![signed.clone()]
+            );
+            assert!(list_warrants(&txn, &validator).unwrap().is_empty());
+            assert_eq!(list_warranted_agents(&txn).unwrap(), vec![offender.clone()]);
+            assert_eq!(
+                list_warrants_to_publish(&txn, &validator, Timestamp::from_micros(0), 0).unwrap(),
+                vec![signed.clone()]
+            );
+        });
+
+        // Once published it isn't due again until the publish interval passes.
+        conn.with_commit_sync(|txn| {
+            set_warrant_last_publish_time(txn, &signed, std::time::Duration::from_secs(10))
+        })
+        .unwrap();
+        conn.with_reader_test(|txn| {
+            assert!(
+                list_warrants_to_publish(&txn, &validator, Timestamp::from_micros(0), 9)
+                    .unwrap()
+                    .is_empty()
+            );
+            assert_eq!(
+                list_warrants_to_publish(&txn, &validator, Timestamp::from_micros(0), 10).unwrap(),
+                vec![signed]
+            );
+        });
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unverified_warrants_are_not_listed() {
+        let test_db = crate::test_utils::test_dht_db();
+        let db = test_db.to_db();
+        let keystore = crate::test_utils::test_keystore();
+        let validator = keystore.new_sign_keypair_random().await.unwrap();
+        let offender = keystore.new_sign_keypair_random().await.unwrap();
+
+        let proof = WarrantProof::InvalidOp(Box::new(invalid_op(&keystore, &offender).await));
+        let warrant = Warrant::new(proof, validator, Timestamp::now());
+        let signed = sign_warrant(warrant, &keystore).await.unwrap();
+
+        let mut conn = db.conn().unwrap();
+        conn.with_commit_sync(|txn| insert_warrant(txn, signed.clone(), false))
+            .unwrap();
+        conn.with_reader_test(|txn| {
+            assert!(list_warrants(&txn, &offender).unwrap().is_empty());
+            assert!(list_warranted_agents(&txn).unwrap().is_empty());
+            assert_eq!(
+                list_unverified_warrants(&txn).unwrap(),
+                vec![signed.clone()]
+            );
+        });
+
+        // Only the first failed attempt to verify the warrant is recorded.
+        let first = Timestamp::from_micros(1);
+        let recorded = conn
+            .with_commit_sync(|txn| {
+                set_warrant_first_verify_attempt(txn, &signed, first)?;
+                set_warrant_first_verify_attempt(txn, &signed, Timestamp::from_micros(2))
+            })
+            .unwrap();
+        assert_eq!(recorded, first);
+
+        conn.with_commit_sync(|txn| set_warrant_verified(txn, &signed))
+            .unwrap();
+        conn.with_reader_test(|txn| {
+            assert_eq!(
+                list_warrants(&txn, &offender).unwrap(),
+                vec![signed.clone()]
+            );
+            assert!(list_unverified_warrants(&txn).unwrap().is_empty());
+        });
+
+        conn.with_commit_sync(|txn| delete_warrant(txn, &signed))
+            .unwrap();
+        conn.with_reader_test(|txn| {
+            assert!(list_warrants(&txn, &offender).unwrap().is_empty());
+        });
+    }
+}
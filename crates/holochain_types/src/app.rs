@@ -97,6 +97,20 @@ pub struct CreateCloneCellPayload {
     pub name: Option<String>,
 }
 
+/// The arguments to migrate the source chain of an app's provisioned cell
+/// to a new DNA.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct MigrateChainPayload {
+    /// The app id that the cell to migrate belongs to
+    pub app_id: InstalledAppId,
+    /// The role id of the provisioned cell to migrate
+    pub role_name: RoleName,
+    /// The hash of the DNA to migrate to, which must already be registered
+    pub new_dna_hash: DnaHash,
+    /// Optionally set a proof of membership for the new cell
+    pub membrane_proof: Option<MembraneProof>,
+}
+
 /// Ways of specifying a clone cell.
 #[derive(Clone, Debug, Display, serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
@@ -144,6 +158,11 @@ pub struct InstallAppPayload {
     /// The app can still use existing Cells, i.e. this does not require that
     /// all Cells have DNAs with the same overridden DNA.
     pub network_seed: Option<NetworkSeed>,
+
+    /// Optional: only install the bundle if it is signed by at least one of
+    /// these publisher keys, as with `hc app pack --signing-key`.
+    /// If not specified, bundle signatures are not checked.
+    pub trusted_publishers: Option<Vec<mr_bundle::PublisherKey>>,
 }
 
 /// The possible locations of an AppBundle
@@ -495,10 +514,19 @@ impl InstalledAppCommon {
         self.disabled_clone_cells().map(|(_, cell_id)| cell_id)
     }
 
-    /// Iterator of all cells, both provisioned and cloned
+    /// Accessor
+    pub fn previous_cell_ids(&self) -> impl Iterator<Item = &CellId> {
+        self.role_assignments
+            .values()
+            .flat_map(|role| role.previous_cells.iter())
+    }
+
+    /// Iterator of all cells, both provisioned and cloned, including the
+    /// cells that provisioned cells were migrated from
     pub fn all_cells(&self) -> impl Iterator<Item = &CellId> {
         self.provisioned_cells()
             .map(|(_, c)| c)
+            .chain(self.previous_cell_ids())
             .chain(self.clone_cell_ids())
             .chain(self.disabled_clone_cell_ids())
     }
@@ -669,6 +697,53 @@ impl InstalledAppCommon {
         }
     }
 
+    /// Replace the provisioned cell of a role with the cell its source chain
+    /// has been migrated to. The replaced cell is kept as a previous cell of
+    /// the role.
+    ///
+    /// # Returns
+    /// The cell id of the replaced cell.
+    pub fn migrate_provisioned_cell(
+        &mut self,
+        role_name: &RoleName,
+        new_cell_id: CellId,
+    ) -> AppResult<CellId> {
+        if self.all_cells().any(|cell_id| *cell_id == new_cell_id) {
+            return Err(AppError::DuplicateCellId(new_cell_id));
+        }
+        let app_role_assignment = self.role_mut(role_name)?;
+        if !app_role_assignment.is_provisioned {
+            return Err(AppError::RoleNotProvisioned(role_name.clone()));
+        }
+        assert_eq!(
+            new_cell_id.agent_pubkey(),
+            app_role_assignment.agent_key(),
+            "A migrated cell must use the same agent key as the role it is added to"
+        );
+        let prev_cell_id = std::mem::replace(&mut app_role_assignment.base_cell_id, new_cell_id);
+        app_role_assignment
+            .previous_cells
+            .push(prev_cell_id.clone());
+        Ok(prev_cell_id)
+    }
+
+    /// Undo the last [`Self::migrate_provisioned_cell`] of a role, making
+    /// its most recent previous cell the provisioned cell again.
+    ///
+    /// # Returns
+    /// The cell id of the cell the role had been migrated to.
+    pub fn revert_migrated_provisioned_cell(&mut self, role_name: &RoleName) -> AppResult<CellId> {
+        let app_role_assignment = self.role_mut(role_name)?;
+        let prev_cell_id = app_role_assignment
+            .previous_cells
+            .pop()
+            .ok_or_else(|| AppError::RoleNotMigrated(role_name.clone()))?;
+        Ok(std::mem::replace(
+            &mut app_role_assignment.base_cell_id,
+            prev_cell_id,
+        ))
+    }
+
     /// Accessor
     pub fn _agent_key(&self) -> &AgentPubKey {
         &self._agent_key
@@ -960,6 +1035,11 @@ pub struct AppRoleAssignment {
     /// any longer and are not returned as part of the app info either.
     /// Disabled clone cells can be deleted through the Admin API.
     disabled_clones: HashMap<CloneId, CellId>,
+    /// Cells which were provisioned for this role before its source chain
+    /// was migrated to a new DNA, oldest first. Their chains are closed,
+    /// but they keep running so that their data can still be read.
+    #[serde(default)]
+    previous_cells: Vec<CellId>,
 }
 
 impl AppRoleAssignment {
@@ -972,6 +1052,7 @@ impl AppRoleAssignment {
             clones: HashMap::new(),
             next_clone_index: 0,
             disabled_clones: HashMap::new(),
+            previous_cells: Vec::new(),
         }
     }
 
@@ -999,6 +1080,11 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Accessor
+    pub fn previous_cells(&self) -> &[CellId] {
+        &self.previous_cells
+    }
+
     /// Accessor
     pub fn clone_ids(&self) -> impl Iterator<Item = &CloneId> {
         self.clones.iter().map(|(clone_id, _)| clone_id)
@@ -1044,6 +1130,56 @@ mod tests {
         assert!(result.is_err())
     }
 
+    #[test]
+    fn chain_migration_management() {
+        let base_cell_id = fixt!(CellId);
+        let agent = base_cell_id.agent_pubkey().clone();
+        let role_name: RoleName = "role_name".into();
+        let role = AppRoleAssignment::new(base_cell_id.clone(), true, 0);
+        let mut app =
+            InstalledAppCommon::new("app", agent.clone(), vec![(role_name.clone(), role)]).unwrap();
+
+        let new_cell_id = CellId::new(fixt!(DnaHash), agent.clone());
+        assert_eq!(
+            app.migrate_provisioned_cell(&role_name, new_cell_id.clone())
+                .unwrap(),
+            base_cell_id
+        );
+
+        let role = app.role(&role_name).unwrap();
+        assert_eq!(role.cell_id(), &new_cell_id);
+        assert_eq!(role.previous_cells(), &[base_cell_id.clone()]);
+        // The previous cell is still part of the app
+        assert_eq!(
+            app.all_cells().collect::<HashSet<_>>(),
+            maplit::hashset! { &base_cell_id, &new_cell_id }
+        );
+
+        // Can't migrate back to a cell that is already part of the app
+        assert!(matches!(
+            app.migrate_provisioned_cell(&role_name, base_cell_id),
+            Err(AppError::DuplicateCellId(_))
+        ));
+        // Can't migrate a role that doesn't exist
+        assert!(matches!(
+            app.migrate_provisioned_cell(&"other".into(), CellId::new(fixt!(DnaHash), agent)),
+            Err(AppError::RoleNameMissing(_))
+        ));
+
+        // A failed migration can be reverted
+        assert_eq!(
+            app.revert_migrated_provisioned_cell(&role_name).unwrap(),
+            new_cell_id
+        );
+        let role = app.role(&role_name).unwrap();
+        assert_eq!(role.cell_id(), &base_cell_id);
+        assert!(role.previous_cells().is_empty());
+        assert!(matches!(
+            app.revert_migrated_provisioned_cell(&role_name),
+            Err(AppError::RoleNotMigrated(_))
+        ));
+    }
+
     #[test]
     fn clone_management() {
         let base_cell_id = fixt!(CellId);
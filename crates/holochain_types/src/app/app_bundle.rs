@@ -2,7 +2,10 @@ use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use self::error::AppBundleResult;
 
-use super::{dna_gamut::DnaGamut, AppManifest, AppManifestValidated};
+use super::{
+    dna_gamut::{CellResolution, DnaGamut, DnaResolution},
+    AppManifest, AppManifestValidated,
+};
 use crate::prelude::*;
 
 #[allow(missing_docs)]
@@ -45,72 +48,93 @@ impl AppBundle {
         self.0
     }
 
+    /// Cache the remote DNAs and zomes of this app whose hashes are pinned
+    /// in their manifests in `cache_dir`.
+    /// See [`mr_bundle::Bundle::with_cache_dir`].
+    pub fn with_cache_dir(self, cache_dir: PathBuf) -> Self {
+        self.0.with_cache_dir(cache_dir).into()
+    }
+
+    /// Verify the signatures of this app against the `trusted` publishers,
+    /// and check that each DNA it installs pins its own zomes, wherever the
+    /// DNA is resolved from.
+    /// See [`mr_bundle::Bundle::with_verified_signatures`].
+    pub fn with_verified_signatures(
+        self,
+        trusted: &[mr_bundle::PublisherKey],
+    ) -> mr_bundle::error::MrBundleResult<Self> {
+        Ok(self.0.with_verified_signatures(trusted)?.into())
+    }
+
     /// Given a DnaGamut, decide which of the available DNAs or Cells should be
     /// used for each cell in this app.
     pub async fn resolve_cells(
         self,
         agent: AgentPubKey,
-        _gamut: DnaGamut,
+        gamut: DnaGamut,
         membrane_proofs: HashMap<RoleName, MembraneProof>,
     ) -> AppBundleResult<AppRoleResolution> {
         let AppManifestValidated { name: _, roles } = self.manifest().clone().validate()?;
         let bundle = Arc::new(self);
+        let gamut = &gamut;
+        let agent_ref = &agent;
         let tasks = roles.into_iter().map(|(role_name, role)| async {
             let bundle = bundle.clone();
-            Ok((role_name, bundle.resolve_cell(role).await?))
+            let op = bundle
+                .resolve_cell(&role_name, role, gamut, agent_ref)
+                .await?;
+            Ok((role_name, op))
         });
-        let resolution = futures::future::join_all(tasks)
+        let ops = futures::future::join_all(tasks)
             .await
             .into_iter()
-            .collect::<AppBundleResult<Vec<_>>>()?
-            .into_iter()
-            .fold(
-                Ok(AppRoleResolution::new(agent.clone())),
-                |acc: AppBundleResult<AppRoleResolution>, (role_name, op)| {
-                    if let Ok(mut resolution) = acc {
-                        match op {
-                            CellProvisioningOp::Create(dna, clone_limit) => {
-                                let agent = resolution.agent.clone();
-                                let dna_hash = dna.dna_hash().clone();
-                                let cell_id = CellId::new(dna_hash, agent);
-                                let role = AppRoleAssignment::new(cell_id, true, clone_limit);
-                                // TODO: could sequentialize this to remove the clone
-                                let proof = membrane_proofs.get(&role_name).cloned();
-                                resolution.dnas_to_register.push((dna, proof));
-                                resolution.role_assignments.push((role_name, role));
-                            }
-                            CellProvisioningOp::Existing(cell_id, clone_limit) => {
-                                let role = AppRoleAssignment::new(cell_id, true, clone_limit);
-                                resolution.role_assignments.push((role_name, role));
-                            }
-                            CellProvisioningOp::Noop(cell_id, clone_limit) => {
-                                resolution.role_assignments.push((
-                                    role_name,
-                                    AppRoleAssignment::new(cell_id, false, clone_limit),
-                                ));
-                            }
-                            other => {
-                                tracing::error!(
-                                    "Encountered unexpected CellProvisioningOp: {:?}",
-                                    other
-                                );
-                                unimplemented!()
-                            }
-                        }
-                        Ok(resolution)
-                    } else {
-                        acc
-                    }
-                },
-            )?;
+            .collect::<AppBundleResult<Vec<_>>>()?;
+
+        let mut resolution = AppRoleResolution::new(agent.clone());
+        for (role_name, op) in ops {
+            match op {
+                CellProvisioningOp::Create(dna, clone_limit) => {
+                    let dna_hash = dna.dna_hash().clone();
+                    let cell_id = CellId::new(dna_hash, agent.clone());
+                    let role = AppRoleAssignment::new(cell_id, true, clone_limit);
+                    let proof = membrane_proofs.get(&role_name).cloned();
+                    resolution.dnas_to_register.push((dna, proof));
+                    resolution.role_assignments.push((role_name, role));
+                }
+                CellProvisioningOp::Existing(cell_id, clone_limit) => {
+                    let role = AppRoleAssignment::new(cell_id, true, clone_limit);
+                    resolution.role_assignments.push((role_name, role));
+                }
+                CellProvisioningOp::Register(dna, clone_limit) => {
+                    let cell_id = CellId::new(dna.dna_hash().clone(), agent.clone());
+                    resolution.dnas_to_register_without_cells.push(dna);
+                    resolution.role_assignments.push((
+                        role_name,
+                        AppRoleAssignment::new(cell_id, false, clone_limit),
+                    ));
+                }
+                CellProvisioningOp::Noop(cell_id, clone_limit) => {
+                    resolution.role_assignments.push((
+                        role_name,
+                        AppRoleAssignment::new(cell_id, false, clone_limit),
+                    ));
+                }
+                CellProvisioningOp::NoMatch => {
+                    return Err(AppBundleError::CellResolutionFailure(role_name));
+                }
+                CellProvisioningOp::Conflict(conflict) => match conflict {},
+            }
+        }
 
-        // let resolution = cells.into_iter();
         Ok(resolution)
     }
 
     async fn resolve_cell(
         &self,
+        role_name: &RoleName,
         role: AppRoleManifestValidated,
+        gamut: &DnaGamut,
+        agent: &AgentPubKey,
     ) -> AppBundleResult<CellProvisioningOp> {
         Ok(match role {
             AppRoleManifestValidated::Create {
@@ -124,42 +148,74 @@ impl AppBundle {
                     .await?
             }
 
-            AppRoleManifestValidated::CreateClone { .. } => {
-                unimplemented!("`create_clone` provisioning strategy is currently unimplemented")
+            AppRoleManifestValidated::CreateClone {
+                location,
+                version,
+                clone_limit,
+                mut modifiers,
+                deferred: _,
+            } => {
+                // A fresh network seed puts the new Cell in its own DHT network,
+                // distinct from any other Cell using the same DNA.
+                modifiers.network_seed = Some(nanoid::nanoid!());
+                self.resolve_cell_create(&location, version.as_ref(), clone_limit, modifiers)
+                    .await?
             }
             AppRoleManifestValidated::UseExisting {
                 version,
                 clone_limit,
                 deferred: _,
-            } => self.resolve_cell_existing(&version, clone_limit),
+            } => self.resolve_cell_existing(role_name, &version, clone_limit, gamut, agent)?,
             AppRoleManifestValidated::CreateIfNotExists {
                 location,
                 version,
                 clone_limit,
                 modifiers,
                 deferred: _,
-            } => match self.resolve_cell_existing(&version, clone_limit) {
-                op @ CellProvisioningOp::Existing(_, _) => op,
-                CellProvisioningOp::NoMatch => {
-                    self.resolve_cell_create(&location, Some(&version), clone_limit, modifiers)
-                        .await?
-                }
-                CellProvisioningOp::Conflict(_) => {
-                    unimplemented!("conflicts are not handled, or even possible yet")
-                }
-                CellProvisioningOp::Create(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a Create op")
-                }
-                CellProvisioningOp::Noop(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a Noop")
+            } => {
+                // Only this agent's own Cell may stand in for the Cell which
+                // would otherwise be created.
+                match gamut.resolve_cell(version.clone(), agent) {
+                    CellResolution::Match(cell_id, _) => {
+                        CellProvisioningOp::Existing(cell_id, clone_limit)
+                    }
+                    CellResolution::NoMatch | CellResolution::Conflict => {
+                        self.resolve_cell_create(&location, Some(&version), clone_limit, modifiers)
+                            .await?
+                    }
                 }
-            },
+            }
             AppRoleManifestValidated::Disabled {
-                version: _,
-                clone_limit: _,
+                version,
+                clone_limit,
+                location,
+                modifiers,
             } => {
-                unimplemented!("`disabled` provisioning strategy is currently unimplemented")
-                // CellProvisioningOp::Noop(clone_limit)
+                // No Cell is provisioned, but clones will be made from this DNA,
+                // so prefer a hash which is already known to the conductor.
+                // Otherwise the DNA must be registered for clones to be created.
+                match (gamut.resolve_dna(version.clone()), location) {
+                    (DnaResolution::Match(hash, _), _) => CellProvisioningOp::Noop(
+                        CellId::new(hash.into(), agent.clone()),
+                        clone_limit,
+                    ),
+                    (_, Some(location)) => match self
+                        .resolve_cell_create(&location, Some(&version), clone_limit, modifiers)
+                        .await?
+                    {
+                        CellProvisioningOp::Create(dna, clone_limit) => {
+                            CellProvisioningOp::Register(dna, clone_limit)
+                        }
+                        op => op,
+                    },
+                    (_, None) => match version.dna_hashes().first() {
+                        Some(hash) => CellProvisioningOp::Noop(
+                            CellId::new((*hash).clone().into(), agent.clone()),
+                            clone_limit,
+                        ),
+                        None => CellProvisioningOp::NoMatch,
+                    },
+                }
             }
         })
     }
@@ -172,7 +228,17 @@ impl AppBundle {
         modifiers: DnaModifiersOpt,
     ) -> AppBundleResult<CellProvisioningOp> {
         let bytes = self.resolve(location).await?;
-        let dna_bundle: DnaBundle = mr_bundle::Bundle::decode(&bytes)?.into();
+        let mut dna_bundle: mr_bundle::Bundle<ValidatedDnaManifest> =
+            mr_bundle::Bundle::decode(&bytes)?;
+        // A DNA resolved by URL or path is only checked against its pinned
+        // hash, so its own zomes must be pinned as well.
+        if self.signatures_verified() {
+            dna_bundle.check_resources_pinned()?;
+        }
+        if let Some(cache_dir) = self.cache_dir() {
+            dna_bundle = dna_bundle.with_cache_dir(cache_dir.to_owned());
+        }
+        let dna_bundle = DnaBundle::from(dna_bundle);
         let (dna_file, original_dna_hash) = dna_bundle.into_dna_file(modifiers).await?;
         if let Some(spec) = version {
             if !spec.matches(original_dna_hash) {
@@ -184,19 +250,25 @@ impl AppBundle {
 
     fn resolve_cell_existing(
         &self,
-        _version: &DnaVersionSpec,
-        _clone_limit: u32,
-    ) -> CellProvisioningOp {
-        unimplemented!("Reusing existing cells is not yet implemented")
+        role_name: &RoleName,
+        version: &DnaVersionSpec,
+        clone_limit: u32,
+        gamut: &DnaGamut,
+        agent: &AgentPubKey,
+    ) -> AppBundleResult<CellProvisioningOp> {
+        match gamut.resolve_cell(version.clone(), agent) {
+            CellResolution::Match(cell_id, _) => {
+                Ok(CellProvisioningOp::Existing(cell_id, clone_limit))
+            }
+            CellResolution::NoMatch => Ok(CellProvisioningOp::NoMatch),
+            // Another agent's Cell cannot be used on behalf of this agent
+            CellResolution::Conflict => {
+                Err(AppBundleError::CellResolutionFailure(role_name.clone()))
+            }
+        }
     }
 }
 
-/// This function is called in places where it will be necessary to rework that
-/// area after use_existing has been implemented
-pub fn we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution(
-) {
-}
-
 /// The answer to the question:
 /// "how do we concretely assign DNAs to the open roles of this App?"
 /// Includes the DNAs selected to fill the roles and the details of the role assignments.
@@ -206,6 +278,8 @@ pub fn we_must_remember_to_rework_cell_panic_handling_after_implementing_use_exi
 pub struct AppRoleResolution {
     pub agent: AgentPubKey,
     pub dnas_to_register: Vec<(DnaFile, Option<MembraneProof>)>,
+    /// DNAs which are only used to create clones, so no Cell is created for them
+    pub dnas_to_register_without_cells: Vec<DnaFile>,
     pub role_assignments: Vec<(RoleName, AppRoleAssignment)>,
 }
 
@@ -215,6 +289,7 @@ impl AppRoleResolution {
         Self {
             agent,
             dnas_to_register: Default::default(),
+            dnas_to_register_without_cells: Default::default(),
             role_assignments: Default::default(),
         }
     }
@@ -242,6 +317,8 @@ pub enum CellProvisioningOp {
     Create(DnaFile, u32),
     /// Use an existing Cell
     Existing(CellId, u32),
+    /// Register the DNA without creating a Cell, so that clones can be made of it
+    Register(DnaFile, u32),
     /// No provisioning needed, but there might be a clone_limit, and so we need
     /// to know which DNA and Agent to use for making clones
     Noop(CellId, u32),
@@ -6,7 +6,10 @@ use app_manifest_v1::tests::{app_manifest_fixture, app_manifest_properties_fixtu
 
 use super::AppBundle;
 
-async fn app_bundle_fixture(modifiers: DnaModifiersOpt<YamlProperties>) -> (AppBundle, DnaFile) {
+async fn app_bundle_fixture(
+    modifiers: DnaModifiersOpt<YamlProperties>,
+    provisioning: CellProvisioning,
+) -> (AppBundle, DnaFile) {
     let dna_wasm = DnaWasmHashed::from_content(DnaWasm::new_invalid()).await;
     let fake_wasms = vec![dna_wasm.clone().into_content()];
     let fake_zomes = vec![IntegrityZome::new(
@@ -21,12 +24,13 @@ async fn app_bundle_fixture(modifiers: DnaModifiersOpt<YamlProperties>) -> (AppB
 
     let path1 = PathBuf::from(format!("{}", dna1.dna_hash()));
 
-    let (manifest, _dna_hashes) = app_manifest_fixture(
+    let (mut manifest, _dna_hashes) = app_manifest_fixture(
         Some(DnaLocation::Bundled(path1.clone())),
         vec![dna1.dna_def().clone(), dna2.dna_def().clone()],
         modifiers,
     )
     .await;
+    manifest.roles[0].provisioning = Some(provisioning);
 
     let resources = vec![(path1, DnaBundle::from_dna_file(dna1.clone()).await.unwrap())];
 
@@ -47,7 +51,8 @@ async fn provisioning_1_create() {
         origin_time: None,
         quantum_time: None,
     };
-    let (bundle, dna) = app_bundle_fixture(modifiers).await;
+    let (bundle, dna) =
+        app_bundle_fixture(modifiers, CellProvisioning::Create { deferred: false }).await;
 
     // Apply the modifier overrides specified in the manifest fixture
     let dna = dna
@@ -70,7 +75,206 @@ async fn provisioning_1_create() {
     let expected = AppRoleResolution {
         agent,
         dnas_to_register: vec![(dna, None)],
+        dnas_to_register_without_cells: vec![],
         role_assignments: vec![("name".into(), role)],
     };
     assert_eq!(resolution, expected);
 }
+
+/// Test that an app with a single UseExisting cell shares the agent's existing Cell
+#[tokio::test]
+async fn provisioning_1_use_existing() {
+    observability::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(
+        DnaModifiersOpt::none(),
+        CellProvisioning::UseExisting { deferred: false },
+    )
+    .await;
+
+    // The existing Cell of the agent belongs to another app
+    let existing = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+    let gamut = DnaGamut::new(vec![
+        CellId::new(dna.dna_hash().to_owned(), fixt!(AgentPubKey)),
+        existing.clone(),
+    ]);
+
+    let resolution = bundle
+        .resolve_cells(agent.clone(), gamut, Default::default())
+        .await
+        .unwrap();
+
+    let expected = AppRoleResolution {
+        agent,
+        dnas_to_register: vec![],
+        dnas_to_register_without_cells: vec![],
+        role_assignments: vec![("name".into(), AppRoleAssignment::new(existing, true, 50))],
+    };
+    assert_eq!(resolution, expected);
+}
+
+/// Test that UseExisting fails if there is no matching Cell
+#[tokio::test]
+async fn provisioning_1_use_existing_no_match() {
+    observability::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, _) = app_bundle_fixture(
+        DnaModifiersOpt::none(),
+        CellProvisioning::UseExisting { deferred: false },
+    )
+    .await;
+
+    let gamut = DnaGamut::new(vec![fixt!(CellId)]);
+    let result = bundle
+        .resolve_cells(agent.clone(), gamut, Default::default())
+        .await;
+    assert!(matches!(
+        result,
+        Err(AppBundleError::CellResolutionFailure(role_name)) if role_name == "name"
+    ));
+}
+
+/// Test that UseExisting never uses the Cell of another agent
+#[tokio::test]
+async fn provisioning_1_use_existing_of_another_agent() {
+    observability::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(
+        DnaModifiersOpt::none(),
+        CellProvisioning::UseExisting { deferred: false },
+    )
+    .await;
+
+    let gamut = DnaGamut::new(vec![CellId::new(
+        dna.dna_hash().to_owned(),
+        fixt!(AgentPubKey),
+    )]);
+    let result = bundle
+        .resolve_cells(agent.clone(), gamut, Default::default())
+        .await;
+    assert!(matches!(
+        result,
+        Err(AppBundleError::CellResolutionFailure(role_name)) if role_name == "name"
+    ));
+}
+
+/// Test that CreateIfNotExists prefers an existing Cell of the same agent,
+/// and otherwise creates a new one, even if another agent has a Cell
+#[tokio::test]
+async fn provisioning_1_create_if_not_exists() {
+    observability::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let provisioning = CellProvisioning::CreateIfNotExists { deferred: false };
+    let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none(), provisioning.clone()).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+
+    let gamut = DnaGamut::new(vec![
+        CellId::new(dna.dna_hash().to_owned(), fixt!(AgentPubKey)),
+        cell_id.clone(),
+    ]);
+    let resolution = bundle
+        .resolve_cells(agent.clone(), gamut, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        resolution,
+        AppRoleResolution {
+            agent: agent.clone(),
+            dnas_to_register: vec![],
+            dnas_to_register_without_cells: vec![],
+            role_assignments: vec![(
+                "name".into(),
+                AppRoleAssignment::new(cell_id.clone(), true, 50)
+            )],
+        }
+    );
+
+    let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none(), provisioning).await;
+    let gamut = DnaGamut::new(vec![CellId::new(
+        dna.dna_hash().to_owned(),
+        fixt!(AgentPubKey),
+    )]);
+    let resolution = bundle
+        .resolve_cells(agent.clone(), gamut, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(resolution.dnas_to_register.len(), 1);
+    assert_eq!(
+        resolution.role_assignments[0].1,
+        AppRoleAssignment::new(
+            CellId::new(
+                resolution.dnas_to_register[0].0.dna_hash().clone(),
+                agent.clone()
+            ),
+            true,
+            50
+        )
+    );
+}
+
+/// Test that CreateClone always creates a Cell in a distinct network
+#[tokio::test]
+async fn provisioning_1_create_clone() {
+    observability::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let provisioning = CellProvisioning::CreateClone { deferred: false };
+
+    let mut hashes = Vec::new();
+    for _ in 0..2 {
+        let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none(), provisioning.clone()).await;
+        // An existing Cell for the same DNA is never reused
+        let gamut = DnaGamut::new(vec![CellId::new(dna.dna_hash().to_owned(), agent.clone())]);
+        let resolution = bundle
+            .resolve_cells(agent.clone(), gamut, Default::default())
+            .await
+            .unwrap();
+        assert_eq!(resolution.dnas_to_register.len(), 1);
+        let created = resolution.dnas_to_register[0].0.dna_hash().clone();
+        assert_ne!(&created, dna.dna_hash());
+        hashes.push(created);
+    }
+    assert_ne!(hashes[0], hashes[1]);
+}
+
+/// Test that a Disabled role provisions no Cell, but still records the
+/// Cell to be used as the basis for clones, and registers its DNA
+#[tokio::test]
+async fn provisioning_1_disabled() {
+    observability::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) =
+        app_bundle_fixture(DnaModifiersOpt::none(), CellProvisioning::Disabled).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+
+    let resolution = bundle
+        .resolve_cells(agent.clone(), DnaGamut::placeholder(), Default::default())
+        .await
+        .unwrap();
+
+    let expected = AppRoleResolution {
+        agent: agent.clone(),
+        dnas_to_register: vec![],
+        dnas_to_register_without_cells: vec![dna.clone()],
+        role_assignments: vec![("name".into(), AppRoleAssignment::new(cell_id, false, 50))],
+    };
+    assert_eq!(resolution, expected);
+    assert!(resolution.cells_to_create().is_empty());
+
+    // A DNA which is already known to the conductor is not registered again
+    let (bundle, dna) =
+        app_bundle_fixture(DnaModifiersOpt::none(), CellProvisioning::Disabled).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+    let gamut = DnaGamut::new(vec![CellId::new(
+        dna.dna_hash().to_owned(),
+        fixt!(AgentPubKey),
+    )]);
+    let resolution = bundle
+        .resolve_cells(agent.clone(), gamut, Default::default())
+        .await
+        .unwrap();
+    assert!(resolution.dnas_to_register_without_cells.is_empty());
+    assert_eq!(
+        resolution.role_assignments,
+        vec![("name".into(), AppRoleAssignment::new(cell_id, false, 50))]
+    );
+}
@@ -3,7 +3,7 @@
 //! Defines the hApp Manifest YAML format, including validation.
 
 use holochain_zome_types::NetworkSeed;
-use mr_bundle::{Location, Manifest};
+use mr_bundle::{error::MrBundleResult, Location, Manifest, ResourceBytes, ResourceHash};
 use std::path::PathBuf;
 
 pub(crate) mod app_manifest_v1;
@@ -38,6 +38,26 @@ impl Manifest for AppManifest {
         }
     }
 
+    fn resource_hash(&self, location: &Location) -> Option<ResourceHash> {
+        match self {
+            AppManifest::V1(m) => m
+                .roles
+                .iter()
+                .find(|role| role.dna.location.as_ref() == Some(location))
+                .and_then(|role| role.dna.resource_hash),
+        }
+    }
+
+    fn check_nested_resources_pinned(
+        &self,
+        _location: &Location,
+        bytes: &ResourceBytes,
+    ) -> MrBundleResult<()> {
+        // Every location in an app manifest is a DNA bundle
+        mr_bundle::Bundle::<crate::dna::ValidatedDnaManifest>::decode(bytes)?
+            .check_resources_pinned()
+    }
+
     fn path() -> PathBuf {
         "happ.yaml".into()
     }
@@ -74,6 +74,11 @@ pub struct AppRoleDnaManifest {
     #[serde(flatten)]
     pub location: Option<mr_bundle::Location>,
 
+    /// The hash of the DNA bundle at the location, which the bundle is checked
+    /// against when it is resolved. A DNA bundle downloaded from a URL with
+    /// this hash is cached, so that installing the app again works offline.
+    pub resource_hash: Option<mr_bundle::ResourceHash>,
+
     /// Optional default modifier values. May be overridden during installation.
     #[serde(default)]
     pub modifiers: DnaModifiersOpt<YamlProperties>,
@@ -97,6 +102,7 @@ impl AppRoleDnaManifest {
             location: Some(mr_bundle::Location::Bundled(
                 "./path/to/my/dnabundle.dna".into(),
             )),
+            resource_hash: None,
             modifiers: DnaModifiersOpt::none(),
             version: None,
             clone_limit: 0,
@@ -218,6 +224,7 @@ impl AppManifestV1 {
                  }| {
                     let AppRoleDnaManifest {
                         location,
+                        resource_hash: _,
                         version,
                         clone_limit,
                         modifiers,
@@ -261,6 +268,8 @@ impl AppManifestV1 {
                         CellProvisioning::Disabled => AppRoleManifestValidated::Disabled {
                             clone_limit,
                             version: Self::require(version, "roles.dna.version")?,
+                            location,
+                            modifiers,
                         },
                     };
                     AppManifestResult::Ok((name, validated))
@@ -319,6 +328,7 @@ pub mod tests {
             name: "name".into(),
             dna: AppRoleDnaManifest {
                 location,
+                resource_hash: None,
                 modifiers,
                 version: Some(version),
                 clone_limit: 50,
@@ -391,6 +401,38 @@ roles:
         assert_eq!(actual.get(fields[3]), expected.get(fields[3]));
     }
 
+    #[test]
+    fn manifest_v1_resource_hash() {
+        use mr_bundle::{Location, Manifest, ResourceHash};
+
+        let hash = ResourceHash::of(b"dna bundle");
+        let yaml = format!(
+            r#"---
+manifest_version: "1"
+name: "Test app"
+roles:
+  - name: "pinned"
+    dna:
+      url: "https://example.com/pinned.dna"
+      resource_hash: "{}"
+  - name: "unpinned"
+    dna:
+      bundled: "unpinned.dna"
+        "#,
+            hash
+        );
+        let manifest: AppManifest = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(
+            manifest.resource_hash(&Location::Url("https://example.com/pinned.dna".into())),
+            Some(hash)
+        );
+        assert_eq!(
+            manifest.resource_hash(&Location::Bundled("unpinned.dna".into())),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn manifest_v1_set_network_seed() {
         let mut u = arbitrary::Unstructured::new(&[0]);
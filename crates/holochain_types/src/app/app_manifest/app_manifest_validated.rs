@@ -86,5 +86,7 @@ pub enum AppRoleManifestValidated {
     Disabled {
         version: DnaVersionSpec,
         clone_limit: u32,
+        location: Option<DnaLocation>,
+        modifiers: DnaModifiersOpt,
     },
 }
@@ -61,26 +61,29 @@ impl DnaGamut {
         DnaResolution::NoMatch
     }
 
-    /// Given a version spec, return the best-matching CellId
-    // TODO: use DPKI to filter Cells which belong to Agents that are not
+    /// Given a version spec, return the best-matching CellId belonging to
+    /// the given agent.
+    ///
+    /// Cells of other agents are never matched. If only other agents have a
+    /// Cell using a matching DNA, the resolution is a [`CellResolution::Conflict`].
+    // TODO: use DPKI to also match Cells which belong to Agents that are
     //       associated with the provided agent
-    pub fn resolve_cell(&self, spec: DnaVersionSpec, _agent: &AgentPubKey) -> CellResolution {
-        for hash in spec.dna_hashes() {
-            if let Some(agent) = self
-                .0
-                .get(hash.as_ref())
-                // TODO: this is where an agent check could go, but for now we
-                //       just return the first one available
-                .map(|agents| agents.iter().next())
-                .unwrap_or(None)
-            {
-                return CellResolution::Match(
-                    CellId::new(hash.clone().into(), agent.clone()),
-                    DnaVersion,
-                );
-            }
+    pub fn resolve_cell(&self, spec: DnaVersionSpec, agent: &AgentPubKey) -> CellResolution {
+        let candidates: Vec<_> = spec
+            .dna_hashes()
+            .into_iter()
+            .filter_map(|hash| self.0.get(hash.as_ref()).map(|agents| (hash, agents)))
+            .collect();
+        if let Some((hash, _)) = candidates.iter().find(|(_, agents)| agents.contains(agent)) {
+            CellResolution::Match(
+                CellId::new((*hash).clone().into(), agent.clone()),
+                DnaVersion,
+            )
+        } else if candidates.iter().any(|(_, agents)| !agents.is_empty()) {
+            CellResolution::Conflict
+        } else {
+            CellResolution::NoMatch
         }
-        CellResolution::NoMatch
     }
 }
 
@@ -97,11 +100,11 @@ pub enum DnaResolution {
 
 /// Possible results of Cell resolution
 pub enum CellResolution {
-    /// A match was found within the gamut
+    /// A Cell of the agent was found within the gamut
     Match(CellId, DnaVersion),
     /// No match was found
     NoMatch,
-    /// Multiple matches were found, or other scenario that requires user
-    /// intervention for resolution (TODO, placeholder)
+    /// Only Cells of other agents were found, which cannot be used on behalf
+    /// of the agent
     Conflict,
 }
@@ -20,6 +20,15 @@ pub enum AppError {
     #[error("Tried to access missing role name: '{0}'")]
     RoleNameMissing(RoleName),
 
+    #[error("Tried to migrate the chain of role '{0}', which has no provisioned cell")]
+    RoleNotProvisioned(RoleName),
+
+    #[error("Tried to migrate a chain to cell {0:?}, which is already part of the app")]
+    DuplicateCellId(CellId),
+
+    #[error("Tried to revert the chain migration of role '{0}', which has never been migrated")]
+    RoleNotMigrated(RoleName),
+
     #[error("Tried to install app '{0}' which contains duplicate role names. The following role names have duplicates: {1:?}")]
     DuplicateRoleNames(InstalledAppId, Vec<RoleName>),
 }
@@ -16,6 +16,7 @@ use holochain_sqlite::rusqlite::types::FromSql;
 use holochain_sqlite::rusqlite::ToSql;
 use holochain_zome_types::action;
 use holochain_zome_types::prelude::*;
+use kitsune_p2p_dht::hash::RegionHash;
 use kitsune_p2p_dht::region::RegionData;
 use kitsune_p2p_dht::Loc;
 use serde::Deserialize;
@@ -113,21 +114,83 @@ pub enum DhtOp {
     RegisterRemoveLink(Signature, action::DeleteLink),
 }
 
-impl kitsune_p2p_dht::prelude::OpRegion for DhtOp {
+/// A [`DhtOp`] ordered by timestamp and then location, with ties broken by
+/// the [`OpOrder`], as required for range queries over
+/// [`kitsune_p2p_dht::prelude::OpRegion`]s. A [`DhtOp`] itself is ordered by
+/// its [`OpOrder`] alone.
+#[derive(Clone, Debug, PartialEq, Eq, derive_more::From, derive_more::Into)]
+pub struct RegionOp(pub DhtOp);
+
+impl RegionOp {
+    fn region_order(&self) -> (Timestamp, Loc, OpOrder) {
+        use kitsune_p2p_dht::prelude::OpRegion;
+        (self.timestamp(), self.loc(), self.0.to_order())
+    }
+}
+
+impl PartialOrd for RegionOp {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RegionOp {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.region_order().cmp(&other.region_order())
+    }
+}
+
+impl kitsune_p2p_dht::prelude::OpRegion for RegionOp {
     fn loc(&self) -> Loc {
-        self.dht_basis().get_loc()
+        self.0.dht_basis().get_loc()
     }
 
     fn timestamp(&self) -> Timestamp {
-        self.timestamp()
+        self.0.timestamp()
     }
 
     fn region_data(&self) -> RegionData {
-        unimplemented!()
+        let op = &self.0;
+        let hash = DhtOpHash::with_data_sync(op);
+        // Sizes are counted the same way as the region queries in the
+        // database: the serialized signed action, plus the serialized entry
+        // only when the op carries the entry itself.
+        let action_size =
+            holochain_serialized_bytes::encode(&SignedAction(op.action(), op.signature().clone()))
+                .map(|bytes| bytes.len())
+                .unwrap_or_default();
+        let entry_size = match op {
+            DhtOp::StoreRecord(_, _, _) | DhtOp::StoreEntry(_, _, _) => op
+                .entry()
+                .and_then(|entry| holochain_serialized_bytes::encode(entry).ok())
+                .map(|bytes| bytes.len())
+                .unwrap_or_default(),
+            _ => 0,
+        };
+        RegionData {
+            hash: RegionHash::from_vec(hash.get_raw_39().to_vec())
+                .expect("region hash must be 32 bytes"),
+            size: (action_size + entry_size).min(u32::MAX as usize) as u32,
+            count: 1,
+        }
     }
 
-    fn bound(_timestamp: Timestamp, _loc: kitsune_p2p_dht::Loc) -> Self {
-        unimplemented!()
+    fn bound(timestamp: Timestamp, loc: kitsune_p2p_dht::Loc) -> Self {
+        // An agent activity op is based on the author, so an author key with
+        // the given location bytes puts the op at that location.
+        // Agent activity is also the lowest op type in the ordering,
+        // so this op sorts before any real op at the same timestamp and location.
+        let mut author = vec![0; 32];
+        author.extend_from_slice(&loc.as_u32().to_le_bytes());
+        let author = AgentPubKey::from_raw_36(author);
+        Self(DhtOp::RegisterAgentActivity(
+            Signature([0; 64]),
+            Action::Dna(action::Dna {
+                author,
+                timestamp,
+                hash: DnaHash::from_raw_36(vec![0; 36]),
+            }),
+        ))
     }
 }
 
@@ -305,6 +368,21 @@ impl DhtOp {
         }
     }
 
+    /// Access to the Timestamp
+    pub fn timestamp(&self) -> Timestamp {
+        match self {
+            DhtOp::StoreRecord(_, h, _) => h.timestamp(),
+            DhtOp::StoreEntry(_, h, _) => h.timestamp(),
+            DhtOp::RegisterAgentActivity(_, h) => h.timestamp(),
+            DhtOp::RegisterUpdatedContent(_, h, _) => h.timestamp,
+            DhtOp::RegisterUpdatedRecord(_, h, _) => h.timestamp,
+            DhtOp::RegisterDeletedBy(_, h) => h.timestamp,
+            DhtOp::RegisterDeletedEntryAction(_, h) => h.timestamp,
+            DhtOp::RegisterAddLink(_, h) => h.timestamp,
+            DhtOp::RegisterRemoveLink(_, h) => h.timestamp,
+        }
+    }
+
     /// Get the signature for this op
     pub fn signature(&self) -> &Signature {
         match self {
@@ -462,3 +462,90 @@ fn test_all_ops_basis() {
         check_all_ops(record);
     }
 }
+
+#[test]
+fn op_region_data_and_bound() {
+    use kitsune_p2p_dht::prelude::OpRegion;
+
+    let entry = fixt!(Entry);
+    let mut create = fixt!(Create);
+    create.entry_hash = EntryHash::with_data_sync(&entry);
+    let op = RegionOp(DhtOp::StoreEntry(
+        fixt!(Signature),
+        NewEntryAction::Create(create),
+        Box::new(entry.clone()),
+    ));
+
+    let data = op.region_data();
+    assert_eq!(data.count, 1);
+    assert_eq!(
+        data.hash,
+        kitsune_p2p_dht::hash::RegionHash::from_vec(
+            DhtOpHash::with_data_sync(&op.0).get_raw_39().to_vec()
+        )
+        .unwrap()
+    );
+    // The entry is counted for ops which carry it, but not for ops which only refer to it
+    let activity = RegionOp(DhtOp::RegisterAgentActivity(
+        op.0.signature().clone(),
+        op.0.action(),
+    ));
+    assert!(data.size > activity.region_data().size);
+
+    let bound = RegionOp::bound(op.timestamp(), op.loc());
+    assert_eq!(bound.timestamp(), op.timestamp());
+    assert_eq!(bound.loc(), op.loc());
+    assert!(bound <= op);
+    assert!(RegionOp::bound(op.timestamp(), op.loc() + kitsune_p2p_dht::Loc::from(1u32)) > op);
+}
+
+#[test]
+fn region_query_returns_every_op_type() {
+    use kitsune_p2p_dht::prelude::AccessOpStore;
+    use kitsune_p2p_dht::prelude::GossipParams;
+    use kitsune_p2p_dht::prelude::RegionCoords;
+    use kitsune_p2p_dht::prelude::SpaceSegment;
+    use kitsune_p2p_dht::prelude::TimeSegment;
+    use kitsune_p2p_dht::prelude::Topology;
+    use kitsune_p2p_dht::test_utils::OpStore;
+    use std::sync::Arc;
+
+    let entry = fixt!(Entry);
+    let mut create = fixt!(Create);
+    create.entry_hash = EntryHash::with_data_sync(&entry);
+    create.timestamp = Timestamp::from_micros(1000);
+    let sig = fixt!(Signature);
+    let ops = vec![
+        DhtOp::StoreRecord(
+            sig.clone(),
+            Action::Create(create.clone()),
+            Some(Box::new(entry.clone())),
+        ),
+        DhtOp::StoreEntry(
+            sig.clone(),
+            NewEntryAction::Create(create.clone()),
+            Box::new(entry),
+        ),
+        DhtOp::RegisterAgentActivity(sig, Action::Create(create)),
+    ];
+
+    let topo = Topology::unit_zero();
+    let mut store = OpStore::new(topo.clone(), GossipParams::zero());
+    store.integrate_ops(ops.clone().into_iter().map(|op| Arc::new(RegionOp(op))));
+
+    // Query both halves of the space over a time segment holding the ops
+    let found: Vec<DhtOp> = [0u32, 1]
+        .into_iter()
+        .flat_map(|offset| {
+            store.query_op_data(&RegionCoords {
+                space: SpaceSegment::new(31, offset),
+                time: TimeSegment::new(12, 0u32),
+            })
+        })
+        .map(|op| op.0.clone())
+        .collect();
+    assert_eq!(found.len(), ops.len());
+    for op in ops {
+        assert!(found.contains(&op));
+    }
+}
@@ -31,6 +31,13 @@ impl Manifest for CoordinatorManifest {
             .collect()
     }
 
+    fn resource_hash(&self, location: &mr_bundle::Location) -> Option<mr_bundle::ResourceHash> {
+        self.zomes
+            .iter()
+            .find(|zome| zome.location == *location)
+            .and_then(|zome| zome.resource_hash)
+    }
+
     fn path() -> std::path::PathBuf {
         "coordinators.yaml".into()
     }
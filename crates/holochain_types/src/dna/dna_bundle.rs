@@ -169,6 +169,7 @@ impl DnaBundle {
                         name,
                         hash: Some(hash),
                         location: Location::Bundled(PathBuf::from(filename)),
+                        resource_hash: None,
                         dependencies: Some(dependencies),
                     }
                 })
@@ -192,6 +193,7 @@ impl DnaBundle {
                         name,
                         hash: Some(hash),
                         location: Location::Bundled(PathBuf::from(filename)),
+                        resource_hash: None,
                         dependencies: Some(dependencies),
                     }
                 })
@@ -273,6 +275,7 @@ mod tests {
                         name: "zome1".into(),
                         hash: None,
                         location: mr_bundle::Location::Bundled(path1.clone()),
+                        resource_hash: None,
                         dependencies: Default::default(),
                     },
                     ZomeManifest {
@@ -280,6 +283,7 @@ mod tests {
                         // Intentional wrong hash
                         hash: Some(hash1.clone().into()),
                         location: mr_bundle::Location::Bundled(path2.clone()),
+                        resource_hash: None,
                         dependencies: Default::default(),
                     },
                 ],
@@ -149,6 +149,11 @@ pub struct ZomeManifest {
     #[serde(flatten)]
     pub location: ZomeLocation,
 
+    /// The hash of the bytes at the location of the wasm, which the wasm is
+    /// checked against when it is resolved. Unlike the wasm `hash`, this hash
+    /// also lets a remote wasm be cached.
+    pub resource_hash: Option<mr_bundle::ResourceHash>,
+
     /// The integrity zomes this zome depends on.
     /// The order of these must match the order the types
     /// are used in the zome.
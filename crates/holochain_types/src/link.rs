@@ -31,6 +31,88 @@ pub struct WireLinkKey {
     pub type_query: LinkTypeFilter,
     /// Optionally specify a tag for more specific queries.
     pub tag: Option<LinkTag>,
+    /// Narrows down and pages through the links.
+    #[serde(default)]
+    pub filter: GetLinksFilter,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+/// Link query for sending across the wire for count links requests.
+///
+/// Unlike a [`WireLinkKey`] there is no limit or cursor,
+/// as all the matching links are counted.
+pub struct WireLinkQuery {
+    /// Base the links are on.
+    pub base: AnyLinkableHash,
+    /// The zome the links are in.
+    pub type_query: LinkTypeFilter,
+    /// Optionally specify a tag for more specific queries.
+    pub tag: Option<LinkTag>,
+    /// Only count links created by this agent.
+    pub author: Option<AgentPubKey>,
+    /// Only count links created after this time.
+    pub after: Option<Timestamp>,
+    /// Only count links created before this time.
+    pub before: Option<Timestamp>,
+}
+
+impl WireLinkQuery {
+    /// The filter matching the links to count.
+    pub fn filter(&self) -> GetLinksFilter {
+        GetLinksFilter {
+            author: self.author.clone(),
+            after: self.after,
+            before: self.before,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<WireLinkKey> for WireLinkQuery {
+    fn from(key: WireLinkKey) -> Self {
+        let WireLinkKey {
+            base,
+            type_query,
+            tag,
+            filter,
+        } = key;
+        Self {
+            base,
+            type_query,
+            tag,
+            author: filter.author,
+            after: filter.after,
+            before: filter.before,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes, Default)]
+/// Response to a count links request.
+///
+/// Holds the hashes of the create link actions which haven't been deleted,
+/// rather than a number. Each authority may hold a different subset of the
+/// links, so the requester needs the hashes to take the union of the
+/// responses without counting a link held by several authorities twice,
+/// and to remove the links it has seen deleted locally. Only the hashes
+/// are sent, not the link actions themselves.
+pub struct CountLinksResponse(Vec<ActionHash>);
+
+impl CountLinksResponse {
+    /// Create a response from the hashes of the matching create link actions.
+    pub fn new(create_link_actions: Vec<ActionHash>) -> Self {
+        Self(create_link_actions)
+    }
+
+    /// The hashes of the matching create link actions.
+    pub fn create_link_actions(&self) -> &[ActionHash] {
+        &self.0
+    }
+
+    /// Take the hashes of the matching create link actions.
+    pub fn into_create_link_actions(self) -> Vec<ActionHash> {
+        self.0
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes, Default)]
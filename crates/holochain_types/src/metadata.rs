@@ -17,24 +17,52 @@ pub struct TimedActionHash {
 
 /// Metadata returned from a GetMeta request.
 /// The Ord derive on TimedActionHash means each set is ordered by time.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, SerializedBytes)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, SerializedBytes)]
 pub struct MetadataSet {
     /// Actions that created or updated an entry.
     /// These are the actions that show the entry exists.
     pub actions: BTreeSet<TimedActionHash>,
-    // TODO: Implement after validation
-    /// Placeholder
+    /// Actions on this basis which were rejected by validation
     pub invalid_actions: BTreeSet<TimedActionHash>,
     /// Deletes on an action
     pub deletes: BTreeSet<TimedActionHash>,
     /// Updates on an action or entry
     pub updates: BTreeSet<TimedActionHash>,
+    /// CreateLink actions on this base which have not been deleted
+    pub links: BTreeSet<TimedActionHash>,
     /// The status of an entry from an authority.
     /// This is simply a faster way of determining if
     /// there are any live actions on an entry.
     pub entry_dht_status: Option<EntryDhtStatus>,
 }
 
+impl MetadataSet {
+    /// Combine metadata from another source into this set.
+    /// An entry is considered live if any source reports it as live.
+    pub fn merge(&mut self, other: MetadataSet) {
+        let MetadataSet {
+            actions,
+            invalid_actions,
+            deletes,
+            updates,
+            links,
+            entry_dht_status,
+        } = other;
+        self.actions.extend(actions);
+        self.invalid_actions.extend(invalid_actions);
+        self.deletes.extend(deletes);
+        self.updates.extend(updates);
+        self.links.extend(links);
+        self.entry_dht_status = match (self.entry_dht_status, entry_dht_status) {
+            (Some(EntryDhtStatus::Live), _) | (_, Some(EntryDhtStatus::Live)) => {
+                Some(EntryDhtStatus::Live)
+            }
+            (Some(status), _) | (None, Some(status)) => Some(status),
+            (None, None) => None,
+        };
+    }
+}
+
 impl From<ActionHashed> for TimedActionHash {
     fn from(h: ActionHashed) -> Self {
         let (action, hash) = h.into_inner();
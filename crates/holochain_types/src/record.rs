@@ -114,6 +114,7 @@ pub struct WireRecord {
 pub struct RecordGroup<'a> {
     actions: Vec<Cow<'a, SignedActionHashed>>,
     rejected: Vec<Cow<'a, SignedActionHashed>>,
+    abandoned: Vec<Cow<'a, SignedActionHashed>>,
     entry: Cow<'a, EntryHashed>,
 }
 
@@ -166,6 +167,7 @@ impl<'a> RecordGroup<'a> {
             .clone()
             .into_iter()
             .chain(self.rejected.clone().into_iter())
+            .chain(self.abandoned.clone().into_iter())
             .map(|shh| shh.into_owned())
     }
 
@@ -179,6 +181,11 @@ impl<'a> RecordGroup<'a> {
         self.rejected.iter().map(|shh| shh.action_address())
     }
 
+    /// Get the abandoned action hashes
+    pub fn abandoned_hashes(&self) -> impl Iterator<Item = &ActionHash> {
+        self.abandoned.iter().map(|shh| shh.action_address())
+    }
+
     /// Create a record group from wire actions and an entry
     pub fn from_wire_records<I: IntoIterator<Item = WireActionStatus<WireNewEntryAction>>>(
         actions_iter: I,
@@ -188,6 +195,7 @@ impl<'a> RecordGroup<'a> {
         let iter = actions_iter.into_iter();
         let mut valid = Vec::with_capacity(iter.size_hint().0);
         let mut rejected = Vec::with_capacity(iter.size_hint().0);
+        let mut abandoned = Vec::new();
         let entry = entry.into_hashed();
         let entry_hash = entry.as_hash().clone();
         let entry = Cow::Owned(entry);
@@ -201,13 +209,17 @@ impl<'a> RecordGroup<'a> {
                     wire.action
                         .into_action(entry_type.clone(), entry_hash.clone()),
                 )),
-                ValidationStatus::Abandoned => todo!(),
+                ValidationStatus::Abandoned => abandoned.push(Cow::Owned(
+                    wire.action
+                        .into_action(entry_type.clone(), entry_hash.clone()),
+                )),
             }
         }
 
         Ok(Self {
             actions: valid,
             rejected,
+            abandoned,
             entry,
         })
     }
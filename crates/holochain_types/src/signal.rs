@@ -12,7 +12,14 @@ use holochain_zome_types::prelude::*;
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum Signal {
     /// Signal from a Cell, generated by `emit_signal`
-    App(CellId, AppSignal),
+    App {
+        /// The Cell which emitted the signal
+        cell_id: CellId,
+        /// The zome which emitted the signal
+        zome_name: ZomeName,
+        /// The signal payload
+        signal: AppSignal,
+    },
     /// System-defined signals
     System(SystemSignal),
 }
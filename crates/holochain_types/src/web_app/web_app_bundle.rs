@@ -28,7 +28,10 @@ impl WebAppBundle {
         let manifest = self.0.manifest();
 
         let bytes = self.0.resolve(&manifest.happ_bundle_location()).await?;
-        let bundle = AppBundle::from(Bundle::decode(&bytes)?);
-        Ok(bundle)
+        let mut bundle: Bundle<AppManifest> = Bundle::decode(&bytes)?;
+        if let Some(cache_dir) = self.0.cache_dir() {
+            bundle = bundle.with_cache_dir(cache_dir.to_owned());
+        }
+        Ok(AppBundle::from(bundle))
     }
 }
@@ -2,7 +2,7 @@
 
 //! Defines the hApp Manifest YAML format, including validation.
 
-use mr_bundle::{Location, Manifest};
+use mr_bundle::{error::MrBundleResult, Location, Manifest, ResourceBytes, ResourceHash};
 use std::path::PathBuf;
 
 mod current;
@@ -29,6 +29,33 @@ impl Manifest for WebAppManifest {
         }
     }
 
+    fn resource_hash(&self, location: &Location) -> Option<ResourceHash> {
+        match self {
+            WebAppManifest::V1(m) => [
+                (&m.ui.location, m.ui.resource_hash),
+                (&m.happ_manifest.location, m.happ_manifest.resource_hash),
+            ]
+            .into_iter()
+            .find(|(l, _)| *l == location)
+            .and_then(|(_, hash)| hash),
+        }
+    }
+
+    fn check_nested_resources_pinned(
+        &self,
+        location: &Location,
+        bytes: &ResourceBytes,
+    ) -> MrBundleResult<()> {
+        match self {
+            // The UI is a zip archive rather than a bundle
+            WebAppManifest::V1(m) if *location == m.happ_manifest.location => {
+                mr_bundle::Bundle::<crate::app::AppManifest>::decode(bytes)?
+                    .check_resources_pinned()
+            }
+            WebAppManifest::V1(_) => Ok(()),
+        }
+    }
+
     fn path() -> PathBuf {
         "web-happ.yaml".into()
     }
@@ -45,9 +72,11 @@ impl WebAppManifest {
             name,
             ui: WebUI {
                 location: Location::Bundled("./path/to/my/ui.zip".into()),
+                resource_hash: None,
             },
             happ_manifest: AppManifestLocation {
                 location: Location::Bundled("./path/to/my/happ-bundle.happ".into()),
+                resource_hash: None,
             },
         })
     }
@@ -92,9 +121,11 @@ pub mod tests {
             name: app_name.clone(),
             ui: WebUI {
                 location: ui_location.clone(),
+                resource_hash: None,
             },
             happ_manifest: AppManifestLocation {
                 location: happ_location.clone(),
+                resource_hash: None,
             },
         });
 
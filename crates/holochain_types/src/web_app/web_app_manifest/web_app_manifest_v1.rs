@@ -31,6 +31,10 @@ pub struct WebUI {
     /// there is no actual "location" key in the manifest.
     #[serde(flatten)]
     pub location: mr_bundle::Location,
+
+    /// The hash of the bytes at the location, which they are checked
+    /// against when they are resolved.
+    pub resource_hash: Option<mr_bundle::ResourceHash>,
 }
 
 /// Location of the happ bundle to bind with the Web UI
@@ -44,4 +48,8 @@ pub struct AppManifestLocation {
     /// there is no actual "location" key in the manifest.
     #[serde(flatten)]
     pub location: mr_bundle::Location,
+
+    /// The hash of the bytes at the location, which they are checked
+    /// against when they are resolved.
+    pub resource_hash: Option<mr_bundle::ResourceHash>,
 }
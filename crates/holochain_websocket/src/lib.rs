@@ -81,6 +81,7 @@ use std::io::ErrorKind;
 use std::sync::Arc;
 
 use holochain_serialized_bytes::prelude::*;
+use socket::Socket;
 use stream_cancel::Valve;
 use tracing::instrument;
 use url2::Url2;
@@ -105,6 +106,7 @@ pub use websocket_receiver::*;
 
 mod websocket;
 
+mod socket;
 mod util;
 
 #[instrument(skip(config))]
@@ -119,13 +121,31 @@ pub async fn connect(
     // socket.set_keepalive(Some(std::time::Duration::from_secs(
     //     config.tcp_keepalive_s as u64,
     // )))?;
-    let (socket, _) = tokio_tungstenite::client_async_with_config(
-        url.as_str(),
-        socket,
-        Some(config.to_tungstenite()),
-    )
-    .await
-    .map_err(|e| Error::new(ErrorKind::Other, e))?;
+    connect_socket(url.as_str(), Socket::Tcp(socket), config).await
+}
+
+#[cfg(unix)]
+#[instrument(skip(config))]
+/// Create a new websocket connection over the unix domain socket at this path.
+pub async fn connect_unix(
+    path: impl AsRef<std::path::Path> + std::fmt::Debug,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
+    let socket = tokio::net::UnixStream::connect(path).await?;
+    // The handshake needs a host, which a unix domain socket doesn't have.
+    let url = format!("{}://localhost/", config.scheme);
+    connect_socket(&url, Socket::Unix(socket), config).await
+}
+
+async fn connect_socket(
+    url: &str,
+    socket: Socket,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
+    let (socket, _) =
+        tokio_tungstenite::client_async_with_config(url, socket, Some(config.to_tungstenite()))
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, e))?;
     tracing::debug!("Client connected");
 
     // Noop valve because we don't have a listener to shutdown the
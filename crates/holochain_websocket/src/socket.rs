@@ -0,0 +1,87 @@
+//! internal stream which a websocket can run over
+
+use std::io::Result;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+use url2::Url2;
+
+use crate::util::addr_to_url;
+#[cfg(unix)]
+use crate::util::unix_path_to_url;
+
+/// A connected socket, either over tcp or a unix domain socket.
+#[derive(Debug)]
+pub(crate) enum Socket {
+    Tcp(tokio::net::TcpStream),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixStream),
+}
+
+impl Socket {
+    /// The url of the other end of this socket.
+    /// Unix domain socket clients are usually unnamed, in which case the
+    /// url is the path of the socket they connected to.
+    pub(crate) fn peer_url(&self, scheme: &str) -> Result<Url2> {
+        match self {
+            Socket::Tcp(socket) => Ok(addr_to_url(socket.peer_addr()?, scheme)),
+            #[cfg(unix)]
+            Socket::Unix(socket) => {
+                let peer = socket.peer_addr()?;
+                let path = match peer.as_pathname() {
+                    Some(path) => path.to_path_buf(),
+                    None => socket
+                        .local_addr()?
+                        .as_pathname()
+                        .map(|p| p.to_path_buf())
+                        .unwrap_or_default(),
+                };
+                Ok(unix_path_to_url(&path))
+            }
+        }
+    }
+}
+
+impl AsyncRead for Socket {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_read(cx, buf),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Socket {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_write(cx, buf),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_flush(cx),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_shutdown(cx),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_shutdown(cx),
+        }
+    }
+}
@@ -1,5 +1,34 @@
 //! defines a builder-style config struct for setting up websockets
 
+use std::sync::Arc;
+
+/// Decides which HTTP `Origin`s a listener accepts websocket handshakes from.
+///
+/// Browsers always send an `Origin` header with a websocket handshake,
+/// so this stops web pages served from other origins from connecting.
+/// Handshakes without an `Origin` header, which come from clients that
+/// aren't browsers, are always accepted.
+#[derive(Clone)]
+pub struct OriginFilter(Arc<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl OriginFilter {
+    /// Accept handshakes from the origins for which `f` returns `true`.
+    pub fn new(f: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Whether a handshake from this origin is accepted.
+    pub fn allows(&self, origin: &str) -> bool {
+        (self.0)(origin)
+    }
+}
+
+impl std::fmt::Debug for OriginFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OriginFilter").finish()
+    }
+}
+
 /// A builder-style config struct for setting up websockets.
 #[derive(Debug)]
 pub struct WebsocketConfig {
@@ -26,6 +55,10 @@ pub struct WebsocketConfig {
 
     /// Maximum number of pending new incoming connections. [default = 255]
     pub max_pending_connections: usize,
+
+    /// Which `Origin`s a listener accepts handshakes from.
+    /// [default = None, accepting any origin]
+    pub origin_filter: Option<OriginFilter>,
 }
 
 impl Default for WebsocketConfig {
@@ -38,6 +71,7 @@ impl Default for WebsocketConfig {
             max_message_size: 64 << 20,
             max_frame_size: 16 << 20,
             max_pending_connections: 255,
+            origin_filter: None,
         }
     }
 }
@@ -78,6 +112,12 @@ impl WebsocketConfig {
         self.max_frame_size = max;
         self
     }
+
+    /// Builder-style setter.
+    pub fn origin_filter(mut self, filter: OriginFilter) -> Self {
+        self.origin_filter = Some(filter);
+        self
+    }
 }
 
 /// internal helper to convert our configs into tungstenite configs
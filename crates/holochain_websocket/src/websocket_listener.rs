@@ -4,14 +4,26 @@ use futures::TryStreamExt;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::sync::Arc;
 use stream_cancel::Trigger;
 use stream_cancel::Valve;
 use tracing::instrument;
+use tungstenite::handshake::server::ErrorResponse;
+use tungstenite::handshake::server::Request;
+use tungstenite::handshake::server::Response;
+use tungstenite::http::header::ORIGIN;
+use tungstenite::http::StatusCode;
 
 use url2::Url2;
 
+use crate::socket::Socket;
 use crate::util::addr_to_url;
+#[cfg(unix)]
+use crate::util::unix_path_to_url;
 use crate::util::url_to_addr;
 use crate::websocket::Websocket;
 use crate::WebsocketConfig;
@@ -73,6 +85,31 @@ pub struct ListenerHandle {
     shutdown: Trigger,
     config: Arc<WebsocketConfig>,
     local_addr: Url2,
+    #[cfg(unix)]
+    socket_file: Option<UnixSocketFile>,
+}
+
+/// The socket file of a listener bound to a unix domain socket, which is
+/// removed when the listener is closed unless another socket has replaced it.
+#[cfg(unix)]
+struct UnixSocketFile {
+    path: PathBuf,
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+impl Drop for UnixSocketFile {
+    fn drop(&mut self) {
+        use std::os::unix::fs::MetadataExt;
+        if let Ok(metadata) = std::fs::symlink_metadata(&self.path) {
+            if metadata.dev() == self.dev && metadata.ino() == self.ino {
+                if let Err(e) = std::fs::remove_file(&self.path) {
+                    tracing::warn!(?e, path = ?self.path, "failed to remove unix socket file");
+                }
+            }
+        }
+    }
 }
 
 /// [`WebsocketSender`] and [`WebsocketReceiver`] for an active connection.
@@ -106,6 +143,41 @@ impl WebsocketListener {
     )> {
         websocket_bind(addr, config).await
     }
+
+    /// Bind to a unix domain socket at this path to accept incoming connections.
+    ///
+    /// The socket file is given the permissions in `mode`, e.g. `0o600`,
+    /// so that only the users allowed by them can connect. It is bound in a
+    /// private directory and only moved to `path` once it has those
+    /// permissions, and it is removed when the listener is closed.
+    /// A socket file left at the path by a listener which is no longer
+    /// running is replaced.
+    #[cfg(unix)]
+    pub async fn bind_unix(
+        path: impl AsRef<Path>,
+        mode: u32,
+        config: Arc<WebsocketConfig>,
+    ) -> WebsocketResult<Self> {
+        let (handle, stream) = Self::bind_unix_with_handle(path, mode, config).await?;
+        Ok(Self {
+            handle,
+            stream: stream.boxed(),
+        })
+    }
+
+    /// Same as [`WebsocketListener::bind_unix`] but gives you a [`ListenerHandle`] to shutdown
+    /// the listener and any open connections.
+    #[cfg(unix)]
+    pub async fn bind_unix_with_handle(
+        path: impl AsRef<Path>,
+        mode: u32,
+        config: Arc<WebsocketConfig>,
+    ) -> WebsocketResult<(
+        ListenerHandle,
+        impl futures::stream::Stream<Item = ListenerItem>,
+    )> {
+        websocket_bind_unix(path.as_ref(), mode, config).await
+    }
     /// Shutdown the listener stream.
     pub fn close(self) {
         self.handle.close()
@@ -202,8 +274,91 @@ async fn websocket_bind(
     socket.set_nonblocking(true)?;
     let local_addr = addr_to_url(socket.local_addr()?, config.scheme);
     let listener = tokio::net::TcpListener::from_std(socket)?;
-    let listener_stream = tokio_stream::wrappers::TcpListenerStream::new(listener);
+    let listener_stream =
+        tokio_stream::wrappers::TcpListenerStream::new(listener).map_ok(Socket::Tcp);
+    Ok(accept_connections(listener_stream, local_addr, config))
+}
+
+#[cfg(unix)]
+async fn websocket_bind_unix(
+    path: &Path,
+    mode: u32,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+)> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        // Don't steal the socket of a listener which is still running.
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(Error::new(
+                ErrorKind::AddrInUse,
+                format!("a listener is already bound to '{}'", path.display()),
+            )
+            .into());
+        }
+        // Only a stale socket is removed, any other file is left alone.
+        if !metadata.file_type().is_socket() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("'{}' exists and is not a socket", path.display()),
+            )
+            .into());
+        }
+        std::fs::remove_file(path)?;
+    }
 
+    // The socket file is created with the permissions allowed by the umask,
+    // so it is bound in a directory only this user can enter and moved into
+    // place once its own permissions have been set. It is linked rather
+    // than renamed, so that a file created at the path meanwhile is not
+    // overwritten.
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let staging_dir = parent.join(format!(".{}", nanoid::nanoid!(8)));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging_dir)?;
+    let staging_path = staging_dir.join("s");
+    let bound = (|| {
+        let listener = tokio::net::UnixListener::bind(&staging_path)?;
+        std::fs::set_permissions(&staging_path, std::fs::Permissions::from_mode(mode))?;
+        std::fs::hard_link(&staging_path, path)?;
+        std::io::Result::Ok(listener)
+    })();
+    let _ = std::fs::remove_file(&staging_path);
+    let _ = std::fs::remove_dir(&staging_dir);
+    let listener = bound?;
+
+    let metadata = std::fs::symlink_metadata(path)?;
+    let local_addr = unix_path_to_url(path);
+    let listener_stream =
+        tokio_stream::wrappers::UnixListenerStream::new(listener).map_ok(Socket::Unix);
+    let (mut handle, stream) = accept_connections(listener_stream, local_addr, config);
+    handle.socket_file = Some(UnixSocketFile {
+        path: path.to_path_buf(),
+        dev: metadata.dev(),
+        ino: metadata.ino(),
+    });
+    Ok((handle, stream))
+}
+
+/// Upgrade each accepted socket to a websocket connection.
+fn accept_connections(
+    listener_stream: impl futures::stream::Stream<Item = std::io::Result<Socket>>,
+    local_addr: Url2,
+    config: Arc<WebsocketConfig>,
+) -> (
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+) {
     // Setup proper shutdown
     let (shutdown, valve) = Valve::new();
 
@@ -223,14 +378,16 @@ async fn websocket_bind(
         shutdown,
         config,
         local_addr,
+        #[cfg(unix)]
+        socket_file: None,
     };
-    Ok((listener_handle, stream))
+    (listener_handle, stream)
 }
 
 #[instrument(skip(config, socket, valve))]
 async fn connect(
     config: Arc<WebsocketConfig>,
-    socket: tokio::net::TcpStream,
+    socket: Socket,
     valve: Valve,
 ) -> WebsocketResult<Pair> {
     // TODO: find alternative to set the keepalive
@@ -239,10 +396,24 @@ async fn connect(
     // )))?;
     tracing::debug!(
         message = "accepted incoming raw socket",
-        remote_addr = %socket.peer_addr()?,
+        remote_addr = %socket.peer_url(config.scheme)?,
     );
-    let socket = tokio_tungstenite::accept_async_with_config(
+    let origin_filter = config.origin_filter.clone();
+    let check_origin = move |request: &Request, response: Response| match (
+        request.headers().get(ORIGIN),
+        &origin_filter,
+    ) {
+        (Some(origin), Some(filter)) if !filter.allows(origin.to_str().unwrap_or_default()) => {
+            tracing::warn!(?origin, "rejected websocket handshake from origin");
+            let mut response = ErrorResponse::new(Some("Origin not allowed".to_string()));
+            *response.status_mut() = StatusCode::FORBIDDEN;
+            Err(response)
+        }
+        _ => Ok(response),
+    };
+    let socket = tokio_tungstenite::accept_hdr_async_with_config(
         socket,
+        check_origin,
         Some(tungstenite::protocol::WebSocketConfig {
             max_send_queue: Some(config.max_send_queue),
             max_message_size: Some(config.max_message_size),
@@ -5,6 +5,7 @@ use holochain_serialized_bytes::prelude::*;
 use holochain_websocket::connect;
 use holochain_websocket::ListenerHandle;
 use holochain_websocket::ListenerItem;
+use holochain_websocket::OriginFilter;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketError;
 use holochain_websocket::WebsocketListener;
@@ -121,6 +122,100 @@ async fn can_connect() {
         .expect("Failed to connect to server");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_handshakes_from_disallowed_origins() {
+    observability::test_run().ok();
+    let origin_filter = OriginFilter::new(|origin| origin == "http://allowed.origin");
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(
+        url2!("ws://127.0.0.1:0"),
+        Arc::new(WebsocketConfig::default().origin_filter(origin_filter)),
+    )
+    .await
+    .unwrap();
+    tokio::task::spawn(async move { while listener.next().await.is_some() {} });
+
+    let url = handle.local_addr().to_string();
+    let handshake = |origin: Option<&str>| {
+        let mut request = tungstenite::http::Request::builder().uri(url.as_str());
+        if let Some(origin) = origin {
+            request = request.header("Origin", origin);
+        }
+        tokio_tungstenite::connect_async(request.body(()).unwrap())
+    };
+
+    assert!(handshake(Some("http://allowed.origin")).await.is_ok());
+    assert!(handshake(Some("http://other.origin")).await.is_err());
+    // Clients which aren't browsers don't send an origin.
+    assert!(handshake(None).await.is_ok());
+    handle.close();
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn can_send_request_over_unix_socket() {
+    use std::os::unix::fs::PermissionsExt;
+
+    observability::test_run().ok();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ws.sock");
+    let (handle, mut listener) = WebsocketListener::bind_unix_with_handle(
+        &path,
+        0o600,
+        Arc::new(WebsocketConfig::default()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(handle.local_addr().scheme(), "unix");
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    // A listener which is still running can't be replaced.
+    assert!(
+        WebsocketListener::bind_unix(&path, 0o600, Arc::new(WebsocketConfig::default()))
+            .await
+            .is_err()
+    );
+
+    let jh = tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (msg, resp) = receiver.next().await.unwrap();
+        let msg: TestString = msg.try_into().unwrap();
+        assert_eq!(msg.0, "Hey from client");
+        resp.respond(TestString("Bye from server".into()).try_into().unwrap())
+            .await
+            .unwrap();
+    });
+
+    let (mut sender, _receiver) =
+        holochain_websocket::connect_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+    let msg: TestString = sender
+        .request(TestString("Hey from client".into()))
+        .await
+        .unwrap();
+    assert_eq!(msg.0, "Bye from server");
+    jh.await.unwrap();
+
+    // Closing the listener removes its socket file, which can then be bound again.
+    handle.close();
+    assert!(!path.exists());
+    drop(sender);
+    WebsocketListener::bind_unix(&path, 0o600, Arc::new(WebsocketConfig::default()))
+        .await
+        .unwrap();
+
+    // A file which isn't a socket is never overwritten.
+    let file = dir.path().join("file");
+    std::fs::write(&file, b"data").unwrap();
+    assert!(
+        WebsocketListener::bind_unix(&file, 0o600, Arc::new(WebsocketConfig::default()))
+            .await
+            .is_err()
+    );
+    assert_eq!(std::fs::read(&file).unwrap(), b"data");
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_send_signal() {
     observability::test_run().ok();
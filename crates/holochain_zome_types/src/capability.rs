@@ -27,6 +27,7 @@
 mod grant;
 pub use grant::*;
 
+use holo_hash::{ActionHash, AgentPubKey};
 pub use holochain_integrity_types::capability::*;
 use serde::{Deserialize, Serialize};
 
@@ -41,3 +42,111 @@ pub struct GrantZomeCallCapabilityPayload {
     /// signing for as well as access level, secret and assignees.
     pub cap_grant: ZomeCallCapGrant,
 }
+
+/// Filter for listing the live capability grants on the local source chain.
+///
+/// Every field that is set must match for a grant to be included.
+/// The default filter matches all grants.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapGrantQuery {
+    /// Only include grants with exactly this tag.
+    pub tag: Option<String>,
+    /// Only include grants assigned to this agent.
+    pub grantee: Option<AgentPubKey>,
+    /// Only include grants which give access to this function.
+    pub function: Option<GrantedFunction>,
+}
+
+impl CapGrantQuery {
+    /// Filter by tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Filter by an agent the grant is assigned to.
+    pub fn grantee(mut self, grantee: AgentPubKey) -> Self {
+        self.grantee = Some(grantee);
+        self
+    }
+
+    /// Filter by a function the grant gives access to.
+    pub fn function(mut self, function: GrantedFunction) -> Self {
+        self.function = Some(function);
+        self
+    }
+
+    /// Does the grant match this filter?
+    pub fn check(&self, grant: &ZomeCallCapGrant) -> bool {
+        self.tag.as_ref().map_or(true, |tag| *tag == grant.tag)
+            && self.grantee.as_ref().map_or(true, |grantee| match &grant.access {
+                CapAccess::Assigned { assignees, .. } => assignees.contains(grantee),
+                _ => false,
+            })
+            && self
+                .function
+                .as_ref()
+                .map_or(true, |function| match &grant.functions {
+                    GrantedFunctions::All => true,
+                    GrantedFunctions::Listed(fns) => fns.contains(function),
+                })
+    }
+}
+
+/// Filter for listing the live capability claims on the local source chain.
+///
+/// Every field that is set must match for a claim to be included.
+/// The default filter matches all claims.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapClaimQuery {
+    /// Only include claims with exactly this tag.
+    pub tag: Option<String>,
+    /// Only include claims on grants made by this agent.
+    pub grantor: Option<AgentPubKey>,
+}
+
+impl CapClaimQuery {
+    /// Filter by tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Filter by grantor.
+    pub fn grantor(mut self, grantor: AgentPubKey) -> Self {
+        self.grantor = Some(grantor);
+        self
+    }
+
+    /// Does the claim match this filter?
+    pub fn check(&self, claim: &CapClaim) -> bool {
+        self.tag.as_ref().map_or(true, |tag| *tag == claim.tag)
+            && self
+                .grantor
+                .as_ref()
+                .map_or(true, |grantor| *grantor == claim.grantor)
+    }
+}
+
+/// A live capability grant on the local source chain.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapGrantInfo {
+    /// The hash of the action which created this grant.
+    /// This is the hash to use to update or delete the grant.
+    pub action_hash: ActionHash,
+    /// The grant itself.
+    pub cap_grant: ZomeCallCapGrant,
+}
+
+/// The capability which authorized the current zome call.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapabilityInfo {
+    /// The agent who made the call.
+    pub provenance: AgentPubKey,
+    /// The grant which authorized the call.
+    pub cap_grant: CapGrant,
+    /// The hash of the action which created the grant.
+    /// This is `None` for the implicit [`CapGrant::ChainAuthor`] grant,
+    /// which is not committed to the source chain.
+    pub action_hash: Option<ActionHash>,
+}
@@ -87,6 +87,9 @@ pub struct GetLinksInput {
     /// The link types to include in this get.
     pub link_type: LinkTypeFilter,
     pub tag_prefix: Option<crate::link::LinkTag>,
+    /// Narrows down and pages through the links.
+    #[serde(default)]
+    pub filter: GetLinksFilter,
 }
 
 impl GetLinksInput {
@@ -99,6 +102,131 @@ impl GetLinksInput {
             base_address,
             link_type,
             tag_prefix,
+            filter: GetLinksFilter::default(),
+        }
+    }
+
+    /// Narrow down and page through the links with a filter.
+    pub fn with_filter(mut self, filter: GetLinksFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// Narrows down the links returned by a get links query, and pages through them.
+///
+/// Links are ordered by the time they were created, with ties broken by the
+/// hash of their create action. To get the next page of links, pass the
+/// [`LinkCursor`] of the last link of the previous page.
+///
+/// A page can hold fewer than `limit` links even when more links follow it,
+/// for example when links were deleted, so only an empty page marks the end.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GetLinksFilter {
+    /// Only get links created by this agent.
+    pub author: Option<holo_hash::AgentPubKey>,
+    /// Only get links created after this time.
+    pub after: Option<crate::Timestamp>,
+    /// Only get links created before this time.
+    pub before: Option<crate::Timestamp>,
+    /// Get at most this many links.
+    pub limit: Option<u32>,
+    /// Only get links which come after this position.
+    pub cursor: Option<LinkCursor>,
+}
+
+impl GetLinksFilter {
+    /// Only get links created by this agent.
+    pub fn author(mut self, author: holo_hash::AgentPubKey) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    /// Only get links created after this time.
+    pub fn after(mut self, after: crate::Timestamp) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Only get links created before this time.
+    pub fn before(mut self, before: crate::Timestamp) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Get at most this many links.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Only get links which come after this position.
+    pub fn cursor(mut self, cursor: LinkCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Does a link, described by the author, timestamp and hash of its create
+    /// action, pass this filter. The limit isn't taken into account.
+    pub fn matches(
+        &self,
+        author: &holo_hash::AgentPubKey,
+        timestamp: crate::Timestamp,
+        create_link_hash: &ActionHash,
+    ) -> bool {
+        self.author.as_ref().map_or(true, |a| a == author)
+            && self.after.map_or(true, |after| timestamp > after)
+            && self.before.map_or(true, |before| timestamp < before)
+            && self.cursor.as_ref().map_or(true, |cursor| {
+                (timestamp, create_link_hash) > (cursor.timestamp, &cursor.create_link_hash)
+            })
+    }
+
+    /// Put links in the order a page of links is returned in, then drop any
+    /// links beyond the limit.
+    pub fn page<T>(&self, links: &mut Vec<T>, key: impl Fn(&T) -> LinkCursor) {
+        links.sort_by_cached_key(|link| key(link));
+        if let Some(limit) = self.limit {
+            links.truncate(limit as usize);
+        }
+    }
+}
+
+/// The position of a link in the ordered results of a get links query.
+///
+/// Take the cursor of the last link in a page, and pass it in a
+/// [`GetLinksFilter`] to get the links which follow it.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Hash, Serialize, Deserialize)]
+pub struct LinkCursor {
+    timestamp: crate::Timestamp,
+    create_link_hash: ActionHash,
+}
+
+impl LinkCursor {
+    /// The cursor of a link's create action, for paging through [`LinkDetails`].
+    pub fn from_create_link(create_link: &SignedActionHashed) -> Self {
+        Self {
+            timestamp: create_link.action().timestamp(),
+            create_link_hash: create_link.as_hash().clone(),
+        }
+    }
+
+    /// The time the link was created at.
+    pub fn timestamp(&self) -> &crate::Timestamp {
+        &self.timestamp
+    }
+
+    /// The hash of the link's create action.
+    pub fn create_link_hash(&self) -> &ActionHash {
+        &self.create_link_hash
+    }
+}
+
+impl From<&Link> for LinkCursor {
+    fn from(link: &Link) -> Self {
+        Self {
+            timestamp: link.timestamp,
+            create_link_hash: link.create_link_hash.clone(),
         }
     }
 }
@@ -51,6 +51,10 @@ pub struct EntryDetails {
     /// These are also the actions that created this entry.
     /// but did not pass validation.
     pub rejected_actions: Vec<SignedActionHashed>,
+    /// Abandoned create relationships.
+    /// These are also the actions that created this entry,
+    /// but validation was given up on before it could complete.
+    pub abandoned_actions: Vec<SignedActionHashed>,
     /// ## Delete relationships
     /// These are the deletes that have the
     /// `deletes_entry_address` set to the above Entry.
@@ -1,6 +1,115 @@
 //! Types for warrants
+//!
+//! A warrant is a signed claim by a validator that another agent has
+//! authored data which breaks the rules of the DNA, along with the proof
+//! needed for anyone else to check that claim for themselves.
+use crate::op::*;
+use crate::signature::Signature;
+use crate::Action;
+use crate::Timestamp;
+use holo_hash::AgentPubKey;
 pub use holochain_serialized_bytes::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-/// Placeholder for warrant type
-pub struct Warrant;
+/// The proof that an agent has misbehaved.
+pub enum WarrantProof {
+    /// The op failed app validation.
+    /// Anyone can run validation on the op to check this.
+    InvalidOp(Box<Op>),
+}
+
+impl WarrantProof {
+    /// The agent this proof is against.
+    pub fn offender(&self) -> &AgentPubKey {
+        match self {
+            WarrantProof::InvalidOp(op) => op.author(),
+        }
+    }
+
+    /// The offending action and the offender's signature on it,
+    /// which shows that the offender really did author it.
+    pub fn offending_action(&self) -> (Action, &Signature) {
+        match self {
+            WarrantProof::InvalidOp(op) => match op.as_ref() {
+                Op::StoreRecord(StoreRecord { record }) => {
+                    (record.action().clone(), record.signature())
+                }
+                Op::StoreEntry(StoreEntry { action, .. }) => {
+                    (action.hashed.content.clone().into(), &action.signature)
+                }
+                Op::RegisterUpdate(RegisterUpdate { update, .. }) => (
+                    Action::Update(update.hashed.content.clone()),
+                    &update.signature,
+                ),
+                Op::RegisterDelete(RegisterDelete { delete, .. }) => (
+                    Action::Delete(delete.hashed.content.clone()),
+                    &delete.signature,
+                ),
+                Op::RegisterAgentActivity(RegisterAgentActivity { action, .. }) => {
+                    (action.hashed.content.clone(), &action.signature)
+                }
+                Op::RegisterCreateLink(RegisterCreateLink { create_link }) => (
+                    Action::CreateLink(create_link.hashed.content.clone()),
+                    &create_link.signature,
+                ),
+                Op::RegisterDeleteLink(RegisterDeleteLink { delete_link, .. }) => (
+                    Action::DeleteLink(delete_link.hashed.content.clone()),
+                    &delete_link.signature,
+                ),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// A claim by a validator that an agent has misbehaved.
+pub struct Warrant {
+    /// The proof of the misbehavior.
+    pub proof: WarrantProof,
+    /// The validator who issued this warrant.
+    pub author: AgentPubKey,
+    /// When this warrant was issued.
+    pub timestamp: Timestamp,
+}
+
+impl Warrant {
+    /// Constructor.
+    pub fn new(proof: WarrantProof, author: AgentPubKey, timestamp: Timestamp) -> Self {
+        Self {
+            proof,
+            author,
+            timestamp,
+        }
+    }
+
+    /// The agent this warrant is against.
+    pub fn offender(&self) -> &AgentPubKey {
+        self.proof.offender()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// A [`Warrant`] along with the signature of the validator who issued it.
+pub struct SignedWarrant {
+    /// The warrant.
+    pub warrant: Warrant,
+    /// The issuing validator's signature of the warrant.
+    pub signature: Signature,
+}
+
+impl SignedWarrant {
+    /// Constructor.
+    pub fn new(warrant: Warrant, signature: Signature) -> Self {
+        Self { warrant, signature }
+    }
+
+    /// Access the warrant.
+    pub fn warrant(&self) -> &Warrant {
+        &self.warrant
+    }
+
+    /// Access the signature.
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
@@ -17,6 +17,8 @@ mod put;
 mod random;
 mod store;
 
+pub use store::{MemStore, SqliteStore, StoreBackend, StoreConfig, StoreResult};
+
 /// No reason to accept a peer data bigger then 1KB.
 // TODO: Maybe even that's too high?
 const SIZE_LIMIT: u64 = 1024;
@@ -42,14 +44,30 @@ pub async fn run_with_prune_freq(
     proxy_list: Vec<String>,
     prune_frequency: std::time::Duration,
 ) -> Result<(BootstrapDriver, SocketAddr, BootstrapShutdown), String> {
-    let store = Store::new(proxy_list);
+    run_with_store(addr, proxy_list, prune_frequency, StoreConfig::Memory).await
+}
+
+/// Run a bootstrap server with a set prune frequency,
+/// keeping agent info in the configured store.
+pub async fn run_with_store(
+    addr: impl Into<SocketAddr> + 'static,
+    proxy_list: Vec<String>,
+    prune_frequency: std::time::Duration,
+    store: StoreConfig,
+) -> Result<(BootstrapDriver, SocketAddr, BootstrapShutdown), String> {
+    let backend = store
+        .open()
+        .map_err(|e| format!("Failed to open store: {}", e))?;
+    let store = Store::with_backend(backend, proxy_list);
 
     {
         let store = store.clone();
         tokio::task::spawn(async move {
             loop {
                 tokio::time::sleep(prune_frequency).await;
-                store.prune();
+                if let Err(e) = store.prune().await {
+                    tracing::warn!("Failed to prune expired agent info: {}", e);
+                }
             }
         });
     }
@@ -1,4 +1,5 @@
 use clap::Parser;
+use kitsune_p2p_bootstrap::{StoreConfig, PRUNE_EXPIRED_FREQ};
 
 #[derive(Parser, Debug)]
 #[clap(version, about, long_about = None)]
@@ -12,10 +13,19 @@ struct Args {
     /// multiple times
     #[clap(short, long, verbatim_doc_comment)]
     proxy: Vec<String>,
+
+    /// keep agent info in a sqlite database at
+    /// this path, so it survives restarts, rather
+    /// than only in memory
+    #[clap(short, long, verbatim_doc_comment)]
+    database: Option<std::path::PathBuf>,
 }
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() {
+    // Logs are filtered with the `RUST_LOG` environment variable,
+    // e.g. `RUST_LOG=warn` to see prune failures.
+    observability::init_fmt(observability::Output::Log).expect("Failed to start logging");
     let args = Args::parse();
 
     use std::net::ToSocketAddrs;
@@ -27,7 +37,12 @@ async fn main() {
         .next()
         .unwrap();
 
-    match kitsune_p2p_bootstrap::run(addr, args.proxy).await {
+    let store = match args.database {
+        Some(path) => StoreConfig::Sqlite(path),
+        None => StoreConfig::Memory,
+    };
+
+    match kitsune_p2p_bootstrap::run_with_store(addr, args.proxy, PRUNE_EXPIRED_FREQ, store).await {
         Ok((driver, addr, _shutdown)) => {
             println!("http://{}", addr);
             driver.await;
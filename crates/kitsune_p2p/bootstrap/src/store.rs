@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use kitsune_p2p_types::{
     agent_info::AgentInfoSigned,
@@ -9,34 +9,150 @@ use kitsune_p2p_types::{
 use parking_lot::RwLock;
 use rand::seq::IteratorRandom;
 
+pub use sqlite::SqliteStore;
+
+mod sqlite;
+
 type AgentMap = HashMap<Arc<KitsuneAgent>, AgentInfoSigned>;
 type SpaceMap = HashMap<Arc<KitsuneSpace>, AgentMap>;
 
+/// The result of a call to a [`StoreBackend`].
+pub type StoreResult<T> = Result<T, String>;
+
+/// Where the bootstrap server keeps the agent info it has been sent.
+#[derive(Clone, Debug)]
+pub enum StoreConfig {
+    /// Keep agent info in memory. It is lost when the server restarts.
+    Memory,
+    /// Keep agent info in a SQLite database at this path,
+    /// which is created if it doesn't exist.
+    Sqlite(PathBuf),
+}
+
+impl StoreConfig {
+    /// Open the backend for this config.
+    pub fn open(&self) -> StoreResult<Arc<dyn StoreBackend>> {
+        Ok(match self {
+            StoreConfig::Memory => Arc::new(MemStore::default()),
+            StoreConfig::Sqlite(path) => Arc::new(SqliteStore::open(path)?),
+        })
+    }
+}
+
+/// The storage behind a bootstrap server.
+///
+/// All times are milliseconds since the unix epoch.
+pub trait StoreBackend: 'static + Send + Sync + std::fmt::Debug {
+    /// Remove all agent info which expired before `now`.
+    fn prune(&self, now: u64) -> StoreResult<()>;
+
+    /// Store agent info, unless newer info for the same agent
+    /// in the same space is already stored.
+    fn put(&self, info: AgentInfoSigned) -> StoreResult<()>;
+
+    /// Choose up to `limit` agents at random from a space which haven't
+    /// expired as of `now` and have at least one url.
+    fn random(
+        &self,
+        space: &KitsuneSpace,
+        limit: usize,
+        now: u64,
+    ) -> StoreResult<Vec<AgentInfoSigned>>;
+
+    /// Remove all agent info.
+    fn clear(&self) -> StoreResult<()>;
+
+    /// Get all stored agent info.
+    fn all(&self) -> StoreResult<SpaceMap>;
+}
+
 #[derive(Clone, Debug)]
-pub(crate) struct Store(Arc<RwLock<SpaceMap>>, Arc<Vec<String>>);
+pub(crate) struct Store(Arc<dyn StoreBackend>, Arc<Vec<String>>);
 
 impl Store {
     pub fn new(proxy_list: Vec<String>) -> Self {
-        Self(Arc::new(RwLock::new(HashMap::new())), Arc::new(proxy_list))
+        Self::with_backend(Arc::new(MemStore::default()), proxy_list)
+    }
+
+    pub fn with_backend(backend: Arc<dyn StoreBackend>, proxy_list: Vec<String>) -> Self {
+        Self(backend, Arc::new(proxy_list))
     }
 
     pub fn proxy_list(&self) -> Arc<Vec<String>> {
         self.1.clone()
     }
 
-    pub fn prune(&self) {
-        let now = std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap server time set before epoch")
-            .as_millis() as u64;
+    pub async fn prune(&self) -> StoreResult<()> {
+        let now = now();
+        self.blocking(move |backend| backend.prune(now)).await
+    }
+
+    pub async fn put(&self, info: AgentInfoSigned) -> StoreResult<()> {
+        self.blocking(move |backend| backend.put(info)).await
+    }
+
+    pub async fn random(&self, query: RandomQuery) -> StoreResult<Vec<Vec<u8>>> {
+        // TODO: Max this limit
+        let limit = query.limit.0 as usize;
+        let now = now();
+        Ok(self
+            .blocking(move |backend| backend.random(query.space.as_ref(), limit, now))
+            .await?
+            .into_iter()
+            .filter_map(|i| {
+                let mut buf = Vec::new();
+                match rmp_encode(&mut buf, i) {
+                    Ok(_) => Some(buf),
+                    Err(_) => None,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn clear(&self) -> StoreResult<()> {
+        self.blocking(|backend| backend.clear()).await
+    }
+
+    /// Call the backend on the blocking thread pool, since a backend
+    /// may block on a lock or on disk IO.
+    async fn blocking<T, F>(&self, f: F) -> StoreResult<T>
+    where
+        T: 'static + Send,
+        F: 'static + Send + FnOnce(&dyn StoreBackend) -> StoreResult<T>,
+    {
+        let backend = self.0.clone();
+        tokio::task::spawn_blocking(move || f(backend.as_ref()))
+            .await
+            .map_err(|e| e.to_string())?
+    }
+
+    #[cfg(test)]
+    pub fn all(&self) -> SpaceMap {
+        self.0.all().unwrap()
+    }
+}
+
+fn now() -> u64 {
+    std::time::UNIX_EPOCH
+        .elapsed()
+        .expect("Bootstrap server time set before epoch")
+        .as_millis() as u64
+}
+
+/// A [`StoreBackend`] which keeps everything in memory.
+#[derive(Debug, Default)]
+pub struct MemStore(RwLock<SpaceMap>);
 
+impl StoreBackend for MemStore {
+    fn prune(&self, now: u64) -> StoreResult<()> {
         self.0.write().retain(|_, map| {
             map.retain(|_, info| info.expires_at_ms >= now);
             !map.is_empty()
         });
+        Ok(())
     }
 
-    pub fn put(&self, info: AgentInfoSigned) {
+    fn put(&self, info: AgentInfoSigned) -> StoreResult<()> {
         let mut lock = self.0.write();
         let space_map = lock.entry(info.space.clone()).or_insert_with(HashMap::new);
         match space_map.entry(info.agent.clone()) {
@@ -49,46 +165,38 @@ impl Store {
                 e.insert(info);
             }
         }
+        Ok(())
     }
 
-    pub fn random(&self, query: RandomQuery) -> Vec<Vec<u8>> {
-        // TODO: Max this limit
-        let limit = query.limit.0 as usize;
+    fn random(
+        &self,
+        space: &KitsuneSpace,
+        limit: usize,
+        now: u64,
+    ) -> StoreResult<Vec<AgentInfoSigned>> {
         let mut rng = rand::thread_rng();
-        let now = std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap server time set before epoch")
-            .as_millis() as u64;
-        self.0
+        Ok(self
+            .0
             .read()
-            .get(query.space.as_ref())
+            .get(space)
             .map(|space| {
                 space
                     .values()
-                    .filter_map(|i| {
-                        if i.expires_at_ms <= now {
-                            return None;
-                        }
-                        if i.url_list.is_empty() {
-                            return None;
-                        }
-                        let mut buf = Vec::new();
-                        match rmp_encode(&mut buf, i) {
-                            Ok(_) => Some(buf),
-                            Err(_) => None,
-                        }
-                    })
+                    .filter(|i| i.expires_at_ms > now && !i.url_list.is_empty())
                     .choose_multiple(&mut rng, limit)
+                    .into_iter()
+                    .cloned()
+                    .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default())
     }
 
-    pub fn clear(&self) {
-        self.0.write().clear()
+    fn clear(&self) -> StoreResult<()> {
+        self.0.write().clear();
+        Ok(())
     }
 
-    #[cfg(test)]
-    pub fn all(&self) -> HashMap<Arc<KitsuneSpace>, HashMap<Arc<KitsuneAgent>, AgentInfoSigned>> {
-        self.0.read().clone()
+    fn all(&self) -> StoreResult<SpaceMap> {
+        Ok(self.0.read().clone())
     }
 }
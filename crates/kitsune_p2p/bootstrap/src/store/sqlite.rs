@@ -0,0 +1,210 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use kitsune_p2p_types::{
+    agent_info::AgentInfoSigned,
+    bin_types::KitsuneSpace,
+    codec::{rmp_decode, rmp_encode},
+};
+use parking_lot::Mutex;
+use rusqlite::{named_params, Connection};
+
+use super::{SpaceMap, StoreBackend, StoreResult};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS agent_info (
+        space           BLOB        NOT NULL,
+        agent           BLOB        NOT NULL,
+        signed_at_ms    INTEGER     NOT NULL,
+        expires_at_ms   INTEGER     NOT NULL,
+        has_urls        INTEGER     NOT NULL,
+        blob            BLOB        NOT NULL,
+        PRIMARY KEY (space, agent)
+    );
+    CREATE INDEX IF NOT EXISTS agent_info_expires_idx ON agent_info (expires_at_ms);
+";
+
+/// Only replace the stored info for an agent with info that was signed later.
+const PUT: &str = "
+    INSERT INTO agent_info (space, agent, signed_at_ms, expires_at_ms, has_urls, blob)
+    VALUES (:space, :agent, :signed_at_ms, :expires_at_ms, :has_urls, :blob)
+    ON CONFLICT (space, agent) DO UPDATE SET
+        signed_at_ms = excluded.signed_at_ms,
+        expires_at_ms = excluded.expires_at_ms,
+        has_urls = excluded.has_urls,
+        blob = excluded.blob
+    WHERE excluded.signed_at_ms > agent_info.signed_at_ms
+";
+
+const RANDOM: &str = "
+    SELECT blob FROM agent_info
+    WHERE space = :space AND expires_at_ms > :now AND has_urls
+    ORDER BY RANDOM()
+    LIMIT :limit
+";
+
+/// A [`StoreBackend`] which keeps agent info in a SQLite database,
+/// so it survives restarts of the bootstrap server.
+#[derive(Debug)]
+pub struct SqliteStore(Mutex<Connection>);
+
+impl SqliteStore {
+    /// Open the database at this path, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> StoreResult<Self> {
+        let conn = Connection::open(path).map_err(err_string)?;
+        // Write-ahead logging lets readers carry on while a put is written.
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .map_err(err_string)?;
+        conn.execute_batch(SCHEMA).map_err(err_string)?;
+        Ok(Self(Mutex::new(conn)))
+    }
+}
+
+impl StoreBackend for SqliteStore {
+    fn prune(&self, now: u64) -> StoreResult<()> {
+        self.0
+            .lock()
+            .execute(
+                "DELETE FROM agent_info WHERE expires_at_ms < :now",
+                named_params! { ":now": now as i64 },
+            )
+            .map_err(err_string)?;
+        Ok(())
+    }
+
+    fn put(&self, info: AgentInfoSigned) -> StoreResult<()> {
+        let mut blob = Vec::new();
+        rmp_encode(&mut blob, &info).map_err(err_string)?;
+        self.0
+            .lock()
+            .execute(
+                PUT,
+                named_params! {
+                    ":space": info.space.0,
+                    ":agent": info.agent.0,
+                    ":signed_at_ms": info.signed_at_ms as i64,
+                    ":expires_at_ms": info.expires_at_ms as i64,
+                    ":has_urls": !info.url_list.is_empty(),
+                    ":blob": blob,
+                },
+            )
+            .map_err(err_string)?;
+        Ok(())
+    }
+
+    fn random(
+        &self,
+        space: &KitsuneSpace,
+        limit: usize,
+        now: u64,
+    ) -> StoreResult<Vec<AgentInfoSigned>> {
+        let conn = self.0.lock();
+        let mut stmt = conn.prepare_cached(RANDOM).map_err(err_string)?;
+        let rows = stmt
+            .query_map(
+                named_params! {
+                    ":space": space.0,
+                    ":now": now as i64,
+                    ":limit": limit as i64,
+                },
+                |row| row.get::<_, Vec<u8>>(0),
+            )
+            .map_err(err_string)?;
+        rows.map(|blob| decode(&blob.map_err(err_string)?))
+            .collect()
+    }
+
+    fn clear(&self) -> StoreResult<()> {
+        self.0
+            .lock()
+            .execute("DELETE FROM agent_info", [])
+            .map_err(err_string)?;
+        Ok(())
+    }
+
+    fn all(&self) -> StoreResult<SpaceMap> {
+        let conn = self.0.lock();
+        let mut stmt = conn
+            .prepare("SELECT blob FROM agent_info")
+            .map_err(err_string)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))
+            .map_err(err_string)?;
+        let mut all = SpaceMap::new();
+        for blob in rows {
+            let info = decode(&blob.map_err(err_string)?)?;
+            all.entry(info.space.clone())
+                .or_insert_with(HashMap::new)
+                .insert(info.agent.clone(), info);
+        }
+        Ok(all)
+    }
+}
+
+fn decode(blob: &[u8]) -> StoreResult<AgentInfoSigned> {
+    rmp_decode(&mut &blob[..]).map_err(err_string)
+}
+
+fn err_string(e: impl std::fmt::Display) -> String {
+    e.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixt::prelude::*;
+    use kitsune_p2p::fixt::*;
+    use kitsune_p2p_types::bin_types::KitsuneAgent;
+
+    async fn agent_info(
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        signed_at_ms: u64,
+        expires_at_ms: u64,
+    ) -> AgentInfoSigned {
+        AgentInfoSigned::sign(
+            space,
+            agent,
+            u32::MAX / 4,
+            vec!["fake:".into()],
+            signed_at_ms,
+            expires_at_ms,
+            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sqlite_store_put_random_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteStore::open(&dir.path().join("bootstrap.sqlite3")).unwrap();
+        let space = Arc::new(fixt!(KitsuneSpace));
+
+        let agent = Arc::new(fixt!(KitsuneAgent, Unpredictable));
+        let live = agent_info(space.clone(), agent.clone(), 10, 1_000).await;
+        let expired = agent_info(
+            space.clone(),
+            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+            10,
+            100,
+        )
+        .await;
+        store.put(live.clone()).unwrap();
+        store.put(expired).unwrap();
+
+        // Older info doesn't replace newer info.
+        let older = agent_info(space.clone(), agent, 5, 2_000).await;
+        store.put(older).unwrap();
+
+        assert_eq!(store.random(&space, 10, 500).unwrap(), vec![live.clone()]);
+        assert_eq!(store.all().unwrap()[&space].len(), 2);
+
+        store.prune(500).unwrap();
+        let all = store.all().unwrap();
+        assert_eq!(all[&space].len(), 1);
+        assert_eq!(all[&space][&live.agent], live);
+
+        store.clear().unwrap();
+        assert!(store.all().unwrap().is_empty());
+    }
+}
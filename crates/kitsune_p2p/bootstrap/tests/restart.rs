@@ -0,0 +1,90 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use fixt::prelude::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::fixt::*;
+use kitsune_p2p::KitsuneSpace;
+use kitsune_p2p_bootstrap::StoreConfig;
+use kitsune_p2p_bootstrap::PRUNE_EXPIRED_FREQ;
+use kitsune_p2p_types::bootstrap::RandomLimit;
+use kitsune_p2p_types::bootstrap::RandomQuery;
+use kitsune_p2p_types::codec::rmp_decode;
+use kitsune_p2p_types::codec::rmp_encode;
+
+async fn api<I: serde::Serialize>(addr: SocketAddr, op: &str, input: I) -> Vec<u8> {
+    let mut body = Vec::new();
+    rmp_encode(&mut body, input).unwrap();
+    let res = reqwest::Client::new()
+        .post(format!("http://{}", addr))
+        .body(body)
+        .header("X-Op", op)
+        .header(reqwest::header::CONTENT_TYPE, "application/octet")
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    res.bytes().await.unwrap().to_vec()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sqlite_store_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = StoreConfig::Sqlite(dir.path().join("bootstrap.sqlite3"));
+    let space: Arc<KitsuneSpace> = Arc::new(fixt!(KitsuneSpace));
+
+    let (driver, addr, shutdown) = kitsune_p2p_bootstrap::run_with_store(
+        ([127, 0, 0, 1], 0),
+        vec![],
+        PRUNE_EXPIRED_FREQ,
+        store.clone(),
+    )
+    .await
+    .unwrap();
+    let server = tokio::spawn(driver);
+
+    let mut peers = Vec::new();
+    for _ in 0..5 {
+        let info = AgentInfoSigned::sign(
+            space.clone(),
+            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+            u32::MAX / 4,
+            vec!["fake:".into()],
+            0,
+            std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
+            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
+        )
+        .await
+        .unwrap();
+        api(addr, "put", info.clone()).await;
+        peers.push(info);
+    }
+
+    shutdown();
+    server.await.unwrap();
+
+    let (driver, addr, _shutdown) = kitsune_p2p_bootstrap::run_with_store(
+        ([127, 0, 0, 1], 0),
+        vec![],
+        PRUNE_EXPIRED_FREQ,
+        store,
+    )
+    .await
+    .unwrap();
+    tokio::spawn(driver);
+
+    let query = RandomQuery {
+        space,
+        limit: RandomLimit(10),
+    };
+    let result: Vec<Vec<u8>> =
+        rmp_decode(&mut api(addr, "random", query).await.as_slice()).unwrap();
+    let result: Vec<AgentInfoSigned> = result
+        .into_iter()
+        .map(|bytes| rmp_decode(&mut bytes.as_slice()).unwrap())
+        .collect();
+    assert_eq!(result.len(), peers.len());
+    for peer in &result {
+        assert!(peers.contains(peer));
+    }
+}
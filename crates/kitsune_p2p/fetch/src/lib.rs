@@ -53,6 +53,29 @@ pub struct FetchQueuePush {
 
     /// Opaque "context" to be provided and interpreted by the host.
     pub context: Option<FetchContext>,
+
+    /// Whether this should be fetched before other items.
+    /// Pushing the same key again can raise its priority, but never lower it.
+    pub priority: FetchPriority,
+}
+
+/// How urgently an item of the fetch queue should be fetched.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum FetchPriority {
+    /// Fetched in the order items were added.
+    Normal,
+
+    /// Fetched before any normal priority item, e.g. ops authored by
+    /// our own agents, or ops we need to validate.
+    High,
+}
+
+impl Default for FetchPriority {
+    fn default() -> Self {
+        Self::Normal
+    }
 }
 
 /// Usage agnostic context data.
@@ -9,14 +9,20 @@
 //! from the current moment. The items thus returned are not guaranteed to be returned in
 //! order of last_fetch time, but they are guaranteed to be at least as old as the specified
 //! interval.
+//!
+//! Each source of an item is retried with an exponential backoff. Sources which never
+//! deliver the item, or to which fetch requests repeatedly cannot be sent, are dropped,
+//! and an item is removed once it has no sources left or has been queued for too long.
+//! [`FetchPriority::High`] items are always fetched before any other items.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::time::{Duration, Instant};
+use tokio::time::Instant;
 
 use kitsune_p2p_types::{tx2::tx2_utils::ShareOpen, KAgent, KSpace /*, Tx2Cert*/};
 use linked_hash_map::{Entry, LinkedHashMap};
 
-use crate::{FetchContext, FetchKey, FetchQueuePush, RoughInt};
+use crate::{FetchContext, FetchKey, FetchPriority, FetchQueuePush, RoughInt};
 
 mod queue_reader;
 pub use queue_reader::*;
@@ -68,12 +74,37 @@ pub trait FetchQueueConfig: 'static + Send + Sync {
 
     /// How long between successive fetches from a particular source, for a particular item?
     /// This protects us from wasting resources on a source which may be offline.
-    /// This will eventually be replaced with an exponential backoff which will be
-    /// tracked for this source across all items.
+    /// The delay is doubled after every further request to the same source,
+    /// up to [`FetchQueueConfig::max_retry_delay`].
     fn source_retry_delay(&self) -> std::time::Duration {
         std::time::Duration::from_secs(5 * 60)
     }
 
+    /// The longest the backed off delay between fetches from a particular source,
+    /// for a particular item, can grow.
+    fn max_retry_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(30 * 60)
+    }
+
+    /// How many times an item is requested from a source before that source is
+    /// dropped from the item. The source is dropped once the delay following its
+    /// last request has passed without the item being received.
+    fn max_source_attempts(&self) -> u32 {
+        5
+    }
+
+    /// How many fetch requests in a row may fail to be sent to a source before
+    /// that source is dropped from every item in the queue.
+    fn max_source_failures(&self) -> u32 {
+        3
+    }
+
+    /// How long an item may stay in the queue before it is dropped, whether
+    /// or not it has sources left to try.
+    fn max_item_age(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(2 * 60 * 60)
+    }
+
     /// When a fetch key is added twice, this determines how the two different contexts
     /// get reconciled.
     fn merge_fetch_contexts(&self, a: u32, b: u32) -> u32;
@@ -82,19 +113,37 @@ pub trait FetchQueueConfig: 'static + Send + Sync {
 /// The actual inner state of the FetchQueue, from which items can be obtained
 #[derive(Debug)]
 pub struct State {
-    /// Items ready to be fetched
-    queue: LinkedHashMap<FetchKey, FetchQueueItem>,
+    /// High priority items, which are fetched before any others
+    high: LinkedHashMap<FetchKey, FetchQueueItem>,
+    /// Normal priority items
+    normal: LinkedHashMap<FetchKey, FetchQueueItem>,
+    /// The number of fetch requests in a row which could not be sent to each source
+    source_failures: HashMap<FetchSource, u32>,
+    /// The number of items removed from the queue without being fetched, per space
+    removed: HashMap<KSpace, RemovedItems>,
 }
 
 #[allow(clippy::derivable_impls)]
 impl Default for State {
     fn default() -> Self {
         Self {
-            queue: Default::default(),
+            high: Default::default(),
+            normal: Default::default(),
+            source_failures: Default::default(),
+            removed: Default::default(),
         }
     }
 }
 
+/// Counts of the items of a space which were removed from the queue without being fetched
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct RemovedItems {
+    /// Items which were queued for longer than [`FetchQueueConfig::max_item_age`]
+    expired: u64,
+    /// Items whose sources were all dropped
+    dropped: u64,
+}
+
 /// A mutable iterator over the FetchQueue State
 pub struct StateIter<'a> {
     state: &'a mut State,
@@ -119,12 +168,18 @@ pub struct FetchQueueItem {
     pub context: Option<FetchContext>,
     /// The last time we tried fetching this item from any source
     last_fetch: Option<Instant>,
+    /// Whether this item is fetched before others
+    priority: FetchPriority,
+    /// When this item was added to the queue
+    added: Instant,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 struct SourceRecord {
     source: FetchSource,
     last_request: Option<Instant>,
+    /// The number of times this item has been requested from this source
+    attempts: u32,
 }
 
 /// A source to fetch from: either a node, or an agent on a node
@@ -164,14 +219,10 @@ impl FetchQueue {
     /// Add an item to the queue.
     /// If the FetchKey does not already exist, add it to the end of the queue.
     /// If the FetchKey exists, add the new source and merge the context in, without
-    /// changing the position in the queue.
+    /// changing the position in the queue, unless its priority is raised.
     pub fn push(&self, args: FetchQueuePush) {
         self.state.share_mut(|s| {
-            tracing::debug!(
-                "FetchQueue (size = {}) item added: {:?}",
-                s.queue.len() + 1,
-                args
-            );
+            tracing::debug!("FetchQueue (size = {}) item added: {:?}", s.len() + 1, args);
             s.push(&*self.config, args);
         });
     }
@@ -182,7 +233,7 @@ impl FetchQueue {
             let removed = s.remove(key);
             tracing::debug!(
                 "FetchQueue (size = {}) item removed: key={:?} val={:?}",
-                s.queue.len(),
+                s.len(),
                 key,
                 removed
             );
@@ -190,6 +241,22 @@ impl FetchQueue {
         })
     }
 
+    /// Record that a fetch request could not be sent to a source.
+    /// After [`FetchQueueConfig::max_source_failures`] failures in a row,
+    /// the source is dropped from every item.
+    pub fn source_failed(&self, source: &FetchSource) {
+        self.state
+            .share_mut(|s| s.source_failed(&*self.config, source));
+    }
+
+    /// Record that a fetch request was sent to a source,
+    /// which resets its count of failures.
+    pub fn source_succeeded(&self, source: &FetchSource) {
+        self.state.share_mut(|s| {
+            s.source_failures.remove(source);
+        });
+    }
+
     /// Get a list of the next items that should be fetched.
     pub fn get_items_to_fetch(&self) -> Vec<(FetchKey, KSpace, FetchSource, Option<FetchContext>)> {
         self.state.share_mut(|s| {
@@ -208,7 +275,8 @@ impl State {
     /// Add an item to the queue.
     /// If the FetchKey does not already exist, add it to the end of the queue.
     /// If the FetchKey exists, add the new source and merge the context in, without
-    /// changing the position in the queue.
+    /// changing the position in the queue, unless its priority is raised, which
+    /// moves it to the end of the high priority items.
     pub fn push(&mut self, config: &dyn FetchQueueConfig, args: FetchQueuePush) {
         let FetchQueuePush {
             key,
@@ -217,14 +285,27 @@ impl State {
             space,
             source,
             size,
+            priority,
         } = args;
 
-        match self.queue.entry(key) {
+        if priority == FetchPriority::High {
+            if let Some(item) = self.normal.remove(&key) {
+                self.high.insert(key.clone(), item);
+            }
+        }
+        let queue = if priority == FetchPriority::High || self.high.contains_key(&key) {
+            &mut self.high
+        } else {
+            &mut self.normal
+        };
+
+        match queue.entry(key) {
             Entry::Vacant(e) => {
-                let sources = if let Some(author) = author {
-                    Sources(vec![SourceRecord::new(source), SourceRecord::agent(author)])
-                } else {
-                    Sources(vec![SourceRecord::new(source)])
+                let sources = match author {
+                    Some(author) if source != FetchSource::Agent(author.clone()) => {
+                        Sources(vec![SourceRecord::new(source), SourceRecord::agent(author)])
+                    }
+                    _ => Sources(vec![SourceRecord::new(source)]),
                 };
                 let item = FetchQueueItem {
                     sources,
@@ -232,12 +313,19 @@ impl State {
                     size,
                     context,
                     last_fetch: None,
+                    priority,
+                    added: Instant::now(),
                 };
                 e.insert(item);
             }
             Entry::Occupied(mut e) => {
                 let v = e.get_mut();
-                v.sources.0.insert(0, SourceRecord::new(source));
+                // A known source keeps its record, so that its backoff
+                // and attempts still apply.
+                if !v.sources.0.iter().any(|s| s.source == source) {
+                    v.sources.0.insert(0, SourceRecord::new(source));
+                }
+                v.priority = v.priority.max(priority);
                 v.context = match (v.context.take(), context) {
                     (Some(a), Some(b)) => Some(config.merge_fetch_contexts(*a, *b).into()),
                     (a, b) => a.and(b),
@@ -259,7 +347,47 @@ impl State {
 
     /// When an item has been successfully fetched, we can remove it from the queue.
     pub fn remove(&mut self, key: &FetchKey) -> Option<FetchQueueItem> {
-        self.queue.remove(key)
+        self.high.remove(key).or_else(|| self.normal.remove(key))
+    }
+
+    /// The number of items in the queue, of any priority.
+    pub fn len(&self) -> usize {
+        self.high.len() + self.normal.len()
+    }
+
+    /// Whether the queue has no items of any priority.
+    pub fn is_empty(&self) -> bool {
+        self.high.is_empty() && self.normal.is_empty()
+    }
+
+    /// All items in the queue, high priority items first.
+    pub(crate) fn items(&self) -> impl Iterator<Item = &FetchQueueItem> {
+        self.high.values().chain(self.normal.values())
+    }
+
+    /// Count a failure to send a fetch request to a source, dropping the source
+    /// from every item once it has failed too many times in a row.
+    pub fn source_failed(&mut self, config: &dyn FetchQueueConfig, source: &FetchSource) {
+        let failures = self.source_failures.entry(source.clone()).or_default();
+        *failures += 1;
+        if *failures < config.max_source_failures() {
+            return;
+        }
+        self.source_failures.remove(source);
+        tracing::debug!("FetchQueue dropping unreachable source: {:?}", source);
+
+        let mut emptied = Vec::new();
+        for (key, item) in self.high.iter_mut().chain(self.normal.iter_mut()) {
+            item.sources.0.retain(|s| s.source != *source);
+            if item.sources.0.is_empty() {
+                emptied.push(key.clone());
+            }
+        }
+        for key in emptied {
+            if let Some(item) = self.remove(&key) {
+                self.removed.entry(item.space).or_default().dropped += 1;
+            }
+        }
     }
 }
 
@@ -267,21 +395,47 @@ impl<'a> Iterator for StateIter<'a> {
     type Item = (FetchKey, KSpace, FetchSource, Option<FetchContext>);
 
     fn next(&mut self) -> Option<Self::Item> {
+        // High priority items are checked first, so they are fetched before
+        // any other items as long as they have a source ready.
         let keys: Vec<_> = self
             .state
-            .queue
+            .high
             .keys()
+            .map(|key| (FetchPriority::High, key))
+            .chain(
+                self.state
+                    .normal
+                    .keys()
+                    .map(|key| (FetchPriority::Normal, key)),
+            )
             .take(NUM_ITEMS_PER_POLL)
-            .cloned()
+            .map(|(priority, key)| (priority, key.clone()))
             .collect();
-        for key in keys {
-            let item = self.state.queue.get_refresh(&key)?;
+        for (priority, key) in keys {
+            let queue = match priority {
+                FetchPriority::High => &mut self.state.high,
+                FetchPriority::Normal => &mut self.state.normal,
+            };
+            let item = queue.get_refresh(&key)?;
+            if item.added.elapsed() >= self.config.max_item_age() {
+                let item = queue.remove(&key)?;
+                tracing::debug!("FetchQueue item expired: key={:?} val={:?}", key, item);
+                self.state.removed.entry(item.space).or_default().expired += 1;
+                continue;
+            }
             let item_not_recently_fetched = item
                 .last_fetch
                 .map(|t| t.elapsed() >= self.config.item_retry_delay())
                 .unwrap_or(true);
             if item_not_recently_fetched {
-                if let Some(source) = item.sources.next(self.config.source_retry_delay()) {
+                let next = item.sources.next(self.config);
+                if item.sources.0.is_empty() {
+                    let item = queue.remove(&key)?;
+                    tracing::debug!("FetchQueue item dropped: key={:?} val={:?}", key, item);
+                    self.state.removed.entry(item.space).or_default().dropped += 1;
+                    continue;
+                }
+                if let Some(source) = next {
                     let space = item.space.clone();
                     item.last_fetch = Some(Instant::now());
                     return Some((key, space, source, item.context));
@@ -297,31 +451,45 @@ impl SourceRecord {
         Self {
             source,
             last_request: None,
+            attempts: 0,
         }
     }
 
     fn agent(agent: KAgent) -> Self {
-        Self {
-            source: FetchSource::Agent(agent),
-            last_request: None,
+        Self::new(FetchSource::Agent(agent))
+    }
+
+    /// Whether the backed off delay following the last request has passed.
+    fn is_ready(&self, config: &dyn FetchQueueConfig) -> bool {
+        match self.last_request {
+            None => true,
+            Some(t) => {
+                let doublings = self.attempts.saturating_sub(1).min(31);
+                let delay = config
+                    .source_retry_delay()
+                    .saturating_mul(1 << doublings)
+                    .min(config.max_retry_delay());
+                t.elapsed() >= delay
+            }
         }
     }
 }
 
 impl Sources {
-    fn next(&mut self, interval: Duration) -> Option<FetchSource> {
+    /// Get the next source whose backoff has passed, and move it to the back of the list.
+    /// Sources which have had all their attempts are dropped instead.
+    fn next(&mut self, config: &dyn FetchQueueConfig) -> Option<FetchSource> {
+        self.0
+            .retain(|s| s.attempts < config.max_source_attempts() || !s.is_ready(config));
         if let Some((i, agent)) = self
             .0
             .iter()
             .enumerate()
-            .find(|(_, s)| {
-                s.last_request
-                    .map(|t| t.elapsed() >= interval)
-                    .unwrap_or(true)
-            })
+            .find(|(_, s)| s.is_ready(config))
             .map(|(i, s)| (i, s.source.clone()))
         {
             self.0[i].last_request = Some(Instant::now());
+            self.0[i].attempts += 1;
             self.0.rotate_left(i + 1);
             Some(agent)
         } else {
@@ -368,6 +536,7 @@ mod tests {
             space: space(0),
             source,
             size: None,
+            priority: FetchPriority::Normal,
         }
     }
 
@@ -382,6 +551,8 @@ mod tests {
             context,
             size: None,
             last_fetch: None,
+            priority: FetchPriority::Normal,
+            added: Instant::now(),
         }
     }
 
@@ -403,41 +574,43 @@ mod tests {
 
     #[tokio::test(start_paused = true)]
     async fn source_rotation() {
-        let sec1 = Duration::from_secs(10);
+        let cfg = &Config(1, 10);
         let mut ss = Sources(vec![
             SourceRecord {
                 source: source(1),
                 last_request: Some(Instant::now()),
+                attempts: 1,
             }
             .into(),
             SourceRecord {
                 source: source(2),
                 last_request: None,
+                attempts: 0,
             }
             .into(),
         ]);
 
         tokio::time::advance(Duration::from_secs(1)).await;
 
-        assert_eq!(ss.next(sec1), Some(source(2)));
-        assert_eq!(ss.next(sec1), None);
+        assert_eq!(ss.next(cfg), Some(source(2)));
+        assert_eq!(ss.next(cfg), None);
 
         tokio::time::advance(Duration::from_secs(9)).await;
 
-        assert_eq!(ss.next(sec1), Some(source(1)));
+        assert_eq!(ss.next(cfg), Some(source(1)));
 
         tokio::time::advance(Duration::from_secs(1)).await;
 
-        assert_eq!(ss.next(sec1), Some(source(2)));
+        assert_eq!(ss.next(cfg), Some(source(2)));
         // source 1 has already had its delay backed off to 20s
         // due to a retry, so it returns None
-        assert_eq!(ss.next(sec1), None);
+        assert_eq!(ss.next(cfg), None);
 
         tokio::time::advance(Duration::from_secs(20)).await;
 
-        assert_eq!(ss.next(sec1), Some(source(1)));
-        assert_eq!(ss.next(sec1), Some(source(2)));
-        assert_eq!(ss.next(sec1), None);
+        assert_eq!(ss.next(cfg), Some(source(1)));
+        assert_eq!(ss.next(cfg), Some(source(2)));
+        assert_eq!(ss.next(cfg), None);
     }
 
     #[test]
@@ -458,7 +631,39 @@ mod tests {
         .into_iter()
         .collect();
 
-        assert_eq!(q.queue, expected_ready);
+        assert_eq!(q.normal, expected_ready);
+        assert!(q.high.is_empty());
+    }
+
+    #[test]
+    fn author_added_as_source() {
+        let mut q = State::default();
+        let c = Config(1, 1);
+
+        q.push(
+            &c,
+            FetchQueuePush {
+                author: Some(Arc::new(KitsuneAgent::new(vec![2; 36]))),
+                ..req(1, ctx(0), source(1))
+            },
+        );
+        // The author is not added twice when it is also the source
+        q.push(
+            &c,
+            FetchQueuePush {
+                author: Some(Arc::new(KitsuneAgent::new(vec![3; 36]))),
+                ..req(2, ctx(0), source(3))
+            },
+        );
+
+        assert_eq!(
+            q.normal[&key_op(1)].sources,
+            Sources(sources([1, 2]).into_iter().map(SourceRecord::new).collect())
+        );
+        assert_eq!(
+            q.normal[&key_op(2)].sources,
+            Sources(vec![SourceRecord::new(source(3))])
+        );
     }
 
     #[tokio::test(start_paused = true)]
@@ -474,8 +679,11 @@ mod tests {
             // so it won't show up in next() right away
             queue[1].1.sources.0[1].last_request = Some(Instant::now() - Duration::from_secs(3));
 
-            let queue = queue.into_iter().collect();
-            State { queue }
+            let normal = queue.into_iter().collect();
+            State {
+                normal,
+                ..Default::default()
+            }
         };
 
         // We can try fetching items one source at a time by waiting 1 sec in between
@@ -507,4 +715,137 @@ mod tests {
 
         assert_eq!(q.iter_mut(&cfg).count(), 3);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn source_backoff_and_drop() {
+        let cfg = Config(1, 1);
+        let mut q = State::default();
+        q.push(&cfg, req(1, ctx(0), source(1)));
+
+        // The only source is retried with a doubling delay
+        let mut fetched_at = vec![];
+        for t in 0..40 {
+            if q.iter_mut(&cfg).count() > 0 {
+                fetched_at.push(t);
+            }
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        assert_eq!(fetched_at, vec![0, 1, 3, 7, 15]);
+
+        // Its last attempt went unanswered, so the source was dropped,
+        // and the item along with it.
+        assert!(q.is_empty());
+        assert_eq!(q.removed[&space(0)].dropped, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn source_pushed_again_keeps_backoff() {
+        let cfg = Config(1, 1);
+        let mut q = State::default();
+        q.push(&cfg, req(1, ctx(0), source(1)));
+
+        // Pushing the same source again doesn't reset its backoff
+        let mut fetched_at = vec![];
+        for t in 0..40 {
+            if !q.is_empty() {
+                q.push(&cfg, req(1, ctx(0), source(1)));
+                assert_eq!(q.normal[&key_op(1)].sources.0.len(), 1);
+            }
+            if q.iter_mut(&cfg).count() > 0 {
+                fetched_at.push(t);
+            }
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        assert_eq!(fetched_at, vec![0, 1, 3, 7, 15]);
+
+        // Nor does it stop the source from being dropped
+        assert!(q.is_empty());
+        assert_eq!(q.removed[&space(0)].dropped, 1);
+    }
+
+    #[test]
+    fn unreachable_source_dropped() {
+        let q = FetchQueue::new(Arc::new(Config(1, 1)));
+        q.push(req(1, ctx(0), source(1)));
+        q.push(req(2, ctx(0), source(1)));
+        q.push(req(2, ctx(0), source(2)));
+
+        // A successful send resets the count of failures
+        q.source_failed(&source(1));
+        q.source_failed(&source(1));
+        q.source_succeeded(&source(1));
+        q.source_failed(&source(1));
+        q.source_failed(&source(1));
+        q.state.share_ref(|s| assert_eq!(s.len(), 2));
+
+        q.source_failed(&source(1));
+        q.state.share_ref(|s| {
+            assert_eq!(
+                s.normal.keys().cloned().collect::<Vec<_>>(),
+                vec![key_op(2)]
+            );
+            assert_eq!(
+                s.normal[&key_op(2)].sources,
+                Sources(vec![SourceRecord::new(source(2))])
+            );
+            assert_eq!(s.removed[&space(0)].dropped, 1);
+        });
+    }
+
+    #[test]
+    fn priority_items_first() {
+        let cfg = Config(1, 1);
+        let mut q = State::default();
+        q.push(&cfg, req(1, ctx(0), source(1)));
+        q.push(&cfg, req(2, ctx(0), source(1)));
+        q.push(
+            &cfg,
+            FetchQueuePush {
+                priority: FetchPriority::High,
+                ..req(3, ctx(0), source(1))
+            },
+        );
+
+        // Pushing an item again can raise its priority, which moves it behind
+        // the other high priority items, but never lower it
+        q.push(
+            &cfg,
+            FetchQueuePush {
+                priority: FetchPriority::High,
+                ..req(2, ctx(0), source(2))
+            },
+        );
+        q.push(&cfg, req(3, ctx(0), source(2)));
+
+        let keys: Vec<_> = q.iter_mut(&cfg).map(|(key, ..)| key).collect();
+        assert_eq!(keys, vec![key_op(3), key_op(2), key_op(1)]);
+        assert_eq!(q.high.len(), 2);
+        assert_eq!(q.normal.len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn items_expire() {
+        struct ExpiringConfig;
+
+        impl FetchQueueConfig for ExpiringConfig {
+            fn merge_fetch_contexts(&self, a: u32, b: u32) -> u32 {
+                a | b
+            }
+
+            fn max_item_age(&self) -> Duration {
+                Duration::from_secs(60)
+            }
+        }
+
+        let cfg = ExpiringConfig;
+        let mut q = State::default();
+        q.push(&cfg, req(1, ctx(0), source(1)));
+        assert_eq!(q.iter_mut(&cfg).count(), 1);
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+
+        assert_eq!(q.iter_mut(&cfg).count(), 0);
+        assert!(q.is_empty());
+        assert_eq!(q.removed[&space(0)].expired, 1);
+    }
 }
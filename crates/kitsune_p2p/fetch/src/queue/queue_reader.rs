@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use kitsune_p2p_types::KSpace;
 
-use crate::FetchQueue;
+use crate::{FetchPriority, FetchQueue};
 
 /// Read-only access to the queue
 #[derive(Clone, derive_more::From)]
@@ -12,8 +12,7 @@ impl FetchQueueReader {
     /// Get info about the queue, filtered by space
     pub fn info(&self, spaces: HashSet<KSpace>) -> FetchQueueInfo {
         let (count, bytes) = self.0.state.share_ref(|s| {
-            s.queue
-                .values()
+            s.items()
                 .filter(|v| spaces.contains(&v.space))
                 .filter_map(|v| v.size.map(|s| s.get()))
                 .fold((0, 0), |(c, s), t| (c + 1, s + t))
@@ -23,6 +22,26 @@ impl FetchQueueReader {
             num_ops_to_fetch: count,
         }
     }
+
+    /// Get metrics about the items of a single space
+    pub fn metrics(&self, space: &KSpace) -> FetchQueueMetrics {
+        self.0.state.share_ref(|s| {
+            let mut metrics = FetchQueueMetrics::default();
+            for item in s.items().filter(|v| v.space == *space) {
+                metrics.num_items += 1;
+                if item.priority == FetchPriority::High {
+                    metrics.num_high_priority_items += 1;
+                }
+                let age = item.added.elapsed().as_millis() as u64;
+                metrics.oldest_item_age_ms = metrics.oldest_item_age_ms.max(age);
+            }
+            if let Some(removed) = s.removed.get(space) {
+                metrics.num_items_expired = removed.expired;
+                metrics.num_items_dropped = removed.dropped;
+            }
+            metrics
+        })
+    }
 }
 
 /// Info about the fetch queue
@@ -35,13 +54,32 @@ pub struct FetchQueueInfo {
     pub num_ops_to_fetch: usize,
 }
 
+/// Metrics about the items of one space in the fetch queue
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FetchQueueMetrics {
+    /// Number of items waiting to be fetched
+    pub num_items: usize,
+
+    /// Number of those items with [`FetchPriority::High`]
+    pub num_high_priority_items: usize,
+
+    /// How long the oldest of those items has been waiting, in milliseconds
+    pub oldest_item_age_ms: u64,
+
+    /// Number of items dropped because they were not fetched in time
+    pub num_items_expired: u64,
+
+    /// Number of items dropped because none of their sources delivered them
+    pub num_items_dropped: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
     use kitsune_p2p_types::tx2::tx2_utils::ShareOpen;
 
-    use crate::{queue::tests::*, State};
+    use crate::{queue::tests::*, FetchQueuePush, State};
 
     use super::*;
 
@@ -58,10 +96,13 @@ mod tests {
             queue[0].1.size = Some(100.into());
             queue[1].1.size = Some(1000.into());
 
-            let queue = queue.into_iter().collect();
+            let normal = queue.into_iter().collect();
             FetchQueueReader(FetchQueue {
                 config: Arc::new(cfg),
-                state: ShareOpen::new(State { queue }),
+                state: ShareOpen::new(State {
+                    normal,
+                    ..Default::default()
+                }),
             })
         };
         let info = q.info([space(0)].into_iter().collect());
@@ -69,4 +110,44 @@ mod tests {
         assert_eq!(info.num_ops_to_fetch, 2);
         assert_eq!(info.op_bytes_to_fetch, 1100);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn queue_metrics() {
+        let q = FetchQueueReader(FetchQueue::new(Arc::new(Config(1, 1))));
+        q.0.push(req(1, ctx(0), source(1)));
+        tokio::time::advance(std::time::Duration::from_secs(3)).await;
+        q.0.push(FetchQueuePush {
+            priority: FetchPriority::High,
+            ..req(2, ctx(0), source(1))
+        });
+        q.0.push(FetchQueuePush {
+            space: space(1),
+            ..req(3, ctx(0), source(1))
+        });
+        q.0.source_failed(&source(2));
+
+        assert_eq!(
+            q.metrics(&space(0)),
+            FetchQueueMetrics {
+                num_items: 2,
+                num_high_priority_items: 1,
+                oldest_item_age_ms: 3000,
+                num_items_expired: 0,
+                num_items_dropped: 0,
+            }
+        );
+
+        // Dropping the only source of every item removes them all
+        for _ in 0..3 {
+            q.0.source_failed(&source(1));
+        }
+        assert_eq!(
+            q.metrics(&space(0)),
+            FetchQueueMetrics {
+                num_items_dropped: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(q.metrics(&space(1)).num_items_dropped, 1);
+    }
 }
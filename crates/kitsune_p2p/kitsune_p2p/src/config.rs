@@ -49,6 +49,10 @@ pub struct KitsuneP2pConfig {
     pub tuning_params: KitsuneP2pTuningParams,
     /// The network used for connecting to other peers
     pub network_type: NetworkType,
+    /// The gossip strategies which the `gossip_strategy` tuning param
+    /// can name. Defaults to the built in strategies.
+    #[serde(skip)]
+    pub gossip_strategies: crate::gossip::GossipStrategyRegistry,
 }
 
 impl Default for KitsuneP2pConfig {
@@ -58,6 +62,7 @@ impl Default for KitsuneP2pConfig {
             bootstrap_service: None,
             tuning_params: KitsuneP2pTuningParams::default(),
             network_type: NetworkType::QuicBootstrap,
+            gossip_strategies: Default::default(),
         }
     }
 }
@@ -19,8 +19,19 @@
 //! deterministic hashes associated with each based on the contents, which are sent to the gossip partner.
 //! For regions which mismatch, the ops in those regions will be exchanged between partners. For regions
 //! which match, no data will be transferred.
+//!
+//! For small networks, where every node holds every op, there is also a much simpler
+//! full sync strategy, see [`full_sync`].
+//!
+//! The strategies a space runs are chosen by name with
+//! [`KitsuneP2pTuningParams::gossip_strategy`](kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::gossip_strategy),
+//! see [`GossipStrategyRegistry`].
 
+pub mod full_sync;
 pub mod sharded_gossip;
 
 mod common;
 pub use common::*;
+
+mod registry;
+pub use registry::*;
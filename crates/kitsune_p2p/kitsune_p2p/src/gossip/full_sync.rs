@@ -0,0 +1,470 @@
+//! A gossip strategy for small networks, in which every node holds every op.
+//!
+//! On every iteration a node picks a random remote node and sends it up to
+//! [`FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND`] of the agent infos it knows of, together
+//! with the hashes of up to [`FULL_SYNC_MAX_OPS_PER_ROUND`] ops it holds, continuing
+//! from where its previous round left off. The remote node replies with the same.
+//! Both nodes then store the agent infos and fetch the ops they are missing.
+//!
+//! Gossip messages grow with the size of the network, and it takes more rounds
+//! to sync every op as the number of ops grows, so this strategy only suits
+//! networks of up to around [`FULL_SYNC_MAX_NODES`] nodes whose agents all hold
+//! the full DHT arc.
+
+#![warn(missing_docs)]
+
+use crate::agent_store::AgentInfoSigned;
+use crate::metrics::MetricsSync;
+use crate::types::event::*;
+use crate::types::gossip::*;
+use crate::{types::*, HostApi};
+use ghost_actor::dependencies::tracing;
+use kitsune_p2p_fetch::{FetchKey, FetchPriority, FetchQueue, FetchQueuePush, FetchSource};
+use kitsune_p2p_timestamp::Timestamp;
+use kitsune_p2p_types::codec::Codec;
+use kitsune_p2p_types::config::*;
+use kitsune_p2p_types::dht_arc::DhtArcSet;
+use kitsune_p2p_types::metrics::metric_task;
+use kitsune_p2p_types::tx2::tx2_api::*;
+use kitsune_p2p_types::tx2::tx2_utils::*;
+use kitsune_p2p_types::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak};
+
+use super::HowToConnect;
+
+/// The number of remote nodes above which full sync gossip warns that
+/// the network has outgrown it.
+pub const FULL_SYNC_MAX_NODES: usize = 20;
+
+/// The most op hashes a node sends in one round of gossip. A node holding
+/// more ops sends the rest over the following rounds. Any further hashes
+/// received in one message are ignored.
+pub const FULL_SYNC_MAX_OPS_PER_ROUND: usize = 5_000;
+
+/// The most agent infos a node sends in one round of gossip. The infos of its
+/// own agents are sent first, and the rest are picked at random, so a node
+/// knowing of more agents sends the others over the following rounds.
+/// Any further infos received in one message are ignored.
+pub const FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND: usize = 1_000;
+
+/// The number of incoming gossip messages which can be waiting to be processed.
+/// Any further messages are dropped.
+const INCOMING_QUEUE_LEN: usize = 100;
+
+type EventSender = futures::channel::mpsc::Sender<event::KitsuneP2pEvent>;
+type Incoming = (Tx2ConHnd<wire::Wire>, TxUrl, FullSyncGossipWire);
+
+kitsune_p2p_types::write_codec_enum! {
+    /// Full sync gossip wire protocol codec
+    codec FullSyncGossipWire {
+        /// Start a round of gossip with a page of what the sending node holds.
+        Initiate(0x10) {
+            /// The agents hosted by the sending node.
+            local_agents.0: Vec<Arc<KitsuneAgent>>,
+            /// Up to [`FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND`] agent infos known to
+            /// the sending node, those of its own agents first.
+            agent_infos.1: Vec<AgentInfoSigned>,
+            /// The hashes of up to [`FULL_SYNC_MAX_OPS_PER_ROUND`] ops held by the
+            /// sending node, continuing from its previous round.
+            ops.2: Vec<Arc<KitsuneOpHash>>,
+        },
+
+        /// Finish a round of gossip with a page of what the accepting node holds.
+        Accept(0x20) {
+            /// The agents hosted by the sending node.
+            local_agents.0: Vec<Arc<KitsuneAgent>>,
+            /// Up to [`FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND`] agent infos known to
+            /// the sending node, those of its own agents first.
+            agent_infos.1: Vec<AgentInfoSigned>,
+            /// The hashes of up to [`FULL_SYNC_MAX_OPS_PER_ROUND`] ops held by the
+            /// sending node, continuing from its previous round.
+            ops.2: Vec<Arc<KitsuneOpHash>>,
+        },
+    }
+}
+
+/// The full sync gossip module of a space.
+pub struct FullSyncGossip {
+    tuning_params: KitsuneP2pTuningParams,
+    space: Arc<KitsuneSpace>,
+    ep_hnd: Tx2EpHnd<wire::Wire>,
+    evt_sender: EventSender,
+    host: HostApi,
+    metrics: MetricsSync,
+    fetch_queue: FetchQueue,
+    local_agents: Share<HashSet<Arc<KitsuneAgent>>>,
+    /// The authored timestamp of the first op to send in the next round.
+    op_cursor: Share<Timestamp>,
+    /// Closed when the module closes, which ends the incoming task.
+    incoming: Share<tokio::sync::mpsc::Sender<Incoming>>,
+}
+
+impl std::fmt::Debug for FullSyncGossip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FullSyncGossip{...}").finish()
+    }
+}
+
+impl FullSyncGossip {
+    /// Constructor, which also spawns the tasks initiating gossip
+    /// and processing incoming gossip.
+    ///
+    /// The tasks only hold weak references to the module, and both end
+    /// once it is closed or dropped.
+    pub fn new(
+        tuning_params: KitsuneP2pTuningParams,
+        space: Arc<KitsuneSpace>,
+        ep_hnd: Tx2EpHnd<wire::Wire>,
+        evt_sender: EventSender,
+        host: HostApi,
+        metrics: MetricsSync,
+        fetch_queue: FetchQueue,
+    ) -> Arc<Self> {
+        let (incoming, mut incoming_recv) = tokio::sync::mpsc::channel(INCOMING_QUEUE_LEN);
+        let delay =
+            std::time::Duration::from_millis(tuning_params.gossip_loop_iteration_delay_ms as u64);
+        let this = Arc::new(Self {
+            tuning_params,
+            space,
+            ep_hnd,
+            evt_sender,
+            host,
+            metrics,
+            fetch_queue,
+            local_agents: Share::new(HashSet::new()),
+            op_cursor: Share::new(Timestamp::MIN),
+            incoming: Share::new(incoming),
+        });
+
+        metric_task({
+            let this = Arc::downgrade(&this);
+            async move {
+                loop {
+                    tokio::time::sleep(delay).await;
+                    let this = match Weak::upgrade(&this) {
+                        Some(this) if !this.is_closing() => this,
+                        _ => break,
+                    };
+                    if let Err(err) = this.try_initiate().await {
+                        tracing::error!("Full sync gossip failed to initiate: {:?}", err);
+                    }
+                }
+                KitsuneResult::Ok(())
+            }
+        });
+
+        metric_task({
+            let this = Arc::downgrade(&this);
+            async move {
+                // Ends once the sender is dropped, when the module closes.
+                while let Some((con, remote_url, msg)) = incoming_recv.recv().await {
+                    let this = match Weak::upgrade(&this) {
+                        Some(this) if !this.is_closing() => this,
+                        _ => break,
+                    };
+                    if let Err(err) = this.process_incoming(con, remote_url, msg).await {
+                        tracing::error!("Full sync gossip failed to process a message: {:?}", err);
+                    }
+                }
+                KitsuneResult::Ok(())
+            }
+        });
+
+        this
+    }
+
+    fn is_closing(&self) -> bool {
+        self.incoming.is_closed()
+    }
+
+    fn local_agents(&self) -> KitsuneResult<HashSet<Arc<KitsuneAgent>>> {
+        self.local_agents.share_mut(|agents, _| Ok(agents.clone()))
+    }
+
+    /// Start a round with a random remote node, if there is one.
+    async fn try_initiate(&self) -> KitsuneResult<()> {
+        let local_agents = self.local_agents()?;
+        if local_agents.is_empty() {
+            return Ok(());
+        }
+        let agent_infos = self.all_agent_info().await?;
+        let remote_nodes = remote_nodes(&agent_infos, &local_agents);
+        if remote_nodes.len() > FULL_SYNC_MAX_NODES {
+            tracing::warn!(
+                "Full sync gossip is running with {} remote nodes, consider switching to sharded gossip",
+                remote_nodes.len()
+            );
+        }
+        let (url, remote_agent_list) = {
+            use rand::prelude::*;
+            match remote_nodes.into_iter().choose(&mut thread_rng()) {
+                Some(node) => node,
+                None => return Ok(()),
+            }
+        };
+
+        let msg = FullSyncGossipWire::initiate(
+            local_agents.iter().cloned().collect(),
+            agent_infos_to_send(agent_infos, &local_agents),
+            self.next_op_hashes().await?,
+        );
+        self.metrics
+            .write()
+            .record_initiate(&remote_agent_list, GossipModuleType::FullSync);
+        if let Err(err) = self.send(HowToConnect::Url(url), msg).await {
+            self.metrics
+                .write()
+                .record_error(&remote_agent_list, GossipModuleType::FullSync);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    async fn process_incoming(
+        &self,
+        con: Tx2ConHnd<wire::Wire>,
+        remote_url: TxUrl,
+        msg: FullSyncGossipWire,
+    ) -> KitsuneResult<()> {
+        let (is_initiate, remote_agents, mut agent_infos, mut ops) = match msg {
+            FullSyncGossipWire::Initiate(Initiate {
+                local_agents,
+                agent_infos,
+                ops,
+            }) => (true, local_agents, agent_infos, ops),
+            FullSyncGossipWire::Accept(Accept {
+                local_agents,
+                agent_infos,
+                ops,
+            }) => (false, local_agents, agent_infos, ops),
+        };
+        if remote_agents.is_empty() {
+            return Ok(());
+        }
+        let local_agents = self.local_agents()?;
+        if local_agents.is_empty() {
+            return Ok(());
+        }
+
+        if is_initiate {
+            self.metrics
+                .write()
+                .record_accept(&remote_agents, GossipModuleType::FullSync);
+            let reply = FullSyncGossipWire::accept(
+                local_agents.iter().cloned().collect(),
+                agent_infos_to_send(self.all_agent_info().await?, &local_agents),
+                self.next_op_hashes().await?,
+            );
+            self.send(HowToConnect::Con(con, remote_url), reply).await?;
+        }
+
+        agent_infos.truncate(FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND);
+        if !agent_infos.is_empty() {
+            self.evt_sender
+                .put_agent_info_signed(PutAgentInfoSignedEvt {
+                    space: self.space.clone(),
+                    peer_data: agent_infos,
+                })
+                .await
+                .map_err(KitsuneError::other)?;
+        }
+
+        ops.truncate(FULL_SYNC_MAX_OPS_PER_ROUND);
+        let held = self
+            .host
+            .check_op_data(self.space.clone(), ops.clone(), None)
+            .await
+            .map_err(KitsuneError::other)?;
+        let source = FetchSource::Agent(remote_agents[0].clone());
+        for (op_hash, _) in ops.into_iter().zip(held).filter(|(_, held)| !held) {
+            self.fetch_queue.push(FetchQueuePush {
+                key: FetchKey::Op(op_hash),
+                author: None,
+                context: None,
+                space: self.space.clone(),
+                source: source.clone(),
+                size: None,
+                priority: FetchPriority::Normal,
+            });
+        }
+
+        self.metrics
+            .write()
+            .record_success(&remote_agents, GossipModuleType::FullSync);
+        Ok(())
+    }
+
+    async fn send(&self, how: HowToConnect, msg: FullSyncGossipWire) -> KitsuneResult<()> {
+        let data = msg.encode_vec().map_err(KitsuneError::other)?;
+        let gossip =
+            wire::Wire::gossip(self.space.clone(), data.into(), GossipModuleType::FullSync);
+        let timeout = self.tuning_params.implicit_timeout();
+        let con = match how {
+            HowToConnect::Con(con, remote_url) => {
+                if con.is_closed() {
+                    self.ep_hnd.get_connection(remote_url, timeout).await?
+                } else {
+                    con
+                }
+            }
+            HowToConnect::Url(url) => self.ep_hnd.get_connection(url, timeout).await?,
+        };
+        con.notify(&gossip, timeout).await?;
+        Ok(())
+    }
+
+    async fn all_agent_info(&self) -> KitsuneResult<Vec<AgentInfoSigned>> {
+        self.evt_sender
+            .query_agents(QueryAgentsEvt::new(self.space.clone()))
+            .await
+            .map_err(KitsuneError::other)
+    }
+
+    /// The hashes of the next [`FULL_SYNC_MAX_OPS_PER_ROUND`] held ops,
+    /// in authored order, starting over once every op has been sent.
+    async fn next_op_hashes(&self) -> KitsuneResult<Vec<Arc<KitsuneOpHash>>> {
+        let start = self.op_cursor.share_ref(|cursor| Ok(*cursor))?;
+        let page = self
+            .evt_sender
+            .query_op_hashes(QueryOpHashesEvt {
+                space: self.space.clone(),
+                arc_set: DhtArcSet::Full,
+                window: start..Timestamp::MAX,
+                max_ops: FULL_SYNC_MAX_OPS_PER_ROUND,
+                include_limbo: false,
+            })
+            .await
+            .map_err(KitsuneError::other)?;
+        let (hashes, next) = match page {
+            Some((hashes, window)) if hashes.len() >= FULL_SYNC_MAX_OPS_PER_ROUND => {
+                // Ops authored at the last timestamp may not all fit in this page,
+                // so send them again next round, unless the whole page has one
+                // timestamp, which would never move the cursor on.
+                let next = if *window.end() > start {
+                    *window.end()
+                } else {
+                    window
+                        .end()
+                        .saturating_add(&std::time::Duration::from_micros(1))
+                };
+                (hashes, next)
+            }
+            Some((hashes, _)) => (hashes, Timestamp::MIN),
+            None => (Vec::new(), Timestamp::MIN),
+        };
+        self.op_cursor.share_mut(|cursor, _| {
+            *cursor = next;
+            Ok(())
+        })?;
+        Ok(hashes)
+    }
+}
+
+/// Pick the agent infos to send in one round: those of the local agents
+/// first, then the others at random, up to [`FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND`].
+fn agent_infos_to_send(
+    agent_infos: Vec<AgentInfoSigned>,
+    local_agents: &HashSet<Arc<KitsuneAgent>>,
+) -> Vec<AgentInfoSigned> {
+    use rand::prelude::*;
+    let (mut to_send, mut others): (Vec<_>, Vec<_>) = agent_infos
+        .into_iter()
+        .partition(|info| local_agents.contains(&info.agent));
+    others.shuffle(&mut thread_rng());
+    to_send.extend(others);
+    to_send.truncate(FULL_SYNC_MAX_AGENT_INFOS_PER_ROUND);
+    to_send
+}
+
+/// Group the unexpired agent infos of remote agents by the url
+/// of the node hosting them.
+fn remote_nodes(
+    agent_infos: &[AgentInfoSigned],
+    local_agents: &HashSet<Arc<KitsuneAgent>>,
+) -> HashMap<TxUrl, Vec<AgentInfoSigned>> {
+    let now = std::time::UNIX_EPOCH
+        .elapsed()
+        .expect("Your system clock is set before UNIX epoch");
+    let mut nodes: HashMap<TxUrl, Vec<AgentInfoSigned>> = HashMap::new();
+    for info in agent_infos
+        .iter()
+        .filter(|info| !local_agents.contains(&info.agent))
+        .filter(|info| std::time::Duration::from_millis(info.expires_at_ms) > now)
+    {
+        if let Some(url) = info.url_list.first() {
+            nodes
+                .entry(TxUrl::from(url.as_str()))
+                .or_default()
+                .push(info.clone());
+        }
+    }
+    nodes
+}
+
+impl AsGossipModule for FullSyncGossip {
+    fn incoming_gossip(
+        &self,
+        con: Tx2ConHnd<wire::Wire>,
+        remote_url: TxUrl,
+        gossip_data: Box<[u8]>,
+    ) -> KitsuneResult<()> {
+        let (_, msg) = FullSyncGossipWire::decode_ref(&gossip_data).map_err(KitsuneError::other)?;
+        let sent = self
+            .incoming
+            .share_ref(|incoming| Ok(incoming.try_send((con, remote_url, msg)).is_ok()));
+        // A closed module drops incoming messages silently.
+        if let Ok(false) = sent {
+            tracing::warn!("Full sync gossip is overloaded, dropping an incoming message");
+        }
+        Ok(())
+    }
+
+    fn local_agent_join(&self, a: Arc<KitsuneAgent>) {
+        let _ = self.local_agents.share_mut(move |agents, _| {
+            agents.insert(a);
+            Ok(())
+        });
+    }
+
+    fn local_agent_leave(&self, a: Arc<KitsuneAgent>) {
+        let _ = self.local_agents.share_mut(move |agents, _| {
+            agents.remove(&a);
+            Ok(())
+        });
+    }
+
+    fn close(&self) {
+        self.incoming.close();
+    }
+}
+
+struct FullSyncGossipFactory;
+
+impl AsGossipModuleFactory for FullSyncGossipFactory {
+    fn spawn_gossip_task(
+        &self,
+        tuning_params: KitsuneP2pTuningParams,
+        space: Arc<KitsuneSpace>,
+        ep_hnd: Tx2EpHnd<wire::Wire>,
+        evt_sender: futures::channel::mpsc::Sender<event::KitsuneP2pEvent>,
+        host: HostApi,
+        metrics: MetricsSync,
+        fetch_queue: FetchQueue,
+    ) -> GossipModule {
+        GossipModule(FullSyncGossip::new(
+            tuning_params,
+            space,
+            ep_hnd,
+            evt_sender,
+            host,
+            metrics,
+            fetch_queue,
+        ))
+    }
+}
+
+/// Create a full sync `GossipModuleFactory`
+pub fn factory() -> GossipModuleFactory {
+    GossipModuleFactory(Arc::new(FullSyncGossipFactory))
+}
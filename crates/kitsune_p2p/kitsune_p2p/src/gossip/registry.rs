@@ -0,0 +1,235 @@
+use crate::gossip::sharded_gossip::BandwidthThrottles;
+use crate::types::gossip::*;
+use crate::types::*;
+use kitsune_p2p_types::config::KitsuneP2pTuningParams;
+use std::collections::HashMap;
+
+/// Creates the gossip modules making up a gossip strategy,
+/// paired with the module type their wire messages are routed by.
+pub type GossipStrategyBuilder = fn(
+    &KitsuneP2pTuningParams,
+    &BandwidthThrottles,
+) -> Vec<(GossipModuleType, GossipModuleFactory)>;
+
+/// The gossip strategies which can be named in the
+/// [`gossip_strategy`](kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::gossip_strategy)
+/// tuning param.
+///
+/// The tuning param is a comma separated list of strategy names,
+/// and a space runs the gossip modules of every strategy in the list.
+///
+/// Set [`KitsuneP2pConfig::gossip_strategies`](crate::KitsuneP2pConfig::gossip_strategies)
+/// to a registry with further strategies to make them available.
+#[derive(Clone)]
+pub struct GossipStrategyRegistry {
+    strategies: HashMap<String, GossipStrategyBuilder>,
+}
+
+impl std::fmt::Debug for GossipStrategyRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("GossipStrategyRegistry")
+            .field(&self.names())
+            .finish()
+    }
+}
+
+/// Registries are equal if they register strategies under the same names.
+impl PartialEq for GossipStrategyRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.names() == other.names()
+    }
+}
+
+impl Default for GossipStrategyRegistry {
+    /// A registry of the built in strategies:
+    /// - `sharded-gossip`: recent and historical sharded gossip,
+    ///   see [`crate::gossip::sharded_gossip`].
+    /// - `full-sync`: full sync gossip for small networks,
+    ///   see [`crate::gossip::full_sync`].
+    /// - `none`: no gossip at all.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("sharded-gossip", sharded_gossip_modules)
+            .register("full-sync", full_sync_modules)
+            .register("none", |_, _| vec![]);
+        registry
+    }
+}
+
+impl GossipStrategyRegistry {
+    /// A registry without any strategies.
+    pub fn empty() -> Self {
+        Self {
+            strategies: HashMap::new(),
+        }
+    }
+
+    /// Register a strategy under a name, replacing any strategy
+    /// which was already registered under that name.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        builder: GossipStrategyBuilder,
+    ) -> &mut Self {
+        self.strategies.insert(name.into(), builder);
+        self
+    }
+
+    /// The names of all registered strategies, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.strategies.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Check that the `gossip_strategy` tuning param only names registered
+    /// strategies, each at most once, and that no two of them run gossip
+    /// modules of the same type.
+    pub fn validate(&self, tuning_params: &KitsuneP2pTuningParams) -> KitsuneP2pResult<()> {
+        self.build(tuning_params, &BandwidthThrottles::new(tuning_params))
+            .map(|_| ())
+    }
+
+    /// Create the gossip module factories for the `gossip_strategy` tuning param.
+    pub fn build(
+        &self,
+        tuning_params: &KitsuneP2pTuningParams,
+        bandwidth_throttles: &BandwidthThrottles,
+    ) -> KitsuneP2pResult<Vec<(GossipModuleType, GossipModuleFactory)>> {
+        let mut names = Vec::new();
+        let mut modules: Vec<(GossipModuleType, GossipModuleFactory)> = Vec::new();
+        for name in tuning_params.gossip_strategy.split(',').map(str::trim) {
+            let builder = self.strategies.get(name).ok_or_else(|| {
+                KitsuneP2pError::InvalidGossipStrategy(
+                    format!(
+                        "unknown strategy {:?}, expected one of {:?}",
+                        name,
+                        self.names()
+                    )
+                    .into(),
+                )
+            })?;
+            if names.contains(&name) {
+                return Err(KitsuneP2pError::InvalidGossipStrategy(
+                    format!("strategy {:?} is listed more than once", name).into(),
+                ));
+            }
+            names.push(name);
+            for (module_type, factory) in builder(tuning_params, bandwidth_throttles) {
+                if modules.iter().any(|(t, _)| *t == module_type) {
+                    return Err(KitsuneP2pError::InvalidGossipStrategy(
+                        format!(
+                            "strategy {:?} runs a {:?} gossip module, which another strategy already runs",
+                            name, module_type
+                        )
+                        .into(),
+                    ));
+                }
+                modules.push((module_type, factory));
+            }
+        }
+        Ok(modules)
+    }
+}
+
+fn sharded_gossip_modules(
+    tuning_params: &KitsuneP2pTuningParams,
+    bandwidth_throttles: &BandwidthThrottles,
+) -> Vec<(GossipModuleType, GossipModuleFactory)> {
+    let mut gossips = vec![];
+    if !tuning_params.disable_recent_gossip {
+        gossips.push((
+            GossipModuleType::ShardedRecent,
+            crate::gossip::sharded_gossip::recent_factory(bandwidth_throttles.recent()),
+        ));
+    }
+    if !tuning_params.disable_historical_gossip {
+        gossips.push((
+            GossipModuleType::ShardedHistorical,
+            crate::gossip::sharded_gossip::historical_factory(bandwidth_throttles.historical()),
+        ));
+    }
+    gossips
+}
+
+fn full_sync_modules(
+    _tuning_params: &KitsuneP2pTuningParams,
+    _bandwidth_throttles: &BandwidthThrottles,
+) -> Vec<(GossipModuleType, GossipModuleFactory)> {
+    vec![(
+        GossipModuleType::FullSync,
+        crate::gossip::full_sync::factory(),
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kitsune_p2p_types::config::tuning_params_struct;
+
+    fn tuning_params(gossip_strategy: &str) -> KitsuneP2pTuningParams {
+        let mut tuning_params = tuning_params_struct::KitsuneP2pTuningParams::default();
+        tuning_params.gossip_strategy = gossip_strategy.to_string();
+        std::sync::Arc::new(tuning_params)
+    }
+
+    #[test]
+    fn validates_gossip_strategies() {
+        let registry = GossipStrategyRegistry::default();
+        assert_eq!(
+            registry.names(),
+            vec!["full-sync", "none", "sharded-gossip"]
+        );
+
+        for ok in [
+            "sharded-gossip",
+            "full-sync",
+            "none",
+            "sharded-gossip, full-sync",
+        ] {
+            registry.validate(&tuning_params(ok)).unwrap();
+        }
+        for bad in [
+            "",
+            "sharded",
+            "sharded-gossip,sharded-gossip",
+            "none,gossip",
+        ] {
+            assert!(matches!(
+                registry.validate(&tuning_params(bad)),
+                Err(KitsuneP2pError::InvalidGossipStrategy(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_strategies_running_the_same_module() {
+        let mut registry = GossipStrategyRegistry::default();
+        registry.register("full-sync-again", full_sync_modules);
+        registry
+            .validate(&tuning_params("full-sync-again"))
+            .unwrap();
+        assert!(matches!(
+            registry.validate(&tuning_params("full-sync,full-sync-again")),
+            Err(KitsuneP2pError::InvalidGossipStrategy(_))
+        ));
+    }
+
+    #[test]
+    fn config_carries_registered_strategies() {
+        let mut config = crate::KitsuneP2pConfig::default();
+        config.tuning_params = tuning_params("custom");
+        assert!(config
+            .gossip_strategies
+            .validate(&config.tuning_params)
+            .is_err());
+        config
+            .gossip_strategies
+            .register("custom", full_sync_modules);
+        config
+            .gossip_strategies
+            .validate(&config.tuning_params)
+            .unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use kitsune_p2p_fetch::{FetchKey, FetchQueuePush, OpHashSized};
+use kitsune_p2p_fetch::{FetchKey, FetchPriority, FetchQueuePush, OpHashSized};
 use kitsune_p2p_types::{combinators::second, dht::region::Region};
 
 use super::*;
@@ -306,6 +306,7 @@ impl ShardedGossipLocal {
                 space: self.space.clone(),
                 source: source.clone(),
                 size,
+                priority: FetchPriority::Normal,
             };
             self.fetch_queue.push(request);
         }
@@ -1,9 +1,9 @@
-use kitsune_p2p_fetch::OpHashSized;
+use kitsune_p2p_fetch::{FetchPriority, OpHashSized};
 use must_future::MustBoxFuture;
 use std::sync::Arc;
 
 use kitsune_p2p_types::{
-    bin_types::KitsuneSpace,
+    bin_types::{KitsuneAgent, KitsuneSpace},
     dht::{
         region::{Region, RegionCoords},
         region_set::RegionSetLtcs,
@@ -85,6 +85,20 @@ pub trait KitsuneHost: 'static + Send + Sync {
         )
         .into()
     }
+
+    /// How urgently ops published to us by this author, with this context,
+    /// should be fetched.
+    /// Ops published by our own agents are always fetched with high priority.
+    fn fetch_priority(
+        &self,
+        space: Arc<KitsuneSpace>,
+        author: Arc<KitsuneAgent>,
+        _context: kitsune_p2p_fetch::FetchContext,
+    ) -> KitsuneHostResult<FetchPriority> {
+        let _space = space;
+        let _author = author;
+        futures::FutureExt::boxed(async move { Ok(FetchPriority::Normal) }).into()
+    }
 }
 
 /// Trait object for the host interface
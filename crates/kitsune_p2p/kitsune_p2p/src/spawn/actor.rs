@@ -23,6 +23,7 @@ use kitsune_p2p_types::tx2::*;
 use kitsune_p2p_types::*;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 /// The bootstrap service is much more thoroughly documented in the default service implementation.
@@ -115,8 +116,16 @@ pub(crate) struct KitsuneP2pActor {
     bandwidth_throttles: BandwidthThrottles,
     parallel_notify_permit: Arc<tokio::sync::Semaphore>,
     fetch_queue: FetchQueue,
+    blocked_urls: BlockedUrls,
 }
 
+/// The urls of remote nodes which are refused any communication.
+/// See [`KitsuneP2p::block`].
+type BlockedUrls = Arc<parking_lot::RwLock<HashSet<TxUrl>>>;
+
+/// The close code sent when a connection with a blocked node is closed.
+const BLOCKED_CLOSE_CODE: u32 = 403;
+
 impl KitsuneP2pActor {
     pub async fn new(
         config: KitsuneP2pConfig,
@@ -128,6 +137,8 @@ impl KitsuneP2pActor {
     ) -> KitsuneP2pResult<Self> {
         crate::types::metrics::init();
 
+        config.gossip_strategies.validate(&config.tuning_params)?;
+
         let tx2_conf = config.to_tx2().map_err(KitsuneP2pError::other)?;
 
         let mut is_mock = false;
@@ -283,8 +294,39 @@ impl KitsuneP2pActor {
             config.tuning_params.clone(),
         ));
 
-        // TODO - use a real config
-        let fetch_queue = FetchQueue::new_bitwise_or();
+        struct FetchQueueTuning(kitsune_p2p_types::config::KitsuneP2pTuningParams);
+
+        impl FetchQueueConfig for FetchQueueTuning {
+            fn item_retry_delay(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(self.0.fetch_queue_item_retry_delay_ms as u64)
+            }
+
+            fn source_retry_delay(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(self.0.fetch_queue_source_retry_delay_ms as u64)
+            }
+
+            fn max_retry_delay(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(self.0.fetch_queue_max_retry_delay_ms as u64)
+            }
+
+            fn max_source_attempts(&self) -> u32 {
+                self.0.fetch_queue_max_source_attempts
+            }
+
+            fn max_source_failures(&self) -> u32 {
+                self.0.fetch_queue_max_source_failures
+            }
+
+            fn max_item_age(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(self.0.fetch_queue_max_item_age_ms as u64)
+            }
+
+            fn merge_fetch_contexts(&self, a: u32, b: u32) -> u32 {
+                a | b
+            }
+        }
+
+        let fetch_queue = FetchQueue::new(Arc::new(FetchQueueTuning(config.tuning_params.clone())));
 
         // Start a loop to handle our fetch queue fetch items.
         {
@@ -308,8 +350,12 @@ impl KitsuneP2pActor {
                             }
                         }
 
-                        if let Err(err) = i_s.fetch(key, space, source).await {
-                            tracing::debug!(?err);
+                        match i_s.fetch(key, space, source.clone()).await {
+                            Ok(()) => fetch_queue.source_succeeded(&source),
+                            Err(err) => {
+                                tracing::debug!(?err);
+                                fetch_queue.source_failed(&source);
+                            }
                         }
                     }
 
@@ -318,8 +364,11 @@ impl KitsuneP2pActor {
             });
         }
 
+        let blocked_urls = BlockedUrls::default();
+
         let i_s = internal_sender.clone();
         tokio::task::spawn({
+            let blocked_urls = blocked_urls.clone();
             let evt_sender = evt_sender.clone();
             let host = host.clone();
             let tuning_params = config.tuning_params.clone();
@@ -332,6 +381,7 @@ impl KitsuneP2pActor {
                     let host = host.clone();
                     let tuning_params = tuning_params.clone();
                     let i_s = i_s.clone();
+                    let blocked_urls = blocked_urls.clone();
                     async move {
                         macro_rules! resp {
                             ($r:expr, $e:expr) => {
@@ -342,9 +392,16 @@ impl KitsuneP2pActor {
                         }
 
                         let evt_sender = &evt_sender;
+                        let is_blocked = |url: &TxUrl| blocked_urls.read().contains(url);
                         use tx2_api::Tx2EpEvent::*;
                         #[allow(clippy::single_match)]
                         match event {
+                            OutgoingConnection(Tx2EpConnection { con, url })
+                            | IncomingConnection(Tx2EpConnection { con, url })
+                                if is_blocked(&url) =>
+                            {
+                                con.close(BLOCKED_CLOSE_CODE, "blocked").await;
+                            }
                             OutgoingConnection(Tx2EpConnection { con, url }) => {
                                 let _ = i_s.new_con(url, con).await;
                             }
@@ -354,6 +411,14 @@ impl KitsuneP2pActor {
                             ConnectionClosed(Tx2EpConnectionClosed { url, .. }) => {
                                 let _ = i_s.del_con(url).await;
                             }
+                            IncomingRequest(Tx2EpIncomingRequest { url, respond, .. })
+                                if is_blocked(&url) =>
+                            {
+                                resp!(respond, wire::Wire::failure("blocked".into()));
+                            }
+                            IncomingNotify(Tx2EpIncomingNotify { url, .. }) if is_blocked(&url) => {
+                                tracing::debug!(?url, "dropping a notify from a blocked node");
+                            }
                             IncomingRequest(Tx2EpIncomingRequest { data, respond, .. }) => {
                                 match data {
                                     wire::Wire::Call(wire::Call {
@@ -697,6 +762,7 @@ impl KitsuneP2pActor {
             bandwidth_throttles,
             parallel_notify_permit,
             fetch_queue,
+            blocked_urls,
         })
     }
 }
@@ -1226,6 +1292,38 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .boxed()
         .into())
     }
+
+    fn handle_block(&mut self, space: KSpace, agent: KAgent) -> KitsuneP2pHandlerResult<bool> {
+        let host = self.host.clone();
+        let ep_hnd = self.ep_hnd.clone();
+        let blocked_urls = self.blocked_urls.clone();
+        Ok(async move {
+            let info = host
+                .get_agent_info_signed(GetAgentInfoSignedEvt {
+                    space,
+                    agent: agent.clone(),
+                })
+                .await
+                .map_err(KitsuneP2pError::other)?;
+            let url_list = match info {
+                Some(info) => info.url_list.clone(),
+                None => {
+                    tracing::debug!(?agent, "cannot block an agent without stored agent info");
+                    return Ok(false);
+                }
+            };
+            for url in url_list {
+                if blocked_urls.write().insert(url.clone()) {
+                    ep_hnd
+                        .close_connection(url, BLOCKED_CLOSE_CODE, "blocked")
+                        .await;
+                }
+            }
+            Ok(true)
+        }
+        .boxed()
+        .into())
+    }
 }
 
 #[cfg(any(test, feature = "test_utils"))]
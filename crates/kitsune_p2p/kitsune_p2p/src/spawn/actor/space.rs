@@ -2,7 +2,7 @@ use super::*;
 use crate::metrics::*;
 use crate::types::gossip::GossipModule;
 use ghost_actor::dependencies::tracing;
-use kitsune_p2p_fetch::FetchQueue;
+use kitsune_p2p_fetch::{FetchPriority, FetchQueue, FetchQueueReader};
 use kitsune_p2p_mdns::*;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::codec::{rmp_decode, rmp_encode};
@@ -139,7 +139,7 @@ pub(crate) async fn spawn_space(
         bandwidth_throttles,
         parallel_notify_permit,
         fetch_queue,
-    )));
+    )?));
 
     Ok((sender, i_s, evt_recv))
 }
@@ -435,6 +435,7 @@ impl SpaceInternalHandler for Space {
         maybe_delegate: MaybeDelegate,
     ) -> InternalHandlerResult<()> {
         let ro_inner = self.ro_inner.clone();
+        let source_is_local = self.local_joined_agents.contains(&source);
 
         let just_hashes = op_hash_list.iter().map(|s| s.data()).collect();
 
@@ -452,6 +453,28 @@ impl SpaceInternalHandler for Space {
                 Ok(res) => res,
             };
 
+            // Ops are only published by their author, and delegated broadcasts
+            // pass the original source on, so the source is the author.
+            let author = source.clone();
+            let priority = if source_is_local {
+                FetchPriority::High
+            } else if have_data_list.iter().all(|have_data| *have_data) {
+                // Nothing will be fetched
+                FetchPriority::Normal
+            } else {
+                match ro_inner
+                    .host_api
+                    .fetch_priority(space.clone(), author.clone(), context)
+                    .await
+                {
+                    Ok(priority) => priority,
+                    Err(err) => {
+                        tracing::warn!(?err, "failed to get fetch priority");
+                        FetchPriority::Normal
+                    }
+                }
+            };
+
             for (op_hash, have_data) in op_hash_list.into_iter().zip(have_data_list) {
                 if have_data {
                     if let Some((basis, mod_idx, mod_cnt)) = &maybe_delegate {
@@ -479,9 +502,9 @@ impl SpaceInternalHandler for Space {
                         space: space.clone(),
                         source: FetchSource::Agent(source.clone()),
                         size: op_hash.maybe_size(),
-                        // TODO - get the author from somewhere
-                        author: None,
+                        author: Some(author.clone()),
                         context: Some(context),
+                        priority,
                     });
 
                     // Register a callback if maybe_delegate.is_some()
@@ -1202,10 +1225,12 @@ impl KitsuneP2pHandler for Space {
     ) -> KitsuneP2pHandlerResult<serde_json::Value> {
         let space = self.ro_inner.space.clone();
         let metrics = self.ro_inner.metrics.read().dump();
+        let fetch_queue = FetchQueueReader::from(self.ro_inner.fetch_queue.clone()).metrics(&space);
         Ok(async move {
             Ok(serde_json::json!({
                 "space": space.to_string(),
                 "metrics": metrics,
+                "fetch_queue": fetch_queue,
             }))
         }
         .boxed()
@@ -1222,6 +1247,12 @@ impl KitsuneP2pHandler for Space {
         };
         Ok(async move { Ok(diagnostics) }.boxed().into())
     }
+
+    fn handle_block(&mut self, _space: KSpace, _agent: KAgent) -> KitsuneP2pHandlerResult<bool> {
+        unreachable!(
+            "These requests are handled at the to actor level and are never propagated down to the space."
+        )
+    }
 }
 
 pub(crate) struct PendingDelegate {
@@ -1326,7 +1357,7 @@ impl Space {
         bandwidth_throttles: BandwidthThrottles,
         parallel_notify_permit: Arc<tokio::sync::Semaphore>,
         fetch_queue: FetchQueue,
-    ) -> Self {
+    ) -> KitsuneP2pResult<Self> {
         let metrics = MetricsSync::default();
 
         {
@@ -1355,35 +1386,9 @@ impl Space {
         );
 
         let gossip_mod = config
-            .tuning_params
-            .gossip_strategy
-            .split(',')
-            .flat_map(|module| match module {
-                "sharded-gossip" => {
-                    let mut gossips = vec![];
-                    if !config.tuning_params.disable_recent_gossip {
-                        gossips.push((
-                            GossipModuleType::ShardedRecent,
-                            crate::gossip::sharded_gossip::recent_factory(
-                                bandwidth_throttles.recent(),
-                            ),
-                        ));
-                    }
-                    if !config.tuning_params.disable_historical_gossip {
-                        gossips.push((
-                            GossipModuleType::ShardedHistorical,
-                            crate::gossip::sharded_gossip::historical_factory(
-                                bandwidth_throttles.historical(),
-                            ),
-                        ));
-                    }
-                    gossips
-                }
-                "none" => vec![],
-                _ => {
-                    panic!("unknown gossip strategy: {}", module);
-                }
-            })
+            .gossip_strategies
+            .build(&config.tuning_params, &bandwidth_throttles)?
+            .into_iter()
             .map(|(module, factory)| {
                 (
                     module,
@@ -1497,7 +1502,7 @@ impl Space {
             fetch_queue,
         });
 
-        Self {
+        Ok(Self {
             ro_inner,
             space,
             i_s,
@@ -1509,7 +1514,7 @@ impl Space {
             mdns_handles: HashMap::new(),
             mdns_listened_spaces: HashSet::new(),
             gossip_mod,
-        }
+        })
     }
 
     fn update_metric_exchange_arcset(&mut self) {
@@ -44,6 +44,10 @@ pub enum KitsuneP2pError {
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
 
+    /// The configured gossip strategy can't be run.
+    #[error("Invalid gossip strategy: {0}")]
+    InvalidGossipStrategy(Box<str>),
+
     /// Other
     #[error("Other: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync>),
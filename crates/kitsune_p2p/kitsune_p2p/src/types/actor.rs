@@ -162,5 +162,14 @@ ghost_actor::ghost_chan! {
 
         /// Get data for diagnostics
         fn get_diagnostics(space: KSpace) -> KitsuneDiagnostics;
+
+        /// Stop communicating with the nodes hosting an agent.
+        /// The agent's stored info in the space is only used to find the
+        /// urls of those nodes: connections to those urls are closed, and any
+        /// connection, request or notify from them is refused from then on,
+        /// in every space. Every agent hosted at those urls is blocked with it.
+        /// Returns false, without blocking anything, if there is no stored
+        /// info for the agent yet.
+        fn block(space: KSpace, agent: KAgent) -> bool;
     }
 }
@@ -15,6 +15,8 @@ pub enum GossipModuleType {
     ShardedRecent,
     /// Historical sharded gossip.
     ShardedHistorical,
+    /// Full sync gossip.
+    FullSync,
 }
 
 /// Represents an interchangeable gossip strategy module
@@ -80,7 +80,11 @@ pub mod tuning_params_struct {
     }
 
     mk_tune! {
-        /// Gossip strategy to use. [Default: "sharded-gossip"]
+        /// Gossip strategy to use, or a comma separated list of strategies.
+        /// The built in strategies are "sharded-gossip", "full-sync"
+        /// (for small networks only) and "none".
+        /// Unknown strategies are rejected when kitsune is spawned.
+        /// [Default: "sharded-gossip"]
         gossip_strategy: String = "sharded-gossip".to_string(),
 
         /// Delay between gossip loop iteration. [Default: 1s]
@@ -242,6 +246,35 @@ pub mod tuning_params_struct {
         /// Disable historical gossip. Useful for testing Recent gossip in isolation.
         disable_historical_gossip: bool = false,
 
+        /// How long to wait between requests for the same op,
+        /// regardless of the source it is requested from.
+        /// [Default: 90 seconds]
+        fetch_queue_item_retry_delay_ms: u32 = 1000 * 90,
+
+        /// How long to wait before requesting the same op from the same source again.
+        /// This delay doubles with every further request to that source.
+        /// [Default: 5 minutes]
+        fetch_queue_source_retry_delay_ms: u32 = 1000 * 60 * 5,
+
+        /// The longest the doubling delay between requests for the same op
+        /// from the same source can grow.
+        /// [Default: 30 minutes]
+        fetch_queue_max_retry_delay_ms: u32 = 1000 * 60 * 30,
+
+        /// How many times an op is requested from a source before giving up
+        /// on fetching it from that source.
+        /// [Default: 5]
+        fetch_queue_max_source_attempts: u32 = 5,
+
+        /// How many fetch requests in a row may fail to be sent to a source
+        /// before that source is dropped for every op in the fetch queue.
+        /// [Default: 3]
+        fetch_queue_max_source_failures: u32 = 3,
+
+        /// How long an op may wait in the fetch queue before giving up on it.
+        /// [Default: 2 hours]
+        fetch_queue_max_item_age_ms: u32 = 1000 * 60 * 60 * 2,
+
     }
 
     impl KitsuneP2pTuningParams {
@@ -3,6 +3,7 @@ use crate::{
     location::Location,
     manifest::Manifest,
     resource::ResourceBytes,
+    signature::{BundleSignature, PublisherKey, SigningKey},
 };
 use holochain_util::ffs;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -34,6 +35,16 @@ where
     /// are always relative paths (relative to the root_dir).
     resources: ResourceMap,
 
+    /// Publisher signatures of the manifest and resources, if any.
+    /// See [`Bundle::sign`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    signatures: Vec<BundleSignature>,
+
+    /// The manifest as it was encoded when the bundle was first signed.
+    /// Signatures cover these bytes, which must decode to `manifest`.
+    #[serde(default, with = "serde_bytes", skip_serializing_if = "Option::is_none")]
+    signed_manifest: Option<Vec<u8>>,
+
     /// Since the Manifest may contain local paths referencing unbundled files,
     /// on the local filesystem, we must have an absolute path at runtime for
     /// normalizing those locations.
@@ -46,6 +57,17 @@ where
     //        struct into two versions for each case.
     #[serde(skip)]
     root_dir: Option<PathBuf>,
+
+    /// The directory in which remote resources with a pinned hash are cached,
+    /// if any. See [`Bundle::with_cache_dir`].
+    #[serde(skip)]
+    cache_dir: Option<PathBuf>,
+
+    /// Whether the signatures of this bundle were verified, in which case the
+    /// bundles nested in it must pin their own resources as well.
+    /// See [`Bundle::with_verified_signatures`].
+    #[serde(skip)]
+    signatures_verified: bool,
 }
 
 impl<M> Bundle<M>
@@ -103,7 +125,11 @@ where
         Ok(Self {
             manifest,
             resources,
+            signatures: Vec::new(),
+            signed_manifest: None,
             root_dir,
+            cache_dir: None,
+            signatures_verified: false,
         })
     }
 
@@ -114,8 +140,128 @@ where
 
     /// Return a new Bundle with an updated manifest, subject to the same
     /// validation constraints as creating a new Bundle from scratch.
+    ///
+    /// Any signatures are dropped, since they do not cover the new manifest.
+    /// The bundle stays marked as verified if its signatures were verified,
+    /// as the bundles nested in it are still the ones published.
     pub fn update_manifest(self, manifest: M) -> MrBundleResult<Self> {
-        Self::from_parts(manifest, self.resources, self.root_dir)
+        let cache_dir = self.cache_dir;
+        let signatures_verified = self.signatures_verified;
+        let mut bundle = Self::from_parts(manifest, self.resources, self.root_dir)?;
+        bundle.cache_dir = cache_dir;
+        bundle.signatures_verified = signatures_verified;
+        Ok(bundle)
+    }
+
+    /// Cache the remote resources whose hash is pinned by the manifest in
+    /// `cache_dir`, so that resolving them again does not need the network.
+    ///
+    /// Cached files are named after their hash, so a cache directory can be
+    /// shared by any number of bundles.
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Accessor for the directory in which remote resources are cached, if any
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Sign the manifest and bundled resources as a publisher, replacing any
+    /// previous signature made with the same key.
+    ///
+    /// Every resource which is not bundled must have its hash pinned by the
+    /// manifest, so that the signature covers it too.
+    pub fn sign(mut self, key: &SigningKey) -> MrBundleResult<Self> {
+        self.check_resources_pinned()?;
+        let signed_manifest = match self.signed_manifest.take() {
+            Some(bytes) => {
+                self.check_signed_manifest(&bytes)?;
+                bytes
+            }
+            None => rmp_serde::to_vec_named(&self.manifest)?,
+        };
+        let signature = key.sign(&signing_payload(&signed_manifest, &self.resources));
+        self.signed_manifest = Some(signed_manifest);
+        self.signatures
+            .retain(|s| s.publisher_key != signature.publisher_key);
+        self.signatures.push(signature);
+        Ok(self)
+    }
+
+    /// Accessor for the publisher signatures of this bundle
+    pub fn signatures(&self) -> &[BundleSignature] {
+        &self.signatures
+    }
+
+    /// Check that this bundle is signed by at least one of the `trusted`
+    /// publisher keys, returning the first such key.
+    ///
+    /// Every signature made with a trusted key must be valid. Signatures made
+    /// with other keys are ignored.
+    pub fn verify_signatures(&self, trusted: &[PublisherKey]) -> MrBundleResult<PublisherKey> {
+        let untrusted = || BundleError::UntrustedBundle(trusted.to_vec());
+        let signed_manifest = self.signed_manifest.as_ref().ok_or_else(untrusted)?;
+        self.check_signed_manifest(signed_manifest)?;
+        self.check_resources_pinned()?;
+        let payload = signing_payload(signed_manifest, &self.resources);
+        let mut signed_by = None;
+        for signature in &self.signatures {
+            if trusted.contains(&signature.publisher_key) {
+                signature.verify(&payload)?;
+                signed_by.get_or_insert(signature.publisher_key);
+            }
+        }
+        signed_by.ok_or_else(|| untrusted().into())
+    }
+
+    /// Verify the signatures as [`Bundle::verify_signatures`] does, and mark
+    /// the bundle as verified.
+    ///
+    /// Bundles nested in this one by URL or path are only checked against
+    /// their pinned hash, so the code decoding them must also check that they
+    /// pin their own resources, with [`Bundle::check_resources_pinned`],
+    /// whenever [`Bundle::signatures_verified`] is true.
+    pub fn with_verified_signatures(mut self, trusted: &[PublisherKey]) -> MrBundleResult<Self> {
+        self.verify_signatures(trusted)?;
+        self.signatures_verified = true;
+        Ok(self)
+    }
+
+    /// Whether the signatures of this bundle were verified.
+    /// See [`Bundle::with_verified_signatures`].
+    pub fn signatures_verified(&self) -> bool {
+        self.signatures_verified
+    }
+
+    /// Check that the signed manifest bytes decode to the manifest in use.
+    fn check_signed_manifest(&self, signed_manifest: &[u8]) -> MrBundleResult<()> {
+        let manifest: M = rmp_serde::from_read_ref(signed_manifest)?;
+        if manifest != self.manifest {
+            return Err(BundleError::SignedManifestMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Check that every resource which is not bundled has a pinned hash,
+    /// including the resources of any bundle nested in this one.
+    pub fn check_resources_pinned(&self) -> MrBundleResult<()> {
+        for location in self.manifest.locations() {
+            match &location {
+                Location::Bundled(path) => {
+                    if let Some(bytes) = self.resources.get(path) {
+                        self.manifest
+                            .check_nested_resources_pinned(&location, bytes)?;
+                    }
+                }
+                _ if self.manifest.resource_hash(&location).is_none() => {
+                    return Err(BundleError::UnpinnedResource(location).into());
+                }
+                _ => (),
+            }
+        }
+        Ok(())
     }
 
     /// Load a Bundle into memory from a file
@@ -129,8 +275,11 @@ where
     }
 
     /// Retrieve the bytes for a resource at a Location, downloading it if
-    /// necessary
+    /// necessary.
+    ///
+    /// If the manifest pins a hash for the resource, the bytes must match it.
     pub async fn resolve(&self, location: &Location) -> MrBundleResult<Cow<'_, ResourceBytes>> {
+        let hash = self.manifest.resource_hash(location);
         let bytes = match &location.normalize(self.root_dir.as_ref())? {
             Location::Bundled(path) => Cow::Borrowed(
                 self.resources
@@ -138,8 +287,16 @@ where
                     .ok_or_else(|| BundleError::BundledResourceMissing(path.clone()))?,
             ),
             Location::Path(path) => Cow::Owned(crate::location::resolve_local(path).await?),
-            Location::Url(url) => Cow::Owned(crate::location::resolve_remote(url).await?),
+            Location::Url(url) => {
+                // Downloaded resources are checked before they are cached
+                return Ok(Cow::Owned(
+                    crate::location::resolve_remote(url, hash.as_ref(), self.cache_dir()).await?,
+                ));
+            }
         };
+        if let Some(hash) = hash {
+            hash.verify(location, &bytes)?;
+        }
         Ok(bytes)
     }
 
@@ -202,6 +359,25 @@ where
     }
 }
 
+/// The bytes covered by a publisher signature: the signed manifest and the
+/// bundled resources exactly as stored, but not the signatures themselves.
+///
+/// Each part is prefixed with its length, so that no two bundles share a payload.
+fn signing_payload(signed_manifest: &[u8], resources: &ResourceMap) -> Vec<u8> {
+    fn push_part(payload: &mut Vec<u8>, part: &[u8]) {
+        payload.extend_from_slice(&(part.len() as u64).to_be_bytes());
+        payload.extend_from_slice(part);
+    }
+
+    let mut payload = Vec::new();
+    push_part(&mut payload, signed_manifest);
+    for (path, bytes) in resources {
+        push_part(&mut payload, path.to_string_lossy().as_bytes());
+        push_part(&mut payload, bytes);
+    }
+    payload
+}
+
 /// A manifest bundled together, optionally, with the Resources that it describes.
 /// The manifest may be of any format. This is useful for deserializing a bundle of
 /// an outdated format, so that it may be modified to fit the supported format.
@@ -227,7 +403,7 @@ impl<M: serde::de::DeserializeOwned> RawBundle<M> {
 
 #[cfg(test)]
 mod tests {
-    use crate::error::MrBundleError;
+    use crate::{error::MrBundleError, ResourceHash};
 
     use super::*;
 
@@ -266,4 +442,175 @@ mod tests {
             Err(MrBundleError::BundleError(BundleError::BundledPathNotInManifest(path))) if path == PathBuf::from("3.thing")
         );
     }
+
+    #[test]
+    fn signatures_cover_manifest_and_resources() {
+        let manifest = TestManifest(vec![Location::Bundled("1.thing".into())]);
+        let publisher = SigningKey::from_seed(&[1; 32]);
+        let other = SigningKey::from_seed(&[2; 32]);
+        let trusted = [publisher.publisher_key()];
+
+        let unsigned =
+            Bundle::new_unchecked(manifest.clone(), vec![("1.thing".into(), vec![1])]).unwrap();
+        matches::assert_matches!(
+            unsigned.verify_signatures(&trusted),
+            Err(MrBundleError::BundleError(BundleError::UntrustedBundle(_)))
+        );
+
+        // Signatures survive encoding, and signing twice with a key replaces
+        // the earlier signature
+        let signed = unsigned
+            .sign(&other)
+            .unwrap()
+            .sign(&publisher)
+            .unwrap()
+            .sign(&publisher)
+            .unwrap();
+        let signed: Bundle<TestManifest> = Bundle::decode(&signed.encode().unwrap()).unwrap();
+        assert_eq!(signed.signatures().len(), 2);
+        assert_eq!(
+            signed.verify_signatures(&trusted).unwrap(),
+            publisher.publisher_key()
+        );
+
+        // A signature by an untrusted key is not enough
+        let signed_by_other = Bundle::new_unchecked(manifest.clone(), vec![])
+            .unwrap()
+            .sign(&other)
+            .unwrap();
+        matches::assert_matches!(
+            signed_by_other.verify_signatures(&trusted),
+            Err(MrBundleError::BundleError(BundleError::UntrustedBundle(_)))
+        );
+
+        // Tampering with the resources invalidates the signature
+        let mut tampered = signed;
+        tampered.resources.insert("1.thing".into(), vec![2]);
+        matches::assert_matches!(
+            tampered.verify_signatures(&trusted),
+            Err(MrBundleError::BundleError(BundleError::InvalidSignature(key)))
+                if key == publisher.publisher_key()
+        );
+
+        // So does tampering with the manifest
+        let mut tampered_manifest: Bundle<TestManifest> =
+            Bundle::decode(&signed_by_other.encode().unwrap()).unwrap();
+        tampered_manifest.manifest = TestManifest(vec![]);
+        matches::assert_matches!(
+            tampered_manifest.verify_signatures(&[other.publisher_key()]),
+            Err(MrBundleError::BundleError(
+                BundleError::SignedManifestMismatch
+            ))
+        );
+
+        // Updating the manifest drops the signatures
+        assert!(tampered
+            .update_manifest(manifest)
+            .unwrap()
+            .signatures()
+            .is_empty());
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct PinnedManifest(Vec<(Location, ResourceHash)>);
+
+    impl Manifest for PinnedManifest {
+        fn locations(&self) -> Vec<Location> {
+            self.0
+                .iter()
+                .map(|(location, _)| location.clone())
+                .collect()
+        }
+
+        fn resource_hash(&self, location: &Location) -> Option<ResourceHash> {
+            self.0
+                .iter()
+                .find(|(l, _)| l == location)
+                .map(|(_, hash)| *hash)
+        }
+
+        #[cfg(feature = "packing")]
+        fn path() -> PathBuf {
+            unimplemented!()
+        }
+
+        #[cfg(feature = "packing")]
+        fn bundle_extension() -> &'static str {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn signatures_require_pinned_resources() {
+        let key = SigningKey::from_seed(&[1; 32]);
+        let unpinned = Location::Path("/thing".into());
+        let pinned = Location::Url("http://example.com/thing".into());
+
+        matches::assert_matches!(
+            Bundle::new_unchecked(TestManifest(vec![unpinned.clone()]), vec![])
+                .unwrap()
+                .sign(&key),
+            Err(MrBundleError::BundleError(BundleError::UnpinnedResource(location)))
+                if location == unpinned
+        );
+
+        let signed = Bundle::new_unchecked(
+            PinnedManifest(vec![(pinned, ResourceHash::of(&[1]))]),
+            vec![],
+        )
+        .unwrap()
+        .sign(&key)
+        .unwrap();
+        assert_eq!(
+            signed.verify_signatures(&[key.publisher_key()]).unwrap(),
+            key.publisher_key()
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_hashes_are_verified() {
+        let bundled = Location::Bundled("1.thing".into());
+        let tampered = Location::Bundled("2.thing".into());
+        let manifest = PinnedManifest(vec![
+            (bundled.clone(), ResourceHash::of(&[1])),
+            (tampered.clone(), ResourceHash::of(&[2])),
+        ]);
+        let bundle = Bundle::new_unchecked(
+            manifest,
+            vec![("1.thing".into(), vec![1]), ("2.thing".into(), vec![3])],
+        )
+        .unwrap();
+
+        assert_eq!(
+            bundle.resolve(&bundled).await.unwrap().into_owned(),
+            vec![1]
+        );
+        matches::assert_matches!(
+            bundle.resolve(&tampered).await,
+            Err(MrBundleError::BundleError(BundleError::ResourceHashMismatch(location, expected, actual)))
+                if location == tampered && expected == ResourceHash::of(&[2]) && actual == ResourceHash::of(&[3])
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_remote_resources_are_cached() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        // Nothing listens on this port, so any download fails
+        let remote = Location::Url("http://127.0.0.1:1/thing".into());
+        let hash = ResourceHash::of(&[1]);
+        let bundle = Bundle::new_unchecked(PinnedManifest(vec![(remote.clone(), hash)]), vec![])
+            .unwrap()
+            .with_cache_dir(cache_dir.path().to_owned());
+
+        // A cached resource is resolved without the network
+        std::fs::write(cache_dir.path().join(hash.to_string()), [1]).unwrap();
+        assert_eq!(bundle.resolve(&remote).await.unwrap().into_owned(), vec![1]);
+
+        // A corrupted cache entry is downloaded again
+        std::fs::write(cache_dir.path().join(hash.to_string()), [2]).unwrap();
+        matches::assert_matches!(
+            bundle.resolve(&remote).await,
+            Err(MrBundleError::HttpError(_))
+        );
+    }
 }
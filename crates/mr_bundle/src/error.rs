@@ -1,5 +1,6 @@
 #![allow(missing_docs)]
 
+use crate::{Location, PublisherKey, ResourceHash};
 use holochain_util::ffs::IoError;
 
 /// Any error which can occur in this crate
@@ -52,6 +53,32 @@ pub enum BundleError {
         "Cannot use relative paths for local locations. The following local path is relative: {0}"
     )]
     RelativeLocalPath(std::path::PathBuf),
+
+    #[error("The resource at {0:?} does not match the hash pinned in the manifest. Expected hash: {1}, actual hash: {2}")]
+    ResourceHashMismatch(Location, ResourceHash, ResourceHash),
+
+    #[error("Invalid resource hash '{0}': expected a blake2b-256 digest written as 64 hex digits")]
+    InvalidResourceHash(String),
+
+    #[error(
+        "Invalid publisher key '{0}': expected an Ed25519 public key written as 64 hex digits"
+    )]
+    InvalidPublisherKey(String),
+
+    #[error("Invalid signing key: expected an Ed25519 secret key written as 64 hex digits")]
+    InvalidSigningKey,
+
+    #[error("The bundle signature made with publisher key {0} is invalid")]
+    InvalidSignature(PublisherKey),
+
+    #[error("The bundle is not signed by any of the trusted publisher keys: {0:?}")]
+    UntrustedBundle(Vec<PublisherKey>),
+
+    #[error("The manifest of the bundle does not match the manifest covered by its signatures")]
+    SignedManifestMismatch,
+
+    #[error("The resource at {0:?} is not bundled and its hash is not pinned in the manifest, so bundle signatures cannot cover it")]
+    UnpinnedResource(Location),
 }
 pub type BundleResult<T> = Result<T, BundleError>;
 
@@ -20,6 +20,7 @@ pub mod error;
 mod location;
 mod manifest;
 mod resource;
+mod signature;
 pub(crate) mod util;
 
 #[cfg(feature = "packing")]
@@ -29,4 +30,5 @@ pub use bundle::{Bundle, RawBundle};
 pub use encoding::{decode, encode};
 pub use location::Location;
 pub use manifest::Manifest;
-pub use resource::ResourceBytes;
+pub use resource::{ResourceBytes, ResourceHash};
+pub use signature::{BundleSignature, PublisherKey, SigningKey};
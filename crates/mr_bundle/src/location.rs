@@ -1,6 +1,6 @@
 use crate::{
     error::{BundleError, MrBundleResult},
-    ResourceBytes,
+    ResourceBytes, ResourceHash,
 };
 use holochain_util::ffs;
 use std::path::{Path, PathBuf};
@@ -48,13 +48,44 @@ pub(crate) async fn resolve_local(path: &Path) -> MrBundleResult<ResourceBytes>
     Ok(ffs::read(path).await?)
 }
 
-pub(crate) async fn resolve_remote(url: &str) -> MrBundleResult<ResourceBytes> {
-    Ok(reqwest::get(url)
+/// Download a resource, checking it against its pinned hash if there is one.
+///
+/// Resources with a pinned hash are cached in `cache_dir`, in a file named
+/// after the hash, and later resolved from the cache without a download.
+pub(crate) async fn resolve_remote(
+    url: &str,
+    hash: Option<&ResourceHash>,
+    cache_dir: Option<&Path>,
+) -> MrBundleResult<ResourceBytes> {
+    let cache_path = hash
+        .zip(cache_dir)
+        .map(|(hash, dir)| dir.join(hash.to_string()));
+    if let (Some(hash), Some(path)) = (hash, &cache_path) {
+        if let Ok(bytes) = ffs::read(path).await {
+            // A cached file which no longer matches its hash is downloaded again
+            if ResourceHash::of(&bytes) == *hash {
+                return Ok(bytes);
+            }
+        }
+    }
+
+    let bytes: ResourceBytes = reqwest::get(url)
         .await?
         .bytes()
         .await?
         .into_iter()
-        .collect())
+        .collect();
+
+    if let Some(hash) = hash {
+        hash.verify(&Location::Url(url.to_string()), &bytes)?;
+    }
+    if let Some(path) = cache_path {
+        if let Some(dir) = path.parent() {
+            ffs::create_dir_all(dir).await?;
+        }
+        ffs::write(&path, &bytes).await?;
+    }
+    Ok(bytes)
 }
 
 #[cfg(test)]
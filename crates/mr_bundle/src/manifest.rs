@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use crate::location::Location;
+use crate::{
+    error::MrBundleResult,
+    location::Location,
+    resource::{ResourceBytes, ResourceHash},
+};
 
 /// A Manifest describes the resources in a [`Bundle`](crate::Bundle) and how
 /// to pack and unpack them.
@@ -18,6 +22,23 @@ pub trait Manifest:
     /// correctly implemented to enable resource resolution.
     fn locations(&self) -> Vec<Location>;
 
+    /// The hash pinned by the manifest for the resource at a Location, if any.
+    /// Resolving a resource fails if its bytes do not match its pinned hash.
+    fn resource_hash(&self, _location: &Location) -> Option<ResourceHash> {
+        None
+    }
+
+    /// Check that the bundled resource at a Location, if it is itself a bundle,
+    /// pins the hash of every resource which it does not bundle.
+    /// See [`Bundle::check_resources_pinned`](crate::Bundle::check_resources_pinned).
+    fn check_nested_resources_pinned(
+        &self,
+        _location: &Location,
+        _bytes: &ResourceBytes,
+    ) -> MrBundleResult<()> {
+        Ok(())
+    }
+
     /// When unpacking the bundle into a directory structure, this becomes
     /// the relative path of the manifest file.
     #[cfg(feature = "packing")]
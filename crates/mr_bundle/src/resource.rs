@@ -1,2 +1,125 @@
+use crate::{
+    error::{BundleError, BundleResult},
+    location::Location,
+};
+
 /// Arbitrary opaque bytes representing a Resource in a [`Bundle`](crate::Bundle)
 pub type ResourceBytes = Vec<u8>;
+
+/// The blake2b-256 digest of the bytes of a Resource.
+///
+/// A [`Manifest`](crate::Manifest) may pin the hash of each of its resources,
+/// in which case the resource is checked against it whenever it is resolved.
+/// In manifests the hash is written as a string of 64 hex digits.
+#[derive(
+    Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ResourceHash([u8; 32]);
+
+impl ResourceHash {
+    /// Hash the bytes of a resource
+    pub fn of(bytes: &[u8]) -> Self {
+        let digest = blake2b_simd::Params::new().hash_length(32).hash(bytes);
+        let mut hash = [0; 32];
+        hash.copy_from_slice(digest.as_bytes());
+        Self(hash)
+    }
+
+    /// Accessor for the raw digest
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Check that the bytes resolved from a location hash to this hash
+    pub fn verify(&self, location: &Location, bytes: &[u8]) -> BundleResult<()> {
+        let actual = Self::of(bytes);
+        if actual == *self {
+            Ok(())
+        } else {
+            Err(BundleError::ResourceHashMismatch(
+                location.clone(),
+                *self,
+                actual,
+            ))
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::util::fmt_hex(&self.0, f)
+    }
+}
+
+impl std::fmt::Debug for ResourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ResourceHash({})", self)
+    }
+}
+
+impl std::str::FromStr for ResourceHash {
+    type Err = BundleError;
+
+    fn from_str(s: &str) -> BundleResult<Self> {
+        crate::util::parse_hex(s)
+            .map(Self)
+            .ok_or_else(|| BundleError::InvalidResourceHash(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for ResourceHash {
+    type Error = BundleError;
+
+    fn try_from(s: String) -> BundleResult<Self> {
+        s.parse()
+    }
+}
+
+impl From<ResourceHash> for String {
+    fn from(hash: ResourceHash) -> Self {
+        hash.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_hash_roundtrip() {
+        let hash = ResourceHash::of(b"resource");
+        let hex = hash.to_string();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex.parse::<ResourceHash>().unwrap(), hash);
+        assert_eq!(
+            serde_yaml::from_str::<ResourceHash>(&serde_yaml::to_string(&hash).unwrap()).unwrap(),
+            hash
+        );
+
+        let not_hex = format!("{}zz", &hex[2..]);
+        let signed = format!("+{}", &hex[1..]);
+        for bad in ["", "abc", &hex[1..], &not_hex, &signed] {
+            assert_eq!(
+                bad.parse::<ResourceHash>(),
+                Err(BundleError::InvalidResourceHash(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn resource_hash_verify() {
+        let location = Location::Url("http://r.co".into());
+        let hash = ResourceHash::of(b"resource");
+        assert_eq!(hash.verify(&location, b"resource"), Ok(()));
+        assert_eq!(
+            hash.verify(&location, b"tampered"),
+            Err(BundleError::ResourceHashMismatch(
+                location,
+                hash,
+                ResourceHash::of(b"tampered")
+            ))
+        );
+    }
+}
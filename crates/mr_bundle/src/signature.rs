@@ -0,0 +1,180 @@
+use crate::error::{BundleError, BundleResult};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer};
+
+/// The Ed25519 public key of a bundle publisher.
+///
+/// Conductors can be told to only install bundles signed by a set of
+/// trusted publisher keys. The key is written as a string of 64 hex digits.
+#[derive(
+    Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PublisherKey([u8; 32]);
+
+impl PublisherKey {
+    /// Accessor for the raw public key
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Check that `signature` is a signature of `payload` made with this key
+    fn verify(&self, payload: &[u8], signature: &[u8]) -> BundleResult<()> {
+        let invalid = || BundleError::InvalidSignature(*self);
+        let public = PublicKey::from_bytes(&self.0).map_err(|_| invalid())?;
+        let signature = Signature::try_from(signature).map_err(|_| invalid())?;
+        public
+            .verify_strict(payload, &signature)
+            .map_err(|_| invalid())
+    }
+}
+
+impl std::fmt::Display for PublisherKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::util::fmt_hex(&self.0, f)
+    }
+}
+
+impl std::fmt::Debug for PublisherKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PublisherKey({})", self)
+    }
+}
+
+impl std::str::FromStr for PublisherKey {
+    type Err = BundleError;
+
+    fn from_str(s: &str) -> BundleResult<Self> {
+        crate::util::parse_hex(s)
+            .filter(|bytes| PublicKey::from_bytes(bytes).is_ok())
+            .map(Self)
+            .ok_or_else(|| BundleError::InvalidPublisherKey(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for PublisherKey {
+    type Error = BundleError;
+
+    fn try_from(s: String) -> BundleResult<Self> {
+        s.parse()
+    }
+}
+
+impl From<PublisherKey> for String {
+    fn from(key: PublisherKey) -> Self {
+        key.to_string()
+    }
+}
+
+/// The Ed25519 key with which a publisher signs bundles.
+///
+/// The secret key is written as a string of 64 hex digits, which is never
+/// echoed back in errors or debug output.
+pub struct SigningKey(Keypair);
+
+impl SigningKey {
+    /// Derive a signing key from a 32 byte secret seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let secret = SecretKey::from_bytes(seed).expect("Any 32 bytes are a valid secret key");
+        let public = PublicKey::from(&secret);
+        Self(Keypair { secret, public })
+    }
+
+    /// The public key corresponding to this signing key
+    pub fn publisher_key(&self) -> PublisherKey {
+        PublisherKey(self.0.public.to_bytes())
+    }
+
+    /// Sign a bundle's signing payload
+    pub(crate) fn sign(&self, payload: &[u8]) -> BundleSignature {
+        BundleSignature {
+            publisher_key: self.publisher_key(),
+            signature: self.0.sign(payload).to_bytes().to_vec(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SigningKey({})", self.publisher_key())
+    }
+}
+
+impl std::str::FromStr for SigningKey {
+    type Err = BundleError;
+
+    fn from_str(s: &str) -> BundleResult<Self> {
+        crate::util::parse_hex(s.trim())
+            .map(|seed| Self::from_seed(&seed))
+            .ok_or(BundleError::InvalidSigningKey)
+    }
+}
+
+/// A publisher's signature of a [`Bundle`](crate::Bundle), stored in the
+/// bundle alongside the manifest.
+///
+/// The signature covers the manifest and all bundled resources.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct BundleSignature {
+    /// The key of the publisher who made this signature
+    pub publisher_key: PublisherKey,
+
+    /// The Ed25519 signature of the bundle's signing payload
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl BundleSignature {
+    /// Check this signature against a bundle's signing payload
+    pub(crate) fn verify(&self, payload: &[u8]) -> BundleResult<()> {
+        self.publisher_key.verify(payload, &self.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publisher_key_roundtrip() {
+        let key = SigningKey::from_seed(&[1; 32]).publisher_key();
+        let hex = key.to_string();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex.parse::<PublisherKey>().unwrap(), key);
+        assert_eq!(
+            serde_yaml::from_str::<PublisherKey>(&serde_yaml::to_string(&key).unwrap()).unwrap(),
+            key
+        );
+        assert_eq!(
+            "abc".parse::<PublisherKey>(),
+            Err(BundleError::InvalidPublisherKey("abc".to_string()))
+        );
+    }
+
+    #[test]
+    fn signing_key_is_not_leaked() {
+        let seed = "01".repeat(32);
+        let key: SigningKey = format!("{}\n", seed).parse().unwrap();
+        assert_eq!(
+            key.publisher_key(),
+            SigningKey::from_seed(&[1; 32]).publisher_key()
+        );
+        assert!(!format!("{:?}", key).contains(&seed));
+        matches::assert_matches!(
+            "not a key".parse::<SigningKey>(),
+            Err(BundleError::InvalidSigningKey)
+        );
+    }
+
+    #[test]
+    fn signatures_are_verified() {
+        let key = SigningKey::from_seed(&[1; 32]);
+        let signature = key.sign(b"payload");
+        assert_eq!(signature.verify(b"payload"), Ok(()));
+        assert_eq!(
+            signature.verify(b"tampered"),
+            Err(BundleError::InvalidSignature(key.publisher_key()))
+        );
+    }
+}
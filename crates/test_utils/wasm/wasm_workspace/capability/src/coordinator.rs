@@ -114,3 +114,18 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
     )?;
     Ok(())
 }
+
+#[hdk_extern]
+fn list_cap_grants(query: CapGrantQuery) -> ExternResult<Vec<CapGrantInfo>> {
+    capability_grants(query)
+}
+
+#[hdk_extern]
+fn list_cap_claims(query: CapClaimQuery) -> ExternResult<Vec<CapClaim>> {
+    capability_claims(query)
+}
+
+#[hdk_extern]
+fn capability_info(_: ()) -> ExternResult<CapabilityInfo> {
+    hdk::prelude::capability_info()
+}
@@ -153,6 +153,16 @@ fn get_external_links(_: ()) -> ExternResult<Vec<Link>> {
     hdk::prelude::get_links(external()?, LinkTypes::SomeLinks, None)
 }
 
+#[hdk_extern]
+fn count_links(_: ()) -> ExternResult<usize> {
+    hdk::prelude::count_links(
+        base()?,
+        LinkTypes::SomeLinks,
+        None,
+        GetLinksFilter::default(),
+    )
+}
+
 #[hdk_extern]
 fn get_link_details(_: ()) -> ExternResult<LinkDetails> {
     hdk::prelude::get_link_details(base()?, LinkTypes::SomeLinks, None)
@@ -4,3 +4,13 @@ use hdk::prelude::*;
 fn migrate_agent(_: MigrateAgent) -> ExternResult<MigrateAgentCallbackResult> {
     Ok(MigrateAgentCallbackResult::Pass)
 }
+
+#[hdk_extern]
+fn prev_dna_hash(_: ()) -> ExternResult<Option<DnaHash>> {
+    get_prev_dna_hash()
+}
+
+#[hdk_extern]
+fn new_dna_hash(_: ()) -> ExternResult<Option<DnaHash>> {
+    get_new_dna_hash()
+}